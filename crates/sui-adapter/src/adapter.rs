@@ -3,8 +3,9 @@
 
 use std::{
     borrow::Borrow,
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::Debug,
+    sync::{Arc, RwLock},
 };
 
 use anyhow::Result;
@@ -13,11 +14,13 @@ use linked_hash_map::LinkedHashMap;
 use move_binary_format::{
     access::ModuleAccess,
     binary_views::BinaryIndexedView,
+    compatibility::Compatibility,
     errors::{VMError, VMResult},
     file_format::{
         AbilitySet, CompiledModule, FunctionHandleIndex, LocalIndex, SignatureToken,
         StructHandleIndex, TypeParameterIndex,
     },
+    normalized,
 };
 use move_bytecode_verifier::VerifierConfig;
 use move_core_types::{
@@ -44,7 +47,12 @@ use sui_types::{
     error::ExecutionError,
     error::{ExecutionErrorKind, SuiError},
     event::Event,
-    messages::{CallArg, EntryArgumentErrorKind, InputObjectKind, ObjectArg},
+    id::ID,
+    messages::{
+        CallArg, EntryArgumentErrorKind, EntryTypeArgumentErrorKind, InputObjectKind, ObjectArg,
+        PackageUpgradeError,
+    },
+    move_package::{MovePackage, UpgradeCap, UpgradeInfo},
     object::{self, Data, MoveObject, Object, Owner, ID_END_INDEX},
     storage::{ChildObjectResolver, DeleteKind, ObjectChange, ParentSync, Storage, WriteKind},
 };
@@ -307,6 +315,7 @@ fn execute_internal<
         deletions,
         user_events,
         loaded_child_objects,
+        created_object_ids: _,
     } = object_runtime.finish()?;
     let session = new_session(
         vm,
@@ -396,6 +405,366 @@ pub fn publish<
     store_package_and_init_modules(state_view, &vm, modules, ctx, gas_status, protocol_config)
 }
 
+/// Publish the next version of the package at `package_id`, authorized by the `UpgradeCap` at
+/// `upgrade_cap_id`. The modules are published at the address of the original package, must be
+/// compatible with the modules of the package being upgraded, and are stored at a new ID. They
+/// link against the versions of the packages in `dependencies`, and against the versions the
+/// package being upgraded links against for the packages not listed there.
+#[allow(clippy::too_many_arguments)]
+#[instrument(name = "adapter_upgrade", level = "trace", skip_all)]
+pub fn upgrade<
+    E: Debug,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
+>(
+    state_view: &mut S,
+    vm: &MoveVM,
+    natives: NativeFunctionTable,
+    package_id: ObjectID,
+    module_bytes: Vec<Vec<u8>>,
+    dependencies: Vec<ObjectID>,
+    upgrade_cap_id: ObjectID,
+    ctx: &mut TxContext,
+    gas_status: &mut GasStatus,
+    protocol_config: &ProtocolConfig,
+) -> Result<(), ExecutionError> {
+    let package = read_package(state_view, package_id)?;
+
+    let mut cap_object = state_view
+        .read_object(&upgrade_cap_id)
+        .expect("We previously checked all input objects exist")
+        .clone();
+    let not_an_upgrade_cap = || {
+        ExecutionError::from_kind(ExecutionErrorKind::package_upgrade_error(
+            PackageUpgradeError::NotAnUpgradeCap {
+                object_id: upgrade_cap_id,
+            },
+        ))
+    };
+    if !cap_object.is_address_owned() {
+        return Err(not_an_upgrade_cap());
+    }
+    let cap_move_object = cap_object
+        .data
+        .try_as_move_mut()
+        .filter(|o| UpgradeCap::is_upgrade_cap(&o.type_))
+        .ok_or_else(not_an_upgrade_cap)?;
+    let mut cap: UpgradeCap = bcs::from_bytes(cap_move_object.contents()).map_err(|e| {
+        ExecutionError::new_with_source(ExecutionErrorKind::InvariantViolation, e.to_string())
+    })?;
+    if cap.package.bytes != package_id {
+        return Err(ExecutionErrorKind::package_upgrade_error(
+            PackageUpgradeError::PackageIDDoesNotMatch {
+                package_id,
+                cap_package_id: cap.package.bytes,
+            },
+        )
+        .into());
+    }
+
+    let mut modules = module_bytes
+        .iter()
+        .map(|b| {
+            CompiledModule::deserialize(b)
+                .map_err(|e| e.finish(move_binary_format::errors::Location::Undefined))
+        })
+        .collect::<move_binary_format::errors::VMResult<Vec<CompiledModule>>>()
+        .map_err(|e| convert_vm_error(e, vm, state_view))?;
+
+    if modules.is_empty() {
+        return Err(ExecutionErrorKind::PublishErrorEmptyPackage.into());
+    }
+
+    let runtime_id = package.original_package_id();
+    substitute_package_id(&mut modules, runtime_id)?;
+    check_compatibility(&package, &modules)?;
+
+    let linkage_table = upgrade_linkage_table(state_view, &package, &dependencies)?;
+    // Link against the package being upgraded rather than the original package, so that the VM
+    // checks compatibility with the latest version too
+    let mut linkage = linkage_by_runtime_id(&linkage_table);
+    linkage.insert(runtime_id, package_id);
+    state_view.set_linkage(linkage);
+    let verified = verify_and_link(
+        state_view,
+        &modules,
+        runtime_id,
+        natives,
+        gas_status,
+        protocol_config,
+    );
+    state_view.set_linkage(BTreeMap::new());
+    verified?;
+
+    let upgraded_package = package.new_upgraded(ctx.fresh_id(), modules, linkage_table)?;
+    cap.package = ID {
+        bytes: upgraded_package.id(),
+    };
+    cap.version += 1;
+    cap_move_object.update_contents(bcs::to_bytes(&cap).unwrap(), protocol_config)?;
+
+    let package_object = Object {
+        data: Data::Package(upgraded_package),
+        owner: Owner::Immutable,
+        previous_transaction: ctx.digest(),
+        storage_rebate: 0,
+    };
+    let changes = BTreeMap::from([
+        (
+            package_object.id(),
+            ObjectChange::Write(
+                SingleTxContext::upgrade(ctx.sender()),
+                package_object,
+                WriteKind::Create,
+            ),
+        ),
+        (
+            upgrade_cap_id,
+            ObjectChange::Write(
+                SingleTxContext::upgrade(ctx.sender()),
+                cap_object,
+                WriteKind::Mutate,
+            ),
+        ),
+    ]);
+    state_view.apply_object_changes(changes);
+    Ok(())
+}
+
+fn read_package<S: Storage>(
+    state_view: &S,
+    package_id: ObjectID,
+) -> Result<MovePackage, ExecutionError> {
+    match state_view.read_object(&package_id) {
+        Some(Object {
+            data: Data::Package(package),
+            ..
+        }) => Ok(package.clone()),
+        Some(_) => Err(ExecutionErrorKind::package_upgrade_error(
+            PackageUpgradeError::NotAPackage {
+                object_id: package_id,
+            },
+        )
+        .into()),
+        None => Err(ExecutionErrorKind::package_upgrade_error(
+            PackageUpgradeError::UnableToFetchPackage { package_id },
+        )
+        .into()),
+    }
+}
+
+/// The linkage table of the next version of `package`, which links against `dependencies`. It
+/// starts from the linkage table of `package`, adds the linkage tables of the dependencies, so
+/// that the packages they depend on are linked too, and then the dependencies themselves. Each
+/// package is linked at the latest version any of these require, and a dependency that is listed
+/// explicitly cannot be older than the version another dependency requires.
+fn upgrade_linkage_table<S: Storage>(
+    state_view: &S,
+    package: &MovePackage,
+    dependencies: &[ObjectID],
+) -> Result<BTreeMap<ObjectID, UpgradeInfo>, ExecutionError> {
+    let package_runtime_id = package.original_package_id();
+    let mut linkage_table: BTreeMap<_, _> = package
+        .linkage_table()
+        .iter()
+        .filter(|(runtime_id, _)| **runtime_id != package_runtime_id)
+        .map(|(runtime_id, info)| (*runtime_id, *info))
+        .collect();
+    let dependencies = dependencies
+        .iter()
+        .map(|id| read_package(state_view, *id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for dependency in &dependencies {
+        for (runtime_id, info) in dependency.linkage_table() {
+            if *runtime_id == package_runtime_id {
+                continue;
+            }
+            let linked = linkage_table.entry(*runtime_id).or_insert(*info);
+            if info.upgraded_version > linked.upgraded_version {
+                *linked = *info;
+            }
+        }
+    }
+    for dependency in &dependencies {
+        let runtime_id = dependency.original_package_id();
+        if runtime_id == package_runtime_id {
+            continue;
+        }
+        if let Some(linked) = linkage_table.get(&runtime_id) {
+            if linked.upgraded_version > dependency.version() {
+                return Err(ExecutionErrorKind::package_upgrade_error(
+                    PackageUpgradeError::DependencyDowngrade {
+                        package_id: dependency.id(),
+                        linked_package_id: linked.upgraded_id,
+                    },
+                )
+                .into());
+            }
+        }
+        if dependency.is_upgraded() {
+            linkage_table.insert(
+                runtime_id,
+                UpgradeInfo {
+                    upgraded_id: dependency.id(),
+                    upgraded_version: dependency.version(),
+                },
+            );
+        } else {
+            linkage_table.remove(&runtime_id);
+        }
+    }
+    Ok(linkage_table)
+}
+
+fn linkage_by_runtime_id(
+    linkage_table: &BTreeMap<ObjectID, UpgradeInfo>,
+) -> BTreeMap<ObjectID, ObjectID> {
+    linkage_table
+        .iter()
+        .map(|(runtime_id, info)| (*runtime_id, info.upgraded_id))
+        .collect()
+}
+
+/// Check that the modules of an upgrade are compatible with the modules of `package`: every
+/// module must still be present, with the same struct layouts, and with public functions and
+/// structs that can still be linked against.
+fn check_compatibility(
+    package: &MovePackage,
+    modules: &[CompiledModule],
+) -> Result<(), ExecutionError> {
+    let new_modules: BTreeMap<_, _> = modules
+        .iter()
+        .map(|m| (m.self_id().name().to_string(), m))
+        .collect();
+    let compatibility = Compatibility::new(
+        /* check_struct_and_pub_function_linking */ true, /* check_struct_layout */ true,
+        /* check_friend_linking */ false,
+    );
+    for (name, bytes) in package.serialized_module_map() {
+        let incompatible = |msg: String| {
+            ExecutionError::new_with_source(
+                ExecutionErrorKind::package_upgrade_error(PackageUpgradeError::IncompatibleUpgrade),
+                msg,
+            )
+        };
+        let Some(new_module) = new_modules.get(name) else {
+            return Err(incompatible(format!("Module {name} is missing from the upgrade")));
+        };
+        let old_module = CompiledModule::deserialize(bytes)
+            .expect("Unwrap safe because Sui serializes/verifies modules before publishing them");
+        compatibility
+            .check(
+                &normalized::Module::new(&old_module),
+                &normalized::Module::new(new_module),
+            )
+            .map_err(|e| incompatible(format!("Module {name} is incompatible: {e:?}")))?;
+    }
+    Ok(())
+}
+
+/// Upper bound on the number of VMs a `LinkedVMCache` holds
+const MAX_LINKED_VMS: usize = 256;
+
+/// The VMs that calls into packages with a linkage table are executed with, by the ID of the
+/// package called. Each VM loads the modules of the package versions in that linkage table, which
+/// would conflict with the modules of the original packages in the loader cache of the shared VM.
+/// Packages are immutable, so a cached VM never goes stale, but the whole cache is dropped once
+/// it is full.
+#[derive(Default)]
+pub struct LinkedVMCache {
+    vms: RwLock<HashMap<ObjectID, Arc<MoveVM>>>,
+}
+
+impl LinkedVMCache {
+    fn get_or_create(
+        &self,
+        package_id: ObjectID,
+        natives: &NativeFunctionTable,
+        protocol_config: &ProtocolConfig,
+    ) -> Arc<MoveVM> {
+        if let Some(vm) = self.vms.read().unwrap().get(&package_id) {
+            return vm.clone();
+        }
+        let mut vms = self.vms.write().unwrap();
+        if vms.len() >= MAX_LINKED_VMS {
+            vms.clear();
+        }
+        vms.entry(package_id)
+            .or_insert_with(|| {
+                Arc::new(new_move_vm(natives.clone(), protocol_config).expect(
+                    "VM creation only fails if natives are invalid, and we created the natives",
+                ))
+            })
+            .clone()
+    }
+}
+
+/// Upgraded packages are stored at new IDs, but their modules keep the address of the original
+/// package. Runs `f` with the VM and runtime ID to call into the package at `package_id` with,
+/// loading the modules of each package in its linkage table, including the package itself, from
+/// the version it links against for the duration of `f`. Packages with a linkage table are
+/// loaded into a VM from `linked_vms`, so that the loader cache of the shared VM only ever
+/// contains the modules of original packages.
+///
+/// The structs of a package are resolved at the address of the original package whichever
+/// version defined them, so `type_arguments` are checked against the type origin table of the
+/// package: a type introduced by a later version of the package cannot be used to call into an
+/// earlier one.
+#[allow(clippy::too_many_arguments)]
+pub fn with_linked_package<S: Storage, R>(
+    vm: &MoveVM,
+    linked_vms: &LinkedVMCache,
+    natives: &NativeFunctionTable,
+    protocol_config: &ProtocolConfig,
+    state_view: &mut S,
+    package_id: ObjectID,
+    type_arguments: &[TypeTag],
+    f: impl FnOnce(&MoveVM, &mut S, ObjectID) -> Result<R, ExecutionError>,
+) -> Result<R, ExecutionError> {
+    let package = match state_view.read_object(&package_id).map(|o| &o.data) {
+        Some(Data::Package(p)) if !p.linkage_table().is_empty() => p,
+        _ => return f(vm, state_view, package_id),
+    };
+    let runtime_id = package.original_package_id();
+    for (idx, ty) in type_arguments.iter().enumerate() {
+        if !type_origins_defined(package, runtime_id, ty) {
+            return Err(ExecutionErrorKind::entry_type_argument_error(
+                idx as TypeParameterIndex,
+                EntryTypeArgumentErrorKind::TypeNotFound,
+            )
+            .into());
+        }
+    }
+    let linkage = linkage_by_runtime_id(package.linkage_table());
+    let linked_vm = linked_vms.get_or_create(package_id, natives, protocol_config);
+    state_view.set_linkage(linkage);
+    let result = f(&linked_vm, state_view, runtime_id);
+    state_view.set_linkage(BTreeMap::new());
+    result
+}
+
+/// Whether every struct of the package published at `runtime_id` that appears in `ty` was defined
+/// by `package` or by an earlier version of it
+fn type_origins_defined(package: &MovePackage, runtime_id: ObjectID, ty: &TypeTag) -> bool {
+    match ty {
+        TypeTag::Vector(ty) => type_origins_defined(package, runtime_id, ty),
+        TypeTag::Struct(s) => {
+            (ObjectID::from(s.address) != runtime_id
+                || package
+                    .type_origin(s.module.as_str(), s.name.as_str())
+                    .is_some())
+                && s.type_params
+                    .iter()
+                    .all(|ty| type_origins_defined(package, runtime_id, ty))
+        }
+        _ => true,
+    }
+}
+
 /// Store package in state_view and call module initializers
 pub fn store_package_and_init_modules<
     E: Debug,
@@ -573,6 +942,16 @@ pub fn generate_package_id(
     ctx: &mut TxContext,
 ) -> Result<ObjectID, ExecutionError> {
     let package_id = ctx.fresh_id();
+    substitute_package_id(modules, package_id)?;
+    Ok(package_id)
+}
+
+/// Mutate each module's self ID to `package_id`, and update its module handle tables to reflect
+/// the new ID of the other modules in the package. The modules' addresses must be 0.
+pub fn substitute_package_id(
+    modules: &mut [CompiledModule],
+    package_id: ObjectID,
+) -> Result<(), ExecutionError> {
     let new_address = AccountAddress::from(package_id);
 
     for module in modules.iter_mut() {
@@ -599,7 +978,7 @@ pub fn generate_package_id(
         *address_mut = new_address;
     }
 
-    Ok(package_id)
+    Ok(())
 }
 
 /// Update `state_view` with the effects of successfully executing a transaction:
@@ -977,7 +1356,7 @@ pub fn resolve_and_type_check<Mode: ExecutionMode>(
 }
 
 // Validates a primitive argument
-fn validate_primitive_arg(
+pub(crate) fn validate_primitive_arg(
     view: &BinaryIndexedView,
     arg: &[u8],
     idx: LocalIndex,
@@ -1365,7 +1744,7 @@ fn type_check_struct(
     }
 }
 
-fn type_tag_equals_sig_token(
+pub(crate) fn type_tag_equals_sig_token(
     view: &BinaryIndexedView,
     function_type_arguments: &[TypeTag],
     arg_type: &TypeTag,
@@ -1374,8 +1753,11 @@ fn type_tag_equals_sig_token(
     match (arg_type, param_type) {
         (TypeTag::Bool, SignatureToken::Bool)
         | (TypeTag::U8, SignatureToken::U8)
+        | (TypeTag::U16, SignatureToken::U16)
+        | (TypeTag::U32, SignatureToken::U32)
         | (TypeTag::U64, SignatureToken::U64)
         | (TypeTag::U128, SignatureToken::U128)
+        | (TypeTag::U256, SignatureToken::U256)
         | (TypeTag::Address, SignatureToken::Address)
         | (TypeTag::Signer, SignatureToken::Signer) => true,
 
@@ -1400,7 +1782,7 @@ fn type_tag_equals_sig_token(
     }
 }
 
-fn struct_tag_equals_sig_token(
+pub(crate) fn struct_tag_equals_sig_token(
     view: &BinaryIndexedView,
     function_type_arguments: &[TypeTag],
     arg_type: &StructTag,
@@ -1447,14 +1829,14 @@ fn struct_tag_equals_struct_inst(
         )
 }
 
-fn missing_unwrapped_msg(id: &ObjectID) -> String {
+pub(crate) fn missing_unwrapped_msg(id: &ObjectID) -> String {
     format!(
         "Unable to unwrap object {}. Was unable to retrieve last known version in the parent sync",
         id
     )
 }
 
-fn convert_type_argument_error<
+pub(crate) fn convert_type_argument_error<
     'r,
     E: Debug,
     S: ResourceResolver<Error = E> + ModuleResolver<Error = E>,
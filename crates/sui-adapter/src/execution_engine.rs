@@ -9,7 +9,8 @@ use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use sui_types::base_types::SequenceNumber;
 use tracing::{debug, instrument};
 
-use crate::adapter;
+use crate::adapter::{self, LinkedVMCache};
+use crate::programmable_transactions;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::coin::{transfer_coin, update_input_coins, Coin};
 use sui_types::epoch_data::EpochData;
@@ -37,8 +38,8 @@ use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest, TxContext},
     gas::SuiGasStatus,
     messages::{
-        CallArg, ChangeEpoch, ExecutionStatus, MoveCall, MoveModulePublish, SingleTransactionKind,
-        TransactionEffects, TransferObject, TransferSui,
+        CallArg, ChangeEpoch, ExecutionStatus, MoveCall, MoveModulePublish, MovePackageUpgrade,
+        SingleTransactionKind, TransactionEffects, TransferObject, TransferSui,
     },
     object::Object,
     storage::BackingPackageStore,
@@ -62,9 +63,11 @@ pub fn execute_transaction_to_effects<
     transaction_kind: TransactionKind,
     transaction_signer: SuiAddress,
     gas_object_ref: ObjectRef,
+    extra_gas_object_refs: Vec<ObjectRef>,
     transaction_digest: TransactionDigest,
     mut transaction_dependencies: BTreeSet<TransactionDigest>,
     move_vm: &Arc<MoveVM>,
+    linked_vms: &LinkedVMCache,
     native_functions: &NativeFunctionTable,
     gas_status: SuiGasStatus,
     epoch_data: &EpochData,
@@ -80,8 +83,13 @@ pub fn execute_transaction_to_effects<
         &mut temporary_store,
         transaction_kind,
         gas_object_ref.0,
+        extra_gas_object_refs
+            .iter()
+            .map(|obj_ref| obj_ref.0)
+            .collect(),
         &mut tx_ctx,
         move_vm,
+        linked_vms,
         native_functions,
         gas_status,
         protocol_config,
@@ -142,8 +150,10 @@ fn execute_transaction<
     temporary_store: &mut TemporaryStore<S>,
     transaction_kind: TransactionKind,
     gas_object_id: ObjectID,
+    extra_gas_object_ids: Vec<ObjectID>,
     tx_ctx: &mut TxContext,
     move_vm: &Arc<MoveVM>,
+    linked_vms: &LinkedVMCache,
     native_functions: &NativeFunctionTable,
     mut gas_status: SuiGasStatus,
     protocol_config: &ProtocolConfig,
@@ -151,9 +161,12 @@ fn execute_transaction<
     GasCostSummary,
     Result<Mode::ExecutionResults, ExecutionError>,
 ) {
+    // The extra gas coins are smashed into the gas object first, as gas is charged from the
+    // merged coin whether or not execution succeeds.
+    let result = temporary_store.smash_gas(tx_ctx.sender(), gas_object_id, extra_gas_object_ids);
     // We must charge object read gas inside here during transaction execution, because if this fails
     // we must still ensure an effect is committed and all objects versions incremented.
-    let result = charge_gas_for_object_read(temporary_store, &mut gas_status);
+    let result = result.and_then(|()| charge_gas_for_object_read(temporary_store, &mut gas_status));
     let mut result = result.and_then(|()| {
         let execution_result = execution_loop::<Mode, _>(
             temporary_store,
@@ -161,6 +174,7 @@ fn execute_transaction<
             gas_object_id,
             tx_ctx,
             move_vm,
+            linked_vms,
             native_functions,
             &mut gas_status,
             protocol_config,
@@ -194,6 +208,7 @@ fn execution_loop<
     gas_object_id: ObjectID,
     tx_ctx: &mut TxContext,
     move_vm: &Arc<MoveVM>,
+    linked_vms: &LinkedVMCache,
     native_functions: &NativeFunctionTable,
     gas_status: &mut SuiGasStatus,
     protocol_config: &ProtocolConfig,
@@ -216,9 +231,10 @@ fn execution_loop<
                 transfer_object(temporary_store, object, tx_ctx.sender(), recipient)?;
             }
             SingleTransactionKind::TransferSui(TransferSui { recipient, amount }) => {
+                // The gas object is read from the written objects, as gas coins may have been
+                // smashed into it.
                 let gas_object = temporary_store
-                    .objects()
-                    .get(&gas_object_id)
+                    .read_object(&gas_object_id)
                     .expect(
                         "We constructed the object map so it should always have the gas object id",
                     )
@@ -269,17 +285,27 @@ fn execution_loop<
                 // Charge gas for this VM execution
                 gas_status.charge_vm_gas()?;
 
-                let module_id = ModuleId::new(package.into(), module);
-                let result = adapter::execute::<Mode, _, _>(
+                let result = adapter::with_linked_package(
                     move_vm,
-                    temporary_store,
-                    module_id,
-                    &function,
-                    type_arguments,
-                    arguments,
-                    gas_status.create_move_gas_status(),
-                    tx_ctx,
+                    linked_vms,
+                    native_functions,
                     protocol_config,
+                    temporary_store,
+                    package,
+                    &type_arguments,
+                    |vm, temporary_store, runtime_id| {
+                        adapter::execute::<Mode, _, _>(
+                            vm,
+                            temporary_store,
+                            ModuleId::new(runtime_id.into(), module),
+                            &function,
+                            type_arguments.clone(),
+                            arguments,
+                            gas_status.create_move_gas_status(),
+                            tx_ctx,
+                            protocol_config,
+                        )
+                    },
                 )?;
                 Mode::add_result(&mut results, idx, result);
             }
@@ -298,6 +324,29 @@ fn execution_loop<
                     protocol_config,
                 )?;
             }
+            SingleTransactionKind::Upgrade(MovePackageUpgrade {
+                package,
+                modules,
+                dependencies,
+                upgrade_cap,
+            }) => {
+                // Charge gas for this VM execution
+                gas_status.charge_vm_gas()?;
+                // Charge gas for this upgrade, like for a publish
+                gas_status.charge_publish_package(modules.iter().map(|v| v.len()).sum())?;
+                adapter::upgrade(
+                    temporary_store,
+                    move_vm,
+                    native_functions.clone(),
+                    package,
+                    modules,
+                    dependencies,
+                    upgrade_cap.0,
+                    tx_ctx,
+                    gas_status.create_move_gas_status(),
+                    protocol_config,
+                )?;
+            }
             SingleTransactionKind::Pay(Pay {
                 coins,
                 recipients,
@@ -351,8 +400,19 @@ fn execution_loop<
                 gas_status,
                 protocol_config,
             )?,
-            SingleTransactionKind::ProgrammableTransaction(_) => {
-                unreachable!("programmable transactions are not yet supported")
+            SingleTransactionKind::ProgrammableTransaction(pt) => {
+                programmable_transactions::execution::execute::<Mode, _, _>(
+                    protocol_config,
+                    move_vm,
+                    linked_vms,
+                    temporary_store,
+                    native_functions,
+                    tx_ctx,
+                    gas_status,
+                    gas_object_id,
+                    pt,
+                    &mut results,
+                )?
             }
         };
     }
@@ -38,6 +38,18 @@ pub trait ExecutionMode {
         return_values: &SerializedReturnValues,
     ) -> Result<Self::ExecutionResult, ExecutionError>;
 
+    /// Like `make_result`, but for a Move call made by a programmable transaction, whose return
+    /// values are kept as results for the following commands.
+    fn make_programmable_result<S: MoveResolver>(
+        session: &Session<S>,
+        module_id: &ModuleId,
+        function: &Identifier,
+        type_arguments: &[TypeTag],
+        return_values: &SerializedReturnValues,
+    ) -> Result<Self::ExecutionResult, ExecutionError> {
+        Self::make_result(session, module_id, function, type_arguments, return_values)
+    }
+
     fn empty_results() -> Self::ExecutionResults;
 
     fn add_result(
@@ -69,6 +81,17 @@ impl ExecutionMode for Normal {
         Ok(())
     }
 
+    fn make_programmable_result<S: MoveResolver>(
+        _session: &Session<S>,
+        _module_id: &ModuleId,
+        _function: &Identifier,
+        _type_arguments: &[TypeTag],
+        _srv: &SerializedReturnValues,
+    ) -> Result<Self::ExecutionResult, ExecutionError> {
+        // return values are passed on to the following commands, but are not part of the result
+        Ok(())
+    }
+
     fn empty_results() -> Self::ExecutionResults {}
 
     fn add_result(_: &mut Self::ExecutionResults, _: TransactionIndex, _: Self::ExecutionResult) {}
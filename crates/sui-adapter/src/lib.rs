@@ -15,3 +15,4 @@ macro_rules! assert_invariant {
 pub mod adapter;
 pub mod execution_engine;
 pub mod execution_mode;
+pub mod programmable_transactions;
@@ -0,0 +1,465 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use move_binary_format::file_format::AbilitySet;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    error::{ExecutionError, ExecutionErrorKind},
+    messages::{Argument, CallArg, CommandArgumentError, ObjectArg},
+    object::{Object, Owner, ID_END_INDEX},
+    storage::{SingleTxContext, Storage},
+};
+
+/// The kind of command an argument is being used in. Only needed for the special handling of the
+/// gas coin
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandKind {
+    MoveCall,
+    TransferObjects,
+    SplitCoin,
+    MergeCoins,
+}
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Object(ObjectValue),
+    Raw(RawValueType, Vec<u8>),
+}
+
+#[derive(Clone, Debug)]
+pub enum RawValueType {
+    /// Pure bytes from the transaction inputs. They are not bound to a type: every usage checks
+    /// that the bytes are valid for the type expected there
+    Any,
+    Loaded {
+        ty: TypeTag,
+        abilities: AbilitySet,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct ObjectValue {
+    pub type_: StructTag,
+    pub has_public_transfer: bool,
+    pub contents: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct InputObjectMetadata {
+    pub id: ObjectID,
+    pub is_mutable_input: bool,
+    pub owner: Owner,
+    pub version: SequenceNumber,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UsageKind {
+    BorrowImm,
+    BorrowMut,
+    ByValue,
+}
+
+#[derive(Clone, Debug)]
+pub struct ResultValue {
+    /// How the value was used in the current command, reset at the start of each command
+    pub last_usage_kind: Option<UsageKind>,
+    /// None if the value has been moved
+    pub value: Option<Value>,
+}
+
+#[derive(Clone, Debug)]
+pub struct InputValue {
+    /// Set if the input is an object from storage
+    pub object_metadata: Option<InputObjectMetadata>,
+    /// The context of the last command that borrowed the object mutably, if any. Used when
+    /// writing the object back to storage
+    pub last_mutation: Option<SingleTxContext>,
+    pub inner: ResultValue,
+}
+
+/// Tracks the values available to the commands of a programmable transaction, and enforces the
+/// rules for how they can be used
+pub struct ExecutionContext {
+    /// The gas coin. Its value is None if the transaction is unmetered and no gas coin was provided
+    pub gas: InputValue,
+    /// The values of the transaction inputs
+    pub inputs: Vec<InputValue>,
+    /// The results of each command executed so far
+    pub results: Vec<Vec<ResultValue>>,
+    /// Objects created by a command in this transaction that have not yet been written to storage
+    /// or deleted
+    pub new_object_ids: BTreeSet<ObjectID>,
+}
+
+impl ObjectValue {
+    pub fn id(&self) -> ObjectID {
+        ObjectID::try_from(&self.contents[0..ID_END_INDEX])
+            .expect("object contents should start with an id")
+    }
+
+    fn from_object(object: &Object) -> Result<Self, ExecutionError> {
+        let Some(move_object) = object.data.try_as_move() else {
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::InvariantViolation,
+                format!("Expected input {} to be a Move object", object.id()),
+            ));
+        };
+        Ok(ObjectValue {
+            type_: move_object.type_.clone(),
+            has_public_transfer: move_object.has_public_transfer(),
+            contents: move_object.contents().to_vec(),
+        })
+    }
+}
+
+impl Value {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Value::Object(object_value) => object_value.contents.clone(),
+            Value::Raw(_, bytes) => bytes.clone(),
+        }
+    }
+
+    fn is_copyable(&self) -> bool {
+        match self {
+            Value::Object(_) => false,
+            Value::Raw(RawValueType::Any, _) => true,
+            Value::Raw(RawValueType::Loaded { abilities, .. }, _) => abilities.has_copy(),
+        }
+    }
+
+    fn is_droppable(&self) -> bool {
+        match self {
+            Value::Object(_) => false,
+            Value::Raw(RawValueType::Any, _) => true,
+            Value::Raw(RawValueType::Loaded { abilities, .. }, _) => abilities.has_drop(),
+        }
+    }
+}
+
+impl ResultValue {
+    fn new(value: Value) -> Self {
+        Self {
+            last_usage_kind: None,
+            value: Some(value),
+        }
+    }
+}
+
+impl InputValue {
+    fn new_object<S: Storage>(
+        state_view: &S,
+        id: &ObjectID,
+        mutable: bool,
+    ) -> Result<Self, ExecutionError> {
+        let Some(object) = state_view.read_object(id) else {
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::InvariantViolation,
+                format!("Object {} does not exist yet", id),
+            ));
+        };
+        let object_metadata = InputObjectMetadata {
+            id: *id,
+            is_mutable_input: mutable && !object.is_immutable(),
+            owner: object.owner,
+            version: object.version(),
+        };
+        Ok(Self {
+            object_metadata: Some(object_metadata),
+            last_mutation: None,
+            inner: ResultValue::new(Value::Object(ObjectValue::from_object(object)?)),
+        })
+    }
+
+    fn new_pure(bytes: Vec<u8>) -> Self {
+        Self {
+            object_metadata: None,
+            last_mutation: None,
+            inner: ResultValue::new(Value::Raw(RawValueType::Any, bytes)),
+        }
+    }
+}
+
+impl ExecutionContext {
+    pub fn new<S: Storage>(
+        state_view: &S,
+        gas_coin: ObjectID,
+        inputs: Vec<CallArg>,
+    ) -> Result<Self, ExecutionError> {
+        let inputs = inputs
+            .into_iter()
+            .map(|input| match input {
+                CallArg::Pure(bytes) => Ok(InputValue::new_pure(bytes)),
+                CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _))) => {
+                    InputValue::new_object(state_view, &id, true)
+                }
+                CallArg::Object(ObjectArg::SharedObject { id, mutable, .. }) => {
+                    InputValue::new_object(state_view, &id, mutable)
+                }
+                CallArg::ObjVec(_) => Err(ExecutionError::new_with_source(
+                    ExecutionErrorKind::InvariantViolation,
+                    "Object vectors are not supported in programmable transactions",
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // the gas coin is missing only for unmetered transactions (e.g. dev-inspect)
+        let gas = if state_view.read_object(&gas_coin).is_some() {
+            InputValue::new_object(state_view, &gas_coin, true)?
+        } else {
+            InputValue {
+                object_metadata: None,
+                last_mutation: None,
+                inner: ResultValue {
+                    last_usage_kind: None,
+                    value: None,
+                },
+            }
+        };
+        Ok(Self {
+            gas,
+            inputs,
+            results: vec![],
+            new_object_ids: BTreeSet::new(),
+        })
+    }
+
+    /// Resets the usage of all values, to be called at the start of each command
+    pub fn start_command(&mut self) {
+        let inputs = std::iter::once(&mut self.gas)
+            .chain(&mut self.inputs)
+            .map(|input| &mut input.inner);
+        for value in inputs.chain(self.results.iter_mut().flatten()) {
+            value.last_usage_kind = None;
+        }
+    }
+
+    /// Returns the metadata of the input object with the given ID, if it is one
+    pub fn input_object_metadata(&self, id: &ObjectID) -> Option<&InputObjectMetadata> {
+        std::iter::once(&self.gas)
+            .chain(&self.inputs)
+            .filter_map(|input| input.object_metadata.as_ref())
+            .find(|metadata| &metadata.id == id)
+    }
+
+    /// Takes the value of `arg`, moving it out of the context unless it is copyable
+    pub fn by_value_arg(
+        &mut self,
+        command_kind: CommandKind,
+        arg_idx: usize,
+        arg: Argument,
+    ) -> Result<Value, ExecutionError> {
+        if matches!(arg, Argument::GasCoin) && command_kind != CommandKind::TransferObjects {
+            return Err(command_argument_error(
+                CommandArgumentError::InvalidGasCoinUsage,
+                arg_idx,
+            ));
+        }
+        let (metadata, result_value) = self
+            .borrow_location(arg)
+            .map_err(|kind| command_argument_error(kind, arg_idx))?;
+        let Some(value) = &result_value.value else {
+            return Err(command_argument_error(CommandArgumentError::InvalidValueUsage, arg_idx));
+        };
+        if value.is_copyable() {
+            if result_value.last_usage_kind == Some(UsageKind::BorrowMut) {
+                return Err(command_argument_error(
+                    CommandArgumentError::InvalidValueUsage,
+                    arg_idx,
+                ));
+            }
+            let value = value.clone();
+            result_value
+                .last_usage_kind
+                .get_or_insert(UsageKind::BorrowImm);
+            return Ok(value);
+        }
+        if result_value.last_usage_kind.is_some() {
+            return Err(command_argument_error(
+                CommandArgumentError::InvalidValueUsage,
+                arg_idx,
+            ));
+        }
+        if let Some(metadata) = metadata {
+            if !metadata.is_mutable_input || matches!(metadata.owner, Owner::Shared { .. }) {
+                return Err(command_argument_error(
+                    CommandArgumentError::InvalidObjectByValue,
+                    arg_idx,
+                ));
+            }
+        }
+        result_value.last_usage_kind = Some(UsageKind::ByValue);
+        Ok(result_value.value.take().unwrap())
+    }
+
+    /// Borrows the value of `arg` mutably. Usable only once per command
+    pub fn borrow_arg_mut(
+        &mut self,
+        arg_idx: usize,
+        arg: Argument,
+    ) -> Result<&mut Value, ExecutionError> {
+        let (metadata, result_value) = self
+            .borrow_location(arg)
+            .map_err(|kind| command_argument_error(kind, arg_idx))?;
+        if let Some(metadata) = metadata {
+            if !metadata.is_mutable_input {
+                return Err(command_argument_error(
+                    CommandArgumentError::InvalidObjectByMutRef,
+                    arg_idx,
+                ));
+            }
+        }
+        if result_value.last_usage_kind.is_some() || result_value.value.is_none() {
+            return Err(command_argument_error(
+                CommandArgumentError::InvalidValueUsage,
+                arg_idx,
+            ));
+        }
+        result_value.last_usage_kind = Some(UsageKind::BorrowMut);
+        Ok(result_value.value.as_mut().unwrap())
+    }
+
+    /// Borrows the value of `arg` immutably. Can be borrowed several times in a single command,
+    /// but not if it has been borrowed mutably or moved
+    pub fn borrow_arg(&mut self, arg_idx: usize, arg: Argument) -> Result<&Value, ExecutionError> {
+        let (_, result_value) = self
+            .borrow_location(arg)
+            .map_err(|kind| command_argument_error(kind, arg_idx))?;
+        if matches!(
+            result_value.last_usage_kind,
+            Some(UsageKind::BorrowMut | UsageKind::ByValue)
+        ) || result_value.value.is_none()
+        {
+            return Err(command_argument_error(
+                CommandArgumentError::InvalidValueUsage,
+                arg_idx,
+            ));
+        }
+        result_value.last_usage_kind = Some(UsageKind::BorrowImm);
+        Ok(result_value.value.as_ref().unwrap())
+    }
+
+    /// Records that `arg`, previously borrowed mutably, was mutated by the command described by
+    /// `mutation_context`
+    pub fn record_mutation(&mut self, arg: Argument, mutation_context: &SingleTxContext) {
+        let input = match arg {
+            Argument::GasCoin => &mut self.gas,
+            Argument::Input(idx) => &mut self.inputs[idx as usize],
+            Argument::Result(_) | Argument::NestedResult(_, _) => return,
+        };
+        input.last_mutation = Some(mutation_context.clone());
+    }
+
+    /// Replaces the contents of the value at `arg` after it was passed by mutable reference
+    pub fn restore_mut_ref(
+        &mut self,
+        arg: Argument,
+        bytes: Vec<u8>,
+        mutation_context: &SingleTxContext,
+    ) -> Result<(), ExecutionError> {
+        let (_, result_value) = self.borrow_location(arg.clone()).map_err(|_| {
+            ExecutionError::new_with_source(
+                ExecutionErrorKind::InvariantViolation,
+                "Mutable reference argument should have been checked",
+            )
+        })?;
+        match &mut result_value.value {
+            Some(Value::Object(object_value)) => object_value.contents = bytes,
+            Some(Value::Raw(_, value_bytes)) => *value_bytes = bytes,
+            None => {
+                return Err(ExecutionError::new_with_source(
+                    ExecutionErrorKind::InvariantViolation,
+                    "Mutably borrowed value should not have been moved",
+                ))
+            }
+        }
+        self.record_mutation(arg, mutation_context);
+        Ok(())
+    }
+
+    pub fn push_command_results(&mut self, results: Vec<Value>) {
+        self.results
+            .push(results.into_iter().map(ResultValue::new).collect())
+    }
+
+    /// Checks that all remaining results can be dropped, and returns the mutated input objects
+    /// (including the gas coin) that need to be written back to storage
+    pub fn finish(
+        self,
+    ) -> Result<Vec<(InputObjectMetadata, SingleTxContext, ObjectValue)>, ExecutionError> {
+        for (result_idx, command_results) in self.results.iter().enumerate() {
+            for (secondary_idx, result_value) in command_results.iter().enumerate() {
+                let droppable = result_value
+                    .value
+                    .as_ref()
+                    .map_or(true, Value::is_droppable);
+                if !droppable {
+                    return Err(ExecutionErrorKind::UnusedValueWithoutDrop {
+                        result_idx: result_idx as u16,
+                        secondary_idx: secondary_idx as u16,
+                    }
+                    .into());
+                }
+            }
+        }
+        let mut to_write_back = vec![];
+        for input in std::iter::once(self.gas).chain(self.inputs) {
+            let InputValue {
+                object_metadata: Some(metadata),
+                last_mutation: Some(mutation_context),
+                inner: ResultValue {
+                    value: Some(Value::Object(object_value)),
+                    ..
+                },
+            } = input else {
+                continue;
+            };
+            to_write_back.push((metadata, mutation_context, object_value));
+        }
+        Ok(to_write_back)
+    }
+
+    /// Resolves `arg` to the location of its value
+    fn borrow_location(
+        &mut self,
+        arg: Argument,
+    ) -> Result<(Option<&InputObjectMetadata>, &mut ResultValue), CommandArgumentError> {
+        let input = match arg {
+            Argument::GasCoin => &mut self.gas,
+            Argument::Input(idx) => self
+                .inputs
+                .get_mut(idx as usize)
+                .ok_or(CommandArgumentError::IndexOutOfBounds { idx })?,
+            Argument::Result(result_idx) => {
+                let command_results = self
+                    .results
+                    .get_mut(result_idx as usize)
+                    .ok_or(CommandArgumentError::IndexOutOfBounds { idx: result_idx })?;
+                if command_results.len() != 1 {
+                    return Err(CommandArgumentError::InvalidResultArity { result_idx });
+                }
+                return Ok((None, &mut command_results[0]));
+            }
+            Argument::NestedResult(result_idx, secondary_idx) => {
+                let command_results = self
+                    .results
+                    .get_mut(result_idx as usize)
+                    .ok_or(CommandArgumentError::IndexOutOfBounds { idx: result_idx })?;
+                let result_value = command_results.get_mut(secondary_idx as usize).ok_or(
+                    CommandArgumentError::SecondaryIndexOutOfBounds {
+                        result_idx,
+                        secondary_idx,
+                    },
+                )?;
+                return Ok((None, result_value));
+            }
+        };
+        Ok((input.object_metadata.as_ref(), &mut input.inner))
+    }
+}
+
+pub fn command_argument_error(kind: CommandArgumentError, arg_idx: usize) -> ExecutionError {
+    ExecutionErrorKind::command_argument_error(kind, arg_idx as u16).into()
+}
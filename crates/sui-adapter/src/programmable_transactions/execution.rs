@@ -0,0 +1,814 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+};
+
+use move_binary_format::{
+    access::ModuleAccess,
+    binary_views::BinaryIndexedView,
+    errors::{Location, VMResult},
+    file_format::{LocalIndex, SignatureToken, Visibility},
+};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+    resolver::{ModuleResolver, ResourceResolver},
+    value::MoveValue,
+};
+use move_vm_runtime::{
+    move_vm::MoveVM, native_functions::NativeFunctionTable, session::SerializedReturnValues,
+};
+use sui_framework::natives::object_runtime::{self, ObjectRuntime};
+use sui_json::primitive_type;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress, TxContext},
+    coin::Coin,
+    error::{convert_vm_error, ExecutionError, ExecutionErrorKind},
+    event::Event,
+    gas::SuiGasStatus,
+    id::UID,
+    messages::{
+        Argument, Command, CommandArgumentError, ProgrammableMoveCall, ProgrammableTransaction,
+    },
+    object::{MoveObject, Object, Owner},
+    storage::{
+        ChildObjectResolver, DeleteKind, ObjectChange, ParentSync, SingleTxContext, Storage,
+        WriteKind,
+    },
+};
+use sui_verifier::entry_points_verifier::{is_tx_context, TxContextKind};
+
+use crate::{
+    adapter::{
+        self, convert_type_argument_error, missing_unwrapped_msg, new_session,
+        struct_tag_equals_sig_token, type_tag_equals_sig_token, validate_primitive_arg,
+        LinkedVMCache,
+    },
+    execution_mode::ExecutionMode,
+};
+
+use super::context::{
+    command_argument_error, CommandKind, ExecutionContext, ObjectValue, RawValueType, Value,
+};
+
+/// Executes the commands of a programmable transaction in order. Each command is run against the
+/// values in the `ExecutionContext`, and any objects transferred, created or deleted are
+/// applied to `state_view` as the command completes. Mutated input objects are written back once
+/// all commands have run.
+#[allow(clippy::too_many_arguments)]
+pub fn execute<
+    Mode: ExecutionMode,
+    E: Debug,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
+>(
+    protocol_config: &ProtocolConfig,
+    vm: &MoveVM,
+    linked_vms: &LinkedVMCache,
+    state_view: &mut S,
+    natives: &NativeFunctionTable,
+    tx_context: &mut TxContext,
+    gas_status: &mut SuiGasStatus,
+    gas_coin: ObjectID,
+    pt: ProgrammableTransaction,
+    results: &mut Mode::ExecutionResults,
+) -> Result<(), ExecutionError> {
+    let ProgrammableTransaction { inputs, commands } = pt;
+    let mut context = ExecutionContext::new(state_view, gas_coin, inputs)?;
+    for (idx, command) in commands.into_iter().enumerate() {
+        context.start_command();
+        let command_results = execute_command::<Mode, _, _>(
+            protocol_config,
+            vm,
+            linked_vms,
+            state_view,
+            natives,
+            tx_context,
+            gas_status,
+            &mut context,
+            idx,
+            command,
+            results,
+        )?;
+        context.push_command_results(command_results);
+    }
+    for (metadata, mutation_context, object_value) in context.finish()? {
+        let mut object = state_view
+            .read_object(&metadata.id)
+            .expect("We previously checked all input objects exist")
+            .clone();
+        object
+            .data
+            .try_as_move_mut()
+            .expect("We previously checked that inputs are Move objects")
+            .update_contents(object_value.contents, protocol_config)?;
+        state_view.apply_object_changes(BTreeMap::from([(
+            metadata.id,
+            ObjectChange::Write(mutation_context, object, WriteKind::Mutate),
+        )]));
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_command<
+    Mode: ExecutionMode,
+    E: Debug,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
+>(
+    protocol_config: &ProtocolConfig,
+    vm: &MoveVM,
+    linked_vms: &LinkedVMCache,
+    state_view: &mut S,
+    natives: &NativeFunctionTable,
+    tx_context: &mut TxContext,
+    gas_status: &mut SuiGasStatus,
+    context: &mut ExecutionContext,
+    command_idx: usize,
+    command: Command,
+    results: &mut Mode::ExecutionResults,
+) -> Result<Vec<Value>, ExecutionError> {
+    let command_results = match command {
+        Command::TransferObjects(objects, recipient) => {
+            transfer_objects(
+                protocol_config,
+                state_view,
+                tx_context,
+                context,
+                objects,
+                recipient,
+            )?;
+            vec![]
+        }
+        Command::SplitCoin(coin, amount) => {
+            vec![split_coin(tx_context, context, coin, amount)?]
+        }
+        Command::MergeCoins(target, coins) => {
+            merge_coins(state_view, tx_context, context, target, coins)?;
+            vec![]
+        }
+        Command::MoveCall(move_call) => {
+            let package = move_call.package;
+            let type_arguments = move_call.type_arguments.clone();
+            adapter::with_linked_package(
+                vm,
+                linked_vms,
+                natives,
+                protocol_config,
+                state_view,
+                package,
+                &type_arguments,
+                |vm, state_view, runtime_id| {
+                    execute_move_call::<Mode, _, _>(
+                        protocol_config,
+                        vm,
+                        state_view,
+                        tx_context,
+                        gas_status,
+                        context,
+                        command_idx,
+                        runtime_id,
+                        *move_call,
+                        results,
+                    )
+                },
+            )?
+        }
+        Command::Publish(modules) => {
+            // Charge gas for this VM execution
+            gas_status.charge_vm_gas()?;
+            // Charge gas for this publish
+            gas_status.charge_publish_package(modules.iter().map(|v| v.len()).sum())?;
+            adapter::publish(
+                state_view,
+                vm,
+                natives.clone(),
+                modules,
+                tx_context,
+                gas_status.create_move_gas_status(),
+                protocol_config,
+            )?;
+            vec![]
+        }
+    };
+    Ok(command_results)
+}
+
+/// Transfers each object to the recipient. Objects must have public transfer, and the gas coin is
+/// the only argument that can be used by-value here.
+fn transfer_objects<S: Storage>(
+    protocol_config: &ProtocolConfig,
+    state_view: &mut S,
+    tx_context: &TxContext,
+    context: &mut ExecutionContext,
+    objects: Vec<Argument>,
+    recipient: Argument,
+) -> Result<(), ExecutionError> {
+    let single_tx_context = SingleTxContext::transfer_objects(tx_context.sender());
+    let recipient_idx = objects.len();
+    let object_values = objects
+        .into_iter()
+        .enumerate()
+        .map(
+            |(idx, arg)| match context.by_value_arg(CommandKind::TransferObjects, idx, arg)? {
+                Value::Object(object_value) if object_value.has_public_transfer => Ok(object_value),
+                Value::Object(_) => Err(ExecutionErrorKind::InvalidTransferObject.into()),
+                Value::Raw(_, _) => Err(command_argument_error(
+                    CommandArgumentError::TypeMismatch,
+                    idx,
+                )),
+            },
+        )
+        .collect::<Result<Vec<_>, ExecutionError>>()?;
+    let recipient: SuiAddress =
+        match context.by_value_arg(CommandKind::TransferObjects, recipient_idx, recipient)? {
+            Value::Raw(RawValueType::Any, bytes)
+            | Value::Raw(
+                RawValueType::Loaded {
+                    ty: TypeTag::Address,
+                    ..
+                },
+                bytes,
+            ) => bcs::from_bytes(&bytes).map_err(|_| {
+                command_argument_error(CommandArgumentError::InvalidBCSBytes, recipient_idx)
+            })?,
+            _ => {
+                return Err(command_argument_error(
+                    CommandArgumentError::TypeMismatch,
+                    recipient_idx,
+                ))
+            }
+        };
+    for object_value in object_values {
+        let id = object_value.id();
+        let (write_kind, version) = object_write_kind_and_version(context, &id);
+        let ObjectValue {
+            type_,
+            has_public_transfer,
+            contents,
+        } = object_value;
+        // safe because `has_public_transfer` was determined from the abilities of the type
+        let move_object = unsafe {
+            MoveObject::new_from_execution(
+                type_,
+                has_public_transfer,
+                version,
+                contents,
+                protocol_config,
+            )?
+        };
+        let object = Object::new_move(
+            move_object,
+            Owner::AddressOwner(recipient),
+            tx_context.digest(),
+        );
+        state_view.apply_object_changes(BTreeMap::from([(
+            id,
+            ObjectChange::Write(single_tx_context.clone(), object, write_kind),
+        )]));
+    }
+    Ok(())
+}
+
+/// Splits `amount` off of the coin, returning the new coin
+fn split_coin(
+    tx_context: &mut TxContext,
+    context: &mut ExecutionContext,
+    coin_arg: Argument,
+    amount_arg: Argument,
+) -> Result<Value, ExecutionError> {
+    let single_tx_context = SingleTxContext::split_coin(tx_context.sender());
+    let amount: u64 = match context.by_value_arg(CommandKind::SplitCoin, 1, amount_arg)? {
+        Value::Raw(RawValueType::Any, bytes)
+        | Value::Raw(
+            RawValueType::Loaded {
+                ty: TypeTag::U64, ..
+            },
+            bytes,
+        ) => bcs::from_bytes(&bytes)
+            .map_err(|_| command_argument_error(CommandArgumentError::InvalidBCSBytes, 1))?,
+        _ => {
+            return Err(command_argument_error(
+                CommandArgumentError::TypeMismatch,
+                1,
+            ))
+        }
+    };
+    let coin_value = match context.borrow_arg_mut(0, coin_arg.clone())? {
+        Value::Object(object_value) if Coin::is_coin(&object_value.type_) => object_value,
+        _ => {
+            return Err(command_argument_error(
+                CommandArgumentError::TypeMismatch,
+                0,
+            ))
+        }
+    };
+    let mut coin = Coin::from_bcs_bytes(&coin_value.contents)?;
+    let new_coin_id = tx_context.fresh_id();
+    let new_coin = coin.split_coin(amount, UID::new(new_coin_id))?;
+    coin_value.contents = coin.to_bcs_bytes();
+    let new_coin_value = ObjectValue {
+        type_: coin_value.type_.clone(),
+        has_public_transfer: coin_value.has_public_transfer,
+        contents: new_coin.to_bcs_bytes(),
+    };
+    context.record_mutation(coin_arg, &single_tx_context);
+    context.new_object_ids.insert(new_coin_id);
+    Ok(Value::Object(new_coin_value))
+}
+
+/// Merges the coins into the target coin, deleting them
+fn merge_coins<S: Storage + ParentSync>(
+    state_view: &mut S,
+    tx_context: &TxContext,
+    context: &mut ExecutionContext,
+    target_arg: Argument,
+    coin_args: Vec<Argument>,
+) -> Result<(), ExecutionError> {
+    let single_tx_context = SingleTxContext::merge_coins(tx_context.sender());
+    let coin_values = coin_args
+        .into_iter()
+        .enumerate()
+        .map(|(idx, arg)| {
+            // the target is the first argument
+            let idx = idx + 1;
+            match context.by_value_arg(CommandKind::MergeCoins, idx, arg)? {
+                Value::Object(object_value) => Ok((idx, object_value)),
+                Value::Raw(_, _) => Err(command_argument_error(
+                    CommandArgumentError::TypeMismatch,
+                    idx,
+                )),
+            }
+        })
+        .collect::<Result<Vec<_>, ExecutionError>>()?;
+    let target_value = match context.borrow_arg_mut(0, target_arg.clone())? {
+        Value::Object(object_value) if Coin::is_coin(&object_value.type_) => object_value,
+        _ => {
+            return Err(command_argument_error(
+                CommandArgumentError::TypeMismatch,
+                0,
+            ))
+        }
+    };
+    let mut target_coin = Coin::from_bcs_bytes(&target_value.contents)?;
+    let mut coins = Vec::with_capacity(coin_values.len());
+    for (idx, coin_value) in coin_values {
+        if coin_value.type_ != target_value.type_ {
+            return Err(command_argument_error(
+                CommandArgumentError::TypeMismatch,
+                idx,
+            ));
+        }
+        coins.push(Coin::from_bcs_bytes(&coin_value.contents)?);
+    }
+    target_coin.merge_coins(&mut coins);
+    target_value.contents = target_coin.to_bcs_bytes();
+    context.record_mutation(target_arg, &single_tx_context);
+
+    let mut changes = BTreeMap::new();
+    for coin in coins {
+        let id = *coin.id();
+        if let Some(version) = object_deleted_version(state_view, context, &id)? {
+            changes.insert(
+                id,
+                ObjectChange::Delete(single_tx_context.clone(), version, DeleteKind::Normal),
+            );
+        }
+    }
+    state_view.apply_object_changes(changes);
+    Ok(())
+}
+
+/// Calls an entry or public Move function, in the modules published at `runtime_id`. Objects
+/// transferred, created or deleted by the call are applied to `state_view`, while the values
+/// returned are added as the results of the command.
+#[allow(clippy::too_many_arguments)]
+fn execute_move_call<
+    Mode: ExecutionMode,
+    E: Debug,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
+>(
+    protocol_config: &ProtocolConfig,
+    vm: &MoveVM,
+    state_view: &mut S,
+    tx_context: &mut TxContext,
+    gas_status: &mut SuiGasStatus,
+    context: &mut ExecutionContext,
+    command_idx: usize,
+    runtime_id: ObjectID,
+    move_call: ProgrammableMoveCall,
+    results: &mut Mode::ExecutionResults,
+) -> Result<Vec<Value>, ExecutionError> {
+    let ProgrammableMoveCall {
+        package,
+        module,
+        function,
+        type_arguments,
+        arguments,
+    } = move_call;
+    // Charge gas for this VM execution
+    gas_status.charge_vm_gas()?;
+
+    let module_id = ModuleId::new(runtime_id.into(), module);
+    let single_tx_context = SingleTxContext {
+        package_id: package,
+        transaction_module: Identifier::from(module_id.name()),
+        sender: tx_context.sender(),
+    };
+    let module = vm
+        .load_module(&module_id, state_view)
+        .map_err(|e| convert_vm_error(e, vm, state_view))?;
+    let view = &BinaryIndexedView::Module(&module);
+    let Some(fdef) = module.function_defs.iter().find(|fdef| {
+        module.identifier_at(module.function_handle_at(fdef.function).name)
+            == function.as_ident_str()
+    }) else {
+        return Err(ExecutionError::new_with_source(
+            ExecutionErrorKind::FunctionNotFound,
+            format!(
+                "Could not resolve function '{}' in module {}",
+                function, &module_id,
+            ),
+        ));
+    };
+    let is_public = fdef.visibility == Visibility::Public;
+    if !fdef.is_entry && !is_public && !Mode::allow_arbitrary_function_calls() {
+        return Err(ExecutionError::new_with_source(
+            ExecutionErrorKind::NonEntryFunctionInvoked,
+            "Can only call `entry` or `public` functions",
+        ));
+    }
+    let fhandle = module.function_handle_at(fdef.function);
+    if fhandle.type_parameters.len() != type_arguments.len() {
+        return Err(ExecutionError::new_with_source(
+            ExecutionErrorKind::EntryTypeArityMismatch,
+            format!(
+                "Expected {:?} type arguments, but found {:?}",
+                fhandle.type_parameters.len(),
+                type_arguments.len()
+            ),
+        ));
+    }
+    // references cannot be returned to the transaction, entry functions are checked by the
+    // verifier to not return references
+    if !Mode::allow_arbitrary_function_calls() {
+        let return_ = &module.signature_at(fhandle.return_).0;
+        if let Some(idx) = return_.iter().position(|ty| {
+            matches!(
+                ty,
+                SignatureToken::Reference(_) | SignatureToken::MutableReference(_)
+            )
+        }) {
+            return Err(
+                ExecutionErrorKind::InvalidPublicFunctionReturnType { idx: idx as u16 }.into(),
+            );
+        }
+    }
+    let parameters = &module.signature_at(fhandle.parameters).0;
+    let tx_ctx_kind = parameters
+        .last()
+        .map(|t| is_tx_context(view, t))
+        .unwrap_or(TxContextKind::None);
+    let num_params = if tx_ctx_kind == TxContextKind::None {
+        parameters.len()
+    } else {
+        parameters.len() - 1
+    };
+    if arguments.len() != num_params {
+        return Err(ExecutionError::new_with_source(
+            ExecutionErrorKind::ArityMismatch,
+            format!(
+                "Expected {:?} arguments calling function '{}', but found {:?}",
+                num_params,
+                function,
+                arguments.len()
+            ),
+        ));
+    }
+
+    // load the arguments, type checking them against the parameters
+    let mut serialized_arguments = Vec::with_capacity(parameters.len());
+    let mut mutable_ref_arguments = BTreeMap::new();
+    let mut input_objects = BTreeMap::new();
+    for (idx, (arg, param_type)) in arguments.into_iter().zip(parameters).enumerate() {
+        let (value, inner_param_type, by_value) = match param_type {
+            SignatureToken::MutableReference(inner) => {
+                mutable_ref_arguments.insert(idx as LocalIndex, arg.clone());
+                (context.borrow_arg_mut(idx, arg)?.clone(), &**inner, false)
+            }
+            SignatureToken::Reference(inner) => {
+                (context.borrow_arg(idx, arg)?.clone(), &**inner, false)
+            }
+            _ => (
+                context.by_value_arg(CommandKind::MoveCall, idx, arg)?,
+                param_type,
+                true,
+            ),
+        };
+        check_param_type::<Mode>(view, &type_arguments, idx, &value, inner_param_type)?;
+        if let Value::Object(object_value) = &value {
+            let id = object_value.id();
+            if let Some(metadata) = context.input_object_metadata(&id) {
+                input_objects.insert(id, (by_value, metadata.owner));
+            }
+        }
+        serialized_arguments.push(value.to_bytes());
+    }
+    if tx_ctx_kind != TxContextKind::None {
+        serialized_arguments.push(tx_context.to_vec());
+    }
+
+    let move_gas_status = gas_status.create_move_gas_status();
+    let is_metered = move_gas_status.is_metered();
+    let mut session = new_session(vm, &*state_view, input_objects, is_metered, protocol_config);
+    // check type arguments separately for error conversion
+    for (idx, ty) in type_arguments.iter().enumerate() {
+        session
+            .load_type(ty)
+            .map_err(|e| convert_type_argument_error(idx, e, vm, state_view))?;
+    }
+    // script visibility checked manually for entry points
+    let result = session
+        .execute_function_bypass_visibility(
+            &module_id,
+            &function,
+            type_arguments.clone(),
+            serialized_arguments,
+            move_gas_status,
+        )
+        .map_err(|e| convert_vm_error(e, vm, state_view))?;
+    let mode_result =
+        Mode::make_programmable_result(&session, &module_id, &function, &type_arguments, &result)?;
+    let return_types = session
+        .load_function(&module_id, &function, &type_arguments)
+        .and_then(|loaded_function| {
+            loaded_function
+                .return_
+                .iter()
+                .map(|ty| {
+                    let ty = ty
+                        .subst(&loaded_function.type_arguments)
+                        .map_err(|e| e.finish(Location::Undefined))?;
+                    Ok((session.get_type_tag(&ty)?, session.get_type_abilities(&ty)?))
+                })
+                .collect::<VMResult<Vec<_>>>()
+        })
+        .map_err(|e| convert_vm_error(e, vm, state_view))?;
+
+    let (change_set, events, mut native_context_extensions) = session
+        .finish_with_extensions()
+        .map_err(|e| convert_vm_error(e, vm, state_view))?;
+    let SerializedReturnValues {
+        mut mutable_reference_outputs,
+        return_values,
+    } = result;
+    let object_runtime: ObjectRuntime = native_context_extensions.remove();
+    std::mem::drop(native_context_extensions);
+
+    // Sui Move programs should never touch global state, so ChangeSet should be empty
+    assert_invariant!(change_set.accounts().is_empty(), "Change set must be empty");
+    // Sui Move no longer uses Move's internal event system
+    assert_invariant!(events.is_empty(), "Events must be empty");
+
+    // the TxContext must reflect the objects created by this call, for the following commands
+    if tx_ctx_kind == TxContextKind::Mutable {
+        let (_, ctx_bytes, _) = mutable_reference_outputs.pop().unwrap();
+        let updated_ctx: TxContext = bcs::from_bytes(&ctx_bytes).unwrap();
+        tx_context.update_state(updated_ctx)?;
+    }
+    for (local_idx, bytes, _layout) in mutable_reference_outputs {
+        let Some(arg) = mutable_ref_arguments.remove(&local_idx) else {
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::InvariantViolation,
+                "Mutable reference output for an unknown argument",
+            ));
+        };
+        context.restore_mut_ref(arg, bytes, &single_tx_context)?;
+    }
+    assert_invariant!(
+        mutable_ref_arguments.is_empty(),
+        "All mutable references should have been marked as updated"
+    );
+
+    let command_results = return_values
+        .into_iter()
+        .zip(return_types)
+        .map(|((bytes, _layout), (ty, abilities))| match ty {
+            TypeTag::Struct(type_) if abilities.has_key() => Value::Object(ObjectValue {
+                type_: *type_,
+                has_public_transfer: abilities.has_store(),
+                contents: bytes,
+            }),
+            ty => Value::Raw(RawValueType::Loaded { ty, abilities }, bytes),
+        })
+        .collect::<Vec<_>>();
+    let returned_object_ids = command_results
+        .iter()
+        .filter_map(|value| match value {
+            Value::Object(object_value) => Some(object_value.id()),
+            Value::Raw(_, _) => None,
+        })
+        .collect::<BTreeSet<_>>();
+
+    let object_runtime::RuntimeResults {
+        writes,
+        deletions,
+        user_events,
+        loaded_child_objects,
+        created_object_ids,
+    } = object_runtime.finish()?;
+    // objects created by this call and returned to the transaction have yet to be written
+    for id in &returned_object_ids {
+        if created_object_ids.contains(id) {
+            context.new_object_ids.insert(*id);
+        }
+    }
+
+    let session = new_session(
+        vm,
+        &*state_view,
+        BTreeMap::new(),
+        is_metered,
+        protocol_config,
+    );
+    let writes = writes
+        .into_iter()
+        .map(|(id, (write_kind, owner, ty, tag, value))| {
+            let abilities = session.get_type_abilities(&ty)?;
+            let layout = session.get_type_layout(&TypeTag::Struct(Box::new(tag.clone())))?;
+            let bytes = value.simple_serialize(&layout).unwrap();
+            Ok((id, (write_kind, owner, tag, abilities, bytes)))
+        })
+        .collect::<VMResult<Vec<_>>>()
+        .map_err(|e| convert_vm_error(e, vm, state_view))?;
+    let user_events = user_events
+        .into_iter()
+        .map(|(tag, value)| {
+            let layout = session.get_type_layout(&TypeTag::Struct(Box::new(tag.clone())))?;
+            let bytes = value.simple_serialize(&layout).unwrap();
+            Ok((tag, bytes))
+        })
+        .collect::<VMResult<Vec<_>>>()
+        .map_err(|e| convert_vm_error(e, vm, state_view))?;
+    let (empty_changes, empty_events) = session
+        .finish()
+        .map_err(|e| convert_vm_error(e, vm, state_view))?;
+    debug_assert!(empty_changes.into_inner().is_empty());
+    debug_assert!(empty_events.is_empty());
+
+    let mut changes = BTreeMap::new();
+    for (id, (write_kind, owner, tag, abilities, contents)) in writes {
+        // the object runtime only knows about the objects used in this call, so objects from the
+        // transaction inputs or created by a previous command need their write kind fixed up
+        let (write_kind, version) = match loaded_child_objects.get(&id) {
+            Some(version) => (WriteKind::Mutate, *version),
+            None if write_kind == WriteKind::Create => (WriteKind::Create, SequenceNumber::new()),
+            None => object_write_kind_and_version(context, &id),
+        };
+        // safe because `has_public_transfer` was properly determined from the abilities
+        let move_object = unsafe {
+            MoveObject::new_from_execution(
+                tag,
+                abilities.has_store(),
+                version,
+                contents,
+                protocol_config,
+            )?
+        };
+        let object = Object::new_move(move_object, owner, tx_context.digest());
+        changes.insert(
+            id,
+            ObjectChange::Write(single_tx_context.clone(), object, write_kind),
+        );
+    }
+    for (id, delete_kind) in deletions {
+        // by-value objects returned from the call are still held by the transaction
+        if delete_kind == DeleteKind::Wrap && returned_object_ids.contains(&id) {
+            continue;
+        }
+        let version = match loaded_child_objects.get(&id) {
+            Some(version) => Some(*version),
+            None => object_deleted_version(state_view, context, &id)?,
+        };
+        if let Some(version) = version {
+            changes.insert(
+                id,
+                ObjectChange::Delete(single_tx_context.clone(), version, delete_kind),
+            );
+        }
+    }
+    for (tag, contents) in user_events {
+        state_view.log_event(Event::move_event(
+            module_id.address(),
+            module_id.name(),
+            tx_context.sender(),
+            tag,
+            contents,
+        ))
+    }
+    state_view.apply_object_changes(changes);
+
+    Mode::add_result(results, command_idx, mode_result);
+    Ok(command_results)
+}
+
+/// Checks that `value` can be used for a parameter of type `param_type`
+fn check_param_type<Mode: ExecutionMode>(
+    view: &BinaryIndexedView,
+    type_arguments: &[TypeTag],
+    idx: usize,
+    value: &Value,
+    param_type: &SignatureToken,
+) -> Result<(), ExecutionError> {
+    let type_matches = match value {
+        Value::Object(object_value) => {
+            struct_tag_equals_sig_token(view, type_arguments, &object_value.type_, param_type)
+        }
+        Value::Raw(RawValueType::Loaded { ty, .. }, _) => {
+            type_tag_equals_sig_token(view, type_arguments, ty, param_type)
+        }
+        // dev-inspect does not make state changes and just a developer aid, so let through
+        // any BCS bytes (they will be checked later by the VM)
+        Value::Raw(RawValueType::Any, _) if Mode::allow_arbitrary_function_calls() => true,
+        Value::Raw(RawValueType::Any, bytes) => {
+            let (is_primitive, type_layout_opt) = primitive_type(view, type_arguments, param_type);
+            if !is_primitive {
+                return Err(command_argument_error(
+                    CommandArgumentError::InvalidUsageOfPureArg,
+                    idx,
+                ));
+            }
+            if let Some(layout) = &type_layout_opt {
+                MoveValue::simple_deserialize(bytes, layout).map_err(|_| {
+                    command_argument_error(CommandArgumentError::InvalidBCSBytes, idx)
+                })?;
+            }
+            validate_primitive_arg(view, bytes, idx as LocalIndex, param_type, type_layout_opt)
+                .map_err(|_| command_argument_error(CommandArgumentError::InvalidBCSBytes, idx))?;
+            true
+        }
+    };
+    if type_matches {
+        Ok(())
+    } else {
+        Err(command_argument_error(
+            CommandArgumentError::TypeMismatch,
+            idx,
+        ))
+    }
+}
+
+/// The write kind and version for an object being written that was previously held by the
+/// transaction. Objects from the inputs are mutated, objects created by a command in this
+/// transaction are created, and anything else was unwrapped
+fn object_write_kind_and_version(
+    context: &mut ExecutionContext,
+    id: &ObjectID,
+) -> (WriteKind, SequenceNumber) {
+    if let Some(metadata) = context.input_object_metadata(id) {
+        (WriteKind::Mutate, metadata.version)
+    } else if context.new_object_ids.remove(id) {
+        (WriteKind::Create, SequenceNumber::new())
+    } else {
+        (WriteKind::Unwrap, SequenceNumber::new())
+    }
+}
+
+/// The version to use when deleting an object that was previously held by the transaction, or
+/// None if the object never existed in storage (in which case there is nothing to delete)
+fn object_deleted_version<S: ParentSync>(
+    state_view: &S,
+    context: &mut ExecutionContext,
+    id: &ObjectID,
+) -> Result<Option<SequenceNumber>, ExecutionError> {
+    if let Some(metadata) = context.input_object_metadata(id) {
+        return Ok(Some(metadata.version));
+    }
+    if context.new_object_ids.remove(id) {
+        return Ok(None);
+    }
+    match state_view.get_latest_parent_entry_ref(*id) {
+        Ok(Some((_, previous_version, _))) => Ok(Some(previous_version)),
+        // This object was not created this transaction but has never existed in storage, skip it.
+        Ok(None) => Ok(None),
+        _ => Err(ExecutionError::new_with_source(
+            ExecutionErrorKind::InvariantViolation,
+            missing_unwrapped_msg(id),
+        )),
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::node::{
     default_end_of_epoch_broadcast_channel_capacity, default_epoch_duration_ms,
-    AuthorityKeyPairWithPath, KeyPairWithPath,
+    default_json_rpc_multi_get_max_size, AuthorityKeyPairWithPath, KeyPairWithPath,
 };
 use crate::{
     genesis,
@@ -370,8 +370,10 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                         _ => utils::get_available_port("127.0.0.1"),
                     },
                     json_rpc_address: utils::available_local_socket_address(),
+                    json_rpc_multi_get_max_size: default_json_rpc_multi_get_max_size(),
                     consensus_config: Some(consensus_config),
                     enable_event_processing: false,
+                    event_store_config: Default::default(),
                     epoch_duration_ms: self.epoch_duration_ms,
                     genesis: crate::node::Genesis::new(genesis.clone()),
                     grpc_load_shed: initial_accounts_config.grpc_load_shed,
@@ -381,6 +383,8 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     end_of_epoch_broadcast_channel_capacity:
                         default_end_of_epoch_broadcast_channel_capacity(),
                     checkpoint_executor_config: Default::default(),
+                    state_snapshot_path: None,
+                    transaction_deny_config: Default::default(),
                 }
             })
             .collect();
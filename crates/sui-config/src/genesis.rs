@@ -816,9 +816,11 @@ fn create_genesis_transaction(
                 transaction_data.kind,
                 signer,
                 gas,
+                vec![],
                 *genesis_transaction.digest(),
                 Default::default(),
                 &move_vm,
+                &adapter::LinkedVMCache::default(),
                 &native_functions,
                 SuiGasStatus::new_unmetered(),
                 &EpochData::genesis(),
@@ -1169,9 +1171,11 @@ mod test {
                 transaction_data.kind,
                 signer,
                 gas,
+                vec![],
                 *genesis_transaction.digest(),
                 Default::default(),
                 &move_vm,
+                &adapter::LinkedVMCache::default(),
                 &native_functions,
                 SuiGasStatus::new_unmetered(),
                 &EpochData::genesis(),
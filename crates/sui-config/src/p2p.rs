@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{net::SocketAddr, num::NonZeroU32, time::Duration};
+use std::{net::SocketAddr, num::NonZeroU32, path::PathBuf, time::Duration};
 
 use multiaddr::Multiaddr;
 use serde::{Deserialize, Serialize};
@@ -122,6 +122,19 @@ pub struct StateSyncConfig {
     /// If unspecified, this will default to no limit.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub get_transaction_and_effects_rate_limit: Option<NonZeroU32>,
+
+    /// Directory of a checkpoint archive. Checkpoints, along with their transactions and effects,
+    /// are read from the archive when none of our peers is able to provide them.
+    ///
+    /// If unspecified, no archive will be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<PathBuf>,
+
+    /// Write every synced checkpoint to the archive at `archive_path`.
+    ///
+    /// If unspecified, this will default to `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_archive_writer: Option<bool>,
 }
 
 impl StateSyncConfig {
@@ -172,6 +185,10 @@ impl StateSyncConfig {
             .map(Duration::from_millis)
             .unwrap_or(DEFAULT_TIMEOUT)
     }
+
+    pub fn enable_archive_writer(&self) -> bool {
+        self.archive_path.is_some() && self.enable_archive_writer.unwrap_or(false)
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
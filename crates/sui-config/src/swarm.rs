@@ -4,7 +4,7 @@
 use crate::node::AuthorityStorePruningConfig;
 use crate::node::{
     default_end_of_epoch_broadcast_channel_capacity, default_epoch_duration_ms,
-    AuthorityKeyPairWithPath, KeyPairWithPath,
+    default_json_rpc_multi_get_max_size, AuthorityKeyPairWithPath, KeyPairWithPath,
 };
 use crate::p2p::{P2pConfig, SeedPeer};
 use crate::{builder, genesis, utils, Config, NodeConfig, ValidatorInfo};
@@ -228,8 +228,10 @@ impl<'a> FullnodeConfigBuilder<'a> {
             // provide the entire socket address here to avoid confusion.
             admin_interface_port: self.admin_port.unwrap_or_else(|| get_available_port(8888)),
             json_rpc_address,
+            json_rpc_multi_get_max_size: default_json_rpc_multi_get_max_size(),
             consensus_config: None,
             enable_event_processing: self.enable_event_store,
+            event_store_config: Default::default(),
             epoch_duration_ms: default_epoch_duration_ms(),
             genesis: validator_config.genesis.clone(),
             grpc_load_shed: None,
@@ -239,6 +241,8 @@ impl<'a> FullnodeConfigBuilder<'a> {
             end_of_epoch_broadcast_channel_capacity:
                 default_end_of_epoch_broadcast_channel_capacity(),
             checkpoint_executor_config: Default::default(),
+            state_snapshot_path: None,
+            transaction_deny_config: Default::default(),
         })
     }
 }
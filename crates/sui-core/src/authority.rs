@@ -8,6 +8,7 @@ use chrono::prelude::*;
 use fastcrypto::encoding::Base58;
 use fastcrypto::encoding::Encoding;
 use fastcrypto::traits::KeyPair;
+use itertools::Itertools;
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
@@ -27,8 +28,11 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
-use std::{collections::HashMap, pin::Pin};
-use sui_config::node::AuthorityStorePruningConfig;
+use std::{
+    collections::{BTreeMap, HashMap},
+    pin::Pin,
+};
+use sui_config::node::{AuthorityStorePruningConfig, TransactionDenyConfig};
 use sui_types::message_envelope::Message;
 use sui_types::parse_sui_struct_tag;
 use tap::TapFallible;
@@ -43,13 +47,17 @@ use narwhal_config::{
     Committee as ConsensusCommittee, WorkerCache as ConsensusWorkerCache,
     WorkerId as ConsensusWorkerId,
 };
-use sui_adapter::{adapter, execution_mode};
+use sui_adapter::{
+    adapter::{self, LinkedVMCache},
+    execution_mode,
+};
 use sui_config::genesis::Genesis;
 use sui_json_rpc_types::{
-    type_and_fields_from_move_struct, DevInspectResults, SuiEvent, SuiEventEnvelope,
-    SuiTransactionEffects,
+    type_and_fields_from_move_struct, DevInspectResults, SuiEvent, SuiEventEnvelope, SuiEventQuery,
+    SuiObjectVersion, SuiObjectVersionStatus, SuiTransactionEffects,
 };
 use sui_macros::nondeterministic;
+use sui_protocol_config::SupportedProtocolVersions;
 use sui_storage::indexes::{ObjectIndexChanges, MAX_GET_OWNED_OBJECT_SIZE};
 use sui_storage::write_ahead_log::WriteAheadLog;
 use sui_storage::{
@@ -57,10 +65,12 @@ use sui_storage::{
     write_ahead_log::{DBTxGuard, TxGuard},
     IndexStore,
 };
-use sui_types::committee::{EpochId, ProtocolVersion};
+use sui_types::coin::Coin;
+use sui_types::committee::{EpochId, ProtocolVersion, StakeUnit};
 use sui_types::crypto::{sha3_hash, AuthorityKeyPair, NetworkKeyPair, Signer};
 use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldType};
 use sui_types::event::{Event, EventID};
+use sui_types::filter::{EventFilter, TransactionFilter};
 use sui_types::gas::{GasCostSummary, GasPrice, SuiCostTable, SuiGasStatus};
 use sui_types::messages_checkpoint::{
     CheckpointContents, CheckpointContentsDigest, CheckpointDigest, CheckpointSequenceNumber,
@@ -68,7 +78,7 @@ use sui_types::messages_checkpoint::{
 };
 use sui_types::messages_checkpoint::{CheckpointRequest, CheckpointResponse};
 use sui_types::object::{MoveObject, Owner, PastObjectRead};
-use sui_types::query::{EventQuery, TransactionQuery};
+use sui_types::query::{EventQuery, ObjectTypeFilter, TransactionQuery};
 use sui_types::storage::{ObjectKey, WriteKind};
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::temporary_store::InnerTemporaryStore;
@@ -97,8 +107,11 @@ use crate::epoch::committee_store::CommitteeStore;
 use crate::epoch::epoch_metrics::EpochMetrics;
 use crate::execution_driver::execution_process;
 use crate::module_cache_gauge::ModuleCacheGauge;
+use crate::streamer::Streamer;
 use crate::{
-    event_handler::EventHandler, transaction_input_checker, transaction_manager::TransactionManager,
+    event_handler::{EventHandler, EVENT_DISPATCH_BUFFER_SIZE},
+    transaction_input_checker,
+    transaction_manager::TransactionManager,
 };
 use sui_adapter::execution_engine;
 use sui_types::epoch_data::EpochData;
@@ -157,6 +170,7 @@ pub struct AuthorityMetrics {
     total_effects: IntCounter,
     pub shared_obj_tx: IntCounter,
     tx_already_processed: IntCounter,
+    denied_transactions: IntCounter,
     num_input_objs: Histogram,
     num_shared_objects: Histogram,
     batch_size: Histogram,
@@ -190,6 +204,9 @@ pub struct AuthorityMetrics {
     pub consensus_handler_processed_bytes: IntCounter,
 }
 
+/// Number of live objects indexed in a single batch when creating the owner index.
+const OWNER_INDEX_BATCH_SIZE: usize = 10_000;
+
 // Override default Prom buckets for positive numbers in 0-50k range
 const POSITIVE_INT_BUCKETS: &[f64] = &[
     1., 2., 5., 10., 20., 50., 100., 200., 500., 1000., 2000., 5000., 10000., 20000., 50000.,
@@ -234,6 +251,12 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            denied_transactions: register_int_counter_with_registry!(
+                "num_denied_transactions",
+                "Number of transaction orders refused because of the transaction deny-list",
+                registry,
+            )
+            .unwrap(),
             tx_already_processed: register_int_counter_with_registry!(
                 "num_tx_already_processed",
                 "Number of transaction orders already processed previously",
@@ -402,6 +425,8 @@ pub struct AuthorityState {
     /// Move native functions that are available to invoke
     pub(crate) _native_functions: NativeFunctionTable,
     pub(crate) move_vm: Arc<MoveVM>,
+    /// VMs for calls into packages that link against upgraded packages
+    pub(crate) linked_vms: LinkedVMCache,
 
     /// The database
     pub database: Arc<AuthorityStore>, // TODO: remove pub
@@ -413,10 +438,16 @@ pub struct AuthorityState {
     pub module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>, // TODO: use strategies (e.g. LRU?) to constraint memory usage
 
     pub event_handler: Option<Arc<EventHandler>>,
+    /// Streams executed transactions to subscribers, on nodes that index transactions.
+    pub transaction_streamer: Option<Arc<Streamer<ExecutedTransaction, TransactionFilter>>>,
     checkpoint_store: Arc<CheckpointStore>,
 
     committee_store: Arc<CommitteeStore>,
 
+    /// Senders, packages and objects whose transactions are refused. Shared with the consensus
+    /// transaction validator, and replaceable at runtime.
+    transaction_deny_config: Arc<ArcSwap<TransactionDenyConfig>>,
+
     /// Manages pending certificates and their missing input objects.
     transaction_manager: Arc<TransactionManager>,
 
@@ -451,6 +482,27 @@ impl AuthorityState {
         self.committee_store.clone()
     }
 
+    pub fn transaction_deny_config(&self) -> Arc<ArcSwap<TransactionDenyConfig>> {
+        self.transaction_deny_config.clone()
+    }
+
+    /// Replaces the deny-list. Transactions that were already signed are not affected.
+    pub fn update_transaction_deny_config(&self, deny_config: TransactionDenyConfig) {
+        info!(?deny_config, "Updating transaction deny-list");
+        self.transaction_deny_config.store(Arc::new(deny_config));
+    }
+
+    /// Returns the certificates enqueued for execution that are waiting for input objects, along
+    /// with the objects they are missing.
+    pub fn pending_certificates(&self) -> Vec<(TransactionDigest, Vec<ObjectKey>)> {
+        self.transaction_manager.pending_certificates()
+    }
+
+    /// Returns the certificates that are being executed.
+    pub fn executing_certificates(&self) -> Vec<TransactionDigest> {
+        self.transaction_manager.executing_certificates()
+    }
+
     /// This is a private method and should be kept that way. It doesn't check whether
     /// the provided transaction is a system transaction, and hence can only be called internally.
     async fn handle_transaction_impl(
@@ -458,6 +510,14 @@ impl AuthorityState {
         transaction: VerifiedTransaction,
         epoch_store: &Arc<AuthorityPerEpochStore>,
     ) -> Result<VerifiedSignedTransaction, SuiError> {
+        if let Err(err) = transaction_input_checker::check_transaction_deny_list(
+            &self.transaction_deny_config.load(),
+            &transaction.data().intent_message.value,
+        ) {
+            self.metrics.denied_transactions.inc();
+            return Err(err);
+        }
+
         let (_gas_status, input_objects) = transaction_input_checker::check_transaction_input(
             &self.database,
             epoch_store.as_ref(),
@@ -925,6 +985,7 @@ impl AuthorityState {
         let transaction_data = certificate.data().intent_message.value.clone();
         let signer = transaction_data.signer();
         let gas = transaction_data.gas();
+        let extra_gas = transaction_data.extra_gas_payment().to_vec();
         let (inner_temp_store, effects, _execution_error) =
             execution_engine::execute_transaction_to_effects::<execution_mode::Normal, _>(
                 shared_object_refs,
@@ -932,9 +993,11 @@ impl AuthorityState {
                 transaction_data.kind,
                 signer,
                 gas,
+                extra_gas,
                 *certificate.digest(),
                 transaction_dependencies,
                 &self.move_vm,
+                &self.linked_vms,
                 &self._native_functions,
                 gas_status,
                 &epoch_store.epoch_start_configuration().epoch_data(),
@@ -984,6 +1047,7 @@ impl AuthorityState {
         );
         let signer = transaction.signer();
         let gas = transaction.gas();
+        let extra_gas = transaction.extra_gas_payment().to_vec();
         let (_inner_temp_store, effects, _execution_error) =
             execution_engine::execute_transaction_to_effects::<execution_mode::Normal, _>(
                 shared_object_refs,
@@ -991,9 +1055,11 @@ impl AuthorityState {
                 transaction.kind,
                 signer,
                 gas,
+                extra_gas,
                 transaction_digest,
                 transaction_dependencies,
                 &self.move_vm,
+                &self.linked_vms,
                 &self._native_functions,
                 gas_status,
                 &epoch_store.epoch_start_configuration().epoch_data(),
@@ -1028,6 +1094,7 @@ impl AuthorityState {
         );
         let (gas_object_ref, input_objects) = transaction_input_checker::check_dev_inspect_input(
             &self.database,
+            protocol_config,
             &transaction_kind,
             gas_object,
         )
@@ -1067,9 +1134,11 @@ impl AuthorityState {
                 transaction_kind,
                 sender,
                 gas_object_ref,
+                vec![],
                 transaction_digest,
                 transaction_dependencies,
                 &self.move_vm,
+                &self.linked_vms,
                 &self._native_functions,
                 gas_status,
                 &EpochData::new(epoch), /* TODO(epoch_data): this needs to be figured out */
@@ -1095,7 +1164,7 @@ impl AuthorityState {
             .process_object_index(effects)
             .tap_err(|e| warn!("{e}"))?;
 
-        indexes.index_tx(
+        let sequence = indexes.index_tx(
             cert.sender_address(),
             cert.data()
                 .intent_message
@@ -1109,13 +1178,24 @@ impl AuthorityState {
             cert.data()
                 .intent_message
                 .value
-                .move_calls()
-                .iter()
-                .map(|mc| (mc.package, mc.module.clone(), mc.function.clone())),
+                .move_functions()
+                .into_iter()
+                .map(|(package, module, function)| (package, module.clone(), function.clone())),
+            effects.events.iter().filter_map(|event| match event {
+                Event::MoveEvent { type_, .. } => Some(type_.clone()),
+                _ => None,
+            }),
             changes,
             digest,
             timestamp_ms,
-        )
+        )?;
+
+        // The checkpoint executor skips transactions which were not indexed yet when their
+        // checkpoint got executed, so index them with their checkpoint here.
+        if let Some(checkpoint) = self.database.get_transaction_checkpoint(digest)? {
+            indexes.index_checkpoint(checkpoint, &[*digest])?;
+        }
+        Ok(sequence)
     }
 
     fn process_object_index(
@@ -1294,6 +1374,18 @@ impl AuthorityState {
                 .tap_ok(|_| self.metrics.post_processing_total_tx_indexed.inc())
                 .tap_err(|e| error!(?tx_digest, "Post processing - Couldn't index tx: {e}"));
 
+            // Stream tx
+            if let (Some(transaction_streamer), Ok(_)) = (&self.transaction_streamer, &res) {
+                let executed_transaction = ExecutedTransaction {
+                    certificate: certificate.clone(),
+                    effects: effects.clone(),
+                    timestamp_ms,
+                };
+                if let Err(e) = transaction_streamer.send(executed_transaction).await {
+                    warn!(?tx_digest, "Post processing - Couldn't stream tx: {e}");
+                }
+            }
+
             // Emit events
             if let (Some(event_handler), Ok(seq)) = (&self.event_handler, res) {
                 event_handler
@@ -1451,7 +1543,7 @@ impl AuthorityState {
         checkpoint_store: Arc<CheckpointStore>,
         prometheus_registry: &Registry,
         pruning_config: &AuthorityStorePruningConfig,
-        genesis_objects: &[Object],
+        transaction_deny_config: TransactionDenyConfig,
     ) -> Arc<Self> {
         let native_functions =
             sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
@@ -1461,10 +1553,13 @@ impl AuthorityState {
         );
         let module_cache = Arc::new(SyncModuleCache::new(ResolverWrapper(store.clone())));
         let event_handler = event_store.map(|es| {
-            let handler = EventHandler::new(es, module_cache.clone());
+            let handler = EventHandler::new(store.clone(), es, module_cache.clone());
             handler.regular_cleanup_task();
             Arc::new(handler)
         });
+        let transaction_streamer = indexes
+            .as_ref()
+            .map(|_| Arc::new(Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE)));
         let metrics = Arc::new(AuthorityMetrics::new(prometheus_registry));
         let (tx_ready_certificates, rx_ready_certificates) = unbounded_channel();
         let transaction_manager = Arc::new(TransactionManager::new(
@@ -1483,6 +1578,7 @@ impl AuthorityState {
             secret,
             _native_functions: native_functions,
             move_vm,
+            linked_vms: LinkedVMCache::default(),
             epoch_store: ArcSwap::new(epoch_store.clone()),
             database: store.clone(),
             indexes,
@@ -1490,8 +1586,10 @@ impl AuthorityState {
             // this is because they largely deal with different types of MoveStructs
             module_cache,
             event_handler,
+            transaction_streamer,
             checkpoint_store,
             committee_store,
+            transaction_deny_config: Arc::new(ArcSwap::from_pointee(transaction_deny_config)),
             transaction_manager,
             tx_execution_shutdown: Mutex::new(Some(tx_execution_shutdown)),
             metrics,
@@ -1518,8 +1616,15 @@ impl AuthorityState {
         ));
 
         state
-            .create_owner_index_if_empty(genesis_objects)
-            .expect("Error indexing genesis objects.");
+            .create_owner_index_if_empty()
+            .expect("Error indexing live objects.");
+        state
+            .backfill_transaction_indexes()
+            .expect("Error indexing the checkpoints and Move events of transactions.");
+        // Balances are only served to RPC reads, leave the index unseeded rather than failing.
+        if let Err(err) = state.seed_balance_index() {
+            error!("Failed to index the balances of live coins: {:?}", err);
+        }
 
         state
     }
@@ -1586,14 +1691,10 @@ impl AuthorityState {
             checkpoint_store,
             &registry,
             &AuthorityStorePruningConfig::default(),
-            genesis.objects(),
+            TransactionDenyConfig::default(),
         )
         .await;
 
-        state
-            .create_owner_index_if_empty(genesis.objects())
-            .unwrap();
-
         state
     }
 
@@ -1601,6 +1702,10 @@ impl AuthorityState {
         &self.transaction_manager
     }
 
+    pub fn index_store(&self) -> Option<&Arc<IndexStore>> {
+        self.indexes.as_ref()
+    }
+
     /// Adds certificates to the pending certificate store and transaction manager for ordered execution.
     pub fn enqueue_certificates_for_execution(
         &self,
@@ -1655,7 +1760,9 @@ impl AuthorityState {
         Ok(())
     }
 
-    fn create_owner_index_if_empty(&self, genesis_objects: &[Object]) -> SuiResult {
+    /// Index the owners of the live objects in the store, which are either the genesis objects or
+    /// the objects restored from a state snapshot, if the owner index is empty.
+    fn create_owner_index_if_empty(&self) -> SuiResult {
         let Some(index_store) = &self.indexes else{
             return Ok(())
         };
@@ -1663,30 +1770,124 @@ impl AuthorityState {
             return Ok(());
         }
 
-        let mut new_owners = vec![];
-        let mut new_dynamic_fields = vec![];
-        for o in genesis_objects.iter() {
-            match o.owner {
-                Owner::AddressOwner(addr) => new_owners.push((
-                    (addr, o.id()),
-                    ObjectInfo::new(&o.compute_object_reference(), o),
-                )),
-                Owner::ObjectOwner(object_id) => {
-                    let id = o.id();
-                    let Some(info) = self.try_create_dynamic_field_info(o)? else{
-                        continue;
-                    };
-                    new_dynamic_fields.push(((ObjectID::from(object_id), id), info));
+        for chunk in &self
+            .database
+            .iter_live_object_set()
+            .chunks(OWNER_INDEX_BATCH_SIZE)
+        {
+            let keys: Vec<_> = chunk.map(|obj_ref| ObjectKey::from(&obj_ref)).collect();
+            let mut new_owners = vec![];
+            let mut new_dynamic_fields = vec![];
+            for o in self
+                .database
+                .multi_get_object_by_key(&keys)?
+                .iter()
+                .flatten()
+            {
+                match o.owner {
+                    Owner::AddressOwner(addr) => new_owners.push((
+                        (addr, o.id()),
+                        ObjectInfo::new(&o.compute_object_reference(), o),
+                    )),
+                    Owner::ObjectOwner(object_id) => {
+                        let id = o.id();
+                        let Some(info) = self.try_create_dynamic_field_info(o)? else{
+                            continue;
+                        };
+                        new_dynamic_fields.push(((ObjectID::from(object_id), id), info));
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
+
+            index_store.insert_genesis_objects(ObjectIndexChanges {
+                deleted_owners: vec![],
+                deleted_dynamic_fields: vec![],
+                new_owners,
+                new_dynamic_fields,
+            })?;
         }
+        Ok(())
+    }
 
-        index_store.insert_genesis_objects(ObjectIndexChanges {
-            deleted_owners: vec![],
-            deleted_dynamic_fields: vec![],
-            new_owners,
-            new_dynamic_fields,
+    /// Index the checkpoints and Move events of the transactions indexed before these indexes
+    /// existed. This runs once, and is a no-op on a database created with them.
+    fn backfill_transaction_indexes(&self) -> SuiResult {
+        let Some(index_store) = &self.indexes else {
+            return Ok(())
+        };
+        index_store.backfill_checkpoints_and_move_events(|digests| {
+            let checkpoints = self.database.multi_get_transaction_checkpoint(digests)?;
+            let effects = self.database.multi_get_executed_effects(digests)?;
+            Ok(checkpoints
+                .into_iter()
+                .zip(effects)
+                .map(|(checkpoint, effects)| {
+                    let move_events = effects
+                        .into_iter()
+                        .flat_map(|effects| effects.events)
+                        .filter_map(|event| match event {
+                            Event::MoveEvent { type_, .. } => Some(type_),
+                            _ => None,
+                        })
+                        .collect();
+                    (checkpoint, move_events)
+                })
+                .collect())
+        })
+    }
+
+    /// Seed the balance index with the coins of the live object set, as of the highest executed
+    /// checkpoint, if it was not seeded yet. The balance changes of transactions executed ahead of
+    /// that checkpoint are taken back out, as indexing their checkpoint later adds them.
+    fn seed_balance_index(&self) -> SuiResult {
+        let Some(index_store) = &self.indexes else {
+            return Ok(())
+        };
+        let checkpoint = self
+            .checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()?
+            .unwrap_or_default();
+        index_store.seed_balances(checkpoint, || {
+            let mut balances = BTreeMap::new();
+            for chunk in &self
+                .database
+                .iter_live_object_set()
+                .chunks(OWNER_INDEX_BATCH_SIZE)
+            {
+                let keys: Vec<_> = chunk.map(|obj_ref| ObjectKey::from(&obj_ref)).collect();
+                for o in self
+                    .database
+                    .multi_get_object_by_key(&keys)?
+                    .iter()
+                    .flatten()
+                {
+                    let (Owner::AddressOwner(owner), Some(type_)) = (o.owner, o.type_()) else {
+                        continue;
+                    };
+                    if let Some(balance) = Coin::extract_balance_if_coin(o)? {
+                        let coin_type = type_.type_params[0].to_string();
+                        *balances.entry((owner, coin_type)).or_default() += balance as i128;
+                    }
+                }
+            }
+            for effects in self
+                .database
+                .get_executed_effects_after_checkpoint(checkpoint, OWNER_INDEX_BATCH_SIZE)?
+            {
+                for event in &effects.events {
+                    if let Event::CoinBalanceChange {
+                        owner: Owner::AddressOwner(owner),
+                        coin_type,
+                        amount,
+                        ..
+                    } = event
+                    {
+                        *balances.entry((*owner, coin_type.clone())).or_default() -= amount;
+                    }
+                }
+            }
+            Ok(balances)
         })
     }
 
@@ -1788,6 +1989,49 @@ impl AuthorityState {
         }
     }
 
+    /// Same as `get_object_read` for many objects, reading the objects in a single batch.
+    pub async fn multi_get_object_read(
+        &self,
+        object_ids: &[ObjectID],
+    ) -> Result<Vec<ObjectRead>, SuiError> {
+        let latest_refs = self.database.multi_get_latest_parent_entries(object_ids)?;
+        let object_keys: Vec<_> = latest_refs
+            .iter()
+            .flatten()
+            .filter(|(obj_ref, _)| obj_ref.2.is_alive())
+            .map(|(obj_ref, _)| ObjectKey::from(obj_ref))
+            .collect();
+        let mut objects = self
+            .database
+            .multi_get_object_by_key(&object_keys)?
+            .into_iter();
+
+        object_ids
+            .iter()
+            .zip(latest_refs)
+            .map(|(object_id, latest_ref)| match latest_ref {
+                None => Ok(ObjectRead::NotExists(*object_id)),
+                Some((obj_ref, _)) if !obj_ref.2.is_alive() => Ok(ObjectRead::Deleted(obj_ref)),
+                Some((obj_ref, _)) => match objects.next().flatten() {
+                    None => {
+                        error!("Object with in parent_entry is missing from object store, datastore is inconsistent");
+                        Err(SuiError::ObjectNotFound {
+                            object_id: *object_id,
+                            version: Some(obj_ref.1),
+                        })
+                    }
+                    Some(object) => {
+                        let layout = object.get_layout(
+                            ObjectFormatOptions::default(),
+                            self.module_cache.as_ref(),
+                        )?;
+                        Ok(ObjectRead::Exists(obj_ref, object, layout))
+                    }
+                },
+            })
+            .collect()
+    }
+
     async fn get_move_object<T>(&self, object_id: &ObjectID) -> SuiResult<T>
     where
         T: DeserializeOwned,
@@ -1806,6 +2050,61 @@ impl AuthorityState {
         }
     }
 
+    /// Returns the versions of an object from the cursor version, inclusive, along with the
+    /// transactions which produced them. Old versions may have been pruned from the objects table,
+    /// but their parent sync entries are kept.
+    pub fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: usize,
+        descending: bool,
+    ) -> SuiResult<Vec<SuiObjectVersion>> {
+        let entries = self
+            .database
+            .get_parent_entries(object_id, cursor, limit, descending)?;
+        if entries.is_empty() && self.database.get_latest_parent_entry(object_id)?.is_none() {
+            return Err(SuiError::ObjectNotFound {
+                object_id,
+                version: None,
+            });
+        }
+
+        let mut history = Vec::with_capacity(entries.len());
+        for ((_, version, digest), previous_transaction) in entries {
+            let (status, owner) = if digest == ObjectDigest::OBJECT_DIGEST_DELETED {
+                (SuiObjectVersionStatus::Deleted, None)
+            } else if digest == ObjectDigest::OBJECT_DIGEST_WRAPPED {
+                (SuiObjectVersionStatus::Wrapped, None)
+            } else {
+                match self.database.get_object_by_key(&object_id, version)? {
+                    Some(object) => (SuiObjectVersionStatus::Exists, Some(object.owner)),
+                    None => (SuiObjectVersionStatus::Pruned, None),
+                }
+            };
+            let checkpoint = self
+                .database
+                .get_transaction_checkpoint(&previous_transaction)?;
+            let timestamp_ms = match checkpoint {
+                Some(checkpoint) => self
+                    .checkpoint_store
+                    .get_checkpoint_by_sequence_number(checkpoint)?
+                    .map(|checkpoint| checkpoint.summary.timestamp_ms),
+                None => None,
+            };
+            history.push(SuiObjectVersion {
+                version,
+                digest,
+                status,
+                owner,
+                previous_transaction,
+                checkpoint,
+                timestamp_ms,
+            });
+        }
+        Ok(history)
+    }
+
     /// This function aims to serve rpc reads on past objects and
     /// we don't expect it to be called for other purposes.
     /// Depending on the object pruning policies that will be enforced in the
@@ -1888,6 +2187,20 @@ impl AuthorityState {
         }
     }
 
+    pub fn get_owner_objects_page(
+        &self,
+        owner: SuiAddress,
+        type_filter: Option<&ObjectTypeFilter>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        if let Some(indexes) = &self.indexes {
+            indexes.get_owner_objects_page(owner, type_filter, cursor, limit)
+        } else {
+            Err(SuiError::IndexStoreNotAvailable)
+        }
+    }
+
     pub fn get_owner_objects_iterator(
         &self,
         owner: SuiAddress,
@@ -1987,6 +2300,24 @@ impl AuthorityState {
         }
     }
 
+    /// Same as `get_transaction` for many transactions, in the order of the digests, with None
+    /// for the transactions which are not found.
+    pub async fn multi_get_transactions(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> Result<Vec<Option<(VerifiedCertificate, TransactionEffects)>>, anyhow::Error> {
+        let certs = self.database.multi_get_certified_transaction(digests)?;
+        let effects = self.database.multi_get_executed_effects(digests)?;
+        Ok(certs
+            .into_iter()
+            .zip(effects)
+            .map(|pair| match pair {
+                (Some(certificate), Some(effects)) => Some((certificate, effects)),
+                _ => None,
+            })
+            .collect())
+    }
+
     fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
         match &self.indexes {
             Some(i) => Ok(i.clone()),
@@ -2007,6 +2338,55 @@ impl AuthorityState {
             .get_transactions(query, cursor, limit, reverse)
     }
 
+    /// Returns the balance of the coins of the given type owned by the address, once the
+    /// checkpoint was executed.
+    pub fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: &str,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<i128, anyhow::Error> {
+        self.ensure_checkpoint_executed(checkpoint)?;
+        Ok(self
+            .get_indexes()?
+            .get_balance_at_checkpoint(owner, coin_type, checkpoint)?)
+    }
+
+    /// Returns the balances of all the coin types owned by the address, once the checkpoint was
+    /// executed.
+    pub fn get_all_balances_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<BTreeMap<String, i128>, anyhow::Error> {
+        self.ensure_checkpoint_executed(checkpoint)?;
+        Ok(self
+            .get_indexes()?
+            .get_all_balances_at_checkpoint(owner, checkpoint)?)
+    }
+
+    /// Balances are known from the checkpoint the balance index was seeded at, up to the latest
+    /// executed checkpoint.
+    fn ensure_checkpoint_executed(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), anyhow::Error> {
+        let latest = self.get_latest_checkpoint_sequence_number()?;
+        if checkpoint > latest {
+            return Err(anyhow!(
+                "Checkpoint {checkpoint} has not been executed yet, the latest executed checkpoint is {latest}."
+            ));
+        }
+        let start = self.get_indexes()?.balance_index_start()?;
+        if start.map_or(true, |start| checkpoint < start) {
+            return Err(anyhow!(
+                "Balances at checkpoint {checkpoint} are not indexed, the earliest indexed checkpoint is {}.",
+                start.map_or("unknown".to_string(), |start| start.to_string())
+            ));
+        }
+        Ok(())
+    }
+
     fn get_checkpoint_store(&self) -> Arc<CheckpointStore> {
         self.checkpoint_store.clone()
     }
@@ -2093,9 +2473,18 @@ impl AuthorityState {
             .map(|handler| handler.event_store.clone())
     }
 
+    /// Resolve an event ID to its `(seq_num, event_num)` position in the event store.
+    pub fn get_event_position(&self, event_id: &EventID) -> Result<(u64, u64), anyhow::Error> {
+        let tx_seq = self
+            .get_indexes()?
+            .get_transaction_seq(&event_id.tx_digest)?
+            .ok_or_else(|| anyhow!("Transaction [{:?}] not found.", event_id.tx_digest))?;
+        Ok((tx_seq, event_id.event_seq as u64))
+    }
+
     pub async fn get_events(
         &self,
-        query: EventQuery,
+        query: SuiEventQuery,
         cursor: Option<EventID>,
         limit: usize,
         descending: bool,
@@ -2115,6 +2504,16 @@ impl AuthorityState {
             (0, 0)
         };
 
+        let query = match query {
+            SuiEventQuery::Query(query) => query,
+            SuiEventQuery::Filter { filter } => {
+                let filter: EventFilter = filter.try_into()?;
+                let stored_events = es
+                    .events_by_filter(&filter, tx_num, event_num, limit, descending)
+                    .await?;
+                return self.stored_events_to_envelopes(stored_events);
+            }
+        };
         let stored_events = match query {
             EventQuery::All => es.all_events(tx_num, event_num, limit, descending).await?,
             EventQuery::Transaction(digest) => {
@@ -2164,6 +2563,13 @@ impl AuthorityState {
                     .await?
             }
         };
+        self.stored_events_to_envelopes(stored_events)
+    }
+
+    fn stored_events_to_envelopes(
+        &self,
+        stored_events: Vec<StoredEvent>,
+    ) -> Result<Vec<(EventID, SuiEventEnvelope)>, anyhow::Error> {
         let mut events = StoredEvent::into_event_envelopes(stored_events)?;
         // populate parsed json event
         for event in &mut events {
@@ -2464,8 +2870,11 @@ impl AuthorityState {
         transaction_certifier: &dyn TransactionCertifier,
     ) -> anyhow::Result<VerifiedCertificate> {
         let next_epoch = epoch_store.epoch() + 1;
+        let next_epoch_protocol_version =
+            Self::choose_protocol_version(epoch_store.committee(), epoch_store.get_capabilities());
         debug!(
             ?next_epoch,
+            ?next_epoch_protocol_version,
             computation_cost=?gas_cost_summary.computation_cost,
             storage_cost=?gas_cost_summary.storage_cost,
             storage_rebase=?gas_cost_summary.storage_rebate,
@@ -2473,8 +2882,7 @@ impl AuthorityState {
         );
         let tx = VerifiedTransaction::new_change_epoch(
             next_epoch,
-            // Protocol version cannot advance yet.
-            ProtocolVersion::MIN,
+            next_epoch_protocol_version,
             gas_cost_summary.storage_cost,
             gas_cost_summary.computation_cost,
             gas_cost_summary.storage_rebate,
@@ -2489,6 +2897,50 @@ impl AuthorityState {
             .await
     }
 
+    /// Decide the protocol version of the next epoch: the version following the current one, if
+    /// validators with a quorum of stake advertised support for it, or the current version
+    /// otherwise. Versions are never skipped, and the protocol version never goes back.
+    fn choose_protocol_version(
+        committee: &Committee,
+        capabilities: Vec<AuthorityCapabilities>,
+    ) -> ProtocolVersion {
+        let current_version = committee.protocol_version;
+        let next_version = current_version.next();
+
+        let supporting_stake: StakeUnit = capabilities
+            .iter()
+            .filter(|c| {
+                c.supported_protocol_versions
+                    .is_version_supported(next_version)
+            })
+            .map(|c| committee.weight(&c.authority))
+            .sum();
+
+        if supporting_stake >= committee.quorum_threshold() {
+            info!(
+                ?current_version,
+                ?next_version,
+                ?supporting_stake,
+                "Quorum of stake supports the next protocol version, upgrading"
+            );
+            if !SupportedProtocolVersions::SYSTEM_DEFAULT.is_version_supported(next_version) {
+                error!(
+                    ?next_version,
+                    "The next protocol version is not supported by this binary, it must be upgraded before the epoch change"
+                );
+            }
+            next_version
+        } else {
+            debug!(
+                ?current_version,
+                ?next_version,
+                ?supporting_stake,
+                "Not enough stake supports the next protocol version"
+            );
+            current_version
+        }
+    }
+
     /// This function is called at the very end of the epoch.
     /// This step is required before updating new epoch in the db and calling reopen_epoch_db.
     async fn revert_uncommitted_epoch_transactions(
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use futures::future::{select, Either};
+use futures::future::{join_all, select, Either};
 use futures::FutureExt;
 use narwhal_executor::ExecutionIndices;
 use narwhal_types::CommittedSubDag;
@@ -17,14 +17,16 @@ use std::sync::Arc;
 use sui_storage::default_db_options;
 use sui_storage::mutex_table::LockGuard;
 use sui_storage::write_ahead_log::{DBWriteAheadLog, TxGuard, WriteAheadLog};
+use sui_types::accumulator::Accumulator;
 use sui_types::base_types::{AuthorityName, EpochId, ObjectID, SequenceNumber, TransactionDigest};
 use sui_types::committee::Committee;
 use sui_types::crypto::AuthoritySignInfo;
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::{
-    CertifiedTransaction, ConsensusTransaction, ConsensusTransactionKey, ConsensusTransactionKind,
-    SenderSignedData, SharedInputObject, TransactionEffects, TrustedCertificate,
-    TrustedSignedTransactionEffects, VerifiedCertificate, VerifiedSignedTransaction,
+    AuthorityCapabilities, CertifiedTransaction, ConsensusTransaction, ConsensusTransactionKey,
+    ConsensusTransactionKind, SenderSignedData, SharedInputObject, TransactionEffects,
+    TrustedCertificate, TrustedSignedTransactionEffects, VerifiedCertificate,
+    VerifiedSignedTransaction,
 };
 use sui_types::signature::GenericSignature;
 use tracing::{debug, info, trace, warn};
@@ -90,6 +92,7 @@ pub struct AuthorityPerEpochStore {
     /// In-memory cache of the content from the reconfig_state db table.
     reconfig_state_mem: RwLock<ReconfigState>,
     consensus_notify_read: NotifyRead<ConsensusTransactionKey, ()>,
+    checkpoint_state_notify_read: NotifyRead<CheckpointSequenceNumber, Accumulator>,
     /// This is used to notify all epoch specific tasks that epoch has ended.
     epoch_alive_notify: NotifyOnce,
     /// This lock acts as a barrier for tasks that should not be executed in parallel with reconfiguration
@@ -237,10 +240,18 @@ pub struct AuthorityEpochTables {
 
     /// Parameters of the system fixed at the epoch start
     epoch_start_configuration: DBMap<(), EpochStartConfiguration>,
+
+    /// The most recent capabilities advertised by each validator in this epoch, used to decide
+    /// the protocol version of the next epoch
+    authority_capabilities: DBMap<AuthorityName, AuthorityCapabilities>,
+
+    /// The accumulated changes to the live object set made by each checkpoint of the epoch, used
+    /// to compute the root state hash at the end of the epoch
+    state_hash_by_checkpoint: DBMap<CheckpointSequenceNumber, Accumulator>,
 }
 
 /// Parameters of the epoch fixed at epoch start.
-#[derive(Default, Serialize, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Clone, Default, Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct EpochStartConfiguration {
     pub epoch_id: EpochId,
     pub epoch_start_timestamp_ms: CheckpointTimestamp,
@@ -359,6 +370,7 @@ impl AuthorityPerEpochStore {
             epoch_alive_notify,
             epoch_alive: tokio::sync::RwLock::new(true),
             consensus_notify_read: NotifyRead::new(),
+            checkpoint_state_notify_read: NotifyRead::new(),
             end_of_publish: Mutex::new(end_of_publish),
             pending_consensus_certificates: Mutex::new(pending_consensus_certificates),
             wal,
@@ -834,6 +846,114 @@ impl AuthorityPerEpochStore {
         Ok(())
     }
 
+    /// Record the capabilities advertised by a validator, unless a more recent advertisement from
+    /// the same validator was already recorded.
+    pub fn record_capabilities(
+        &self,
+        capabilities: &AuthorityCapabilities,
+        key: ConsensusTransactionKey,
+        consensus_index: ExecutionIndicesWithHash,
+    ) -> SuiResult {
+        let mut write_batch = self.tables.last_consensus_index.batch();
+        // Capabilities received after the end of publish quorum are ignored, so that all
+        // validators decide the next protocol version using the same set of capabilities
+        if self
+            .get_reconfig_state_read_lock_guard()
+            .should_accept_consensus_certs()
+        {
+            let is_newer = self
+                .tables
+                .authority_capabilities
+                .get(&capabilities.authority)?
+                .map_or(true, |existing| {
+                    existing.generation < capabilities.generation
+                });
+            if is_newer {
+                write_batch = write_batch.insert_batch(
+                    &self.tables.authority_capabilities,
+                    [(capabilities.authority, capabilities)],
+                )?;
+            } else {
+                debug!(
+                    "Ignoring stale capabilities {:?}, a more recent message was already recorded",
+                    capabilities
+                );
+            }
+        } else {
+            debug!(
+                "Ignoring capabilities {:?} received after the end of publish quorum",
+                capabilities
+            );
+        }
+        self.finish_consensus_transaction_process_with_batch(write_batch, key, consensus_index)
+    }
+
+    pub fn get_capabilities(&self) -> Vec<AuthorityCapabilities> {
+        self.tables
+            .authority_capabilities
+            .iter()
+            .map(|(_, capabilities)| capabilities)
+            .collect()
+    }
+
+    pub fn get_state_hash_for_checkpoint(
+        &self,
+        checkpoint: &CheckpointSequenceNumber,
+    ) -> SuiResult<Option<Accumulator>> {
+        Ok(self.tables.state_hash_by_checkpoint.get(checkpoint)?)
+    }
+
+    /// Record the accumulator of a checkpoint. Both the checkpoint builder and the checkpoint
+    /// executor accumulate checkpoints, so the accumulator may already be present, in which case
+    /// this is a no-op.
+    pub fn insert_state_hash_for_checkpoint(
+        &self,
+        checkpoint: &CheckpointSequenceNumber,
+        accumulator: &Accumulator,
+    ) -> SuiResult {
+        if self
+            .tables
+            .state_hash_by_checkpoint
+            .contains_key(checkpoint)?
+        {
+            return Ok(());
+        }
+        self.tables
+            .state_hash_by_checkpoint
+            .insert(checkpoint, accumulator)?;
+        self.checkpoint_state_notify_read
+            .notify(checkpoint, accumulator);
+        Ok(())
+    }
+
+    /// Reads the accumulators of the given checkpoints, waiting for the checkpoints that have not
+    /// been accumulated yet.
+    pub async fn notify_read_checkpoint_state_digests(
+        &self,
+        checkpoints: Vec<CheckpointSequenceNumber>,
+    ) -> SuiResult<Vec<Accumulator>> {
+        // We need to register waiters _before_ reading from the database to avoid race conditions
+        let registrations = self
+            .checkpoint_state_notify_read
+            .register_all(checkpoints.clone());
+        let accumulators = self
+            .tables
+            .state_hash_by_checkpoint
+            .multi_get(&checkpoints)?;
+
+        let results =
+            accumulators
+                .into_iter()
+                .zip(registrations.into_iter())
+                .map(|(a, r)| match a {
+                    // Note that Some() clause also drops registration that is already fulfilled
+                    Some(ready) => Either::Left(futures::future::ready(ready)),
+                    None => Either::Right(r),
+                });
+
+        Ok(join_all(results).await)
+    }
+
     pub fn has_sent_end_of_publish(&self, authority: &AuthorityName) -> SuiResult<bool> {
         Ok(self
             .end_of_publish
@@ -1353,6 +1473,16 @@ impl AuthorityPerEpochStore {
                     return Err(());
                 }
             }
+            ConsensusTransactionKind::CapabilityNotification(capabilities) => {
+                if transaction.sender_authority() != capabilities.authority {
+                    warn!(
+                        "CapabilityNotification authority {} does not match narwhal certificate source {}",
+                        capabilities.authority,
+                        transaction.certificate.origin()
+                    );
+                    return Err(());
+                }
+            }
         }
         Ok(VerifiedSequencedConsensusTransaction(transaction))
     }
@@ -1463,6 +1593,11 @@ impl AuthorityPerEpochStore {
                 self.record_end_of_publish(*authority, transaction.key(), consensus_index)?;
                 Ok(None)
             }
+            ConsensusTransactionKind::CapabilityNotification(capabilities) => {
+                debug!("Received CapabilityNotification {:?}", capabilities);
+                self.record_capabilities(capabilities, transaction.key(), consensus_index)?;
+                Ok(None)
+            }
         }
     }
 
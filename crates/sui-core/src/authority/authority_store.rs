@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::authority_store_pruner::AuthorityStorePruner;
-use super::{authority_store_tables::AuthorityPerpetualTables, *};
+use super::{
+    authority_store_tables::{AuthorityPerpetualTables, StateSnapshotRestore},
+    *,
+};
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::checkpoints::checkpoint_executor::CheckpointExecutionMessage;
+use itertools::Itertools;
 use once_cell::sync::OnceCell;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
@@ -14,6 +18,7 @@ use std::path::Path;
 use std::sync::Arc;
 use sui_config::node::AuthorityStorePruningConfig;
 use sui_storage::mutex_table::{LockGuard, MutexTable};
+use sui_types::accumulator::Accumulator;
 use sui_types::message_envelope::Message;
 use sui_types::object::Owner;
 use sui_types::object::PACKAGE_VERSION;
@@ -41,7 +46,7 @@ pub struct AuthorityStore {
 
     // Implementation detail to support notify_read_effects().
     pub(crate) executed_effects_notify_read: NotifyRead<TransactionDigest, TransactionEffects>,
-    _store_pruner: AuthorityStorePruner,
+    store_pruner: AuthorityStorePruner,
     /// This lock denotes current 'execution epoch'.
     /// Execution acquires read lock, checks certificate epoch and holds it until all writes are complete.
     /// Reconfiguration acquires write lock, changes the epoch and revert all transactions
@@ -62,11 +67,17 @@ impl AuthorityStore {
         committee_store: &Arc<CommitteeStore>,
         pruning_config: &AuthorityStorePruningConfig,
         checkpoint_stream: mpsc::Receiver<CheckpointExecutionMessage>,
+        registry: &Registry,
     ) -> SuiResult<Self> {
         let perpetual_tables = Arc::new(AuthorityPerpetualTables::open(path, db_options.clone()));
         if perpetual_tables.database_is_empty()? {
             perpetual_tables.set_recovery_epoch(0)?;
         }
+        if let Some(StateSnapshotRestore::InProgress(digest)) =
+            perpetual_tables.get_state_snapshot_restore()?
+        {
+            return Err(SuiError::StateSnapshotRestoreInterrupted { digest });
+        }
         let cur_epoch = perpetual_tables.get_recovery_epoch_at_restart()?;
         let committee = committee_store
             .get_committee(&cur_epoch)?
@@ -77,6 +88,7 @@ impl AuthorityStore {
             committee,
             pruning_config,
             checkpoint_stream,
+            registry,
         )
         .await
     }
@@ -98,6 +110,7 @@ impl AuthorityStore {
             committee.clone(),
             pruning_config,
             mpsc::channel(1).1,
+            &Registry::new(),
         )
         .await
     }
@@ -108,16 +121,21 @@ impl AuthorityStore {
         committee: Committee,
         pruning_config: &AuthorityStorePruningConfig,
         checkpoint_stream: mpsc::Receiver<CheckpointExecutionMessage>,
+        registry: &Registry,
     ) -> SuiResult<Self> {
         let epoch = committee.epoch;
 
-        let _store_pruner =
-            AuthorityStorePruner::new(perpetual_tables.clone(), pruning_config, checkpoint_stream);
+        let store_pruner = AuthorityStorePruner::new(
+            perpetual_tables.clone(),
+            pruning_config,
+            checkpoint_stream,
+            registry,
+        );
 
         let store = Self {
             mutex_table: MutexTable::new(NUM_SHARDS, SHARD_SIZE),
             perpetual_tables,
-            _store_pruner,
+            store_pruner,
             executed_effects_notify_read: NotifyRead::new(),
             execution_lock: RwLock::new(epoch),
         };
@@ -259,6 +277,57 @@ impl AuthorityStore {
             .map(|(epoch, _)| epoch))
     }
 
+    pub fn get_transaction_checkpoint(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self
+            .perpetual_tables
+            .executed_transactions_to_checkpoint
+            .get(digest)?
+            .map(|(_, sequence)| sequence))
+    }
+
+    pub fn multi_get_transaction_checkpoint(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<CheckpointSequenceNumber>>> {
+        Ok(self
+            .perpetual_tables
+            .executed_transactions_to_checkpoint
+            .multi_get(digests)?
+            .into_iter()
+            .map(|entry| entry.map(|(_, sequence)| sequence))
+            .collect())
+    }
+
+    /// Returns the effects of the executed transactions that are not part of a checkpoint at or
+    /// before `checkpoint`, whether they are not checkpointed yet or part of a later one.
+    pub fn get_executed_effects_after_checkpoint(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        batch_size: usize,
+    ) -> SuiResult<Vec<TransactionEffects>> {
+        let mut effects = vec![];
+        for chunk in &self
+            .perpetual_tables
+            .executed_effects
+            .iter()
+            .chunks(batch_size)
+        {
+            let (digests, effects_digests): (Vec<_>, Vec<_>) = chunk.unzip();
+            let ahead = self
+                .multi_get_transaction_checkpoint(&digests)?
+                .into_iter()
+                .zip(effects_digests)
+                .filter(|(sequence, _)| sequence.map_or(true, |sequence| sequence > checkpoint))
+                .map(|(_, effects_digest)| effects_digest)
+                .collect::<Vec<_>>();
+            effects.extend(self.multi_get_effects(ahead.iter())?.into_iter().flatten());
+        }
+        Ok(effects)
+    }
+
     /// Returns true if there are no objects in the database
     pub fn database_is_empty(&self) -> SuiResult<bool> {
         self.perpetual_tables.database_is_empty()
@@ -282,6 +351,13 @@ impl AuthorityStore {
             .get(&ObjectKey(*object_id, version))?)
     }
 
+    pub fn multi_get_object_by_key(
+        &self,
+        object_keys: &[ObjectKey],
+    ) -> Result<Vec<Option<Object>>, SuiError> {
+        Ok(self.perpetual_tables.objects.multi_get(object_keys)?)
+    }
+
     pub fn object_version_exists(
         &self,
         object_id: &ObjectID,
@@ -337,7 +413,9 @@ impl AuthorityStore {
 
     /// When making changes, please see if check_sequenced_input_objects() below needs
     /// similar changes as well.
-    pub fn get_missing_input_objects(
+    /// Returns the keys of the input objects of a transaction, with the versions of shared objects
+    /// resolved through the shared object locks of the transaction.
+    pub fn get_input_object_keys(
         &self,
         digest: &TransactionDigest,
         objects: &[InputObjectKind],
@@ -345,9 +423,9 @@ impl AuthorityStore {
     ) -> Result<Vec<ObjectKey>, SuiError> {
         let shared_locks_cell: OnceCell<HashMap<_, _>> = OnceCell::new();
 
-        let mut missing = Vec::new();
+        let mut keys = Vec::with_capacity(objects.len());
         for kind in objects {
-            match kind {
+            let key = match kind {
                 InputObjectKind::SharedMoveObject { id, .. } => {
                     let shared_locks = shared_locks_cell.get_or_try_init(|| {
                         Ok::<HashMap<ObjectID, SequenceNumber>, SuiError>(
@@ -355,33 +433,35 @@ impl AuthorityStore {
                         )
                     })?;
                     match shared_locks.get(id) {
-                        Some(version) => {
-                            if !self.object_version_exists(id, *version)? {
-                                // When this happens, other transactions that use smaller versions of
-                                // this shared object haven't finished execution.
-                                missing.push(ObjectKey(*id, *version));
-                            }
-                        }
+                        Some(version) => ObjectKey(*id, *version),
                         None => {
                             // Abort the function because the lock should have been set.
                             return Err(SuiError::SharedObjectLockNotSetError);
                         }
-                    };
-                }
-                InputObjectKind::MovePackage(id) => {
-                    if !self.object_version_exists(id, PACKAGE_VERSION)? {
-                        // The cert cannot have been formed if immutable inputs were missing.
-                        missing.push(ObjectKey(*id, PACKAGE_VERSION));
-                    }
-                }
-                InputObjectKind::ImmOrOwnedMoveObject(objref) => {
-                    if self.get_object_by_key(&objref.0, objref.1)?.is_none() {
-                        missing.push(ObjectKey::from(objref));
                     }
                 }
+                InputObjectKind::MovePackage(id) => ObjectKey(*id, PACKAGE_VERSION),
+                InputObjectKind::ImmOrOwnedMoveObject(objref) => ObjectKey::from(objref),
             };
+            keys.push(key);
         }
+        Ok(keys)
+    }
 
+    /// Returns the input objects of a transaction which are not available yet.
+    /// When this happens for a shared object, other transactions that use smaller versions of
+    /// this shared object haven't finished execution. The cert cannot have been formed if
+    /// immutable inputs were missing.
+    pub fn get_missing_input_objects(
+        &self,
+        input_keys: &[ObjectKey],
+    ) -> Result<Vec<ObjectKey>, SuiError> {
+        let mut missing = Vec::new();
+        for key in input_keys {
+            if !self.object_version_exists(&key.0, key.1)? {
+                missing.push(*key);
+            }
+        }
         Ok(missing)
     }
 
@@ -1116,6 +1196,47 @@ impl AuthorityStore {
         self.perpetual_tables.get_latest_parent_entry(object_id)
     }
 
+    /// Same as `get_latest_parent_entry` for many objects, in the order of the IDs.
+    pub fn multi_get_latest_parent_entries(
+        &self,
+        object_ids: &[ObjectID],
+    ) -> Result<Vec<Option<(ObjectRef, TransactionDigest)>>, SuiError> {
+        self.perpetual_tables
+            .multi_get_latest_parent_entries(object_ids)
+    }
+
+    /// Returns the parent sync entries of an object, that is every version of the object and the
+    /// transaction which produced it, starting at the given version.
+    pub fn get_parent_entries(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: usize,
+        descending: bool,
+    ) -> SuiResult<Vec<(ObjectRef, TransactionDigest)>> {
+        let iter = self.perpetual_tables.parent_sync.iter();
+        Ok(if descending {
+            iter.skip_prior_to(&(
+                object_id,
+                cursor.unwrap_or(SequenceNumber::MAX),
+                ObjectDigest::MAX,
+            ))?
+            .reverse()
+            .take_while(|((id, _, _), _)| *id == object_id)
+            .take(limit)
+            .collect()
+        } else {
+            iter.skip_to(&(
+                object_id,
+                cursor.unwrap_or(SequenceNumber::MIN),
+                ObjectDigest::MIN,
+            ))?
+            .take_while(|((id, _, _), _)| *id == object_id)
+            .take(limit)
+            .collect()
+        })
+    }
+
     pub fn object_exists(&self, object_id: ObjectID) -> SuiResult<bool> {
         match self.get_latest_parent_entry(object_id)? {
             None => Ok(false),
@@ -1151,6 +1272,80 @@ impl AuthorityStore {
     pub fn iter_live_object_set(&self) -> impl Iterator<Item = ObjectRef> + '_ {
         self.perpetual_tables.iter_live_object_set()
     }
+
+    /// Returns the root state hash of the given epoch, along with the sequence number of the
+    /// last checkpoint of that epoch.
+    pub fn get_root_state_hash(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Option<(CheckpointSequenceNumber, Accumulator)>> {
+        Ok(self.perpetual_tables.root_state_hash_by_epoch.get(&epoch)?)
+    }
+
+    pub fn insert_root_state_hash(
+        &self,
+        epoch: EpochId,
+        last_checkpoint_of_epoch: CheckpointSequenceNumber,
+        accumulator: Accumulator,
+    ) -> SuiResult {
+        self.perpetual_tables
+            .root_state_hash_by_epoch
+            .insert(&epoch, &(last_checkpoint_of_epoch, accumulator))?;
+        Ok(())
+    }
+
+    /// Resets the lock on the latest version of an owned object, releasing it from the
+    /// transaction it is locked to. Returns the reference of the unlocked object, along with the
+    /// transaction that held the lock, if any.
+    ///
+    /// This is an escape hatch for operators, to recover objects locked by transactions that will
+    /// never be certified. If the transaction does get certified after all, the object can end up
+    /// being used by two conflicting certificates.
+    pub async fn clear_object_lock(
+        &self,
+        object_id: ObjectID,
+    ) -> SuiResult<(ObjectRef, Option<TransactionDigest>)> {
+        let obj_ref = self.get_latest_lock_for_object_id(object_id)?;
+        let _mutexes = self.acquire_locks(&[obj_ref]).await;
+
+        // The object may have been consumed before we acquired the mutex.
+        let lock = self
+            .perpetual_tables
+            .owned_object_transaction_locks
+            .get(&obj_ref)?
+            .ok_or(SuiError::ObjectNotFound {
+                object_id,
+                version: Some(obj_ref.1),
+            })?;
+        self.perpetual_tables
+            .owned_object_transaction_locks
+            .insert(&obj_ref, &None)?;
+        info!(?obj_ref, ?lock, "Cleared object lock");
+        Ok((obj_ref, lock.map(|lock| lock.tx_digest)))
+    }
+
+    /// Creates a RocksDB checkpoint of the perpetual tables in `path`, which must not exist yet.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        self.perpetual_tables
+            .objects
+            .rocksdb
+            .checkpoint(path)
+            .map_err(|e| SuiError::GenericStorageError(e.to_string()))
+    }
+
+    /// Compacts the objects table, reclaiming the space used by pruned object versions.
+    pub fn compact_objects(&self) -> SuiResult {
+        self.perpetual_tables
+            .objects
+            .compact_range(&ObjectKey::ZERO, &ObjectKey::max_for_id(&ObjectID::MAX))?;
+        Ok(())
+    }
+
+    /// Prunes old object versions without waiting for the next pruning period, returning the
+    /// number of versions pruned.
+    pub async fn prune_objects_immediately(&self) -> SuiResult<u64> {
+        self.store_pruner.prune_objects_immediately().await
+    }
 }
 
 impl BackingPackageStore for AuthorityStore {
@@ -5,11 +5,16 @@ use crate::checkpoints::checkpoint_executor::{
     CheckpointExecutionMessage, CheckpointExecutionState,
 };
 use mysten_metrics::monitored_scope;
-use std::cmp::max;
-use std::collections::HashMap;
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
+use std::cmp::{max, min};
+use std::collections::{HashMap, HashSet};
 use std::{sync::Arc, time::Duration};
 use sui_config::node::AuthorityStorePruningConfig;
 use sui_types::base_types::SequenceNumber;
+use sui_types::error::{SuiError, SuiResult};
 use sui_types::object::Object;
 use sui_types::{
     base_types::{ObjectID, VersionNumber},
@@ -22,17 +27,162 @@ use tokio::{
     },
     time::{self, Instant},
 };
-use tracing::log::{error, info};
+use tracing::log::{debug, error, info};
 use typed_store::rocks::DBMap;
 use typed_store::Map;
 
 use super::authority_store_tables::AuthorityPerpetualTables;
 
 const MAX_OPS_IN_ONE_WRITE_BATCH: u64 = 10000;
-const ENABLE_LIVE_PRUNER: bool = cfg!(test) || cfg!(msim);
 
 pub struct AuthorityStorePruner {
     _objects_pruner_cancel_handle: oneshot::Sender<()>,
+    /// Requests for an immediate pruning run, answered with the number of versions pruned.
+    manual_pruning_sender: mpsc::Sender<oneshot::Sender<u64>>,
+}
+
+pub struct AuthorityStorePruningMetrics {
+    last_pruned_checkpoint: IntGauge,
+    num_pruned_objects: IntCounter,
+    num_deferred_objects: IntGauge,
+}
+
+impl AuthorityStorePruningMetrics {
+    pub fn new(registry: &Registry) -> Arc<Self> {
+        Arc::new(Self {
+            last_pruned_checkpoint: register_int_gauge_with_registry!(
+                "objects_pruner_last_pruned_checkpoint",
+                "Sequence number of the last checkpoint whose consumed object versions were pruned",
+                registry
+            )
+            .unwrap(),
+            num_pruned_objects: register_int_counter_with_registry!(
+                "objects_pruner_num_pruned_objects",
+                "Number of object versions pruned from the objects table",
+                registry
+            )
+            .unwrap(),
+            num_deferred_objects: register_int_gauge_with_registry!(
+                "objects_pruner_num_deferred_objects",
+                "Number of objects whose pruning is deferred because they are used by unexecuted certificates",
+                registry
+            )
+            .unwrap(),
+        })
+    }
+}
+
+/// Prunes the object versions consumed by executed checkpoints.
+///
+/// With lamport versioning, the versions of an object are not contiguous, so the pruner relies on
+/// the versions recorded in the effects of each checkpoint, rather than on a number of versions to
+/// retain. Versions consumed by a checkpoint are only pruned once the next checkpoint has been
+/// executed, so that the inputs of the latest executed checkpoint remain available, e.g. to
+/// accumulate its state hash. Objects which are inputs to certificates that have not been executed
+/// yet are not pruned, and their pruning is retried with the following checkpoints. This can only
+/// happen when a certificate executed by the checkpoint is concurrently being executed from
+/// another source, since versions consumed by a transaction cannot be inputs to any other.
+/// Versions which have not been pruned yet are persisted, and pruned with the first checkpoint
+/// executed after a restart.
+struct LiveObjectPruner {
+    /// Latest versions of objects consumed by the latest executed checkpoint.
+    latest_checkpoint_inputs: HashMap<ObjectID, SequenceNumber>,
+    /// Latest versions of objects whose pruning has been deferred.
+    deferred: HashMap<ObjectID, SequenceNumber>,
+}
+
+impl LiveObjectPruner {
+    /// Resumes pruning the versions left pending before a restart. They were consumed by
+    /// checkpoints which have all been executed by now, so they are pruned with the next one
+    /// unless they are still used by unexecuted certificates.
+    fn new(perpetual_db: &AuthorityPerpetualTables) -> Self {
+        Self {
+            latest_checkpoint_inputs: HashMap::new(),
+            deferred: perpetual_db.live_pruner_pending_versions.iter().collect(),
+        }
+    }
+
+    fn handle_checkpoint(
+        &mut self,
+        checkpoint_execution_state: &CheckpointExecutionState,
+        unexecuted_inputs: &HashSet<ObjectKey>,
+        perpetual_db: &AuthorityPerpetualTables,
+    ) -> anyhow::Result<(usize, usize)> {
+        let _scope = monitored_scope("ObjectsLivePruner");
+        let mut checkpoint_inputs: HashMap<ObjectID, SequenceNumber> = HashMap::new();
+        for effects in &checkpoint_execution_state.effects {
+            for (object_id, seq_number) in &effects.modified_at_versions {
+                merge_version(&mut checkpoint_inputs, *object_id, *seq_number);
+            }
+        }
+
+        let mut candidates = self.deferred.clone();
+        for (object_id, version) in &self.latest_checkpoint_inputs {
+            merge_version(&mut candidates, *object_id, *version);
+        }
+        // Lowest version of each object used by an unexecuted certificate.
+        let mut protected: HashMap<ObjectID, SequenceNumber> = HashMap::new();
+        for ObjectKey(object_id, version) in unexecuted_inputs {
+            protected
+                .entry(*object_id)
+                .and_modify(|v| *v = min(*v, *version))
+                .or_insert(*version);
+        }
+        let (deferred, to_prune): (HashMap<_, _>, HashMap<_, _>) =
+            candidates.into_iter().partition(|(object_id, version)| {
+                protected
+                    .get(object_id)
+                    .map_or(false, |protected_version| protected_version <= version)
+            });
+
+        let mut pending = deferred.clone();
+        for (object_id, version) in &checkpoint_inputs {
+            merge_version(&mut pending, *object_id, *version);
+        }
+        let pruned = Self::prune(to_prune, &pending, perpetual_db)?;
+
+        self.latest_checkpoint_inputs = checkpoint_inputs;
+        self.deferred = deferred;
+        Ok((pruned, self.deferred.len()))
+    }
+
+    /// Deletes all versions of the given objects up to and including the given versions, and
+    /// records the versions left `pending`.
+    fn prune(
+        updates: HashMap<ObjectID, SequenceNumber>,
+        pending: &HashMap<ObjectID, SequenceNumber>,
+        perpetual_db: &AuthorityPerpetualTables,
+    ) -> anyhow::Result<usize> {
+        let objects = &perpetual_db.objects;
+        let mut pruned = 0;
+        let mut wb = objects.batch();
+        wb = wb.delete_batch(&perpetual_db.live_pruner_pending_versions, updates.keys())?;
+        wb = wb.insert_batch(&perpetual_db.live_pruner_pending_versions, pending)?;
+        for (object_id, version) in updates {
+            let object_key = ObjectKey(object_id, version);
+            let iter = objects.iter().skip_prior_to(&object_key)?.reverse();
+            let mut start_range = object_key;
+            let end_range = ObjectKey(object_key.0, SequenceNumber::from(object_key.1.value() + 1));
+            for (key, _) in iter.take_while(|(key, _)| key.0 == object_key.0) {
+                start_range = key;
+                pruned += 1;
+            }
+            wb = wb.delete_range(objects, &start_range, &end_range)?;
+        }
+        wb.write()?;
+        Ok(pruned)
+    }
+}
+
+fn merge_version(
+    versions: &mut HashMap<ObjectID, SequenceNumber>,
+    object_id: ObjectID,
+    version: SequenceNumber,
+) {
+    versions
+        .entry(object_id)
+        .and_modify(|v| *v = max(*v, version))
+        .or_insert(version);
 }
 
 impl AuthorityStorePruner {
@@ -133,49 +283,21 @@ impl AuthorityStorePruner {
         total_pruned
     }
 
-    fn handle_checkpoint(
-        checkpoint_execution_state: CheckpointExecutionState,
-        objects: &DBMap<ObjectKey, Object>,
-    ) -> anyhow::Result<usize> {
-        let _scope = monitored_scope("ObjectsLivePruner");
-        let mut pruned = 0;
-        let mut wb = objects.batch();
-        let mut updates = HashMap::new();
-
-        for effects in checkpoint_execution_state.effects {
-            for (object_id, seq_number) in effects.modified_at_versions {
-                updates
-                    .entry(object_id)
-                    .and_modify(|version| *version = max(*version, seq_number))
-                    .or_insert(seq_number);
-            }
-        }
-        for (object_id, version) in updates {
-            let object_key = ObjectKey(object_id, version);
-            let iter = objects.iter().skip_prior_to(&object_key)?.reverse();
-            let mut start_range = object_key;
-            let end_range = ObjectKey(object_key.0, SequenceNumber::from(object_key.1.value() + 1));
-            for (key, _) in iter.take_while(|(key, _)| key.0 == object_key.0) {
-                start_range = key;
-                pruned += 1;
-            }
-            wb = wb.delete_range(objects, &start_range, &end_range)?;
-        }
-        wb.write()?;
-        Ok(pruned)
-    }
-
     fn setup_objects_pruning(
         num_versions_to_retain: u64,
         pruning_timeperiod: Duration,
         pruning_initial_delay: Duration,
+        enable_live_pruner: bool,
         perpetual_db: Arc<AuthorityPerpetualTables>,
+        metrics: Arc<AuthorityStorePruningMetrics>,
         mut checkpoint_stream: mpsc::Receiver<CheckpointExecutionMessage>,
+        mut manual_pruning_requests: mpsc::Receiver<oneshot::Sender<u64>>,
     ) -> Sender<()> {
         let (sender, mut recv) = tokio::sync::oneshot::channel();
         info!(
-            "Starting object pruning service with num_versions_to_retain={num_versions_to_retain}"
+            "Starting object pruning service with num_versions_to_retain={num_versions_to_retain}, enable_live_pruner={enable_live_pruner}"
         );
+        let mut live_pruner = LiveObjectPruner::new(&perpetual_db);
         let mut prune_interval =
             tokio::time::interval_at(Instant::now() + pruning_initial_delay, pruning_timeperiod);
         prune_interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
@@ -185,6 +307,7 @@ impl AuthorityStorePruner {
                     _ = prune_interval.tick(), if num_versions_to_retain != u64::MAX => {
                         info!("Starting pruning of objects table");
                         let num_pruned = Self::prune_objects(num_versions_to_retain, &perpetual_db.objects);
+                        metrics.num_pruned_objects.inc_by(num_pruned);
                         info!("Finished pruning with total object versions pruned = {}", num_pruned);
                         if let Ok(()) = perpetual_db.objects.flush() {
                             info!("Completed flushing objects table");
@@ -192,15 +315,18 @@ impl AuthorityStorePruner {
                             error!("Failed to flush objects table");
                         }
                     },
-                    Some((state, callback)) = checkpoint_stream.recv() => {
-                        if !ENABLE_LIVE_PRUNER {
+                    Some((state, unexecuted_inputs, callback)) = checkpoint_stream.recv() => {
+                        if !enable_live_pruner {
                             callback.send(()).expect("failed to notify checkpoint executor");
                             continue;
                         }
                         loop {
-                            match Self::handle_checkpoint(state.clone(), &perpetual_db.objects) {
-                                Ok(pruned) => {
-                                    info!("Pruned {} objects", pruned);
+                            match live_pruner.handle_checkpoint(&state, &unexecuted_inputs, &perpetual_db) {
+                                Ok((pruned, deferred)) => {
+                                    debug!("Pruned {} object versions at checkpoint {}", pruned, state.checkpoint_sequence_number);
+                                    metrics.num_pruned_objects.inc_by(pruned as u64);
+                                    metrics.num_deferred_objects.set(deferred as i64);
+                                    metrics.last_pruned_checkpoint.set(state.checkpoint_sequence_number as i64);
                                     callback.send(()).expect("failed to notify checkpoint executor");
                                     break;
                                 }
@@ -211,6 +337,18 @@ impl AuthorityStorePruner {
                             }
                         }
                     },
+                    Some(callback) = manual_pruning_requests.recv() => {
+                        let num_pruned = if num_versions_to_retain == u64::MAX {
+                            info!("Object pruning is disabled, skipping manual pruning");
+                            0
+                        } else {
+                            info!("Starting manual pruning of objects table");
+                            Self::prune_objects(num_versions_to_retain, &perpetual_db.objects)
+                        };
+                        metrics.num_pruned_objects.inc_by(num_pruned);
+                        info!("Finished manual pruning with total object versions pruned = {}", num_pruned);
+                        let _ = callback.send(num_pruned);
+                    },
                     _ = &mut recv => break,
                 }
             }
@@ -221,17 +359,36 @@ impl AuthorityStorePruner {
         perpetual_db: Arc<AuthorityPerpetualTables>,
         pruning_config: &AuthorityStorePruningConfig,
         checkpoint_stream: mpsc::Receiver<CheckpointExecutionMessage>,
+        registry: &Registry,
     ) -> Self {
+        let (manual_pruning_sender, manual_pruning_requests) = mpsc::channel(1);
         AuthorityStorePruner {
             _objects_pruner_cancel_handle: Self::setup_objects_pruning(
                 pruning_config.objects_num_latest_versions_to_retain,
                 Duration::from_secs(pruning_config.objects_pruning_period_secs),
                 Duration::from_secs(pruning_config.objects_pruning_initial_delay_secs),
+                pruning_config.enable_live_pruner,
                 perpetual_db,
+                AuthorityStorePruningMetrics::new(registry),
                 checkpoint_stream,
+                manual_pruning_requests,
             ),
+            manual_pruning_sender,
         }
     }
+
+    /// Runs a pruning pass over the objects table without waiting for the next pruning period,
+    /// returning the number of object versions pruned.
+    pub async fn prune_objects_immediately(&self) -> SuiResult<u64> {
+        let (sender, receiver) = oneshot::channel();
+        self.manual_pruning_sender
+            .send(sender)
+            .await
+            .map_err(|_| SuiError::GenericStorageError("Object pruner has stopped".to_string()))?;
+        receiver
+            .await
+            .map_err(|_| SuiError::GenericStorageError("Object pruner has stopped".to_string()))
+    }
 }
 
 #[cfg(test)]
@@ -257,7 +414,7 @@ mod tests {
     use typed_store::rocks::{DBMap, MetricConf, ReadWriteOptions};
     use typed_store::Map;
 
-    use super::AuthorityStorePruner;
+    use super::{AuthorityStorePruner, LiveObjectPruner};
 
     fn get_keys_after_pruning(db_path: PathBuf) -> anyhow::Result<HashSet<ObjectKey>> {
         let perpetual_db_path = db_path.join(Path::new("perpetual"));
@@ -360,13 +517,27 @@ mod tests {
                 modified_at_versions: to_delete.into_iter().map(|o| (o.0, o.1)).collect(),
                 ..Default::default()
             };
+            let mut pruner = LiveObjectPruner::new(&db);
+
+            // The inputs of the latest executed checkpoint are retained.
             let checkpoint_state = CheckpointExecutionState {
                 effects: vec![effects],
                 checkpoint_sequence_number: 0,
             };
-            let pruned =
-                AuthorityStorePruner::handle_checkpoint(checkpoint_state, &db.objects).unwrap();
+            let (pruned, _) = pruner
+                .handle_checkpoint(&checkpoint_state, &HashSet::new(), &db)
+                .unwrap();
+            assert_eq!(pruned, 0);
+
+            let checkpoint_state = CheckpointExecutionState {
+                effects: vec![],
+                checkpoint_sequence_number: 1,
+            };
+            let (pruned, deferred) = pruner
+                .handle_checkpoint(&checkpoint_state, &HashSet::new(), &db)
+                .unwrap();
             assert_eq!(pruned, 1000);
+            assert_eq!(deferred, 0);
             to_keep
         };
 
@@ -377,6 +548,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_live_pruning_defers_unexecuted_inputs() {
+        let path = tempfile::tempdir().unwrap().into_path();
+        let db = Arc::new(AuthorityPerpetualTables::open(&path, None));
+        let (_, to_delete) = generate_test_data(db.clone(), 3, 2, 10).unwrap();
+        let protected = to_delete[0];
+        let effects = TransactionEffects {
+            modified_at_versions: to_delete.iter().map(|o| (o.0, o.1)).collect(),
+            ..Default::default()
+        };
+        let mut pruner = LiveObjectPruner::new(&db);
+        let checkpoint_state = |checkpoint_sequence_number, effects| CheckpointExecutionState {
+            effects,
+            checkpoint_sequence_number,
+        };
+
+        pruner
+            .handle_checkpoint(&checkpoint_state(0, vec![effects]), &HashSet::new(), &db)
+            .unwrap();
+        // An unexecuted certificate still uses one of the consumed versions.
+        let (pruned, deferred) = pruner
+            .handle_checkpoint(
+                &checkpoint_state(1, vec![]),
+                &HashSet::from([protected]),
+                &db,
+            )
+            .unwrap();
+        assert_eq!((pruned, deferred), (9, 1));
+        assert!(db.objects.contains_key(&protected).unwrap());
+
+        // The deferred version is still pruned after a restart.
+        let mut pruner = LiveObjectPruner::new(&db);
+        let (pruned, deferred) = pruner
+            .handle_checkpoint(&checkpoint_state(2, vec![]), &HashSet::new(), &db)
+            .unwrap();
+        assert_eq!((pruned, deferred), (1, 0));
+        assert!(db.live_pruner_pending_versions.is_empty());
+    }
+
     async fn test_pruning(
         primary_path: PathBuf,
         num_versions_per_object: u64,
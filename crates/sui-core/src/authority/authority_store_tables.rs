@@ -4,8 +4,10 @@
 use super::*;
 use crate::authority::authority_store::LockDetails;
 use rocksdb::Options;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use sui_storage::default_db_options;
+use sui_types::accumulator::Accumulator;
 use sui_types::base_types::SequenceNumber;
 use sui_types::messages::TrustedCertificate;
 use typed_store::metrics::SamplingInterval;
@@ -15,6 +17,16 @@ use typed_store::traits::{Map, TableSummary, TypedStoreDebug};
 use typed_store_derive::DBMapUtils;
 
 const CURRENT_EPOCH_KEY: u64 = 0;
+const STATE_SNAPSHOT_RESTORE_KEY: u64 = 0;
+
+/// Progress of restoring the store from a state snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StateSnapshotRestore {
+    /// Restoring the snapshot of the given checkpoint started, but has not completed yet.
+    InProgress(CheckpointDigest),
+    /// The store was fully restored from the snapshot of the given checkpoint.
+    Complete(CheckpointDigest),
+}
 
 /// AuthorityPerpetualTables contains data that must be preserved from one epoch to the next.
 #[derive(DBMapUtils)]
@@ -78,11 +90,24 @@ pub struct AuthorityPerpetualTables {
     pub(crate) executed_transactions_to_checkpoint:
         DBMap<TransactionDigest, (EpochId, CheckpointSequenceNumber)>,
 
+    /// The root of the live object set accumulator at the end of each epoch, along with the
+    /// sequence number of the last checkpoint of that epoch.
+    pub(crate) root_state_hash_by_epoch: DBMap<EpochId, (CheckpointSequenceNumber, Accumulator)>,
+
+    /// Latest versions of objects consumed by executed checkpoints which the live object pruner
+    /// has not pruned yet, so that they are still pruned after a restart.
+    pub(crate) live_pruner_pending_versions: DBMap<ObjectID, SequenceNumber>,
+
     /// A singleton table that stores the current epoch number. This is used only for the purpose of
     /// crash recovery so that when we restart we know which epoch we are at. This is needed because
     /// there will be moments where the on-chain epoch doesn't match with the per-epoch table epoch.
     /// This number should match the epoch of the per-epoch table in the authority store.
     current_epoch: DBMap<u64, u64>,
+
+    /// A singleton table that records whether the store was restored from a state snapshot. It is
+    /// marked complete only once everything else has been written, so that a store left behind by
+    /// an interrupted restore can be told apart from one that was fully restored.
+    state_snapshot_restore: DBMap<u64, StateSnapshotRestore>,
 }
 
 impl AuthorityPerpetualTables {
@@ -176,6 +201,27 @@ impl AuthorityPerpetualTables {
         }))
     }
 
+    /// Same as `get_latest_parent_entry` for many objects, in the order of the IDs. The latest
+    /// entry of an object cannot be read with a point lookup, so a single iterator is re-seeked
+    /// for every object instead of opening one per object.
+    pub fn multi_get_latest_parent_entries(
+        &self,
+        object_ids: &[ObjectID],
+    ) -> Result<Vec<Option<(ObjectRef, TransactionDigest)>>, SuiError> {
+        let mut iterator = self.parent_sync.iter();
+        let mut entries = Vec::with_capacity(object_ids.len());
+        for object_id in object_ids {
+            iterator =
+                iterator.skip_prior_to(&(*object_id, SequenceNumber::MAX, ObjectDigest::MAX))?;
+            entries.push(
+                iterator
+                    .next()
+                    .filter(|(obj_ref, _)| obj_ref.0 == *object_id),
+            );
+        }
+        Ok(entries)
+    }
+
     pub fn get_sui_system_state_object(&self) -> SuiResult<SuiSystemState> {
         let sui_system_object = self
             .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)?
@@ -201,6 +247,18 @@ impl AuthorityPerpetualTables {
         Ok(())
     }
 
+    pub fn get_state_snapshot_restore(&self) -> SuiResult<Option<StateSnapshotRestore>> {
+        Ok(self
+            .state_snapshot_restore
+            .get(&STATE_SNAPSHOT_RESTORE_KEY)?)
+    }
+
+    pub fn set_state_snapshot_restore(&self, restore: StateSnapshotRestore) -> SuiResult {
+        self.state_snapshot_restore
+            .insert(&STATE_SNAPSHOT_RESTORE_KEY, &restore)?;
+        Ok(())
+    }
+
     pub fn database_is_empty(&self) -> SuiResult<bool> {
         Ok(self
             .objects
@@ -14,6 +14,7 @@ pub struct CheckpointExecutorMetrics {
     pub checkpoint_exec_epoch: IntGauge,
     pub checkpoint_transaction_count: Histogram,
     pub checkpoint_contents_age_ms: Histogram,
+    pub epoch_root_state_hash_mismatch: IntCounter,
 }
 
 impl CheckpointExecutorMetrics {
@@ -47,6 +48,12 @@ impl CheckpointExecutorMetrics {
                 "Age of checkpoints when they arrive for execution",
                 registry,
             ),
+            epoch_root_state_hash_mismatch: register_int_counter_with_registry!(
+                "epoch_root_state_hash_mismatch",
+                "Number of epochs whose locally accumulated root state hash differs from the one committed to by the last checkpoint of the epoch",
+                registry
+            )
+            .unwrap(),
         };
         Arc::new(this)
     }
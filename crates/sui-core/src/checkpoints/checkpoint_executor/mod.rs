@@ -19,7 +19,7 @@
 //! end of epoch. This allows us to use it as a signal for reconfig.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::Arc,
     time::{Duration, SystemTime},
 };
@@ -29,11 +29,16 @@ use itertools::izip;
 use mysten_metrics::{monitored_scope, spawn_monitored_task};
 use prometheus::Registry;
 use sui_config::node::CheckpointExecutorConfig;
+use sui_storage::IndexStore;
 use sui_types::error::SuiError;
+use sui_types::storage::ObjectKey;
 use sui_types::{
     base_types::{ExecutionDigests, TransactionDigest},
     messages::{TransactionEffects, VerifiedCertificate},
-    messages_checkpoint::{CheckpointSequenceNumber, EndOfEpochData, VerifiedCheckpoint},
+    messages_checkpoint::{
+        CheckpointCommitment, CheckpointSequenceNumber, ECMHLiveObjectSetDigest, EndOfEpochData,
+        VerifiedCheckpoint,
+    },
 };
 use sui_types::{
     committee::{Committee, EpochId},
@@ -54,6 +59,7 @@ use typed_store::Map;
 
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::authority::AuthorityStore;
+use crate::state_accumulator::StateAccumulator;
 use crate::transaction_manager::TransactionManager;
 use crate::{authority::EffectsNotifyRead, checkpoints::CheckpointStore};
 
@@ -68,7 +74,13 @@ pub struct CheckpointExecutionState {
     pub effects: Vec<TransactionEffects>,
     pub checkpoint_sequence_number: CheckpointSequenceNumber,
 }
-pub type CheckpointExecutionMessage = (CheckpointExecutionState, oneshot::Sender<()>);
+/// Sent to the object pruner once a checkpoint has been executed, along with the input objects of
+/// the certificates which have not been executed yet.
+pub type CheckpointExecutionMessage = (
+    CheckpointExecutionState,
+    HashSet<ObjectKey>,
+    oneshot::Sender<()>,
+);
 
 type CheckpointExecutionBuffer =
     FuturesOrdered<JoinHandle<(VerifiedCheckpoint, CheckpointExecutionState)>>;
@@ -78,9 +90,11 @@ pub struct CheckpointExecutor {
     checkpoint_store: Arc<CheckpointStore>,
     authority_store: Arc<AuthorityStore>,
     tx_manager: Arc<TransactionManager>,
+    accumulator: Arc<StateAccumulator>,
     config: CheckpointExecutorConfig,
     metrics: Arc<CheckpointExecutorMetrics>,
     pruner_subscriber: mpsc::Sender<CheckpointExecutionMessage>,
+    indexes: Option<Arc<IndexStore>>,
 }
 
 impl CheckpointExecutor {
@@ -89,18 +103,22 @@ impl CheckpointExecutor {
         checkpoint_store: Arc<CheckpointStore>,
         authority_store: Arc<AuthorityStore>,
         tx_manager: Arc<TransactionManager>,
+        accumulator: Arc<StateAccumulator>,
         config: CheckpointExecutorConfig,
         prometheus_registry: &Registry,
         pruner_subscriber: mpsc::Sender<CheckpointExecutionMessage>,
+        indexes: Option<Arc<IndexStore>>,
     ) -> Self {
         Self {
             mailbox,
             checkpoint_store,
             authority_store,
             tx_manager,
+            accumulator,
             config,
             metrics: CheckpointExecutorMetrics::new(prometheus_registry),
             pruner_subscriber,
+            indexes,
         }
     }
 
@@ -110,14 +128,17 @@ impl CheckpointExecutor {
         authority_store: Arc<AuthorityStore>,
         tx_manager: Arc<TransactionManager>,
     ) -> Self {
+        let accumulator = Arc::new(StateAccumulator::new(authority_store.clone()));
         Self {
             mailbox,
             checkpoint_store,
             authority_store,
             tx_manager,
+            accumulator,
             config: Default::default(),
             metrics: CheckpointExecutorMetrics::new_for_tests(),
             pruner_subscriber: mpsc::channel(2).0,
+            indexes: None,
         }
     }
 
@@ -175,7 +196,7 @@ impl CheckpointExecutor {
                 // be processed (added to FuturesOrdered) in seq_number order, using FuturesOrdered
                 // guarantees that we will also ratchet the watermarks in order.
                 Some(Ok((checkpoint, checkpoint_execution_state))) = pending.next() => {
-                    self.process_executed_checkpoint(&checkpoint, checkpoint_execution_state).await;
+                    self.process_executed_checkpoint(&checkpoint, checkpoint_execution_state, &epoch_store).await;
                     highest_executed = Some(checkpoint);
                 }
                 // Check for newly synced checkpoints from StateSync.
@@ -214,6 +235,7 @@ impl CheckpointExecutor {
         &self,
         checkpoint: &VerifiedCheckpoint,
         execution_state: CheckpointExecutionState,
+        epoch_store: &AuthorityPerEpochStore,
     ) {
         let _scope = monitored_scope("ProcessExecutedCheckpoint");
         // Ensure that we are not skipping checkpoints at any point
@@ -229,10 +251,48 @@ impl CheckpointExecutor {
         }
         debug!("Bumping highest_executed_checkpoint watermark to {:?}", seq,);
 
+        if let Err(err) =
+            self.accumulator
+                .accumulate_checkpoint(&execution_state.effects, seq, epoch_store)
+        {
+            error!(
+                checkpoint = seq,
+                "Failed to accumulate checkpoint, the root state hash of epoch {} will not be \
+                checked: {:?}",
+                checkpoint.epoch(),
+                err
+            );
+        }
+        if let Some(indexes) = &self.indexes {
+            let digests: Vec<_> = execution_state
+                .effects
+                .iter()
+                .map(|effects| effects.transaction_digest)
+                .collect();
+            // Indexes only serve RPC reads, failing to update them must not stop execution.
+            if let Err(err) = indexes.index_checkpoint(seq, &digests) {
+                error!(
+                    checkpoint = seq,
+                    "Failed to index the transactions of checkpoint: {:?}", err
+                );
+            }
+            if let Err(err) = indexes.index_checkpoint_balances(seq, &execution_state.effects) {
+                error!(
+                    checkpoint = seq,
+                    "Failed to index the balances of checkpoint: {:?}", err
+                );
+            }
+        }
+        if checkpoint.summary.end_of_epoch_data.is_some() {
+            self.check_epoch_root_state_hash(checkpoint, epoch_store)
+                .await;
+        }
+
         let (callback_sender, callback_receiver) = oneshot::channel();
+        let unexecuted_inputs = self.tx_manager.unexecuted_input_objects();
         match self
             .pruner_subscriber
-            .send((execution_state, callback_sender))
+            .send((execution_state, unexecuted_inputs, callback_sender))
             .await
         {
             Ok(_) => callback_receiver
@@ -247,6 +307,72 @@ impl CheckpointExecutor {
         self.metrics.last_executed_checkpoint.set(seq as i64);
     }
 
+    /// Compare the root state hash we accumulated locally at the end of the epoch against the
+    /// commitment in the last checkpoint of the epoch, raising an alarm if they differ. Checkpoints
+    /// are processed in order, so every checkpoint of the epoch has been accumulated by now unless
+    /// accumulating it failed, in which case the check is skipped.
+    async fn check_epoch_root_state_hash(
+        &self,
+        checkpoint: &VerifiedCheckpoint,
+        epoch_store: &AuthorityPerEpochStore,
+    ) {
+        let root = match self
+            .accumulator
+            .accumulate_epoch_if_complete(
+                checkpoint.epoch(),
+                checkpoint.sequence_number(),
+                epoch_store,
+            )
+            .await
+        {
+            Ok(Some(root)) => root,
+            Ok(None) => {
+                warn!(
+                    epoch = checkpoint.epoch(),
+                    "Not all checkpoints of the epoch were accumulated, skipping the root state \
+                    hash check"
+                );
+                return;
+            }
+            Err(err) => {
+                error!(
+                    epoch = checkpoint.epoch(),
+                    "Failed to accumulate the root state hash of the epoch: {:?}", err
+                );
+                return;
+            }
+        };
+        let local_digest = ECMHLiveObjectSetDigest::from(&root);
+
+        let epoch_commitments = match checkpoint.summary.epoch_commitments() {
+            Ok(epoch_commitments) => epoch_commitments,
+            Err(err) => {
+                error!(
+                    epoch = checkpoint.epoch(),
+                    "Failed to read the commitments of the epoch: {:?}", err
+                );
+                return;
+            }
+        };
+        for commitment in &epoch_commitments {
+            match commitment {
+                CheckpointCommitment::ECMHLiveObjectSetDigest(digest) => {
+                    if digest != &local_digest {
+                        self.metrics.epoch_root_state_hash_mismatch.inc();
+                        error!(
+                            epoch = checkpoint.epoch(),
+                            checkpoint = checkpoint.sequence_number(),
+                            ?local_digest,
+                            committed_digest = ?digest,
+                            "Fork detected! Locally accumulated root state hash does not match \
+                            the one committed to by the last checkpoint of the epoch",
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     fn schedule_synced_checkpoints(
         &self,
         pending: &mut CheckpointExecutionBuffer,
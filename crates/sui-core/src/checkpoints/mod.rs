@@ -14,6 +14,7 @@ pub use crate::checkpoints::checkpoint_output::{
 };
 pub use crate::checkpoints::metrics::CheckpointMetrics;
 use crate::stake_aggregator::{InsertResult, StakeAggregator};
+use crate::state_accumulator::StateAccumulator;
 use futures::future::{select, Either};
 use futures::FutureExt;
 use mysten_metrics::{monitored_scope, spawn_monitored_task, MonitoredFutureExt};
@@ -35,7 +36,8 @@ use sui_types::gas::GasCostSummary;
 use sui_types::messages::TransactionEffects;
 use sui_types::messages_checkpoint::{
     CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
-    CheckpointSignatureMessage, CheckpointSummary, CheckpointTimestamp, VerifiedCheckpoint,
+    CheckpointSignatureMessage, CheckpointSummary, CheckpointTimestamp, ECMHLiveObjectSetDigest,
+    EndOfEpochData, VerifiedCheckpoint,
 };
 use tokio::sync::{mpsc, watch, Notify};
 use tokio::time::Instant;
@@ -375,6 +377,7 @@ pub struct CheckpointBuilder {
     exit: watch::Receiver<()>,
     metrics: Arc<CheckpointMetrics>,
     transaction_certifier: Box<dyn TransactionCertifier>,
+    accumulator: Arc<StateAccumulator>,
     max_transactions_per_checkpoint: usize,
 }
 
@@ -408,6 +411,7 @@ impl CheckpointBuilder {
         notify_aggregator: Arc<Notify>,
         metrics: Arc<CheckpointMetrics>,
         transaction_certifier: Box<dyn TransactionCertifier>,
+        accumulator: Arc<StateAccumulator>,
         max_transactions_per_checkpoint: usize,
     ) -> Self {
         Self {
@@ -421,6 +425,7 @@ impl CheckpointBuilder {
             notify_aggregator,
             metrics,
             transaction_certifier,
+            accumulator,
             max_transactions_per_checkpoint,
         }
     }
@@ -623,6 +628,33 @@ impl CheckpointBuilder {
                 .map(|(_, c)| c.network_total_transactions + num_txns)
                 .unwrap_or(num_txns);
 
+            self.accumulator
+                .accumulate_checkpoint(&effects, sequence_number, &self.epoch_store)?;
+            let mut epoch_commitments = vec![];
+            let end_of_epoch_data = if last_checkpoint_of_epoch {
+                let committee = self
+                    .state
+                    .get_sui_system_state_object()
+                    .unwrap()
+                    .get_current_epoch_committee()
+                    .committee;
+                let root_state_digest = self
+                    .accumulator
+                    .accumulate_epoch(epoch, sequence_number, &self.epoch_store)
+                    .in_monitored_scope("CheckpointBuilder::accumulate_epoch")
+                    .await?;
+                if self.epoch_store.protocol_config().epoch_commitments() {
+                    epoch_commitments
+                        .push(ECMHLiveObjectSetDigest::from(&root_state_digest).into());
+                }
+                Some(EndOfEpochData {
+                    next_epoch_committee: committee.voting_rights,
+                    next_epoch_protocol_version: committee.protocol_version,
+                })
+            } else {
+                None
+            };
+
             let previous_digest = last_checkpoint.as_ref().map(|(_, c)| c.digest());
             let summary = CheckpointSummary::new(
                 epoch,
@@ -631,19 +663,10 @@ impl CheckpointBuilder {
                 &contents,
                 previous_digest,
                 epoch_rolling_gas_cost_summary,
-                if last_checkpoint_of_epoch {
-                    Some(
-                        self.state
-                            .get_sui_system_state_object()
-                            .unwrap()
-                            .get_current_epoch_committee()
-                            .committee,
-                    )
-                } else {
-                    None
-                },
+                end_of_epoch_data,
                 timestamp_ms,
-            );
+            )
+            .with_epoch_commitments(epoch_commitments);
             if last_checkpoint_of_epoch {
                 info!(
                     ?sequence_number,
@@ -971,6 +994,7 @@ impl CheckpointService {
         checkpoint_output: Box<dyn CheckpointOutput>,
         certified_checkpoint_output: Box<dyn CertifiedCheckpointOutput>,
         transaction_certifier: Box<dyn TransactionCertifier>,
+        accumulator: Arc<StateAccumulator>,
         metrics: Arc<CheckpointMetrics>,
         max_transactions_per_checkpoint: usize,
     ) -> (Arc<Self>, watch::Sender<()> /* The exit sender */) {
@@ -990,6 +1014,7 @@ impl CheckpointService {
             notify_aggregator.clone(),
             metrics.clone(),
             transaction_certifier,
+            accumulator,
             max_transactions_per_checkpoint,
         );
 
@@ -1237,6 +1262,7 @@ mod tests {
 
         let checkpoint_store = CheckpointStore::new(tempdir.path());
         let epoch_store = state.epoch_store_for_testing();
+        let accumulator = StateAccumulator::new(state.database.clone());
         let (checkpoint_service, _exit) = CheckpointService::spawn(
             state.clone(),
             checkpoint_store,
@@ -1245,6 +1271,7 @@ mod tests {
             Box::new(output),
             Box::new(certified_output),
             Box::new(NetworkTransactionCertifier::default()),
+            Arc::new(accumulator),
             CheckpointMetrics::new_for_tests(),
             3,
         );
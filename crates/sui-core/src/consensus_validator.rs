@@ -97,7 +97,8 @@ impl TransactionValidator for SuiTxValidator {
                             idx,
                         )?;
                 }
-                ConsensusTransactionKind::EndOfPublish(_) => {}
+                ConsensusTransactionKind::EndOfPublish(_)
+                | ConsensusTransactionKind::CapabilityNotification(_) => {}
             }
         }
         // verify the user transaction signatures as a batch
@@ -153,6 +154,7 @@ mod tests {
         base_types::AuthorityName, messages::ConsensusTransaction, signature::GenericSignature,
     };
 
+    use sui_config::node::TransactionDenyConfig;
     use sui_macros::sim_test;
     use sui_types::crypto::Ed25519SuiSignature;
     use sui_types::object::Object;
@@ -201,6 +203,16 @@ mod tests {
             })
             .collect();
 
+        let batch = Batch::new(transaction_bytes.clone());
+        let res_batch = validator.validate_batch(&batch);
+        assert!(res_batch.is_ok(), "{res_batch:?}");
+
+        // The local deny-list only applies to signing, certificates are still accepted
+        let sender = certificates[0].sender_address();
+        state.update_transaction_deny_config(TransactionDenyConfig {
+            address_deny_list: [sender].into_iter().collect(),
+            ..Default::default()
+        });
         let batch = Batch::new(transaction_bytes);
         let res_batch = validator.validate_batch(&batch);
         assert!(res_batch.is_ok(), "{res_batch:?}");
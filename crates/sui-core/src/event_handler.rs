@@ -2,16 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::time::Duration;
+use std::collections::VecDeque;
 use std::sync::Arc;
 
+use futures::stream::BoxStream;
+use futures::{stream, StreamExt};
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use tokio_stream::Stream;
 use tracing::{debug, error, instrument, trace, warn};
 
 use sui_json_rpc_types::SuiMoveStruct;
-use sui_storage::event_store::{EventStore, EventStoreType};
+use sui_storage::event_store::{EventStore, EventStoreType, StoredEvent};
 use sui_types::base_types::TransactionDigest;
-use sui_types::filter::EventFilter;
+use sui_types::filter::{EventFilter, Filter};
 use sui_types::{
     error::{SuiError, SuiResult},
     event::{Event, EventEnvelope},
@@ -26,8 +29,11 @@ use crate::streamer::Streamer;
 mod event_handler_tests;
 
 pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 1000;
+/// Number of stored events read at a time when replaying events to a subscriber.
+const EVENT_REPLAY_CHUNK_SIZE: usize = 100;
 
 pub struct EventHandler {
+    store: Arc<AuthorityStore>,
     module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>,
     event_streamer: Streamer<EventEnvelope, EventFilter>,
     pub(crate) event_store: Arc<EventStoreType>,
@@ -35,11 +41,13 @@ pub struct EventHandler {
 
 impl EventHandler {
     pub fn new(
+        store: Arc<AuthorityStore>,
         event_store: Arc<EventStoreType>,
         module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>,
     ) -> Self {
         let streamer = Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE);
         Self {
+            store,
             module_cache,
             event_streamer: streamer,
             event_store,
@@ -50,13 +58,10 @@ impl EventHandler {
     pub fn regular_cleanup_task(&self) {
         let store_copy = self.event_store.clone();
         tokio::spawn(async move {
-            match store_copy.as_ref() {
-                EventStoreType::SqlEventStore(db) => {
-                    // Start periodic task to clean up WAL every 30 minutes
-                    db.wal_cleanup_thread(Some(Duration::from_secs(30 * 60)))
-                        .await;
-                }
-            }
+            // Start periodic task to clean up the store every 30 minutes
+            store_copy
+                .maintenance_task(Duration::from_secs(30 * 60))
+                .await;
         });
     }
 
@@ -147,7 +152,118 @@ impl EventHandler {
         ))
     }
 
-    pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = EventEnvelope> {
+    pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = SuiResult<EventEnvelope>> {
         self.event_streamer.subscribe(filter)
     }
+
+    /// Subscribe to the events matching `filter`, starting right after `cursor`, the
+    /// `(seq_num, event_num)` position of the last event seen by the subscriber.
+    /// Stored events are replayed from the event store up to the live tip, after which
+    /// the subscription switches to live delivery without gaps or duplicates.
+    pub fn subscribe_from(
+        self: &Arc<Self>,
+        filter: EventFilter,
+        cursor: (u64, u64),
+    ) -> impl Stream<Item = SuiResult<EventEnvelope>> {
+        let state = ReplayState {
+            handler: self.clone(),
+            live: None,
+            filter,
+            position: cursor,
+            replayed: VecDeque::new(),
+            replay_done: false,
+        };
+        stream::try_unfold(state, |mut state| async move {
+            loop {
+                if let Some(envelope) = state.replayed.pop_front() {
+                    return Ok(Some((envelope, state)));
+                }
+                if !state.replay_done {
+                    state.replay_next_chunk().await?;
+                    continue;
+                }
+                let Some(live) = state.live.as_mut() else {
+                    // Caught up with the event store. Only subscribe to live events now, so that
+                    // they don't pile up in the live buffer during a long replay, and replay once
+                    // more the events stored in the meantime. Events are stored before being
+                    // dispatched, so any event emitted from now on is either replayed or live.
+                    state.live = Some(state.handler.subscribe(state.filter.clone()).boxed());
+                    state.replay_done = false;
+                    continue;
+                };
+                match live.next().await {
+                    // Already delivered during the replay.
+                    Some(Ok(envelope))
+                        if (envelope.seq_num, envelope.event_num) <= state.position =>
+                    {
+                        continue
+                    }
+                    Some(Ok(envelope)) => return Ok(Some((envelope, state))),
+                    Some(Err(e)) => return Err(e),
+                    None => return Ok(None),
+                }
+            }
+        })
+    }
+
+    /// Rebuild the envelope of a stored event from the effects of its transaction.
+    fn stored_event_to_envelope(&self, stored_event: &StoredEvent) -> SuiResult<EventEnvelope> {
+        let id = stored_event.id();
+        let effects = self.store.get_executed_effects(&id.tx_digest)?.ok_or(
+            SuiError::TransactionNotFound {
+                digest: id.tx_digest,
+            },
+        )?;
+        let event = effects.events.get(id.event_seq as usize).ok_or_else(|| {
+            SuiError::EventFailedToDispatch {
+                error: format!("Event {id:?} not found in transaction effects"),
+            }
+        })?;
+        self.create_envelope(
+            event,
+            id.tx_digest,
+            id.event_seq as u64,
+            stored_event.seq_num() as u64,
+            stored_event.timestamp(),
+        )
+    }
+}
+
+struct ReplayState {
+    handler: Arc<EventHandler>,
+    /// Live events, subscribed to once the replay has caught up with the event store.
+    live: Option<BoxStream<'static, SuiResult<EventEnvelope>>>,
+    filter: EventFilter,
+    /// Position `(seq_num, event_num)` of the last replayed event.
+    position: (u64, u64),
+    replayed: VecDeque<EventEnvelope>,
+    replay_done: bool,
+}
+
+impl ReplayState {
+    async fn replay_next_chunk(&mut self) -> SuiResult {
+        let (seq_num, event_num) = self.position;
+        // The event store cursor is inclusive, start from the next event.
+        let stored_events = self
+            .handler
+            .event_store
+            .event_iterator(
+                0,
+                i64::MAX as u64,
+                seq_num as i64,
+                event_num as i64 + 1,
+                EVENT_REPLAY_CHUNK_SIZE,
+                false,
+            )
+            .await?;
+        self.replay_done = stored_events.len() < EVENT_REPLAY_CHUNK_SIZE;
+        for stored_event in &stored_events {
+            let envelope = self.handler.stored_event_to_envelope(stored_event)?;
+            self.position = (envelope.seq_num, envelope.event_num);
+            if self.filter.matches(&envelope) {
+                self.replayed.push_back(envelope);
+            }
+        }
+        Ok(())
+    }
 }
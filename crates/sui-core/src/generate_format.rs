@@ -18,9 +18,9 @@ use sui_types::{
         AuthoritySignature, KeypairTraits, Signature,
     },
     messages::{
-        Argument, CallArg, Command, EntryArgumentErrorKind, EntryTypeArgumentErrorKind,
-        ExecutionFailureStatus, ExecutionStatus, ObjectArg, ObjectInfoRequestKind,
-        SingleTransactionKind, TransactionKind,
+        Argument, CallArg, Command, CommandArgumentError, EntryArgumentErrorKind,
+        EntryTypeArgumentErrorKind, ExecutionFailureStatus, ExecutionStatus, ObjectArg,
+        ObjectInfoRequestKind, PackageUpgradeError, SingleTransactionKind, TransactionKind,
     },
     object::{Data, Owner},
     storage::DeleteKind,
@@ -78,6 +78,8 @@ fn get_registry() -> Result<Registry> {
     tracer.trace_type::<AbortLocation>(&samples)?;
     tracer.trace_type::<EntryArgumentErrorKind>(&samples)?;
     tracer.trace_type::<EntryTypeArgumentErrorKind>(&samples)?;
+    tracer.trace_type::<CommandArgumentError>(&samples)?;
+    tracer.trace_type::<PackageUpgradeError>(&samples)?;
     tracer.trace_type::<CallArg>(&samples)?;
     tracer.trace_type::<ObjectArg>(&samples)?;
     tracer.trace_type::<Data>(&samples)?;
@@ -22,6 +22,8 @@ mod notify_once;
 pub mod quorum_driver;
 pub mod safe_client;
 mod stake_aggregator;
+pub mod state_accumulator;
+pub mod state_snapshot;
 pub mod storage;
 pub mod streamer;
 pub mod tbls;
@@ -31,9 +33,21 @@ mod transaction_manager;
 pub mod transaction_orchestrator;
 pub mod validator_info;
 
+#[cfg(test)]
+#[path = "unit_tests/package_upgrade_tests.rs"]
+mod package_upgrade_tests;
+
 #[cfg(test)]
 #[path = "unit_tests/pay_sui_tests.rs"]
 mod pay_sui_tests;
+
+#[cfg(test)]
+#[path = "unit_tests/programmable_transaction_tests.rs"]
+mod programmable_transaction_tests;
+
+#[cfg(test)]
+#[path = "unit_tests/state_snapshot_tests.rs"]
+mod state_snapshot_tests;
 pub mod test_authority_clients;
 
 pub const SUI_CORE_VERSION: &str = env!("CARGO_PKG_VERSION");
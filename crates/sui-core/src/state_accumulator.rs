@@ -0,0 +1,222 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! StateAccumulator maintains a running multiset hash of the live object set. Each checkpoint is
+//! accumulated from the effects of its transactions: the digests of the objects written by the
+//! checkpoint are inserted, and the digests of the versions they replaced are removed. At the end
+//! of an epoch, the accumulators of all checkpoints of the epoch are combined with the root of
+//! the previous epoch, yielding a root state hash that is committed to by the last checkpoint of
+//! the epoch.
+
+use fastcrypto::hash::MultisetHash;
+use mysten_metrics::monitored_scope;
+use std::sync::Arc;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::ObjectDigest;
+use sui_types::committee::EpochId;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages::TransactionEffects;
+use sui_types::messages_checkpoint::{CheckpointSequenceNumber, ECMHLiveObjectSetDigest};
+use tracing::{debug, warn};
+
+use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
+use crate::authority::AuthorityStore;
+
+pub struct StateAccumulator {
+    authority_store: Arc<AuthorityStore>,
+}
+
+impl StateAccumulator {
+    pub fn new(authority_store: Arc<AuthorityStore>) -> Self {
+        Self { authority_store }
+    }
+
+    /// Accumulates the effects of a single checkpoint and persists the accumulator.
+    /// This function is idempotent.
+    pub fn accumulate_checkpoint(
+        &self,
+        effects: &[TransactionEffects],
+        checkpoint_seq_num: CheckpointSequenceNumber,
+        epoch_store: &AuthorityPerEpochStore,
+    ) -> SuiResult<Accumulator> {
+        let _scope = monitored_scope("AccumulateCheckpoint");
+        if let Some(accumulator) = epoch_store.get_state_hash_for_checkpoint(&checkpoint_seq_num)? {
+            return Ok(accumulator);
+        }
+
+        let accumulator = self.accumulate_effects(effects)?;
+        epoch_store.insert_state_hash_for_checkpoint(&checkpoint_seq_num, &accumulator)?;
+        debug!("Accumulated checkpoint {}", checkpoint_seq_num);
+        Ok(accumulator)
+    }
+
+    /// Returns the changes to the live object set made by the given effects.
+    pub fn accumulate_effects(&self, effects: &[TransactionEffects]) -> SuiResult<Accumulator> {
+        let mut accumulator = Accumulator::default();
+
+        accumulator.insert_all(
+            effects
+                .iter()
+                .flat_map(|fx| fx.all_mutated().map(|(obj_ref, _, _)| obj_ref.2))
+                .collect::<Vec<ObjectDigest>>(),
+        );
+
+        // Every version an object was modified at was part of the live object set until the
+        // transaction replaced, deleted or wrapped it.
+        let removed_digests = effects
+            .iter()
+            .flat_map(|fx| fx.modified_at_versions.iter())
+            .map(|(id, version)| {
+                self.authority_store
+                    .get_object_by_key(id, *version)?
+                    .map(|object| object.compute_object_reference().2)
+                    .ok_or(SuiError::ObjectNotFound {
+                        object_id: *id,
+                        version: Some(*version),
+                    })
+            })
+            .collect::<SuiResult<Vec<ObjectDigest>>>()?;
+        accumulator.remove_all(removed_digests);
+
+        Ok(accumulator)
+    }
+
+    /// Combines the root of the previous epoch with the accumulators of all checkpoints of
+    /// `epoch`, waiting for checkpoints that have not been accumulated yet, and persists the
+    /// resulting root state hash of the epoch. If the root of the previous epoch is missing, the
+    /// root is seeded from the live object set instead, see `seed_epoch`.
+    /// This function is idempotent.
+    pub async fn accumulate_epoch(
+        &self,
+        epoch: EpochId,
+        last_checkpoint_of_epoch: CheckpointSequenceNumber,
+        epoch_store: &AuthorityPerEpochStore,
+    ) -> SuiResult<Accumulator> {
+        if let Some((_, accumulator)) = self.authority_store.get_root_state_hash(epoch)? {
+            return Ok(accumulator);
+        }
+        let Some((first_checkpoint_of_epoch, root)) = self.epoch_start(epoch)? else {
+            return self.seed_epoch(epoch, last_checkpoint_of_epoch).await;
+        };
+
+        let accumulators = epoch_store
+            .notify_read_checkpoint_state_digests(
+                (first_checkpoint_of_epoch..=last_checkpoint_of_epoch).collect(),
+            )
+            .await?;
+        self.finish_epoch(epoch, last_checkpoint_of_epoch, root, accumulators)
+    }
+
+    /// Like `accumulate_epoch`, but only combines checkpoints that have already been accumulated,
+    /// returning `None` rather than waiting if some checkpoint of `epoch` has not been.
+    pub async fn accumulate_epoch_if_complete(
+        &self,
+        epoch: EpochId,
+        last_checkpoint_of_epoch: CheckpointSequenceNumber,
+        epoch_store: &AuthorityPerEpochStore,
+    ) -> SuiResult<Option<Accumulator>> {
+        if let Some((_, accumulator)) = self.authority_store.get_root_state_hash(epoch)? {
+            return Ok(Some(accumulator));
+        }
+        let Some((first_checkpoint_of_epoch, root)) = self.epoch_start(epoch)? else {
+            return self.seed_epoch(epoch, last_checkpoint_of_epoch).await.map(Some);
+        };
+
+        let mut accumulators = vec![];
+        for seq in first_checkpoint_of_epoch..=last_checkpoint_of_epoch {
+            let Some(accumulator) = epoch_store.get_state_hash_for_checkpoint(&seq)? else {
+                return Ok(None);
+            };
+            accumulators.push(accumulator);
+        }
+        self.finish_epoch(epoch, last_checkpoint_of_epoch, root, accumulators)
+            .map(Some)
+    }
+
+    /// The first checkpoint of `epoch` and the root state hash it starts from, or `None` if the
+    /// root state hash of the previous epoch was never accumulated, e.g. because the node started
+    /// from a snapshot or was running a version that did not accumulate state.
+    fn epoch_start(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Option<(CheckpointSequenceNumber, Accumulator)>> {
+        if epoch == 0 {
+            return Ok(Some((0, Accumulator::default())));
+        }
+        Ok(self.authority_store.get_root_state_hash(epoch - 1)?.map(
+            |(last_checkpoint_of_previous_epoch, previous_root)| {
+                (last_checkpoint_of_previous_epoch + 1, previous_root)
+            },
+        ))
+    }
+
+    /// Seeds the root state hash of `epoch` from the live object set, for when the root of the
+    /// previous epoch is missing. This is only accurate if the checkpoints executed so far are
+    /// exactly those up to `last_checkpoint_of_epoch`, which holds at the end of an epoch because
+    /// the checkpoints of the next epoch are only executed after reconfiguration. Later epochs
+    /// are accumulated from this root as usual. The live object set is scanned on a blocking
+    /// thread, as it takes a while.
+    async fn seed_epoch(
+        &self,
+        epoch: EpochId,
+        last_checkpoint_of_epoch: CheckpointSequenceNumber,
+    ) -> SuiResult<Accumulator> {
+        warn!(
+            "Root state hash of epoch {} not found, seeding the root state hash of epoch {} \
+            from the live object set",
+            epoch.saturating_sub(1),
+            epoch
+        );
+        let authority_store = self.authority_store.clone();
+        let root =
+            tokio::task::spawn_blocking(move || accumulate_live_object_set(&authority_store))
+                .await
+                .map_err(|e| {
+                    SuiError::GenericStorageError(format!(
+                        "Failed to accumulate the live object set: {e}"
+                    ))
+                })?;
+        self.finish_epoch(epoch, last_checkpoint_of_epoch, root, vec![])
+    }
+
+    fn finish_epoch(
+        &self,
+        epoch: EpochId,
+        last_checkpoint_of_epoch: CheckpointSequenceNumber,
+        mut root: Accumulator,
+        accumulators: Vec<Accumulator>,
+    ) -> SuiResult<Accumulator> {
+        for accumulator in accumulators {
+            root.union(&accumulator);
+        }
+
+        self.authority_store.insert_root_state_hash(
+            epoch,
+            last_checkpoint_of_epoch,
+            root.clone(),
+        )?;
+        debug!(
+            "Accumulated root state hash of epoch {}: {:?}",
+            epoch,
+            ECMHLiveObjectSetDigest::from(&root)
+        );
+        Ok(root)
+    }
+
+    /// Computes the accumulator of the live object set from scratch. This is expensive, and is
+    /// meant to be used to check the running accumulator.
+    pub fn accumulate_live_object_set(&self) -> Accumulator {
+        accumulate_live_object_set(&self.authority_store)
+    }
+}
+
+fn accumulate_live_object_set(authority_store: &AuthorityStore) -> Accumulator {
+    let mut accumulator = Accumulator::default();
+    accumulator.insert_all(
+        authority_store
+            .iter_live_object_set()
+            .map(|obj_ref| obj_ref.2)
+            .collect::<Vec<ObjectDigest>>(),
+    );
+    accumulator
+}
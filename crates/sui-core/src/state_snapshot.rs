@@ -0,0 +1,492 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! State snapshots allow bootstrapping a full node without replaying every checkpoint from
+//! genesis. A snapshot contains the live object set at the end of an epoch, along with the last
+//! checkpoint of every epoch up to that point. This allows the snapshot to be verified starting
+//! from the genesis committee: each end of epoch checkpoint is certified by the committee of its
+//! epoch and names the committee of the next one, and the last checkpoint commits to the root
+//! state hash of the live object set.
+//!
+//! A snapshot file is a sequence of BCS-encoded records, each prefixed by its length as a little
+//! endian u64: a `StateSnapshotHeader`, followed by `object_count` objects.
+
+use anyhow::{anyhow, bail, ensure, Result};
+use fastcrypto::hash::MultisetHash;
+use itertools::Itertools;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use sui_config::genesis::Genesis;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointCommitment, ECMHLiveObjectSetDigest, VerifiedCheckpoint,
+};
+use sui_types::object::Object;
+use sui_types::storage::ObjectKey;
+use tracing::info;
+use typed_store::Map;
+
+use crate::authority::authority_per_epoch_store::EpochStartConfiguration;
+use crate::authority::authority_store_tables::{AuthorityPerpetualTables, StateSnapshotRestore};
+use crate::checkpoints::CheckpointStore;
+use crate::epoch::committee_store::CommitteeStore;
+
+/// Number of objects written to the store in a single batch when restoring a snapshot.
+const RESTORE_BATCH_SIZE: usize = 10_000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateSnapshotHeader {
+    /// The last checkpoint of every epoch from genesis up to and including the epoch of the
+    /// snapshot, in order.
+    pub end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+    /// Number of objects following the header.
+    pub object_count: u64,
+}
+
+impl StateSnapshotHeader {
+    /// The checkpoint the snapshot corresponds to.
+    pub fn checkpoint(&self) -> Option<&CertifiedCheckpointSummary> {
+        self.end_of_epoch_checkpoints.last()
+    }
+}
+
+/// A snapshot that has been verified against the genesis committee. Its objects are not kept in
+/// memory, and are read from the snapshot file again when restoring it.
+pub struct VerifiedStateSnapshot {
+    /// The verified end of epoch checkpoints, in order.
+    pub checkpoints: Vec<VerifiedCheckpoint>,
+    /// The committees of the epochs following each of the checkpoints.
+    pub committees: Vec<Committee>,
+    pub root_state_hash: Accumulator,
+}
+
+impl VerifiedStateSnapshot {
+    pub fn checkpoint(&self) -> &VerifiedCheckpoint {
+        self.checkpoints
+            .last()
+            .expect("Verified snapshot has at least one checkpoint")
+    }
+}
+
+/// The state of a node that was just restored from a snapshot.
+pub struct RestoredStateSnapshot {
+    /// Configuration of the epoch following the snapshot, which the node starts in.
+    pub epoch_start_configuration: EpochStartConfiguration,
+}
+
+fn write_record<T: Serialize>(writer: &mut impl Write, record: &T) -> Result<()> {
+    let bytes = bcs::to_bytes(record)?;
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+fn read_record<T: DeserializeOwned>(reader: &mut impl Read) -> Result<T> {
+    let mut len = [0u8; 8];
+    reader.read_exact(&mut len)?;
+    let mut bytes = vec![0u8; u64::from_le_bytes(len) as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(bcs::from_bytes(&bytes)?)
+}
+
+/// Write a snapshot to `path` as its objects are produced, returning the root state hash of the
+/// objects written.
+pub fn write_state_snapshot(
+    path: &Path,
+    header: &StateSnapshotHeader,
+    objects: impl IntoIterator<Item = Result<Object>>,
+) -> Result<Accumulator> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_record(&mut writer, header)?;
+    let mut accumulator = Accumulator::default();
+    let mut object_count = 0;
+    for object in objects {
+        let object = object?;
+        accumulator.insert(object.compute_object_reference().2);
+        write_record(&mut writer, &object)?;
+        object_count += 1;
+    }
+    ensure!(
+        header.object_count == object_count,
+        "Header announces {} objects, but {} were provided",
+        header.object_count,
+        object_count
+    );
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    Ok(accumulator)
+}
+
+/// Read the header of the snapshot at `path`, returning it along with an iterator over the objects
+/// that follow it, which are read lazily.
+pub fn read_state_snapshot(path: &Path) -> Result<(StateSnapshotHeader, StateSnapshotObjects)> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let header: StateSnapshotHeader = read_record(&mut reader)?;
+    let objects = StateSnapshotObjects {
+        reader,
+        remaining: header.object_count,
+    };
+    Ok((header, objects))
+}
+
+/// The objects of a snapshot file, read one at a time.
+pub struct StateSnapshotObjects {
+    reader: BufReader<File>,
+    remaining: u64,
+}
+
+impl Iterator for StateSnapshotObjects {
+    type Item = Result<Object>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let object = read_record(&mut self.reader);
+        // Stop after the first error, the rest of the file cannot be trusted.
+        self.remaining = if object.is_ok() {
+            self.remaining - 1
+        } else {
+            0
+        };
+        Some(object)
+    }
+}
+
+/// Check that the objects of a snapshot match the root state hash committed to by its checkpoint.
+fn check_state_commitment(
+    checkpoint: &CertifiedCheckpointSummary,
+    root_state_hash: &Accumulator,
+) -> Result<()> {
+    ensure!(
+        checkpoint.summary.end_of_epoch_data.is_some(),
+        "Checkpoint {} is not the last checkpoint of an epoch",
+        checkpoint.sequence_number()
+    );
+    let epoch_commitments = checkpoint.summary.epoch_commitments()?;
+    let Some(CheckpointCommitment::ECMHLiveObjectSetDigest(committed)) =
+        epoch_commitments.first() else {
+        bail!(
+            "Checkpoint {} does not commit to the live object set",
+            checkpoint.sequence_number()
+        );
+    };
+    let digest = ECMHLiveObjectSetDigest::from(root_state_hash);
+    ensure!(
+        committed == &digest,
+        "Live object set digest {:?} does not match the digest {:?} committed to by checkpoint {}",
+        digest,
+        committed,
+        checkpoint.sequence_number()
+    );
+    Ok(())
+}
+
+/// Verify the checkpoints of a snapshot starting from the genesis committee, and its objects
+/// against the state commitment of its checkpoint.
+pub fn verify_state_snapshot(
+    genesis_committee: &Committee,
+    header: StateSnapshotHeader,
+    objects: impl IntoIterator<Item = Result<Object>>,
+) -> Result<VerifiedStateSnapshot> {
+    ensure!(
+        !header.end_of_epoch_checkpoints.is_empty(),
+        "Snapshot does not contain any checkpoint"
+    );
+
+    let mut committee = genesis_committee.clone();
+    let mut checkpoints = Vec::with_capacity(header.end_of_epoch_checkpoints.len());
+    let mut committees = Vec::with_capacity(header.end_of_epoch_checkpoints.len());
+    for checkpoint in header.end_of_epoch_checkpoints {
+        ensure!(
+            checkpoint.epoch() == committee.epoch,
+            "Expected the last checkpoint of epoch {}, found checkpoint {} of epoch {}",
+            committee.epoch,
+            checkpoint.sequence_number(),
+            checkpoint.epoch()
+        );
+        let checkpoint =
+            VerifiedCheckpoint::new(checkpoint, &committee).map_err(|(checkpoint, e)| {
+                anyhow!(
+                    "Failed to verify checkpoint {}: {e}",
+                    checkpoint.sequence_number()
+                )
+            })?;
+        let end_of_epoch_data = checkpoint
+            .summary
+            .end_of_epoch_data
+            .as_ref()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Checkpoint {} is not the last checkpoint of epoch {}",
+                    checkpoint.sequence_number(),
+                    checkpoint.epoch()
+                )
+            })?;
+        committee = Committee::new(
+            checkpoint.epoch() + 1,
+            end_of_epoch_data.next_epoch_protocol_version,
+            end_of_epoch_data
+                .next_epoch_committee
+                .iter()
+                .cloned()
+                .collect(),
+        )?;
+        checkpoints.push(checkpoint);
+        committees.push(committee.clone());
+    }
+
+    let mut root_state_hash = Accumulator::default();
+    let mut object_count = 0;
+    for object in objects {
+        root_state_hash.insert(object?.compute_object_reference().2);
+        object_count += 1;
+    }
+    ensure!(
+        header.object_count == object_count,
+        "Snapshot announces {} objects, but contains {}",
+        header.object_count,
+        object_count
+    );
+    check_state_commitment(checkpoints.last().unwrap().inner(), &root_state_hash)?;
+
+    Ok(VerifiedStateSnapshot {
+        checkpoints,
+        committees,
+        root_state_hash,
+    })
+}
+
+/// Export the live object set of the node whose database is at `db_path` at the end of `epoch`,
+/// or at the end of the last epoch the node executed if `epoch` is not set. The node must not be
+/// running, and must still hold the versions of the objects that were live at the end of the
+/// epoch. The snapshot is written to a temporary file next to `output` as objects are read from
+/// the store, and only moved to `output` once it has been checked against the state commitment.
+pub fn export_state_snapshot(
+    db_path: &Path,
+    epoch: Option<EpochId>,
+    output: &Path,
+) -> Result<StateSnapshotHeader> {
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    let highest_executed = checkpoint_store
+        .get_highest_executed_checkpoint_seq_number()?
+        .ok_or_else(|| anyhow!("No checkpoint has been executed"))?;
+
+    let mut end_of_epoch_checkpoints = vec![];
+    while let Some(checkpoint) =
+        checkpoint_store.get_epoch_last_checkpoint(end_of_epoch_checkpoints.len() as EpochId)?
+    {
+        if checkpoint.sequence_number() > highest_executed
+            || epoch.map_or(false, |epoch| checkpoint.epoch() > epoch)
+        {
+            break;
+        }
+        end_of_epoch_checkpoints.push(checkpoint.into_inner());
+    }
+    let Some(checkpoint) = end_of_epoch_checkpoints.last() else {
+        bail!("No epoch has been executed to the end");
+    };
+    if let Some(epoch) = epoch {
+        ensure!(
+            checkpoint.epoch() == epoch,
+            "The last checkpoint of epoch {epoch} has not been executed"
+        );
+    }
+    info!(
+        epoch = checkpoint.epoch(),
+        checkpoint = checkpoint.sequence_number(),
+        "Exporting state snapshot"
+    );
+
+    // Roll the live object set back to the end of the epoch, by undoing the effects of every
+    // checkpoint executed since, in reverse order.
+    let mut live_objects: BTreeMap<ObjectID, SequenceNumber> = perpetual_tables
+        .iter_live_object_set()
+        .map(|(id, version, _)| (id, version))
+        .collect();
+    for seq in (checkpoint.sequence_number() + 1..=highest_executed).rev() {
+        let later = checkpoint_store
+            .get_checkpoint_by_sequence_number(seq)?
+            .ok_or_else(|| anyhow!("Checkpoint {seq} not found"))?;
+        let contents = checkpoint_store
+            .get_checkpoint_contents(&later.content_digest())?
+            .ok_or_else(|| anyhow!("Contents of checkpoint {seq} not found"))?;
+        let effects_digests: Vec<_> = contents.iter().map(|digests| digests.effects).collect();
+        for effects in perpetual_tables
+            .effects
+            .multi_get(&effects_digests)?
+            .into_iter()
+            .rev()
+        {
+            let effects =
+                effects.ok_or_else(|| anyhow!("Effects of checkpoint {seq} not found"))?;
+            for (obj_ref, _, _) in effects.all_mutated() {
+                live_objects.remove(&obj_ref.0);
+            }
+            for (id, version) in &effects.modified_at_versions {
+                live_objects.insert(*id, *version);
+            }
+        }
+    }
+
+    let header = StateSnapshotHeader {
+        end_of_epoch_checkpoints,
+        object_count: live_objects.len() as u64,
+    };
+    let objects = live_objects.into_iter().map(|(id, version)| {
+        perpetual_tables
+            .objects
+            .get(&ObjectKey(id, version))?
+            .ok_or_else(|| anyhow!("Object {id} at version {version} has been pruned"))
+    });
+
+    let tmp_output = output.with_extension("tmp");
+    let written = write_state_snapshot(&tmp_output, &header, objects).and_then(|root_state_hash| {
+        check_state_commitment(
+            header.checkpoint().expect("Header has a checkpoint"),
+            &root_state_hash,
+        )
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp_output);
+        return Err(e);
+    }
+    fs::rename(&tmp_output, output)?;
+    Ok(header)
+}
+
+/// Initialize the stores of a node from the snapshot at `snapshot_path`, if the authority store
+/// at `store_path` is empty. The node then starts in the epoch following the snapshot, and state
+/// sync resumes from its checkpoint.
+///
+/// The store is marked as restored only once everything has been written. A restore that was
+/// interrupted is resumed from the start, as long as it is given the same snapshot: writing the
+/// same objects again is idempotent, while objects of another snapshot would be mixed with the
+/// ones already written.
+pub fn restore_state_snapshot(
+    snapshot_path: &Path,
+    store_path: &Path,
+    genesis: &Genesis,
+    committee_store: &CommitteeStore,
+    checkpoint_store: &CheckpointStore,
+) -> Result<Option<RestoredStateSnapshot>> {
+    let perpetual_tables = AuthorityPerpetualTables::open(store_path, None);
+    let interrupted = match perpetual_tables.get_state_snapshot_restore()? {
+        Some(StateSnapshotRestore::Complete(digest)) => {
+            info!("Authority store was already restored from the state snapshot of checkpoint {digest}");
+            return Ok(None);
+        }
+        Some(StateSnapshotRestore::InProgress(digest)) => Some(digest),
+        None if !perpetual_tables.database_is_empty()? => {
+            info!("Authority store is already initialized, not restoring state snapshot");
+            return Ok(None);
+        }
+        None => None,
+    };
+
+    let (header, objects) = read_state_snapshot(snapshot_path)?;
+    let digest = header
+        .checkpoint()
+        .ok_or_else(|| anyhow!("Snapshot does not contain any checkpoint"))?
+        .digest();
+    if let Some(interrupted) = interrupted {
+        ensure!(
+            interrupted == digest,
+            "Restoring the state snapshot of checkpoint {interrupted} was interrupted, \
+             restore the same snapshot again or delete the store"
+        );
+        info!("Resuming the interrupted restore of the state snapshot of checkpoint {digest}");
+    }
+
+    // Verify the whole snapshot before writing anything to the store.
+    let object_count = header.object_count;
+    let snapshot = verify_state_snapshot(&genesis.committee()?, header, objects)?;
+    let checkpoint = snapshot.checkpoint().clone();
+    let epoch = checkpoint.epoch();
+    info!(
+        epoch,
+        checkpoint = checkpoint.sequence_number(),
+        objects = object_count,
+        "Restoring state snapshot"
+    );
+    perpetual_tables.set_state_snapshot_restore(StateSnapshotRestore::InProgress(digest))?;
+
+    // The objects are read from the file again, so check that they are still the verified ones
+    // before marking the restore complete.
+    let (_, objects) = read_state_snapshot(snapshot_path)?;
+    let mut root_state_hash = Accumulator::default();
+    for chunk in &objects.chunks(RESTORE_BATCH_SIZE) {
+        let chunk = chunk.collect::<Result<Vec<_>>>()?;
+        let refs: Vec<_> = chunk
+            .iter()
+            .map(|object| (object.compute_object_reference(), object))
+            .collect();
+        root_state_hash.insert_all(refs.iter().map(|(obj_ref, _)| obj_ref.2));
+        perpetual_tables
+            .objects
+            .batch()
+            .insert_batch(
+                &perpetual_tables.objects,
+                refs.iter()
+                    .map(|(obj_ref, object)| (ObjectKey::from(obj_ref), *object)),
+            )?
+            .insert_batch(
+                &perpetual_tables.parent_sync,
+                refs.iter()
+                    .map(|(obj_ref, object)| (obj_ref, object.previous_transaction)),
+            )?
+            .insert_batch(
+                &perpetual_tables.owned_object_transaction_locks,
+                refs.iter()
+                    .filter(|(_, object)| !object.is_child_object())
+                    .map(|(obj_ref, _)| (obj_ref, None)),
+            )?
+            .write()?;
+    }
+    ensure!(
+        root_state_hash == snapshot.root_state_hash,
+        "State snapshot changed while it was being restored"
+    );
+    perpetual_tables.root_state_hash_by_epoch.insert(
+        &epoch,
+        &(checkpoint.sequence_number(), snapshot.root_state_hash),
+    )?;
+
+    let system_state = perpetual_tables.get_sui_system_state_object()?;
+    ensure!(
+        system_state.epoch == epoch + 1,
+        "Snapshot of epoch {epoch} contains the system state of epoch {}",
+        system_state.epoch
+    );
+
+    for committee in &snapshot.committees {
+        committee_store.insert_new_committee(committee)?;
+    }
+
+    let genesis_checkpoint = genesis.checkpoint();
+    checkpoint_store.insert_checkpoint_contents(genesis.checkpoint_contents().clone())?;
+    checkpoint_store.insert_verified_checkpoint(genesis_checkpoint)?;
+    for end_of_epoch_checkpoint in snapshot.checkpoints {
+        checkpoint_store.insert_verified_checkpoint(end_of_epoch_checkpoint)?;
+    }
+    checkpoint_store.update_highest_synced_checkpoint(&checkpoint)?;
+    checkpoint_store.update_highest_executed_checkpoint(&checkpoint)?;
+    perpetual_tables.set_recovery_epoch(epoch + 1)?;
+
+    // Written last: until then, the store is known to be partially restored.
+    perpetual_tables.set_state_snapshot_restore(StateSnapshotRestore::Complete(digest))?;
+
+    Ok(Some(RestoredStateSnapshot {
+        epoch_start_configuration: EpochStartConfiguration {
+            epoch_id: epoch + 1,
+            epoch_start_timestamp_ms: system_state.epoch_start_timestamp_ms,
+        },
+    }))
+}
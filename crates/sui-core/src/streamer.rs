@@ -2,24 +2,28 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::event_handler::EVENT_DISPATCH_BUFFER_SIZE;
-use futures::Stream;
+use futures::{stream, Stream, StreamExt};
 use mysten_metrics::spawn_monitored_task;
 use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use sui_types::base_types::ObjectID;
 use sui_types::error::SuiError;
 use sui_types::filter::Filter;
 use tokio::runtime::Handle;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::{mpsc, RwLock};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, warn};
 
-type Subscribers<T, F> = Arc<RwLock<BTreeMap<String, (Sender<T>, F)>>>;
+/// A subscriber's channel, filter, and a flag raised when it is dropped for lagging behind.
+type Subscribers<T, F> = Arc<RwLock<BTreeMap<String, (Sender<T>, F, Arc<AtomicBool>)>>>;
 
 /// The Streamer splits a mpsc channel into multiple mpsc channels using the subscriber's `Filter<T>` object.
-/// Data will be sent to the subscribers in parallel and the subscription will be dropped if it received a send error.
+/// Data is sent to the subscribers in order. A subscriber is dropped once its receiver is closed, or when
+/// its buffer is full, in which case its stream ends with a `SuiError::SubscriptionLagged` error.
 pub struct Streamer<T, F: Filter<T>> {
     streamer_queue: Sender<T>,
     subscribers: Subscribers<T, F>,
@@ -40,41 +44,61 @@ where
         let subscribers = streamer.subscribers.clone();
         spawn_monitored_task!(async move {
             while let Some(data) = rx.recv().await {
-                Self::send_to_all_subscribers(subscribers.clone(), data).await;
+                Self::send_to_all_subscribers(&subscribers, data).await;
             }
         });
         streamer
     }
 
-    async fn send_to_all_subscribers(subscribers: Subscribers<T, F>, data: T) {
-        for (id, (subscriber, filter)) in subscribers.read().await.clone() {
+    async fn send_to_all_subscribers(subscribers: &Subscribers<T, F>, data: T) {
+        let mut dropped = vec![];
+        for (id, (subscriber, filter, lagged)) in subscribers.read().await.iter() {
             if !(filter.matches(&data)) {
                 continue;
             }
-            let data = data.clone();
-            let subscribers = subscribers.clone();
-            spawn_monitored_task!(async move {
-                match subscriber.send(data).await {
-                    Ok(_) => {
-                        debug!("Sending Move event to subscriber [{id}].")
-                    }
-                    Err(e) => {
-                        subscribers.write().await.remove(&id);
-                        warn!("Error sending event, removing subscriber [{id}] from subscriber list. Error: {e}");
-                    }
+            match subscriber.try_send(data.clone()) {
+                Ok(_) => {
+                    debug!("Sending Move event to subscriber [{id}].")
                 }
-            });
+                Err(TrySendError::Full(_)) => {
+                    lagged.store(true, Ordering::Release);
+                    warn!("Subscriber [{id}] is lagging behind, removing it from subscriber list.");
+                    dropped.push(id.clone());
+                }
+                Err(TrySendError::Closed(_)) => {
+                    debug!(
+                        "Subscriber [{id}] closed its stream, removing it from subscriber list."
+                    );
+                    dropped.push(id.clone());
+                }
+            }
+        }
+        if !dropped.is_empty() {
+            let mut subscribers = subscribers.write().await;
+            for id in dropped {
+                subscribers.remove(&id);
+            }
         }
     }
 
     /// Subscribe to the data stream filtered by the filter object.
-    pub fn subscribe(&self, filter: F) -> impl Stream<Item = T> {
+    /// If the subscriber falls more than `EVENT_DISPATCH_BUFFER_SIZE` items behind, the stream
+    /// yields the items already buffered, then a `SuiError::SubscriptionLagged` error, and ends.
+    pub fn subscribe(&self, filter: F) -> impl Stream<Item = Result<T, SuiError>> {
         let handle = Handle::current();
         let _ = handle.enter();
         let mut subscribers = futures::executor::block_on(async { self.subscribers.write().await });
         let (tx, rx) = mpsc::channel::<T>(EVENT_DISPATCH_BUFFER_SIZE);
-        subscribers.insert(ObjectID::random().to_string(), (tx, filter));
-        ReceiverStream::new(rx)
+        let lagged = Arc::new(AtomicBool::new(false));
+        subscribers.insert(ObjectID::random().to_string(), (tx, filter, lagged.clone()));
+        ReceiverStream::new(rx).map(Ok).chain(
+            stream::once(async move {
+                lagged
+                    .load(Ordering::Acquire)
+                    .then_some(SuiError::SubscriptionLagged(EVENT_DISPATCH_BUFFER_SIZE))
+            })
+            .filter_map(|lagged| async move { lagged.map(Err) }),
+        )
     }
 
     pub async fn send(&self, data: T) -> Result<(), SuiError> {
@@ -4,6 +4,8 @@
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::authority::AuthorityStore;
 use std::collections::HashSet;
+use sui_config::node::TransactionDenyConfig;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::ObjectRef;
 use sui_types::gas::SuiCostTable;
 use sui_types::messages::TransactionKind;
@@ -42,6 +44,7 @@ async fn get_gas_status(
         transaction.gas_price,
         &transaction.kind,
         extra_gas_object_refs,
+        transaction.extra_gas_payment(),
     )
     .await
     .map_err(SuiError::into_transaction_input_error)
@@ -56,7 +59,7 @@ pub async fn check_transaction_input(
     transaction: &TransactionData,
 ) -> SuiResult<(SuiGasStatus<'static>, InputObjects)> {
     transaction
-        .validity_check()
+        .validity_check(epoch_store.protocol_config())
         .map_err(SuiError::into_transaction_input_error)?;
     let gas_status = get_gas_status(store, epoch_store, transaction).await?;
     let input_objects = transaction.input_objects()?;
@@ -65,15 +68,51 @@ pub async fn check_transaction_input(
     Ok((gas_status, input_objects))
 }
 
+/// Checks the sender and input objects of `transaction` against the deny-list. Input packages
+/// include the packages called by the transaction, as well as the dependencies of the packages it
+/// publishes.
+pub fn check_transaction_deny_list(
+    deny_config: &TransactionDenyConfig,
+    transaction: &TransactionData,
+) -> SuiResult {
+    if deny_config.is_empty() {
+        return Ok(());
+    }
+    let sender = transaction.sender_address();
+    fp_ensure!(
+        !deny_config.address_deny_list.contains(&sender),
+        SuiError::TransactionDenied {
+            error: format!("Sender {sender} is denied"),
+        }
+    );
+    for input in transaction.input_objects()? {
+        let id = input.object_id();
+        let denied = match input {
+            InputObjectKind::MovePackage(_) => deny_config.package_deny_list.contains(&id),
+            InputObjectKind::ImmOrOwnedMoveObject(_) | InputObjectKind::SharedMoveObject { .. } => {
+                deny_config.object_deny_list.contains(&id)
+            }
+        };
+        fp_ensure!(
+            !denied,
+            SuiError::TransactionDenied {
+                error: format!("Input {id} is denied"),
+            }
+        );
+    }
+    Ok(())
+}
+
 /// WARNING! This should only be used for the dev-inspect transaction. This transaction type
 /// bypasses many of the normal object checks
 pub(crate) async fn check_dev_inspect_input(
     store: &AuthorityStore,
+    config: &ProtocolConfig,
     kind: &TransactionKind,
     gas_object: Object,
 ) -> Result<(ObjectRef, InputObjects), anyhow::Error> {
     let gas_object_ref = gas_object.compute_object_reference();
-    TransactionData::validity_check_impl(kind, &gas_object_ref)?;
+    TransactionData::validity_check_impl(config, kind, &gas_object_ref)?;
     for k in kind.single_transactions() {
         match k {
             SingleTransactionKind::TransferObject(_)
@@ -86,7 +125,8 @@ pub(crate) async fn check_dev_inspect_input(
             | SingleTransactionKind::ChangeEpoch(_)
             | SingleTransactionKind::Genesis(_)
             | SingleTransactionKind::ConsensusCommitPrologue(_)
-            | SingleTransactionKind::ProgrammableTransaction(_) => {
+            | SingleTransactionKind::ProgrammableTransaction(_)
+            | SingleTransactionKind::Upgrade(_) => {
                 anyhow::bail!("Transaction kind {} is not supported in dev-inspect", k)
             }
         }
@@ -139,8 +179,9 @@ pub async fn check_certificate_input(
     Ok((gas_status, input_objects))
 }
 
-/// Checking gas budget by fetching the gas object only from the store,
+/// Checking gas budget by fetching the gas objects only from the store,
 /// and check whether the balance and budget satisfies the miminum requirement.
+/// The balance of the `smashed_gas_payment` coins counts toward the gas budget.
 /// Returns the gas object (to be able to reuse it latter) and a gas status
 /// that will be used in the entire lifecycle of the transaction execution.
 #[instrument(level = "trace", skip_all)]
@@ -152,6 +193,7 @@ async fn check_gas(
     computation_gas_price: u64,
     tx_kind: &TransactionKind,
     additional_objects_for_gas_payment: Vec<ObjectRef>,
+    smashed_gas_payment: &[ObjectRef],
 ) -> SuiResult<SuiGasStatus<'static>> {
     if tx_kind.is_system_tx() {
         Ok(SuiGasStatus::new_unmetered())
@@ -178,6 +220,16 @@ async fn check_gas(
         // TODO: We should revisit how we compute gas price and compare to gas budget.
         let gas_price = std::cmp::max(computation_gas_price, storage_gas_price);
 
+        let mut smashed_gas_objs = vec![];
+        for obj_ref in smashed_gas_payment {
+            let obj = store.get_object_by_key(&obj_ref.0, obj_ref.1)?;
+            let obj = obj.ok_or(SuiError::ObjectNotFound {
+                object_id: obj_ref.0,
+                version: Some(obj_ref.1),
+            })?;
+            smashed_gas_objs.push(obj);
+        }
+
         if tx_kind.is_pay_sui_tx() {
             let mut additional_objs = vec![];
             for obj_ref in additional_objects_for_gas_payment.iter() {
@@ -194,6 +246,7 @@ async fn check_gas(
                 gas_price,
                 extra_amount,
                 additional_objs,
+                smashed_gas_objs,
                 &cost_table,
             )?;
         } else {
@@ -203,6 +256,7 @@ async fn check_gas(
                 gas_price,
                 extra_amount,
                 vec![],
+                smashed_gas_objs,
                 &cost_table,
             )?;
         }
@@ -31,6 +31,7 @@ pub struct TransactionManager {
 
 struct PendingCertificate {
     certificate: VerifiedCertificate,
+    inputs: Vec<ObjectKey>,
     missing: BTreeSet<ObjectKey>,
 }
 
@@ -55,8 +56,9 @@ struct Inner {
 
     // Maps transaction digests to their content and missing input objects.
     pending_certificates: HashMap<TransactionDigest, PendingCertificate>,
-    // Transactions that have all input objects available, but have not finished execution.
-    executing_certificates: HashSet<TransactionDigest>,
+    // Transactions that have all input objects available, but have not finished execution,
+    // along with their input objects.
+    executing_certificates: HashMap<TransactionDigest, Vec<ObjectKey>>,
 }
 
 impl Inner {
@@ -126,7 +128,7 @@ impl TransactionManager {
                 continue;
             }
             // skip already executing txes
-            if inner.executing_certificates.contains(&digest) {
+            if inner.executing_certificates.contains_key(&digest) {
                 self.metrics
                     .transaction_manager_num_enqueued_certificates
                     .with_label_values(&["already_executing"])
@@ -144,18 +146,22 @@ impl TransactionManager {
                 continue;
             }
 
-            let missing = self
+            let inputs = self
                 .authority_store
-                .get_missing_input_objects(
+                .get_input_object_keys(
                     &digest,
                     &cert.data().intent_message.value.input_objects()?,
                     epoch_store,
                 )
                 .expect("Are shared object locks set prior to enqueueing certificates?");
+            let missing = self.authority_store.get_missing_input_objects(&inputs)?;
 
             if missing.is_empty() {
                 debug!("certificate ready");
-                assert!(inner.executing_certificates.insert(digest));
+                assert!(inner
+                    .executing_certificates
+                    .insert(digest, inputs)
+                    .is_none());
                 self.certificate_ready(cert);
                 self.metrics
                     .transaction_manager_num_enqueued_certificates
@@ -197,6 +203,7 @@ impl TransactionManager {
                         digest,
                         PendingCertificate {
                             certificate: cert,
+                            inputs,
                             missing: missing.into_iter().collect()
                         }
                     )
@@ -251,7 +258,10 @@ impl TransactionManager {
                         if pending_cert.missing.is_empty() {
                             debug!(tx_digest = ?digest, "certificate ready");
                             let pending_cert = inner.pending_certificates.remove(digest).unwrap();
-                            assert!(inner.executing_certificates.insert(*digest));
+                            assert!(inner
+                                .executing_certificates
+                                .insert(*digest, pending_cert.inputs)
+                                .is_none());
                             ready_digests.push(*digest);
                             self.certificate_ready(pending_cert.certificate);
                         } else {
@@ -315,6 +325,36 @@ impl TransactionManager {
             .collect()
     }
 
+    // Returns the certificates waiting for input objects, along with the objects they are
+    // missing.
+    pub(crate) fn pending_certificates(&self) -> Vec<(TransactionDigest, Vec<ObjectKey>)> {
+        let inner = self.inner.read();
+        inner
+            .pending_certificates
+            .iter()
+            .map(|(digest, pending)| (*digest, pending.missing.iter().cloned().collect()))
+            .collect()
+    }
+
+    // Returns the certificates that have all their input objects available, and are executing.
+    pub(crate) fn executing_certificates(&self) -> Vec<TransactionDigest> {
+        let inner = self.inner.read();
+        inner.executing_certificates.keys().cloned().collect()
+    }
+
+    // Returns the input objects of all certificates which have not finished execution, whether
+    // they are waiting for input objects or executing.
+    pub(crate) fn unexecuted_input_objects(&self) -> HashSet<ObjectKey> {
+        let inner = self.inner.read();
+        inner
+            .pending_certificates
+            .values()
+            .flat_map(|pending| pending.inputs.iter())
+            .chain(inner.executing_certificates.values().flatten())
+            .copied()
+            .collect()
+    }
+
     // Reconfigures the TransactionManager for a new epoch. Existing transactions will be dropped
     // because they are no longer relevant and may be incorrect in the new epoch.
     pub(crate) fn reconfigure(&self, new_epoch: EpochId) {
@@ -30,7 +30,9 @@ use std::fs;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use sui_json_rpc_types::{SuiExecutionResult, SuiExecutionStatus, SuiGasCostSummary};
+use sui_json_rpc_types::{
+    SuiExecutionResult, SuiExecutionStatus, SuiGasCostSummary, SuiObjectVersionStatus,
+};
 use sui_types::utils::{
     make_committee_key, mock_certified_checkpoint, to_sender_signed_transaction,
 };
@@ -1072,6 +1074,71 @@ async fn test_handle_transfer_transaction_ok() {
     );
 }
 
+#[tokio::test]
+async fn test_handle_transfer_transaction_denied() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let transfer_transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+
+    authority_state.update_transaction_deny_config(TransactionDenyConfig {
+        address_deny_list: [sender].into(),
+        ..Default::default()
+    });
+    assert!(matches!(
+        authority_state
+            .handle_transaction(transfer_transaction.clone())
+            .await,
+        Err(SuiError::TransactionDenied { .. })
+    ));
+
+    authority_state.update_transaction_deny_config(TransactionDenyConfig {
+        object_deny_list: [gas_object_id].into(),
+        ..Default::default()
+    });
+    assert!(matches!(
+        authority_state
+            .handle_transaction(transfer_transaction.clone())
+            .await,
+        Err(SuiError::TransactionDenied { .. })
+    ));
+
+    // No lock was taken on the denied transaction's inputs.
+    assert!(authority_state
+        .get_transaction_lock(
+            &object.compute_object_reference(),
+            &authority_state.epoch_store_for_testing()
+        )
+        .await
+        .unwrap()
+        .is_none());
+
+    authority_state.update_transaction_deny_config(TransactionDenyConfig::default());
+    authority_state
+        .handle_transaction(transfer_transaction)
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_transfer_package() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1909,6 +1976,81 @@ async fn test_handle_confirmation_transaction_ok() {
     );
 }
 
+#[tokio::test]
+async fn test_get_object_history() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let certified_transfer_transaction = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    authority_state
+        .execute_certificate(
+            &certified_transfer_transaction,
+            &authority_state.epoch_store_for_testing(),
+        )
+        .await
+        .unwrap()
+        .into_message()
+        .status
+        .unwrap();
+    let new_object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let history = authority_state
+        .get_object_history(object_id, None, 10, false)
+        .unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].version, object.version());
+    assert_eq!(history[0].status, SuiObjectVersionStatus::Exists);
+    assert_eq!(history[0].owner, Some(Owner::AddressOwner(sender)));
+    assert_eq!(history[0].previous_transaction, object.previous_transaction);
+    assert_eq!(history[1].version, new_object.version());
+    assert_eq!(history[1].digest, new_object.digest());
+    assert_eq!(history[1].owner, Some(Owner::AddressOwner(recipient)));
+    assert_eq!(
+        history[1].previous_transaction,
+        *certified_transfer_transaction.digest()
+    );
+    // Not executed as part of a checkpoint.
+    assert_eq!(history[1].checkpoint, None);
+
+    let latest = authority_state
+        .get_object_history(object_id, None, 1, true)
+        .unwrap();
+    assert_eq!(latest, history[1..]);
+    let from_cursor = authority_state
+        .get_object_history(object_id, Some(new_object.version()), 10, false)
+        .unwrap();
+    assert_eq!(from_cursor, history[1..]);
+
+    assert!(authority_state
+        .get_object_history(ObjectID::random(), None, 10, false)
+        .is_err());
+}
+
 struct LimitedPoll<F: Future> {
     inner: Pin<Box<F>>,
     count: u64,
@@ -2536,7 +2678,7 @@ async fn test_authority_persist() {
             checkpoint_store,
             &registry,
             &AuthorityStorePruningConfig::default(),
-            &[], // no genesis objects
+            TransactionDenyConfig::default(),
         )
         .await
     }
@@ -4444,3 +4586,154 @@ async fn test_tallying_rule_score_updates() {
     assert_eq!(get_auth_score_metric(&auth_2_name), 2);
     assert_eq!(get_auth_score_metric(&auth_3_name), 1);
 }
+
+#[test]
+fn test_choose_next_protocol_version() {
+    use sui_protocol_config::SupportedProtocolVersions;
+
+    let (committee, _) = Committee::new_simple_test_committee();
+    let current = committee.protocol_version;
+    let next = current.next();
+    let capabilities = |supported: SupportedProtocolVersions, count: usize| {
+        committee
+            .names()
+            .take(count)
+            .map(|name| AuthorityCapabilities::new(*name, supported))
+            .collect::<Vec<_>>()
+    };
+    let upgraded =
+        SupportedProtocolVersions::new_for_testing(ProtocolVersion::MIN.as_u64(), next.as_u64());
+
+    // No validator advertised its capabilities yet.
+    assert_eq!(
+        AuthorityState::choose_protocol_version(&committee, vec![]),
+        current
+    );
+
+    // Only the current version is supported.
+    assert_eq!(
+        AuthorityState::choose_protocol_version(
+            &committee,
+            capabilities(SupportedProtocolVersions::SYSTEM_DEFAULT, 4)
+        ),
+        current
+    );
+
+    // Supporters of the next version hold less than a quorum of stake.
+    assert_eq!(
+        AuthorityState::choose_protocol_version(&committee, capabilities(upgraded, 2)),
+        current
+    );
+
+    // A quorum of stake supports the next version.
+    assert_eq!(
+        AuthorityState::choose_protocol_version(&committee, capabilities(upgraded, 3)),
+        next
+    );
+
+    // Versions are never skipped.
+    let far_ahead =
+        SupportedProtocolVersions::new_for_testing(next.next().as_u64(), next.next().as_u64());
+    assert_eq!(
+        AuthorityState::choose_protocol_version(&committee, capabilities(far_ahead, 4)),
+        current
+    );
+}
+
+#[tokio::test]
+async fn test_state_accumulator_tracks_live_object_set() {
+    use crate::state_accumulator::StateAccumulator;
+    use fastcrypto::hash::MultisetHash;
+
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, pkg_ref) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+    let accumulator = StateAccumulator::new(authority_state.database.clone());
+    let mut running = accumulator.accumulate_live_object_set();
+
+    let effects = create_move_object(
+        &pkg_ref.0,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    let (object_id, _, _) = effects.created[0].0;
+    running.union(&accumulator.accumulate_effects(&[effects]).unwrap());
+    assert_eq!(running, accumulator.accumulate_live_object_set());
+
+    // Mutate and then delete the object, accumulating both transactions at once as if they were
+    // part of the same checkpoint.
+    let update_effects = call_move(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &pkg_ref.0,
+        "object_basics",
+        "set_value",
+        vec![],
+        vec![
+            TestCallArg::Object(object_id),
+            TestCallArg::Pure(bcs::to_bytes(&42u64).unwrap()),
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(update_effects.status.is_ok());
+    let delete_effects = call_move(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &pkg_ref.0,
+        "object_basics",
+        "delete",
+        vec![],
+        vec![TestCallArg::Object(object_id)],
+    )
+    .await
+    .unwrap();
+    assert!(delete_effects.status.is_ok());
+    running.union(
+        &accumulator
+            .accumulate_effects(&[update_effects, delete_effects])
+            .unwrap(),
+    );
+    assert_eq!(running, accumulator.accumulate_live_object_set());
+}
+
+#[tokio::test]
+async fn test_state_accumulator_seeds_epoch_without_previous_root() {
+    use crate::state_accumulator::StateAccumulator;
+
+    let (sender, _): (_, AccountKeyPair) = get_key_pair();
+    let authority_state = init_state_with_ids(vec![(sender, ObjectID::random())]).await;
+    let epoch_store = authority_state.epoch_store_for_testing();
+    let accumulator = StateAccumulator::new(authority_state.database.clone());
+
+    // No checkpoint of epoch 0 has been accumulated, so its root cannot be computed yet.
+    assert_eq!(
+        accumulator
+            .accumulate_epoch_if_complete(0, 0, &epoch_store)
+            .await
+            .unwrap(),
+        None
+    );
+
+    // The root of epoch 0 is missing, so the root of epoch 1 is seeded from the live object set.
+    let root = accumulator
+        .accumulate_epoch_if_complete(1, 5, &epoch_store)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(root, accumulator.accumulate_live_object_set());
+    assert_eq!(
+        authority_state.database.get_root_state_hash(1).unwrap(),
+        Some((5, root))
+    );
+}
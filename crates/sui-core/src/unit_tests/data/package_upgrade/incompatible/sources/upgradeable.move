@@ -0,0 +1,32 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module examples::upgradeable {
+    use sui::object::{Self, UID};
+    use sui::package;
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    struct UPGRADEABLE has drop {}
+
+    struct Counter has key {
+        id: UID,
+        value: u64,
+    }
+
+    fun init(otw: UPGRADEABLE, ctx: &mut TxContext) {
+        package::claim_upgrade_cap_and_keep(&otw, ctx)
+    }
+
+    /// Changed signature of a public function
+    public fun value(v: u64): u64 {
+        v
+    }
+
+    public entry fun create(ctx: &mut TxContext) {
+        transfer::transfer(
+            Counter { id: object::new(ctx), value: value(1) },
+            tx_context::sender(ctx),
+        )
+    }
+}
@@ -138,6 +138,49 @@ async fn test_native_transfer_sufficient_gas() -> SuiResult {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_native_transfer_smashed_gas() -> SuiResult {
+    // This test pays for a native transfer with three gas coins, none of which covers the gas
+    // budget alone. The extra coins are expected to be smashed into the first one.
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object_id: ObjectID = ObjectID::random();
+    let authority_state = init_state_with_ids(vec![(sender, object_id)]).await;
+    let coin_balance = *MAX_GAS_BUDGET / 2;
+    let mut gas_refs = vec![];
+    for _ in 0..3 {
+        let gas_object =
+            Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, coin_balance);
+        gas_refs.push(gas_object.compute_object_reference());
+        authority_state.insert_genesis_object(gas_object).await;
+    }
+    let object = authority_state.get_object(&object_id).await?.unwrap();
+
+    let kind = TransactionKind::Single(SingleTransactionKind::TransferObject(TransferObject {
+        recipient: dbg_addr(2),
+        object_ref: object.compute_object_reference(),
+    }));
+    let data = TransactionData::new(kind, sender, gas_refs[0], *MAX_GAS_BUDGET, 1)
+        .with_extra_gas_payment(gas_refs[1..].to_vec());
+    let tx = to_sender_signed_transaction(data, &sender_key);
+    let effects = send_and_confirm_transaction(&authority_state, tx)
+        .await?
+        .1
+        .into_data();
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.gas_object.0 .0, gas_refs[0].0);
+    for (smashed_id, _, _) in &gas_refs[1..] {
+        assert!(effects.deleted.iter().any(|(id, _, _)| id == smashed_id));
+        assert!(authority_state.get_object(smashed_id).await?.is_none());
+    }
+
+    let gas_object = authority_state.get_object(&gas_refs[0].0).await?.unwrap();
+    assert_eq!(
+        GasCoin::try_from(&gas_object)?.value(),
+        3 * coin_balance - effects.gas_used.gas_used()
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_native_transfer_gas_price_is_used() {
     let gas_price_1 = 1;
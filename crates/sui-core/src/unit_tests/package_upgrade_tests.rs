@@ -0,0 +1,380 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::authority::authority_tests::{
+    call_move, init_state_with_ids, send_and_confirm_transaction,
+};
+use crate::authority::move_integration_tests::build_and_try_publish_test_package;
+use crate::authority::AuthorityState;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_framework_build::compiled_package::BuildConfig;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::error::SuiError;
+use sui_types::id::UID;
+use sui_types::messages::{
+    EntryTypeArgumentErrorKind, ExecutionFailureStatus, ExecutionStatus, PackageUpgradeError,
+    TransactionData, TransactionEffects, DUMMY_GAS_PRICE,
+};
+use sui_types::move_package::{MovePackage, UpgradeCap, UpgradeInfo};
+use sui_types::object::{Data, Owner, PACKAGE_VERSION};
+use sui_types::utils::to_sender_signed_transaction;
+
+const MAX_GAS: u64 = 10000;
+
+#[derive(serde::Deserialize)]
+struct Counter {
+    #[allow(dead_code)]
+    id: UID,
+    value: u64,
+}
+
+struct TestRunner {
+    authority: Arc<AuthorityState>,
+    sender: SuiAddress,
+    sender_key: AccountKeyPair,
+    gas: ObjectID,
+}
+
+impl TestRunner {
+    async fn new() -> Self {
+        let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+        let gas = ObjectID::random();
+        let authority = init_state_with_ids(vec![(sender, gas)]).await;
+        Self {
+            authority,
+            sender,
+            sender_key,
+            gas,
+        }
+    }
+
+    /// Publish the package in `package_upgrade/<name>`, returning the ID of the package and the
+    /// reference of the upgrade capability claimed by its initializer.
+    async fn publish(&self, name: &str) -> (ObjectID, ObjectRef) {
+        let effects = build_and_try_publish_test_package(
+            &self.authority,
+            &self.sender,
+            &self.sender_key,
+            &self.gas,
+            &format!("package_upgrade/{name}"),
+            MAX_GAS,
+            /* with_unpublished_deps */ false,
+        )
+        .await
+        .1
+        .into_data();
+        assert!(effects.status.is_ok(), "{:?}", effects.status);
+        assert_eq!(effects.created.len(), 2);
+
+        let (package, cap): (Vec<_>, Vec<_>) = effects
+            .created
+            .iter()
+            .partition(|(_, owner)| owner == &Owner::Immutable);
+        (package[0].0 .0, cap[0].0)
+    }
+
+    async fn upgrade(
+        &self,
+        package: ObjectID,
+        upgrade_cap: ObjectRef,
+        name: &str,
+    ) -> TransactionEffects {
+        let data = self.upgrade_data(package, upgrade_cap, name).await;
+        let transaction = to_sender_signed_transaction(data, &self.sender_key);
+        send_and_confirm_transaction(&self.authority, transaction)
+            .await
+            .unwrap()
+            .1
+            .into_data()
+    }
+
+    async fn upgrade_data(
+        &self,
+        package: ObjectID,
+        upgrade_cap: ObjectRef,
+        name: &str,
+    ) -> TransactionData {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("src/unit_tests/data/package_upgrade");
+        path.push(name);
+        let modules = sui_framework::build_move_package(&path, BuildConfig::new_for_testing())
+            .unwrap()
+            .get_package_bytes(/* with_unpublished_deps */ false);
+
+        let gas_ref = self
+            .authority
+            .get_object(&self.gas)
+            .await
+            .unwrap()
+            .unwrap()
+            .compute_object_reference();
+        TransactionData::new_upgrade(
+            self.sender,
+            gas_ref,
+            package,
+            modules,
+            vec![],
+            upgrade_cap,
+            MAX_GAS,
+            DUMMY_GAS_PRICE,
+        )
+    }
+
+    async fn package(&self, id: &ObjectID) -> MovePackage {
+        let Some(Data::Package(package)) = self
+            .authority
+            .get_object(id)
+            .await
+            .unwrap()
+            .map(|o| o.data)
+        else {
+            panic!("Package {id} not found");
+        };
+        package
+    }
+
+    async fn upgrade_cap(&self, id: &ObjectID) -> (ObjectRef, UpgradeCap) {
+        let object = self.authority.get_object(id).await.unwrap().unwrap();
+        let cap = bcs::from_bytes(object.data.try_as_move().unwrap().contents()).unwrap();
+        (object.compute_object_reference(), cap)
+    }
+}
+
+#[tokio::test]
+async fn test_upgrade_package() {
+    let runner = TestRunner::new().await;
+    let (package, cap_ref) = runner.publish("base").await;
+
+    let effects = runner.upgrade(package, cap_ref, "compatible").await;
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    assert_eq!(effects.created.len(), 1);
+    let (upgraded_ref, owner) = effects.created[0];
+    assert_eq!(owner, Owner::Immutable);
+    let upgraded = upgraded_ref.0;
+    assert_ne!(upgraded, package);
+
+    let upgraded_package = runner.package(&upgraded).await;
+    assert_eq!(upgraded_package.original_package_id(), package);
+    assert_eq!(
+        upgraded_package.type_origin("upgradeable", "Counter"),
+        Some(package)
+    );
+    assert_eq!(
+        upgraded_package.type_origin("upgradeable", "Marker"),
+        Some(upgraded)
+    );
+    assert_eq!(
+        upgraded_package.linkage_table().get(&package),
+        Some(&UpgradeInfo {
+            upgraded_id: upgraded,
+            upgraded_version: SequenceNumber::from_u64(2),
+        })
+    );
+
+    // The capability now refers to the upgraded package.
+    let (_, cap) = runner.upgrade_cap(&cap_ref.0).await;
+    assert_eq!(cap.package.bytes, upgraded);
+    assert_eq!(cap.version, 1);
+
+    // Calling into the upgraded package runs the new code, but creates objects of the types
+    // defined by the original package.
+    let effects = call_move(
+        &runner.authority,
+        &runner.gas,
+        &runner.sender,
+        &runner.sender_key,
+        &upgraded,
+        "upgradeable",
+        "create",
+        vec![],
+        vec![],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    assert_eq!(effects.created.len(), 1);
+    let counter = runner
+        .authority
+        .get_object(&effects.created[0].0 .0)
+        .await
+        .unwrap()
+        .unwrap();
+    let counter_object = counter.data.try_as_move().unwrap();
+    assert_eq!(ObjectID::from(counter_object.type_.address), package);
+    let counter: Counter = bcs::from_bytes(counter_object.contents()).unwrap();
+    assert_eq!(counter.value, 4);
+
+    // The original package is still available.
+    let effects = call_move(
+        &runner.authority,
+        &runner.gas,
+        &runner.sender,
+        &runner.sender_key,
+        &package,
+        "upgradeable",
+        "create",
+        vec![],
+        vec![],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+}
+
+#[tokio::test]
+async fn test_upgrade_upgraded_package() {
+    let runner = TestRunner::new().await;
+    let (package, cap_ref) = runner.publish("base").await;
+
+    let effects = runner.upgrade(package, cap_ref, "compatible").await;
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    let upgraded = effects.created[0].0 .0;
+
+    // Upgrading the original package again is not allowed, only the latest version.
+    let (cap_ref, _) = runner.upgrade_cap(&cap_ref.0).await;
+    let effects = runner.upgrade(package, cap_ref, "compatible").await;
+    assert_eq!(
+        effects.status,
+        ExecutionStatus::new_failure(ExecutionFailureStatus::package_upgrade_error(
+            PackageUpgradeError::PackageIDDoesNotMatch {
+                package_id: package,
+                cap_package_id: upgraded,
+            }
+        ))
+    );
+
+    let (cap_ref, _) = runner.upgrade_cap(&cap_ref.0).await;
+    let effects = runner.upgrade(upgraded, cap_ref, "compatible").await;
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    let (_, cap) = runner.upgrade_cap(&cap_ref.0).await;
+    assert_eq!(cap.package.bytes, effects.created[0].0 .0);
+    assert_eq!(cap.version, 2);
+}
+
+#[tokio::test]
+async fn test_incompatible_upgrade() {
+    let runner = TestRunner::new().await;
+    let (package, cap_ref) = runner.publish("base").await;
+
+    let effects = runner.upgrade(package, cap_ref, "incompatible").await;
+    assert_eq!(
+        effects.status,
+        ExecutionStatus::new_failure(ExecutionFailureStatus::package_upgrade_error(
+            PackageUpgradeError::IncompatibleUpgrade
+        ))
+    );
+    assert!(effects.created.is_empty());
+}
+
+#[tokio::test]
+async fn test_upgrade_with_wrong_cap() {
+    let runner = TestRunner::new().await;
+    let (package, _) = runner.publish("base").await;
+    let (other_package, other_cap_ref) = runner.publish("base").await;
+
+    let effects = runner.upgrade(package, other_cap_ref, "compatible").await;
+    assert_eq!(
+        effects.status,
+        ExecutionStatus::new_failure(ExecutionFailureStatus::package_upgrade_error(
+            PackageUpgradeError::PackageIDDoesNotMatch {
+                package_id: package,
+                cap_package_id: other_package,
+            }
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_types_introduced_by_upgrade() {
+    let runner = TestRunner::new().await;
+    let (package, cap_ref) = runner.publish("base").await;
+    let effects = runner.upgrade(package, cap_ref, "compatible").await;
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    let upgraded = effects.created[0].0 .0;
+
+    // Types introduced by an upgrade are published at the address of the original package, but
+    // only the versions that define them can be called with them.
+    let marker = TypeTag::Struct(Box::new(StructTag {
+        address: package.into(),
+        module: "upgradeable".parse().unwrap(),
+        name: "Marker".parse().unwrap(),
+        type_params: vec![],
+    }));
+    let noop = |package: ObjectID| {
+        let runner = &runner;
+        let marker = marker.clone();
+        async move {
+            call_move(
+                &runner.authority,
+                &runner.gas,
+                &runner.sender,
+                &runner.sender_key,
+                &package,
+                "upgradeable",
+                "noop",
+                vec![marker],
+                vec![],
+            )
+            .await
+            .unwrap()
+        }
+    };
+
+    let effects = noop(upgraded).await;
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    let effects = noop(package).await;
+    assert_eq!(
+        effects.status,
+        ExecutionStatus::new_failure(ExecutionFailureStatus::entry_type_argument_error(
+            0,
+            EntryTypeArgumentErrorKind::TypeNotFound
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_package_encoding() {
+    let runner = TestRunner::new().await;
+    let (package, cap_ref) = runner.publish("base").await;
+    let effects = runner.upgrade(package, cap_ref, "compatible").await;
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    let upgraded = effects.created[0].0 .0;
+
+    // Packages that have not been upgraded keep the encoding that predates upgrades.
+    let original_package = runner.package(&package).await;
+    assert_eq!(original_package.version(), PACKAGE_VERSION);
+    assert!(original_package.linkage_table().is_empty());
+    let mut legacy_bytes = vec![1];
+    legacy_bytes
+        .extend(bcs::to_bytes(&(package, original_package.serialized_module_map())).unwrap());
+    let data = Data::Package(original_package);
+    assert_eq!(bcs::to_bytes(&data).unwrap(), legacy_bytes);
+    assert_eq!(bcs::from_bytes::<Data>(&legacy_bytes).unwrap(), data);
+
+    // Upgraded packages are encoded with their version and linkage in a separate variant.
+    let data = Data::Package(runner.package(&upgraded).await);
+    let bytes = bcs::to_bytes(&data).unwrap();
+    assert_eq!(bytes[0], 2);
+    assert_eq!(bcs::from_bytes::<Data>(&bytes).unwrap(), data);
+}
+
+#[tokio::test]
+async fn test_package_upgrades_feature_gate() {
+    let runner = TestRunner::new().await;
+    let (package, cap_ref) = runner.publish("base").await;
+    let data = runner.upgrade_data(package, cap_ref, "compatible").await;
+
+    let disabled = ProtocolConfig::get_for_version(ProtocolVersion::new(1));
+    assert!(!disabled.package_upgrades());
+    assert!(matches!(
+        data.validity_check(&disabled),
+        Err(SuiError::UnsupportedFeatureError { .. })
+    ));
+    let enabled = ProtocolConfig::get_for_version(ProtocolVersion::new(2));
+    assert!(enabled.package_upgrades());
+    data.validity_check(&enabled).unwrap();
+}
@@ -0,0 +1,303 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::authority::authority_tests::{init_state, send_and_confirm_transaction};
+use crate::authority::AuthorityState;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_framework_build::compiled_package::BuildConfig;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_types::base_types::{dbg_addr, ObjectID, SuiAddress};
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::error::SuiError;
+use sui_types::gas_coin::{GasCoin, GAS};
+use sui_types::messages::{
+    Argument, CallArg, Command, CommandArgumentError, ExecutionFailureStatus, ExecutionStatus,
+    ProgrammableMoveCall, ProgrammableTransaction, SignedTransactionEffects, SingleTransactionKind,
+    TransactionData, TransactionKind,
+};
+use sui_types::object::{Object, Owner};
+use sui_types::utils::to_sender_signed_transaction;
+use sui_types::SUI_FRAMEWORK_OBJECT_ID;
+
+const MAX_GAS: u64 = 10000;
+
+#[tokio::test]
+async fn test_split_gas_coin_and_transfer() -> anyhow::Result<()> {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_id = ObjectID::random();
+    let gas_coin = Object::with_id_owner_gas_for_testing(gas_id, sender, 2000);
+    let recipient = dbg_addr(1);
+
+    let pt = ProgrammableTransaction {
+        inputs: vec![
+            CallArg::Pure(bcs::to_bytes(&100u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&recipient).unwrap()),
+        ],
+        commands: vec![
+            Command::SplitCoin(Argument::GasCoin, Argument::Input(0)),
+            Command::TransferObjects(vec![Argument::Result(0)], Argument::Input(1)),
+        ],
+    };
+    let res = execute_programmable_transaction(gas_coin, pt, sender, sender_key, 1000).await;
+
+    let effects = res.txn_result.unwrap().into_data();
+    assert_eq!(effects.status, ExecutionStatus::Success);
+    assert_eq!(effects.created.len(), 1);
+    assert_eq!(effects.created[0].1.get_owner_address()?, recipient);
+    let created = res
+        .authority_state
+        .get_object(&effects.created[0].0 .0)
+        .await?
+        .unwrap();
+    assert_eq!(GasCoin::try_from(&created)?.value(), 100);
+
+    let gas_used = effects.gas_used.gas_used();
+    let gas_object = res.authority_state.get_object(&gas_id).await?.unwrap();
+    assert_eq!(
+        GasCoin::try_from(&gas_object)?.value(),
+        2000 - 100 - gas_used
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_call_results_passed_to_later_commands() -> anyhow::Result<()> {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_id = ObjectID::random();
+    let gas_coin = Object::with_id_owner_gas_for_testing(gas_id, sender, 20000);
+    let recipient = dbg_addr(1);
+
+    let coin_split = |coin: Argument, amount: Argument| {
+        Command::MoveCall(Box::new(ProgrammableMoveCall {
+            package: SUI_FRAMEWORK_OBJECT_ID,
+            module: Identifier::new("coin").unwrap(),
+            function: Identifier::new("split").unwrap(),
+            type_arguments: vec![GAS::type_tag()],
+            arguments: vec![coin, amount],
+        }))
+    };
+    let pt = ProgrammableTransaction {
+        inputs: vec![
+            CallArg::Pure(bcs::to_bytes(&100u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&40u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&recipient).unwrap()),
+        ],
+        commands: vec![
+            // the returned coin is a result of the call...
+            coin_split(Argument::GasCoin, Argument::Input(0)),
+            // ...which the next call borrows mutably
+            coin_split(Argument::Result(0), Argument::Input(1)),
+            Command::TransferObjects(
+                vec![Argument::Result(0), Argument::Result(1)],
+                Argument::Input(2),
+            ),
+        ],
+    };
+    let res = execute_programmable_transaction(gas_coin, pt, sender, sender_key, MAX_GAS).await;
+
+    let effects = res.txn_result.unwrap().into_data();
+    assert_eq!(effects.status, ExecutionStatus::Success);
+    assert_eq!(effects.created.len(), 2);
+    let mut values = vec![];
+    for (obj_ref, owner) in &effects.created {
+        assert_eq!(owner.get_owner_address()?, recipient);
+        let created = res.authority_state.get_object(&obj_ref.0).await?.unwrap();
+        values.push(GasCoin::try_from(&created)?.value());
+    }
+    values.sort();
+    assert_eq!(values, vec![40, 60]);
+
+    let gas_used = effects.gas_used.gas_used();
+    let gas_object = res.authority_state.get_object(&gas_id).await?.unwrap();
+    assert_eq!(
+        GasCoin::try_from(&gas_object)?.value(),
+        20000 - 100 - gas_used
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_call_type_argument_mismatch() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_coin = Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, 20000);
+
+    let pt = ProgrammableTransaction {
+        inputs: vec![CallArg::Pure(bcs::to_bytes(&100u64).unwrap())],
+        commands: vec![Command::MoveCall(Box::new(ProgrammableMoveCall {
+            package: SUI_FRAMEWORK_OBJECT_ID,
+            module: Identifier::new("coin").unwrap(),
+            function: Identifier::new("value").unwrap(),
+            type_arguments: vec![TypeTag::U64],
+            arguments: vec![Argument::GasCoin],
+        }))],
+    };
+    let res = execute_programmable_transaction(gas_coin, pt, sender, sender_key, MAX_GAS).await;
+
+    let effects = res.txn_result.unwrap().into_data();
+    assert_eq!(
+        effects.status,
+        ExecutionStatus::new_failure(ExecutionFailureStatus::command_argument_error(
+            CommandArgumentError::TypeMismatch,
+            0,
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_publish() -> anyhow::Result<()> {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_coin = Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, 20000);
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("src/unit_tests/data/object_basics");
+    let modules = sui_framework::build_move_package(&path, BuildConfig::new_for_testing())
+        .unwrap()
+        .get_package_bytes(/* with_unpublished_deps */ false);
+
+    let pt = ProgrammableTransaction {
+        inputs: vec![],
+        commands: vec![Command::Publish(modules)],
+    };
+    let res = execute_programmable_transaction(gas_coin, pt, sender, sender_key, MAX_GAS).await;
+
+    let effects = res.txn_result.unwrap().into_data();
+    assert_eq!(effects.status, ExecutionStatus::Success);
+    let (package_ref, _) = effects
+        .created
+        .iter()
+        .find(|(_, owner)| owner == &Owner::Immutable)
+        .expect("the package should be created");
+    let package = res
+        .authority_state
+        .get_object(&package_ref.0)
+        .await?
+        .unwrap();
+    let package = package.data.try_as_package().unwrap();
+    assert!(package
+        .serialized_module_map()
+        .contains_key("object_basics"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_merge_gas_coin_by_value() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_coin = Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, 2000);
+
+    let pt = ProgrammableTransaction {
+        inputs: vec![CallArg::Pure(bcs::to_bytes(&100u64).unwrap())],
+        commands: vec![
+            Command::SplitCoin(Argument::GasCoin, Argument::Input(0)),
+            Command::MergeCoins(Argument::Result(0), vec![Argument::GasCoin]),
+        ],
+    };
+    let res = execute_programmable_transaction(gas_coin, pt, sender, sender_key, 1000).await;
+
+    let effects = res.txn_result.unwrap().into_data();
+    assert_eq!(
+        effects.status,
+        ExecutionStatus::new_failure(ExecutionFailureStatus::command_argument_error(
+            CommandArgumentError::InvalidGasCoinUsage,
+            1,
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_unused_value_without_drop() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_coin = Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, 2000);
+
+    let pt = ProgrammableTransaction {
+        inputs: vec![CallArg::Pure(bcs::to_bytes(&100u64).unwrap())],
+        commands: vec![Command::SplitCoin(Argument::GasCoin, Argument::Input(0))],
+    };
+    let res = execute_programmable_transaction(gas_coin, pt, sender, sender_key, 1000).await;
+
+    let effects = res.txn_result.unwrap().into_data();
+    assert_eq!(
+        effects.status,
+        ExecutionStatus::new_failure(ExecutionFailureStatus::UnusedValueWithoutDrop {
+            result_idx: 0,
+            secondary_idx: 0,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_input_index_out_of_bounds() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_coin = Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, 2000);
+
+    let pt = ProgrammableTransaction {
+        inputs: vec![],
+        commands: vec![Command::SplitCoin(Argument::GasCoin, Argument::Input(0))],
+    };
+    let res = execute_programmable_transaction(gas_coin, pt, sender, sender_key, 1000).await;
+
+    let effects = res.txn_result.unwrap().into_data();
+    assert_eq!(
+        effects.status,
+        ExecutionStatus::new_failure(ExecutionFailureStatus::command_argument_error(
+            CommandArgumentError::IndexOutOfBounds { idx: 0 },
+            1,
+        ))
+    );
+}
+
+#[test]
+fn test_programmable_transactions_feature_gate() {
+    let (sender, _): (_, AccountKeyPair) = get_key_pair();
+    let gas_coin = Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, 2000);
+    let pt = ProgrammableTransaction {
+        inputs: vec![CallArg::Pure(bcs::to_bytes(&sender).unwrap())],
+        commands: vec![Command::TransferObjects(
+            vec![Argument::GasCoin],
+            Argument::Input(0),
+        )],
+    };
+    let kind = TransactionKind::Single(SingleTransactionKind::ProgrammableTransaction(pt));
+    let data = TransactionData::new(kind, sender, gas_coin.compute_object_reference(), 1000, 1);
+
+    let disabled = ProtocolConfig::get_for_version(ProtocolVersion::new(1));
+    assert!(!disabled.programmable_transactions());
+    assert!(matches!(
+        data.validity_check(&disabled),
+        Err(SuiError::UnsupportedFeatureError { .. })
+    ));
+    let enabled = ProtocolConfig::get_for_version(ProtocolVersion::new(2));
+    assert!(enabled.programmable_transactions());
+    data.validity_check(&enabled).unwrap();
+}
+
+struct ProgrammableTransactionExecutionResult {
+    pub authority_state: Arc<AuthorityState>,
+    pub txn_result: Result<SignedTransactionEffects, SuiError>,
+}
+
+async fn execute_programmable_transaction(
+    gas_coin: Object,
+    pt: ProgrammableTransaction,
+    sender: SuiAddress,
+    sender_key: AccountKeyPair,
+    gas_budget: u64,
+) -> ProgrammableTransactionExecutionResult {
+    let authority_state = init_state().await;
+    let gas_object_ref = gas_coin.compute_object_reference();
+    authority_state.insert_genesis_object(gas_coin).await;
+
+    let kind = TransactionKind::Single(SingleTransactionKind::ProgrammableTransaction(pt));
+    let data = TransactionData::new(kind, sender, gas_object_ref, gas_budget, 1);
+    let tx = to_sender_signed_transaction(data, &sender_key);
+    let txn_result = send_and_confirm_transaction(&authority_state, tx)
+        .await
+        .map(|(_, effects)| effects);
+
+    ProgrammableTransactionExecutionResult {
+        authority_state,
+        txn_result,
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::authority::authority_store_tables::{AuthorityPerpetualTables, StateSnapshotRestore};
+use crate::checkpoints::CheckpointStore;
+use crate::epoch::committee_store::CommitteeStore;
+use crate::state_snapshot::{
+    export_state_snapshot, read_state_snapshot, restore_state_snapshot, verify_state_snapshot,
+    write_state_snapshot, StateSnapshotHeader,
+};
+use crate::test_utils::init_state_parameters_from_rng;
+use anyhow::Result;
+use fastcrypto::hash::MultisetHash;
+use std::path::Path;
+use sui_config::genesis::Genesis;
+use sui_network::state_sync::test_utils::CommitteeFixture;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::committee::ProtocolVersion;
+use sui_types::digests::CheckpointDigest;
+use sui_types::messages_checkpoint::{
+    CheckpointCommitment, ECMHLiveObjectSetDigest, EndOfEpochData, VerifiedCheckpoint,
+};
+use sui_types::object::Object;
+use sui_types::storage::ObjectKey;
+use typed_store::Map;
+
+fn end_of_epoch_data(next: &CommitteeFixture) -> EndOfEpochData {
+    EndOfEpochData {
+        next_epoch_committee: next.committee().voting_rights.clone(),
+        next_epoch_protocol_version: ProtocolVersion::MIN,
+    }
+}
+
+fn epoch_commitments(root_state_hash: &Accumulator) -> Vec<CheckpointCommitment> {
+    vec![ECMHLiveObjectSetDigest::from(root_state_hash).into()]
+}
+
+fn accumulate(objects: &[Object]) -> Accumulator {
+    let mut accumulator = Accumulator::default();
+    accumulator.insert_all(objects.iter().map(|o| o.compute_object_reference().2));
+    accumulator
+}
+
+/// Builds a snapshot at the end of epoch 1, returning it along with the genesis committee.
+fn make_snapshot() -> (CommitteeFixture, StateSnapshotHeader, Vec<Object>) {
+    let mut rng = rand::rngs::OsRng;
+    let epoch_0 = CommitteeFixture::generate(&mut rng, 0, 4);
+    let epoch_1 = CommitteeFixture::generate(&mut rng, 1, 4);
+    let epoch_2 = CommitteeFixture::generate(&mut rng, 2, 4);
+
+    let objects: Vec<_> = (0..10)
+        .map(|_| {
+            Object::with_id_owner_for_testing(
+                ObjectID::random(),
+                SuiAddress::random_for_testing_only(),
+            )
+        })
+        .collect();
+
+    let (checkpoints, _, _) = epoch_0.make_checkpoints(3, None);
+    let (_, _, end_of_epoch_0) = epoch_0.make_end_of_epoch_checkpoint_with_commitments(
+        checkpoints.last().cloned().unwrap(),
+        Some(end_of_epoch_data(&epoch_1)),
+        epoch_commitments(&Accumulator::default()),
+    );
+    let (checkpoints, _, _) = epoch_1.make_checkpoints(3, Some(end_of_epoch_0.clone()));
+    let (_, _, end_of_epoch_1) = epoch_1.make_end_of_epoch_checkpoint_with_commitments(
+        checkpoints.last().cloned().unwrap(),
+        Some(end_of_epoch_data(&epoch_2)),
+        epoch_commitments(&accumulate(&objects)),
+    );
+
+    let header = StateSnapshotHeader {
+        end_of_epoch_checkpoints: vec![end_of_epoch_0, end_of_epoch_1]
+            .into_iter()
+            .map(VerifiedCheckpoint::into_inner)
+            .collect(),
+        object_count: objects.len() as u64,
+    };
+    (epoch_0, header, objects)
+}
+
+fn snapshot_objects(objects: &[Object]) -> impl Iterator<Item = Result<Object>> + '_ {
+    objects.iter().cloned().map(Ok)
+}
+
+/// Populates the DB of a node at `db_path` that executed up to the checkpoint of `header`, and
+/// whose live object set is `objects`.
+fn make_node_db(db_path: &Path, header: &StateSnapshotHeader, objects: &[Object]) {
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    let refs: Vec<_> = objects
+        .iter()
+        .map(|object| (object.compute_object_reference(), object))
+        .collect();
+    perpetual_tables
+        .objects
+        .batch()
+        .insert_batch(
+            &perpetual_tables.objects,
+            refs.iter()
+                .map(|(obj_ref, object)| (ObjectKey::from(obj_ref), *object)),
+        )
+        .unwrap()
+        .insert_batch(
+            &perpetual_tables.parent_sync,
+            refs.iter()
+                .map(|(obj_ref, object)| (obj_ref, object.previous_transaction)),
+        )
+        .unwrap()
+        .write()
+        .unwrap();
+
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    for checkpoint in &header.end_of_epoch_checkpoints {
+        checkpoint_store
+            .insert_verified_checkpoint(VerifiedCheckpoint::new_unchecked(checkpoint.clone()))
+            .unwrap();
+    }
+    checkpoint_store
+        .update_highest_executed_checkpoint(&VerifiedCheckpoint::new_unchecked(
+            header.checkpoint().unwrap().clone(),
+        ))
+        .unwrap();
+}
+
+fn restore(snapshot_path: &Path, db_path: &Path, genesis: &Genesis) -> Result<bool> {
+    let committee_store =
+        CommitteeStore::new(db_path.join("epochs"), &genesis.committee().unwrap(), None);
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    let restored = restore_state_snapshot(
+        snapshot_path,
+        &db_path.join("store"),
+        genesis,
+        &committee_store,
+        &checkpoint_store,
+    )?;
+    Ok(restored.is_some())
+}
+
+#[test]
+fn test_state_snapshot_round_trip() {
+    let (genesis, header, objects) = make_snapshot();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snapshot");
+    let root_state_hash = write_state_snapshot(&path, &header, snapshot_objects(&objects)).unwrap();
+    assert_eq!(root_state_hash, accumulate(&objects));
+    let (header, read_objects) = read_state_snapshot(&path).unwrap();
+    let read_objects = read_objects.collect::<Result<Vec<_>>>().unwrap();
+    assert_eq!(read_objects, objects);
+
+    let snapshot =
+        verify_state_snapshot(genesis.committee(), header, snapshot_objects(&read_objects))
+            .unwrap();
+    assert_eq!(snapshot.checkpoint().epoch(), 1);
+    assert_eq!(snapshot.checkpoints.len(), 2);
+    assert_eq!(
+        snapshot
+            .committees
+            .iter()
+            .map(|c| c.epoch)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(snapshot.root_state_hash, accumulate(&objects));
+}
+
+#[test]
+fn test_state_snapshot_rejects_tampered_objects() {
+    let (genesis, mut header, mut objects) = make_snapshot();
+
+    objects.pop();
+    header.object_count -= 1;
+    assert!(
+        verify_state_snapshot(genesis.committee(), header, snapshot_objects(&objects)).is_err()
+    );
+}
+
+#[test]
+fn test_state_snapshot_rejects_truncated_file() {
+    let (genesis, header, objects) = make_snapshot();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snapshot");
+    write_state_snapshot(&path, &header, snapshot_objects(&objects)).unwrap();
+    let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+    file.set_len(file.metadata().unwrap().len() - 1).unwrap();
+
+    let (header, read_objects) = read_state_snapshot(&path).unwrap();
+    assert!(verify_state_snapshot(genesis.committee(), header, read_objects).is_err());
+}
+
+#[test]
+fn test_state_snapshot_rejects_unknown_committee() {
+    let (_, header, objects) = make_snapshot();
+
+    let other = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    assert!(verify_state_snapshot(other.committee(), header, snapshot_objects(&objects)).is_err());
+}
+
+#[test]
+fn test_export_state_snapshot() {
+    let (genesis, header, objects) = make_snapshot();
+
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("db");
+    make_node_db(&db_path, &header, &objects);
+
+    let output = dir.path().join("snapshot");
+    let exported = export_state_snapshot(&db_path, None, &output).unwrap();
+    assert_eq!(exported.object_count, objects.len() as u64);
+
+    let (header, read_objects) = read_state_snapshot(&output).unwrap();
+    let snapshot = verify_state_snapshot(genesis.committee(), header, read_objects).unwrap();
+    assert_eq!(snapshot.checkpoint().epoch(), 1);
+    assert_eq!(snapshot.root_state_hash, accumulate(&objects));
+}
+
+#[test]
+fn test_export_state_snapshot_checks_commitment() {
+    let (_, header, mut objects) = make_snapshot();
+
+    // The store holds an object that is not part of the committed live object set.
+    objects.push(Object::with_id_owner_for_testing(
+        ObjectID::random(),
+        SuiAddress::random_for_testing_only(),
+    ));
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("db");
+    make_node_db(&db_path, &header, &objects);
+
+    let output = dir.path().join("snapshot");
+    assert!(export_state_snapshot(&db_path, None, &output).is_err());
+    assert!(!output.exists());
+    assert!(!output.with_extension("tmp").exists());
+}
+
+#[test]
+fn test_restore_state_snapshot_verifies_before_writing() {
+    let (genesis, _) = init_state_parameters_from_rng(&mut rand::rngs::OsRng);
+    // Signed by committees unrelated to the genesis committee.
+    let (_, header, objects) = make_snapshot();
+
+    let dir = tempfile::tempdir().unwrap();
+    let snapshot_path = dir.path().join("snapshot");
+    write_state_snapshot(&snapshot_path, &header, snapshot_objects(&objects)).unwrap();
+    let db_path = dir.path().join("db");
+    assert!(restore(&snapshot_path, &db_path, &genesis).is_err());
+
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    assert!(perpetual_tables.database_is_empty().unwrap());
+    assert_eq!(perpetual_tables.get_state_snapshot_restore().unwrap(), None);
+}
+
+#[test]
+fn test_restore_state_snapshot_after_interruption() {
+    let (genesis, _) = init_state_parameters_from_rng(&mut rand::rngs::OsRng);
+    let (_, header, objects) = make_snapshot();
+
+    let dir = tempfile::tempdir().unwrap();
+    let snapshot_path = dir.path().join("snapshot");
+    write_state_snapshot(&snapshot_path, &header, snapshot_objects(&objects)).unwrap();
+    let db_path = dir.path().join("db");
+
+    // A store partially restored from another snapshot must not be mixed with this one.
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    perpetual_tables
+        .set_state_snapshot_restore(StateSnapshotRestore::InProgress(CheckpointDigest::random()))
+        .unwrap();
+    drop(perpetual_tables);
+    let err = restore(&snapshot_path, &db_path, &genesis).unwrap_err();
+    assert!(err.to_string().contains("was interrupted"), "{err}");
+
+    // A completely restored store is left as is, even if the snapshot is gone.
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    perpetual_tables
+        .set_state_snapshot_restore(StateSnapshotRestore::Complete(
+            header.checkpoint().unwrap().digest(),
+        ))
+        .unwrap();
+    drop(perpetual_tables);
+    std::fs::remove_file(&snapshot_path).unwrap();
+    assert!(!restore(&snapshot_path, &db_path, &genesis).unwrap());
+}
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use move_core_types::gas_algebra::InternalGas;
+
 //
 // Native function costs
 //
@@ -22,4 +24,25 @@ pub enum SuiNativeCostIndex {
 
     TX_CONTEXT_DERIVE_ID = 7,
     TX_CONTEXT_NEW_SIGNER_FROM_ADDR = 8,
+
+    ECDSA_R1_SECP256R1_ECRECOVER = 9,
+    ECDSA_R1_SECP256R1_VERIFY = 10,
+
+    HASH_SHA2_256 = 11,
+    HASH_SHA3_256 = 12,
+}
+
+impl SuiNativeCostIndex {
+    /// The cost of a call to the native, given the size in bytes of the input it processes: a
+    /// flat cost per call plus a cost per byte. Natives not calibrated yet cost the legacy flat
+    /// cost of a call.
+    pub fn cost(self, input_size: usize) -> InternalGas {
+        let (per_call, per_byte) = match self {
+            SuiNativeCostIndex::ECDSA_R1_SECP256R1_ECRECOVER => (4_000, 2),
+            SuiNativeCostIndex::ECDSA_R1_SECP256R1_VERIFY => (3_000, 2),
+            SuiNativeCostIndex::HASH_SHA2_256 | SuiNativeCostIndex::HASH_SHA3_256 => (84, 2),
+            _ => (84, 0),
+        };
+        InternalGas::new(per_call + per_byte * input_size as u64)
+    }
 }
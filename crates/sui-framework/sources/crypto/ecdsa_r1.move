@@ -0,0 +1,28 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module sui::ecdsa_r1 {
+
+    const EFailToRecoverPubKey: u64 = 0;
+    const EInvalidSignature: u64 = 1;
+
+    /// @param signature: A 65-bytes signature in form (r, s, v) that is signed using
+    /// Secp256r1. The accepted v values are {0, 1, 2, 3}.
+    ///
+    /// @param msg: The message that the signature is signed against, it is hashed with SHA-256
+    /// before recovering the public key.
+    ///
+    /// If the signature is valid, return the corresponding recovered 33-bytes compressed
+    /// Secp256r1 public key, otherwise throw error.
+    public native fun secp256r1_ecrecover(signature: &vector<u8>, msg: &vector<u8>): vector<u8>;
+
+    /// @param signature: A 64-bytes signature in form (r, s) that is signed using
+    /// Secp256r1. This is an non-recoverable signature without recovery id.
+    ///
+    /// @param public_key: The 33-bytes compressed public key to verify the signature against
+    /// @param msg: The message that the signature is signed against, it is hashed with SHA-256
+    /// before verifying, as for WebAuthn assertions.
+    ///
+    /// If the signature is valid to the pubkey and message, return true. Else false.
+    public native fun secp256r1_verify(signature: &vector<u8>, public_key: &vector<u8>, msg: &vector<u8>): bool;
+}
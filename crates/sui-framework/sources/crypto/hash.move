@@ -1,8 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-/// Module which defines hash functions. Note that Sha-256 and Sha3-256 is available in the std::hash module in the
-/// standard library.
+/// Module which defines hash functions. Sha-256 and Sha3-256 are also available in the std::hash module in the
+/// standard library, which takes the data by value.
 module sui::hash {
     /// @param data: Arbitrary binary data to hash
     /// Hash the input bytes using Blake2b-256 and returns 32 bytes.
@@ -11,4 +11,12 @@ module sui::hash {
     /// @param data: Arbitrary binary data to hash
     /// Hash the input bytes using keccak256 and returns 32 bytes.
     native public fun keccak256(data: &vector<u8>): vector<u8>;
+
+    /// @param data: Arbitrary binary data to hash
+    /// Hash the input bytes using SHA2-256 and returns 32 bytes.
+    native public fun sha2_256(data: &vector<u8>): vector<u8>;
+
+    /// @param data: Arbitrary binary data to hash
+    /// Hash the input bytes using SHA3-256 and returns 32 bytes.
+    native public fun sha3_256(data: &vector<u8>): vector<u8>;
 }
@@ -15,4 +15,18 @@ spec sui::hash {
         aborts_if [abstract] true;
         // TODO: specify actual function behavior
     }
+
+    spec sha2_256 {
+        pragma opaque;
+        // TODO: stub to be replaced by actual abort conditions if any
+        aborts_if [abstract] true;
+        // TODO: specify actual function behavior
+    }
+
+    spec sha3_256 {
+        pragma opaque;
+        // TODO: stub to be replaced by actual abort conditions if any
+        aborts_if [abstract] true;
+        // TODO: specify actual function behavior
+    }
 }
@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Module that allows upgrading packages. Packages are immutable, an
+/// upgrade publishes the next version of the package at a new ID,
+/// after checking that its modules are compatible with the modules of
+/// the version being upgraded.
+module sui::package {
+    use sui::address;
+    use sui::hex;
+    use sui::object::{Self, ID, UID};
+    use sui::tx_context::{TxContext, sender};
+    use std::ascii;
+    use std::type_name;
+    use sui::types;
+
+    /// Tried to claim an upgrade capability using a type that isn't a
+    /// one-time witness.
+    const ENotOneTimeWitness: u64 = 0;
+
+    /// Capability controlling the ability to upgrade a package. Each
+    /// upgrade performed with it makes it refer to the new version of
+    /// the package.
+    struct UpgradeCap has key, store {
+        id: UID,
+        /// The latest version of the package that can be upgraded
+        /// with this capability.
+        package: ID,
+        /// The number of upgrades performed with this capability.
+        version: u64,
+    }
+
+    /// Claim the capability to upgrade the package a one-time witness
+    /// is defined in. Can only be called in the module initializer.
+    public fun claim_upgrade_cap<OTW: drop>(
+        otw: &OTW,
+        ctx: &mut TxContext
+    ): UpgradeCap {
+        assert!(types::is_one_time_witness(otw), ENotOneTimeWitness);
+
+        let package = type_name::get_address(&type_name::get<OTW>());
+        let package = address::from_bytes(hex::decode(ascii::into_bytes(package)));
+
+        UpgradeCap {
+            id: object::new(ctx),
+            package: object::id_from_address(package),
+            version: 0,
+        }
+    }
+
+    /// Claim the capability to upgrade a package and send it to the
+    /// transaction sender.
+    public fun claim_upgrade_cap_and_keep<OTW: drop>(otw: &OTW, ctx: &mut TxContext) {
+        sui::transfer::transfer(claim_upgrade_cap(otw, ctx), sender(ctx))
+    }
+
+    /// Discard the capability, making the package it refers to
+    /// permanently immutable.
+    public entry fun make_immutable(cap: UpgradeCap) {
+        let UpgradeCap { id, package: _, version: _ } = cap;
+        object::delete(id);
+    }
+
+    /// The ID of the latest version of the package that can be
+    /// upgraded with the capability.
+    public fun upgrade_package(cap: &UpgradeCap): ID {
+        cap.package
+    }
+
+    /// The number of upgrades performed with the capability.
+    public fun version(cap: &UpgradeCap): u64 {
+        cap.version
+    }
+}
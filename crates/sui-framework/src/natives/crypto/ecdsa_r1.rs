@@ -0,0 +1,85 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use fastcrypto::{
+    secp256r1::{
+        recoverable::Secp256r1RecoverableSignature, Secp256r1PublicKey, Secp256r1Signature,
+    },
+    traits::{ToFromBytes, VerifyingKey},
+};
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{Value, VectorRef},
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+use sui_cost_tables::natives_tables::SuiNativeCostIndex;
+
+pub const FAIL_TO_RECOVER_PUBKEY: u64 = 0;
+pub const INVALID_SIGNATURE: u64 = 1;
+
+pub fn secp256r1_ecrecover(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let msg = pop_arg!(args, VectorRef);
+    let signature = pop_arg!(args, VectorRef);
+
+    let msg_ref = msg.as_bytes_ref();
+    let signature_ref = signature.as_bytes_ref();
+
+    let cost = SuiNativeCostIndex::ECDSA_R1_SECP256R1_ECRECOVER.cost(msg_ref.len());
+
+    let signature = match <Secp256r1RecoverableSignature as ToFromBytes>::from_bytes(&signature_ref)
+    {
+        Ok(signature) => signature,
+        Err(_) => return Ok(NativeResult::err(cost, INVALID_SIGNATURE)),
+    };
+
+    match signature.recover(&msg_ref) {
+        Ok(pubkey) => Ok(NativeResult::ok(
+            cost,
+            smallvec![Value::vector_u8(pubkey.as_bytes().to_vec())],
+        )),
+        Err(_) => Ok(NativeResult::err(cost, FAIL_TO_RECOVER_PUBKEY)),
+    }
+}
+
+pub fn secp256r1_verify(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let msg = pop_arg!(args, VectorRef);
+    let public_key_bytes = pop_arg!(args, VectorRef);
+    let signature_bytes = pop_arg!(args, VectorRef);
+
+    let msg_ref = msg.as_bytes_ref();
+    let public_key_bytes_ref = public_key_bytes.as_bytes_ref();
+    let signature_bytes_ref = signature_bytes.as_bytes_ref();
+
+    let cost = SuiNativeCostIndex::ECDSA_R1_SECP256R1_VERIFY.cost(msg_ref.len());
+
+    let signature = match <Secp256r1Signature as ToFromBytes>::from_bytes(&signature_bytes_ref) {
+        Ok(signature) => signature,
+        Err(_) => return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
+    };
+
+    let public_key = match <Secp256r1PublicKey as ToFromBytes>::from_bytes(&public_key_bytes_ref) {
+        Ok(public_key) => public_key,
+        Err(_) => return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
+    };
+
+    let result = public_key.verify(&msg_ref, &signature).is_ok();
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(result)]))
+}
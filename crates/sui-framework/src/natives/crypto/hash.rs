@@ -1,8 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use crate::legacy_empty_cost;
-use fastcrypto::hash::{Blake2b256, HashFunction, Keccak256};
+use fastcrypto::hash::{Blake2b256, HashFunction, Keccak256, Sha256, Sha3_256};
 use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::InternalGas;
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
     loaded_data::runtime_types::Type,
@@ -12,24 +13,25 @@ use move_vm_types::{
 };
 use smallvec::smallvec;
 use std::collections::VecDeque;
+use sui_cost_tables::natives_tables::SuiNativeCostIndex;
 
+/// Hashes the message argument, charging `cost` of the size of the message.
 fn hash<H: HashFunction<DIGEST_SIZE>, const DIGEST_SIZE: usize>(
     _context: &mut NativeContext,
     ty_args: Vec<Type>,
     mut args: VecDeque<Value>,
+    cost: impl FnOnce(usize) -> InternalGas,
 ) -> PartialVMResult<NativeResult> {
     debug_assert!(ty_args.is_empty());
     debug_assert!(args.len() == 1);
 
-    // TODO: implement native gas cost estimation https://github.com/MystenLabs/sui/issues/3593
-    let cost = legacy_empty_cost();
     let msg = pop_arg!(args, VectorRef);
+    let msg_ref = msg.as_bytes_ref();
+    let cost = cost(msg_ref.len());
 
     Ok(NativeResult::ok(
         cost,
-        smallvec![Value::vector_u8(
-            H::digest(msg.as_bytes_ref().as_slice()).digest
-        )],
+        smallvec![Value::vector_u8(H::digest(msg_ref.as_slice()).digest)],
     ))
 }
 
@@ -38,7 +40,8 @@ pub fn keccak256(
     ty_args: Vec<Type>,
     args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
-    hash::<Keccak256, 32>(context, ty_args, args)
+    // TODO: implement native gas cost estimation https://github.com/MystenLabs/sui/issues/3593
+    hash::<Keccak256, 32>(context, ty_args, args, |_| legacy_empty_cost())
 }
 
 pub fn blake2b256(
@@ -46,5 +49,26 @@ pub fn blake2b256(
     ty_args: Vec<Type>,
     args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
-    hash::<Blake2b256, 32>(context, ty_args, args)
+    // TODO: implement native gas cost estimation https://github.com/MystenLabs/sui/issues/3593
+    hash::<Blake2b256, 32>(context, ty_args, args, |_| legacy_empty_cost())
+}
+
+pub fn sha2_256(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    hash::<Sha256, 32>(context, ty_args, args, |size| {
+        SuiNativeCostIndex::HASH_SHA2_256.cost(size)
+    })
+}
+
+pub fn sha3_256(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    hash::<Sha3_256, 32>(context, ty_args, args, |size| {
+        SuiNativeCostIndex::HASH_SHA3_256.cost(size)
+    })
 }
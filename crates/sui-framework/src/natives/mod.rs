@@ -24,7 +24,8 @@ use move_vm_types::{
 use std::sync::Arc;
 
 use self::crypto::{
-    bls12381, bulletproofs, ecdsa_k1, ecvrf, ed25519, elliptic_curve, groth16, hash, hmac, tbls,
+    bls12381, bulletproofs, ecdsa_k1, ecdsa_r1, ecvrf, ed25519, elliptic_curve, groth16, hash,
+    hmac, tbls,
 };
 
 pub fn all_natives(
@@ -102,6 +103,16 @@ pub fn all_natives(
             "secp256k1_verify_recoverable",
             make_native!(ecdsa_k1::secp256k1_verify_recoverable),
         ),
+        (
+            "ecdsa_r1",
+            "secp256r1_ecrecover",
+            make_native!(ecdsa_r1::secp256r1_ecrecover),
+        ),
+        (
+            "ecdsa_r1",
+            "secp256r1_verify",
+            make_native!(ecdsa_r1::secp256r1_verify),
+        ),
         ("ecvrf", "ecvrf_verify", make_native!(ecvrf::ecvrf_verify)),
         (
             "ed25519",
@@ -150,6 +161,8 @@ pub fn all_natives(
             make_native!(hmac::hmac_sha3_256),
         ),
         ("hash", "keccak256", make_native!(hash::keccak256)),
+        ("hash", "sha2_256", make_native!(hash::sha2_256)),
+        ("hash", "sha3_256", make_native!(hash::sha3_256)),
         ("object", "delete_impl", make_native!(object::delete_impl)),
         ("object", "borrow_uid", make_native!(object::borrow_uid)),
         (
@@ -59,6 +59,9 @@ pub struct RuntimeResults {
     pub user_events: Vec<(StructTag, Value)>,
     // loaded child objects and their versions
     pub loaded_child_objects: BTreeMap<ObjectID, SequenceNumber>,
+    // ids of objects created during this session that were not deleted, including those that
+    // were not transferred (e.g. objects returned to the caller)
+    pub created_object_ids: BTreeSet<ObjectID>,
 }
 
 #[derive(Default)]
@@ -450,11 +453,13 @@ impl ObjectRuntimeState {
 
         debug_assert!(writes.keys().all(|id| !deletions.contains_key(id)));
         debug_assert!(deletions.keys().all(|id| !writes.contains_key(id)));
+        let created_object_ids = new_ids.into_iter().map(|(id, ())| id).collect();
         Ok(RuntimeResults {
             writes,
             deletions,
             user_events,
             loaded_child_objects,
+            created_object_ids,
         })
     }
 }
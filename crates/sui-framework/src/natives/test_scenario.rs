@@ -76,6 +76,7 @@ pub fn end_transaction(
         deletions,
         user_events,
         loaded_child_objects: _,
+        created_object_ids: _,
     } = match results {
         Ok(res) => res,
         Err(_) => {
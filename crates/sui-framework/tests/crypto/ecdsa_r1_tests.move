@@ -0,0 +1,57 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::ecdsa_r1_tests {
+    use sui::ecdsa_r1;
+
+    #[test]
+    fun test_ecrecover_pubkey() {
+        let msg = b"Hello, world!";
+        let sig = x"882b099f48aa33b98344999660e0f66aec781ac522eedeea557cf7398d56353c24d2b6e67347b863c2483eb0725b615ed08d1400f115f64c46fd02b0219d9dd900";
+        let pubkey_bytes = x"02650702ed8f3f9f015dd1088b00776176ed521b41c0381ef4ad316ab87a095abc";
+
+        let pubkey = ecdsa_r1::secp256r1_ecrecover(&sig, &msg);
+        assert!(pubkey == pubkey_bytes, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ecdsa_r1::EFailToRecoverPubKey)]
+    fun test_ecrecover_pubkey_fail_to_recover() {
+        let msg = x"00";
+        // r = 1 is not the x coordinate of any point on the curve
+        let sig = x"0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000100";
+        ecdsa_r1::secp256r1_ecrecover(&sig, &msg);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ecdsa_r1::EInvalidSignature)]
+    fun test_ecrecover_pubkey_invalid_sig() {
+        let msg = b"Hello, world!";
+        // incorrect length sig
+        let sig = x"882b099f48aa33b98344999660e0f66aec781ac522eedeea557cf7398d56353c24d2b6e67347b863c2483eb0725b615ed08d1400f115f64c46fd02b0219d9d";
+        ecdsa_r1::secp256r1_ecrecover(&sig, &msg);
+    }
+
+    #[test]
+    fun test_secp256r1_verify() {
+        let msg = b"Hello, world!";
+        let pk = x"02650702ed8f3f9f015dd1088b00776176ed521b41c0381ef4ad316ab87a095abc";
+        let sig = x"882b099f48aa33b98344999660e0f66aec781ac522eedeea557cf7398d56353c24d2b6e67347b863c2483eb0725b615ed08d1400f115f64c46fd02b0219d9dd9";
+        let verify = ecdsa_r1::secp256r1_verify(&sig, &pk, &msg);
+        assert!(verify == true, 0);
+
+        let other_msg = b"Hello, world?";
+        let verify = ecdsa_r1::secp256r1_verify(&sig, &pk, &other_msg);
+        assert!(verify == false, 0);
+    }
+
+    #[test]
+    fun test_secp256r1_verify_fails_with_recoverable_sig() {
+        let msg = b"Hello, world!";
+        let pk = x"02650702ed8f3f9f015dd1088b00776176ed521b41c0381ef4ad316ab87a095abc";
+        let sig = x"882b099f48aa33b98344999660e0f66aec781ac522eedeea557cf7398d56353c24d2b6e67347b863c2483eb0725b615ed08d1400f115f64c46fd02b0219d9dd900";
+        let verify = ecdsa_r1::secp256r1_verify(&sig, &pk, &msg);
+        assert!(verify == false, 0);
+    }
+}
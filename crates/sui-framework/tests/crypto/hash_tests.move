@@ -21,4 +21,20 @@ module sui::hash_tests {
         assert!(hashed_msg == hashed_msg_bytes, 0);
     }
 
+    #[test]
+    fun test_sha2_256_hash() {
+        let msg = b"hello world!";
+        let hashed_msg_bytes = x"7509e5bda0c762d2bac7f90d758b5b2263fa01ccbc542ab5e3df163be08e6ca9";
+        let hashed_msg = hash::sha2_256(&msg);
+        assert!(hashed_msg == hashed_msg_bytes, 0);
+    }
+
+    #[test]
+    fun test_sha3_256_hash() {
+        let msg = b"hello world!";
+        let hashed_msg_bytes = x"9c24b06143c07224c897bac972e6e92b46cf18063f1a469ebe2f7a0966306105";
+        let hashed_msg = hash::sha3_256(&msg);
+        assert!(hashed_msg == hashed_msg_bytes, 0);
+    }
+
 }
\ No newline at end of file
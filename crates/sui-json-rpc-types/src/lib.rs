@@ -46,16 +46,18 @@ use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 use sui_types::message_envelope::Message;
 use sui_types::messages::{
-    CallArg, CertifiedTransaction, EffectsFinalityInfo, ExecuteTransactionResponse,
-    ExecutionStatus, FinalizedEffects, GenesisObject, InputObjectKind, MoveModulePublish,
-    ObjectArg, Pay, PayAllSui, PaySui, SingleTransactionKind, TransactionData, TransactionEffects,
+    Argument, CallArg, CertifiedTransaction, Command, EffectsFinalityInfo,
+    ExecuteTransactionResponse, ExecutionStatus, FinalizedEffects, GenesisObject, InputObjectKind,
+    MoveModulePublish, MovePackageUpgrade, ObjectArg, Pay, PayAllSui, PaySui, ProgrammableMoveCall,
+    ProgrammableTransaction, SingleTransactionKind, TransactionData, TransactionEffects,
     TransactionKind, VerifiedCertificate,
 };
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
-use sui_types::move_package::{disassemble_modules, MovePackage};
+use sui_types::move_package::{disassemble_modules, MovePackage, TypeOrigin, UpgradeInfo};
 use sui_types::object::{
     Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner, PastObjectRead,
 };
+use sui_types::query::EventQuery;
 use sui_types::signature::GenericSignature;
 use sui_types::{parse_sui_struct_tag, parse_sui_type_tag};
 use tracing::warn;
@@ -69,14 +71,20 @@ pub type TransactionsPage = Page<TransactionDigest, TransactionDigest>;
 pub type EventPage = Page<SuiEventEnvelope, EventID>;
 pub type CoinPage = Page<Coin, ObjectID>;
 pub type DynamicFieldPage = Page<DynamicFieldInfo, ObjectID>;
+pub type ObjectsPage = Page<SuiObjectInfo, ObjectID>;
+pub type ObjectHistoryPage = Page<SuiObjectVersion, SequenceNumber>;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Balance {
     pub coin_type: String,
-    pub coin_object_count: usize,
+    /// Not tracked for balances at a past checkpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_object_count: Option<usize>,
     pub total_balance: u128,
-    pub locked_balance: HashMap<EpochId, u128>,
+    /// Not tracked for balances at a past checkpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_balance: Option<HashMap<EpochId, u128>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -355,6 +363,24 @@ pub struct SuiTransactionResponse {
     pub parsed_data: Option<SuiParsedTransactionResponse>,
 }
 
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(tag = "status", content = "details", rename = "TransactionRead")]
+pub enum SuiTransactionRead {
+    Exists(SuiTransactionResponse),
+    NotExists(TransactionDigest),
+}
+
+impl SuiTransactionRead {
+    /// Returns the transaction response if there is any, otherwise an Err if
+    /// the transaction does not exist.
+    pub fn into_transaction(self) -> Result<SuiTransactionResponse, SuiError> {
+        match self {
+            Self::NotExists(digest) => Err(SuiError::TransactionNotFound { digest }),
+            Self::Exists(tx) => Ok(tx),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct SuiTransactionAuthSignersResponse {
     pub signers: Vec<AuthorityName>,
@@ -581,7 +607,13 @@ impl TryInto<Object> for SuiObject<SuiRawData> {
                     )?
                 })
             }
-            SuiRawData::Package(p) => Data::Package(MovePackage::new(p.id, &p.module_map)?),
+            SuiRawData::Package(p) => Data::Package(MovePackage::new_with_upgrade_info(
+                p.id,
+                p.version,
+                &p.module_map,
+                p.type_origin_table,
+                p.linkage_table,
+            )?),
         };
         Ok(Object {
             data,
@@ -944,16 +976,22 @@ impl SuiRawMoveObject {
 #[serde(rename = "RawMovePackage")]
 pub struct SuiRawMovePackage {
     pub id: ObjectID,
+    pub version: SequenceNumber,
     #[schemars(with = "BTreeMap<String, Base64>")]
     #[serde_as(as = "BTreeMap<_, Base64>")]
     pub module_map: BTreeMap<String, Vec<u8>>,
+    pub type_origin_table: Vec<TypeOrigin>,
+    pub linkage_table: BTreeMap<ObjectID, UpgradeInfo>,
 }
 
 impl From<MovePackage> for SuiRawMovePackage {
     fn from(p: MovePackage) -> Self {
         Self {
             id: p.id(),
+            version: p.version(),
             module_map: p.serialized_module_map().clone(),
+            type_origin_table: p.type_origin_table(),
+            linkage_table: p.linkage_table().clone(),
         }
     }
 }
@@ -1201,6 +1239,34 @@ impl<T: SuiData> TryFrom<PastObjectRead> for SuiPastObjectRead<T> {
     }
 }
 
+/// A version of an object, and the transaction which produced it.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiObjectVersion {
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub status: SuiObjectVersionStatus,
+    /// The owner of the object at this version, unless it was deleted, wrapped or pruned.
+    pub owner: Option<Owner>,
+    pub previous_transaction: TransactionDigest,
+    /// The checkpoint including the previous transaction, once executed by this node.
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    /// The timestamp of that checkpoint, in milliseconds since epoch.
+    pub timestamp_ms: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Copy, PartialEq, Eq)]
+pub enum SuiObjectVersionStatus {
+    /// The object is stored with this version
+    Exists,
+    /// The object existed with this version, but it was pruned from this node
+    Pruned,
+    /// The object was deleted at this version
+    Deleted,
+    /// The object was wrapped into another object at this version
+    Wrapped,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(untagged, rename = "MoveValue")]
 pub enum SuiMoveValue {
@@ -1454,6 +1520,36 @@ impl From<MoveModulePublish> for SuiMovePackage {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "MovePackageUpgrade", rename_all = "camelCase")]
+pub struct SuiMovePackageUpgrade {
+    /// The package being upgraded
+    pub package: ObjectID,
+    /// The modules of the next version of the package
+    pub modules: SuiMovePackage,
+    /// The versions of its dependencies the upgrade links against
+    pub dependencies: Vec<ObjectID>,
+    /// The capability authorizing the upgrade
+    pub upgrade_cap: SuiObjectRef,
+}
+
+impl From<MovePackageUpgrade> for SuiMovePackageUpgrade {
+    fn from(u: MovePackageUpgrade) -> Self {
+        let MovePackageUpgrade {
+            package,
+            modules,
+            dependencies,
+            upgrade_cap,
+        } = u;
+        Self {
+            package,
+            modules: MoveModulePublish { modules }.into(),
+            dependencies,
+            upgrade_cap: upgrade_cap.into(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(rename = "Pay")]
 pub struct SuiPay {
@@ -1542,6 +1638,9 @@ pub struct SuiTransactionData {
     pub transactions: Vec<SuiTransactionKind>,
     pub sender: SuiAddress,
     pub gas_payment: SuiObjectRef,
+    /// Coins smashed into the gas payment coin to pay for gas.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_gas_payment: Vec<SuiObjectRef>,
     pub gas_price: u64,
     pub gas_budget: u64,
 }
@@ -1560,6 +1659,9 @@ impl Display for SuiTransactionData {
         }
         writeln!(writer, "Sender: {}", self.sender)?;
         writeln!(writer, "Gas Payment: {}", self.gas_payment)?;
+        for extra_gas_payment in &self.extra_gas_payment {
+            writeln!(writer, "Extra Gas Payment: {}", extra_gas_payment)?;
+        }
         writeln!(writer, "Gas Price: {}", self.gas_price)?;
         writeln!(writer, "Gas Budget: {}", self.gas_budget)?;
         write!(f, "{}", writer)
@@ -1583,6 +1685,11 @@ impl TryFrom<TransactionData> for SuiTransactionData {
             transactions,
             sender: data.signer(),
             gas_payment: data.gas().into(),
+            extra_gas_payment: data
+                .extra_gas_payment()
+                .iter()
+                .map(|obj_ref| (*obj_ref).into())
+                .collect(),
             gas_price: data.gas_price,
             gas_budget: data.gas_budget,
         })
@@ -1625,6 +1732,11 @@ pub enum SuiTransactionKind {
     /// A system transaction marking the start of a series of transactions scheduled as part of a
     /// checkpoint
     ConsensusCommitPrologue(SuiConsensusCommitPrologue),
+    /// A series of commands where the results of one command can be used in future
+    /// commands
+    ProgrammableTransaction(SuiProgrammableTransaction),
+    /// Upgrade a Move package
+    Upgrade(SuiMovePackageUpgrade),
     // .. more transaction types go here
 }
 
@@ -1694,6 +1806,11 @@ impl Display for SuiTransactionKind {
             Self::Publish(_p) => {
                 write!(writer, "Transaction Kind : Publish")?;
             }
+            Self::Upgrade(u) => {
+                writeln!(writer, "Transaction Kind : Upgrade")?;
+                writeln!(writer, "Package ID : {}", u.package.to_hex_literal())?;
+                writeln!(writer, "Upgrade Capability ID : {}", u.upgrade_cap.object_id)?;
+            }
             Self::Call(c) => {
                 writeln!(writer, "Transaction Kind : Call")?;
                 writeln!(writer, "Package ID : {}", c.package.to_hex_literal())?;
@@ -1717,6 +1834,10 @@ impl Display for SuiTransactionKind {
                 writeln!(writer, "Transaction Kind : Consensus Commit Prologue")?;
                 writeln!(writer, "Timestamp : {}", p.checkpoint_start_timestamp_ms)?;
             }
+            Self::ProgrammableTransaction(p) => {
+                writeln!(writer, "Transaction Kind : Programmable")?;
+                write!(writer, "{p}")?;
+            }
         }
         write!(f, "{}", writer)
     }
@@ -1747,23 +1868,7 @@ impl TryFrom<SingleTransactionKind> for SuiTransactionKind {
                 arguments: c
                     .arguments
                     .into_iter()
-                    .map(|arg| match arg {
-                        CallArg::Pure(p) => SuiJsonValue::from_bcs_bytes(&p),
-                        CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))
-                        | CallArg::Object(ObjectArg::SharedObject { id, .. }) => {
-                            SuiJsonValue::new(Value::String(id.to_hex_literal()))
-                        }
-                        CallArg::ObjVec(vec) => SuiJsonValue::new(Value::Array(
-                            vec.iter()
-                                .map(|obj_arg| match obj_arg {
-                                    ObjectArg::ImmOrOwnedObject((id, _, _))
-                                    | ObjectArg::SharedObject { id, .. } => {
-                                        Value::String(id.to_hex_literal())
-                                    }
-                                })
-                                .collect(),
-                        )),
-                    })
+                    .map(call_arg_to_json_value)
                     .collect::<Result<Vec<_>, _>>()?,
             }),
             SingleTransactionKind::ChangeEpoch(e) => Self::ChangeEpoch(SuiChangeEpoch {
@@ -1781,13 +1886,32 @@ impl TryFrom<SingleTransactionKind> for SuiTransactionKind {
                     checkpoint_start_timestamp_ms: p.checkpoint_start_timestamp_ms,
                 })
             }
-            SingleTransactionKind::ProgrammableTransaction(_) => {
-                anyhow::bail!("programmable transactions are not yet supported")
+            SingleTransactionKind::ProgrammableTransaction(p) => {
+                Self::ProgrammableTransaction(p.try_into()?)
             }
+            SingleTransactionKind::Upgrade(u) => Self::Upgrade(u.into()),
         })
     }
 }
 
+fn call_arg_to_json_value(arg: CallArg) -> Result<SuiJsonValue, anyhow::Error> {
+    match arg {
+        CallArg::Pure(p) => SuiJsonValue::from_bcs_bytes(&p),
+        CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))
+        | CallArg::Object(ObjectArg::SharedObject { id, .. }) => {
+            SuiJsonValue::new(Value::String(id.to_hex_literal()))
+        }
+        CallArg::ObjVec(vec) => SuiJsonValue::new(Value::Array(
+            vec.iter()
+                .map(|obj_arg| match obj_arg {
+                    ObjectArg::ImmOrOwnedObject((id, _, _))
+                    | ObjectArg::SharedObject { id, .. } => Value::String(id.to_hex_literal()),
+                })
+                .collect(),
+        )),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "MoveCall", rename_all = "camelCase")]
 pub struct SuiMoveCall {
@@ -1800,6 +1924,228 @@ pub struct SuiMoveCall {
     pub arguments: Vec<SuiJsonValue>,
 }
 
+/// A series of commands where the results of one command can be used in future
+/// commands
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ProgrammableTransaction", rename_all = "camelCase")]
+pub struct SuiProgrammableTransaction {
+    /// Input objects or primitive values
+    pub inputs: Vec<SuiJsonValue>,
+    /// The commands to be executed sequentially. A failure in any command will
+    /// result in the failure of the entire transaction.
+    pub commands: Vec<SuiCommand>,
+}
+
+impl Display for SuiProgrammableTransaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self { inputs, commands } = self;
+        writeln!(f, "Inputs: {inputs:?}")?;
+        writeln!(f, "Commands: [")?;
+        for c in commands {
+            writeln!(f, "  {c},")?;
+        }
+        writeln!(f, "]")
+    }
+}
+
+impl TryFrom<ProgrammableTransaction> for SuiProgrammableTransaction {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ProgrammableTransaction) -> Result<Self, Self::Error> {
+        let ProgrammableTransaction { inputs, commands } = value;
+        Ok(SuiProgrammableTransaction {
+            inputs: inputs
+                .into_iter()
+                .map(call_arg_to_json_value)
+                .collect::<Result<_, _>>()?,
+            commands: commands.into_iter().map(SuiCommand::from).collect(),
+        })
+    }
+}
+
+/// A single command in a programmable transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "Command")]
+pub enum SuiCommand {
+    /// A call to either an entry or a public Move function
+    MoveCall(Box<SuiProgrammableMoveCall>),
+    /// `(Vec<forall T:key+store. T>, address)`
+    /// It sends n-objects to the specified address. These objects must have store
+    /// (public transfer) and either the previous owner must be an address or the object must
+    /// be newly created.
+    TransferObjects(Vec<SuiArgument>, SuiArgument),
+    /// `(&mut Coin<T>, u64)` -> `Coin<T>`
+    /// It splits off some amount into a new coin
+    SplitCoin(SuiArgument, SuiArgument),
+    /// `(&mut Coin<T>, Vec<Coin<T>>)`
+    /// It merges n-coins into the first coin
+    MergeCoins(SuiArgument, Vec<SuiArgument>),
+    /// Publishes a Move package
+    Publish(SuiMovePackage),
+}
+
+impl Display for SuiCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MoveCall(p) => write!(f, "MoveCall({p})"),
+            Self::TransferObjects(objs, addr) => {
+                write!(f, "TransferObjects([")?;
+                write_sep(f, objs, ",")?;
+                write!(f, "],{addr})")
+            }
+            Self::SplitCoin(coin, amount) => write!(f, "SplitCoin({coin},{amount})"),
+            Self::MergeCoins(target, coins) => {
+                write!(f, "MergeCoins({target},")?;
+                write_sep(f, coins, ",")?;
+                write!(f, ")")
+            }
+            Self::Publish(_) => write!(f, "Publish(_)"),
+        }
+    }
+}
+
+impl From<Command> for SuiCommand {
+    fn from(value: Command) -> Self {
+        match value {
+            Command::MoveCall(m) => SuiCommand::MoveCall(Box::new((*m).into())),
+            Command::TransferObjects(args, arg) => SuiCommand::TransferObjects(
+                args.into_iter().map(SuiArgument::from).collect(),
+                arg.into(),
+            ),
+            Command::SplitCoin(coin, amount) => SuiCommand::SplitCoin(coin.into(), amount.into()),
+            Command::MergeCoins(target, coins) => SuiCommand::MergeCoins(
+                target.into(),
+                coins.into_iter().map(SuiArgument::from).collect(),
+            ),
+            Command::Publish(modules) => SuiCommand::Publish(MoveModulePublish { modules }.into()),
+        }
+    }
+}
+
+/// An argument to a programmable transaction command
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "Argument")]
+pub enum SuiArgument {
+    /// The gas coin. The gas coin can only be used by-ref, except for with
+    /// `TransferObjects`, which can use it by-value.
+    GasCoin,
+    /// One of the input objects or primitive values (from
+    /// `ProgrammableTransaction` inputs)
+    Input(u16),
+    /// The result of another command (from `ProgrammableTransaction` commands)
+    Result(u16),
+    /// Like a `Result` but it accesses a nested result. Currently, the only usage
+    /// of this is to access a value from a Move call with multiple return values.
+    NestedResult(u16, u16),
+}
+
+impl Display for SuiArgument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GasCoin => write!(f, "GasCoin"),
+            Self::Input(i) => write!(f, "Input({i})"),
+            Self::Result(i) => write!(f, "Result({i})"),
+            Self::NestedResult(i, j) => write!(f, "NestedResult({i},{j})"),
+        }
+    }
+}
+
+impl From<Argument> for SuiArgument {
+    fn from(value: Argument) -> Self {
+        match value {
+            Argument::GasCoin => Self::GasCoin,
+            Argument::Input(i) => Self::Input(i),
+            Argument::Result(i) => Self::Result(i),
+            Argument::NestedResult(i, j) => Self::NestedResult(i, j),
+        }
+    }
+}
+
+impl From<SuiArgument> for Argument {
+    fn from(value: SuiArgument) -> Self {
+        match value {
+            SuiArgument::GasCoin => Self::GasCoin,
+            SuiArgument::Input(i) => Self::Input(i),
+            SuiArgument::Result(i) => Self::Result(i),
+            SuiArgument::NestedResult(i, j) => Self::NestedResult(i, j),
+        }
+    }
+}
+
+/// The command for calling a Move function, either an entry function or a public
+/// function (which cannot return references).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ProgrammableMoveCall", rename_all = "camelCase")]
+pub struct SuiProgrammableMoveCall {
+    /// The package containing the module and function.
+    pub package: ObjectID,
+    /// The specific module in the package containing the function.
+    pub module: String,
+    /// The function to be called.
+    pub function: String,
+    /// The type arguments to the function.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub type_arguments: Vec<String>,
+    /// The arguments to the function.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<SuiArgument>,
+}
+
+impl Display for SuiProgrammableMoveCall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self {
+            package,
+            module,
+            function,
+            type_arguments,
+            arguments,
+        } = self;
+        write!(f, "{package}::{module}::{function}")?;
+        if !type_arguments.is_empty() {
+            write!(f, "<")?;
+            write_sep(f, type_arguments, ",")?;
+            write!(f, ">")?;
+        }
+        write!(f, "(")?;
+        write_sep(f, arguments, ",")?;
+        write!(f, ")")
+    }
+}
+
+impl From<ProgrammableMoveCall> for SuiProgrammableMoveCall {
+    fn from(value: ProgrammableMoveCall) -> Self {
+        let ProgrammableMoveCall {
+            package,
+            module,
+            function,
+            type_arguments,
+            arguments,
+        } = value;
+        Self {
+            package,
+            module: module.to_string(),
+            function: function.to_string(),
+            type_arguments: type_arguments.into_iter().map(|t| t.to_string()).collect(),
+            arguments: arguments.into_iter().map(SuiArgument::from).collect(),
+        }
+    }
+}
+
+fn write_sep<T: Display>(
+    f: &mut Formatter<'_>,
+    items: impl IntoIterator<Item = T>,
+    sep: &str,
+) -> fmt::Result {
+    let mut xs = items.into_iter().peekable();
+    while let Some(x) = xs.next() {
+        write!(f, "{x}")?;
+        if xs.peek().is_some() {
+            write!(f, "{sep}")?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SuiChangeEpoch {
     pub epoch: EpochId,
@@ -2921,7 +3267,98 @@ pub struct MoveCallParams {
     pub arguments: Vec<SuiJsonValue>,
 }
 
-#[derive(Serialize, Deserialize, JsonSchema, Debug)]
+/// An input to a programmable transaction
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ProgrammableTransactionInputParams {
+    /// An object, used at its latest version
+    Object(ObjectID),
+    /// The BCS bytes of a primitive value
+    Pure(Base64),
+}
+
+/// A command of a programmable transaction. Arguments refer to the transaction inputs, the gas
+/// coin, or the results of previous commands
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ProgrammableTransactionCommandParams {
+    MoveCall(ProgrammableMoveCallParams),
+    TransferObjects {
+        objects: Vec<SuiArgument>,
+        recipient: SuiArgument,
+    },
+    SplitCoin {
+        coin: SuiArgument,
+        amount: SuiArgument,
+    },
+    MergeCoins {
+        target: SuiArgument,
+        coins: Vec<SuiArgument>,
+    },
+    Publish {
+        compiled_modules: Vec<Base64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgrammableMoveCallParams {
+    pub package_object_id: ObjectID,
+    pub module: String,
+    pub function: String,
+    #[serde(default)]
+    pub type_arguments: Vec<SuiTypeTag>,
+    #[serde(default)]
+    pub arguments: Vec<SuiArgument>,
+}
+
+impl TryFrom<ProgrammableTransactionCommandParams> for Command {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ProgrammableTransactionCommandParams) -> Result<Self, Self::Error> {
+        Ok(match value {
+            ProgrammableTransactionCommandParams::MoveCall(ProgrammableMoveCallParams {
+                package_object_id,
+                module,
+                function,
+                type_arguments,
+                arguments,
+            }) => Command::MoveCall(Box::new(ProgrammableMoveCall {
+                package: package_object_id,
+                module: Identifier::from_str(&module)?,
+                function: Identifier::from_str(&function)?,
+                type_arguments: type_arguments
+                    .into_iter()
+                    .map(|ty| ty.try_into())
+                    .collect::<Result<Vec<_>, _>>()?,
+                arguments: arguments.into_iter().map(Argument::from).collect(),
+            })),
+            ProgrammableTransactionCommandParams::TransferObjects { objects, recipient } => {
+                Command::TransferObjects(
+                    objects.into_iter().map(Argument::from).collect(),
+                    recipient.into(),
+                )
+            }
+            ProgrammableTransactionCommandParams::SplitCoin { coin, amount } => {
+                Command::SplitCoin(coin.into(), amount.into())
+            }
+            ProgrammableTransactionCommandParams::MergeCoins { target, coins } => {
+                Command::MergeCoins(
+                    target.into(),
+                    coins.into_iter().map(Argument::from).collect(),
+                )
+            }
+            ProgrammableTransactionCommandParams::Publish { compiled_modules } => Command::Publish(
+                compiled_modules
+                    .into_iter()
+                    .map(|data| data.to_vec().map_err(|e| anyhow::anyhow!(e)))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename = "EventFilter")]
 pub enum SuiEventFilter {
     Package(ObjectID),
@@ -2932,9 +3369,27 @@ pub enum SuiEventFilter {
         path: String,
         value: Value,
     },
+    /// Numeric move event field at the JSON pointer `path` is greater than `value`
+    MoveEventFieldGreaterThan {
+        path: String,
+        value: u64,
+    },
+    /// Numeric move event field at the JSON pointer `path` is less than `value`
+    MoveEventFieldLessThan {
+        path: String,
+        value: u64,
+    },
     SenderAddress(SuiAddress),
+    Recipient(Owner),
     EventType(EventType),
     ObjectId(ObjectID),
+    Transaction(TransactionDigest),
+    /// Events emitted within [start_time, end_time), in milliseconds since epoch
+    #[serde(rename_all = "camelCase")]
+    TimeRange {
+        start_time: u64,
+        end_time: u64,
+    },
     All(Vec<SuiEventFilter>),
     Any(Vec<SuiEventFilter>),
     And(Box<SuiEventFilter>, Box<SuiEventFilter>),
@@ -2954,8 +3409,25 @@ impl TryInto<EventFilter> for SuiEventFilter {
                 EventFilter::MoveEventType(parse_sui_struct_tag(&event_type)?)
             }
             MoveEventField { path, value } => EventFilter::MoveEventField { path, value },
+            MoveEventFieldGreaterThan { path, value } => EventFilter::MoveEventFieldGreaterThan {
+                path,
+                value: check_field_comparison_value(value)?,
+            },
+            MoveEventFieldLessThan { path, value } => EventFilter::MoveEventFieldLessThan {
+                path,
+                value: check_field_comparison_value(value)?,
+            },
             SenderAddress(address) => EventFilter::SenderAddress(address),
+            Recipient(recipient) => EventFilter::Recipient(recipient),
             ObjectId(id) => EventFilter::ObjectId(id),
+            Transaction(digest) => EventFilter::Transaction(digest),
+            TimeRange {
+                start_time,
+                end_time,
+            } => EventFilter::TimeRange {
+                start_time,
+                end_time,
+            },
             All(filters) => EventFilter::MatchAll(
                 filters
                     .into_iter()
@@ -2975,6 +3447,39 @@ impl TryInto<EventFilter> for SuiEventFilter {
     }
 }
 
+/// Event stores compare numeric fields as signed 64-bit integers, larger values are rejected.
+fn check_field_comparison_value(value: u64) -> Result<u64, anyhow::Error> {
+    if value > i64::MAX as u64 {
+        return Err(anyhow::anyhow!(
+            "Field comparison value {value} is larger than the maximum {}",
+            i64::MAX
+        ));
+    }
+    Ok(value)
+}
+
+/// Query accepted by `sui_getEvents`: either a single `EventQuery` criterion, or an
+/// `EventFilter` tree combining several criteria, as used by event subscriptions, wrapped as
+/// `{"filter": ...}` since both share variant names.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(untagged)]
+pub enum SuiEventQuery {
+    Query(EventQuery),
+    Filter { filter: SuiEventFilter },
+}
+
+impl From<EventQuery> for SuiEventQuery {
+    fn from(query: EventQuery) -> Self {
+        Self::Query(query)
+    }
+}
+
+impl From<SuiEventFilter> for SuiEventQuery {
+    fn from(filter: SuiEventFilter) -> Self {
+        Self::Filter { filter }
+    }
+}
+
 #[serde_as]
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
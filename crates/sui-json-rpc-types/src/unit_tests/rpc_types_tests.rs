@@ -9,13 +9,22 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::value::{MoveStruct, MoveValue};
 
-use sui_types::base_types::SequenceNumber;
+use sui_types::base_types::{ObjectDigest, SequenceNumber};
 use sui_types::base_types::{ObjectID, SuiAddress};
-use sui_types::gas_coin::GasCoin;
+use sui_types::gas_coin::{GasCoin, GAS};
+use sui_types::messages::{Argument, Command, ObjectArg};
 use sui_types::object::MoveObject;
-use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_OBJECT_ID};
 
-use crate::{SuiMoveStruct, SuiMoveValue};
+use sui_types::event::EventType;
+use sui_types::filter::EventFilter;
+use sui_types::query::EventQuery;
+
+use crate::{
+    ProgrammableTransactionCommandParams, SuiArgument, SuiCommand, SuiEventFilter, SuiEventQuery,
+    SuiMoveStruct, SuiMoveValue, SuiProgrammableTransaction,
+};
 
 #[test]
 fn test_move_value_to_sui_coin() {
@@ -143,3 +152,142 @@ fn test_serde() {
         )
     }
 }
+
+#[test]
+fn test_programmable_transaction_to_sui() {
+    let coin_id = ObjectID::random();
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let coin = builder.obj(ObjectArg::ImmOrOwnedObject((
+        coin_id,
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    )));
+    let amount = builder.pure(7u8).unwrap();
+    let split = builder.command(Command::SplitCoin(coin, amount));
+    builder.move_call(
+        SUI_FRAMEWORK_OBJECT_ID,
+        Identifier::new("coin").unwrap(),
+        Identifier::new("value").unwrap(),
+        vec![GAS::type_tag()],
+        vec![split],
+    );
+    builder.merge_coins(coin, vec![Argument::NestedResult(0, 0)]);
+
+    let sui_pt = SuiProgrammableTransaction::try_from(builder.finish()).unwrap();
+    assert_eq!(
+        sui_pt
+            .inputs
+            .iter()
+            .map(|input| input.to_json_value())
+            .collect::<Vec<_>>(),
+        vec![
+            serde_json::json!(coin_id.to_hex_literal()),
+            serde_json::json!(7),
+        ]
+    );
+    assert!(matches!(
+        sui_pt.commands[0],
+        SuiCommand::SplitCoin(SuiArgument::Input(0), SuiArgument::Input(1))
+    ));
+    let SuiCommand::MoveCall(call) = &sui_pt.commands[1] else {
+        panic!("Expected a Move call")
+    };
+    assert_eq!(call.module, "coin");
+    assert_eq!(call.function, "value");
+    assert_eq!(call.type_arguments, vec![GAS::type_tag().to_string()]);
+    assert_eq!(call.arguments, vec![SuiArgument::Result(0)]);
+    assert_eq!(
+        sui_pt.commands[2].to_string(),
+        "MergeCoins(Input(0),NestedResult(0,0))"
+    );
+}
+
+#[test]
+fn test_command_params_to_command() {
+    let package = ObjectID::random();
+    let params: Vec<ProgrammableTransactionCommandParams> =
+        serde_json::from_value(serde_json::json!([
+            {
+                "moveCall": {
+                    "packageObjectId": package,
+                    "module": "m",
+                    "function": "f",
+                    "typeArguments": ["0x2::sui::SUI"],
+                    "arguments": ["GasCoin", { "Input": 0 }],
+                }
+            },
+            { "splitCoin": { "coin": "GasCoin", "amount": { "Input": 1 } } },
+            { "transferObjects": { "objects": [{ "Result": 1 }], "recipient": { "Input": 2 } } },
+            { "mergeCoins": { "target": { "Result": 0 }, "coins": [{ "NestedResult": [1, 0] }] } },
+        ]))
+        .unwrap();
+    let commands = params
+        .into_iter()
+        .map(Command::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let Command::MoveCall(call) = &commands[0] else {
+        panic!("Expected a Move call")
+    };
+    assert_eq!(call.package, package);
+    assert_eq!(call.module.as_str(), "m");
+    assert_eq!(call.function.as_str(), "f");
+    assert_eq!(call.type_arguments, vec![GAS::type_tag()]);
+    assert_eq!(call.arguments, vec![Argument::GasCoin, Argument::Input(0)]);
+    assert_eq!(
+        commands[1..],
+        [
+            Command::SplitCoin(Argument::GasCoin, Argument::Input(1)),
+            Command::TransferObjects(vec![Argument::Result(1)], Argument::Input(2)),
+            Command::MergeCoins(Argument::Result(0), vec![Argument::NestedResult(1, 0)]),
+        ]
+    );
+
+    // and back to the JSON-RPC representation
+    let sui_commands = commands
+        .into_iter()
+        .map(SuiCommand::from)
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        &sui_commands[3],
+        SuiCommand::MergeCoins(SuiArgument::Result(0), coins)
+            if coins == &vec![SuiArgument::NestedResult(1, 0)]
+    ));
+}
+
+#[test]
+fn test_event_query_representation() {
+    // Variants shared by `EventQuery` and `EventFilter` are read as an `EventQuery`...
+    let query: SuiEventQuery =
+        serde_json::from_value(serde_json::json!({ "EventType": "NewObject" })).unwrap();
+    assert!(matches!(
+        query,
+        SuiEventQuery::Query(EventQuery::EventType(EventType::NewObject))
+    ));
+
+    // ...and as a filter only when wrapped.
+    let query: SuiEventQuery = serde_json::from_value(serde_json::json!({
+        "filter": { "Any": [{ "EventType": "NewObject" }, { "EventType": "DeleteObject" }] }
+    }))
+    .unwrap();
+    let SuiEventQuery::Filter { filter: SuiEventFilter::Any(filters) } = &query else {
+        panic!("Expected a filter, got {query:?}")
+    };
+    assert_eq!(filters.len(), 2);
+    let json = serde_json::to_value(&query).unwrap();
+    assert!(json.get("filter").is_some());
+}
+
+#[test]
+fn test_event_filter_rejects_out_of_range_comparison() {
+    let filter = |value| {
+        SuiEventFilter::All(vec![SuiEventFilter::MoveEventFieldLessThan {
+            path: "/amount".to_string(),
+            value,
+        }])
+    };
+    let max: EventFilter = filter(i64::MAX as u64).try_into().unwrap();
+    assert!(matches!(max, EventFilter::MatchAll(filters) if filters.len() == 1));
+    assert!(TryInto::<EventFilter>::try_into(filter(i64::MAX as u64 + 1)).is_err());
+}
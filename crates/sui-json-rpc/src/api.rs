@@ -10,13 +10,14 @@ use jsonrpsee_proc_macros::rpc;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
     Balance, CoinPage, DevInspectResults, DynamicFieldPage, EventPage, GetObjectDataResponse,
-    GetPastObjectDataResponse, GetRawObjectDataResponse, MoveFunctionArgType,
-    RPCTransactionRequestParams, SuiCoinMetadata, SuiEventEnvelope, SuiEventFilter,
+    GetPastObjectDataResponse, GetRawObjectDataResponse, MoveFunctionArgType, ObjectHistoryPage,
+    ObjectsPage, ProgrammableTransactionCommandParams, ProgrammableTransactionInputParams,
+    RPCTransactionRequestParams, SuiCoinMetadata, SuiEventEnvelope, SuiEventFilter, SuiEventQuery,
     SuiExecuteTransactionResponse, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
     SuiMoveNormalizedStruct, SuiObjectInfo, SuiTBlsSignObjectCommitmentType,
     SuiTBlsSignRandomnessObjectResponse, SuiTransactionAuthSignersResponse,
-    SuiTransactionBuilderMode, SuiTransactionEffects, SuiTransactionResponse, SuiTypeTag,
-    TransactionBytes, TransactionsPage,
+    SuiTransactionBuilderMode, SuiTransactionEffects, SuiTransactionRead, SuiTransactionResponse,
+    SuiTypeTag, TransactionBytes, TransactionsPage,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::balance::Supply;
@@ -25,6 +26,7 @@ use sui_types::base_types::{
 };
 use sui_types::committee::EpochId;
 use sui_types::event::EventID;
+use sui_types::filter::TransactionFilter;
 use sui_types::governance::DelegatedStake;
 use sui_types::messages::CommitteeInfoResponse;
 use sui_types::messages::ExecuteTransactionRequestType;
@@ -32,7 +34,7 @@ use sui_types::messages_checkpoint::{
     CheckpointContents, CheckpointContentsDigest, CheckpointDigest, CheckpointSequenceNumber,
     CheckpointSummary,
 };
-use sui_types::query::{EventQuery, TransactionQuery};
+use sui_types::query::TransactionQuery;
 use sui_types::sui_system_state::{SuiSystemState, ValidatorMetadata};
 
 /// Maximum number of events returned in an event query.
@@ -71,6 +73,8 @@ pub trait CoinReadApi {
     ) -> RpcResult<CoinPage>;
 
     /// Return the total coin balance for one coin type, owned by the address owner.
+    /// Balances at a checkpoint only include the total balance of unlocked coins, without the coin
+    /// object count and locked balances, from the checkpoint the node started indexing balances at.
     #[method(name = "getBalance")]
     async fn get_balance(
         &self,
@@ -78,14 +82,20 @@ pub trait CoinReadApi {
         owner: SuiAddress,
         /// optional type names for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
         coin_type: Option<String>,
+        /// optional checkpoint sequence number, to return the balance once that checkpoint was executed instead of the current one.
+        checkpoint: Option<CheckpointSequenceNumber>,
     ) -> RpcResult<Balance>;
 
     /// Return the total coin balance for all coin type, owned by the address owner.
+    /// Balances at a checkpoint only include the total balance of unlocked coins, without the coin
+    /// object count and locked balances, from the checkpoint the node started indexing balances at.
     #[method(name = "getAllBalances")]
     async fn get_all_balances(
         &self,
         /// the owner's Sui address
         owner: SuiAddress,
+        /// optional checkpoint sequence number, to return the balance once that checkpoint was executed instead of the current one.
+        checkpoint: Option<CheckpointSequenceNumber>,
     ) -> RpcResult<Vec<Balance>>;
 
     /// Return metadata(e.g., symbol, decimals) for a coin
@@ -116,6 +126,20 @@ pub trait RpcReadApi {
         address: SuiAddress,
     ) -> RpcResult<Vec<SuiObjectInfo>>;
 
+    /// Return the list of objects owned by an address, one page at a time, optionally filtered by Move type.
+    #[method(name = "getOwnedObjects")]
+    async fn get_owned_objects(
+        &self,
+        /// the owner's Sui address
+        address: SuiAddress,
+        /// Optional Move type of the objects, either a package, a module, a struct of any type parameters or an exact type, e.g. `0x2`, `0x2::coin`, `0x2::coin::Coin` or `0x2::coin::Coin<0x2::sui::SUI>`
+        type_filter: Option<String>,
+        /// Optional paging cursor
+        cursor: Option<ObjectID>,
+        /// Maximum item returned per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage>;
+
     /// Return the list of dynamic field objects owned by an object.
     #[method(name = "getDynamicFields")]
     async fn get_dynamic_fields(
@@ -150,6 +174,16 @@ pub trait RpcReadApi {
         digest: TransactionDigest,
     ) -> RpcResult<SuiTransactionResponse>;
 
+    /// Return the transaction response objects of many transactions, in the order of the digests,
+    /// or `NotExists` for the transactions not found.
+    /// At most `json-rpc-multi-get-max-size` transactions, 50 by default, can be requested at once.
+    #[method(name = "multiGetTransactions")]
+    async fn multi_get_transactions(
+        &self,
+        /// the digests of the queried transactions
+        digests: Vec<TransactionDigest>,
+    ) -> RpcResult<Vec<SuiTransactionRead>>;
+
     /// Return the authority public keys that commits to the authority signature of the transaction.
     #[method(name = "getTransactionAuthSigners")]
     async fn get_transaction_auth_signers(
@@ -166,6 +200,15 @@ pub trait RpcReadApi {
         object_id: ObjectID,
     ) -> RpcResult<GetObjectDataResponse>;
 
+    /// Return the object information for many objects, in the order of the IDs.
+    /// At most `json-rpc-multi-get-max-size` objects, 50 by default, can be requested at once.
+    #[method(name = "multiGetObjects")]
+    async fn multi_get_objects(
+        &self,
+        /// the IDs of the queried objects
+        object_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<GetObjectDataResponse>>;
+
     /// Return the dynamic field object information for a specified object
     #[method(name = "getDynamicFieldObject")]
     async fn get_dynamic_field_object(
@@ -270,6 +313,21 @@ pub trait RpcFullNodeReadApi {
         version: SequenceNumber,
     ) -> RpcResult<GetPastObjectDataResponse>;
 
+    /// Return every version of an object, and the transaction which produced it.
+    /// Versions pruned from this node are listed with the `Pruned` status and without their owner.
+    #[method(name = "getObjectHistory")]
+    async fn get_object_history(
+        &self,
+        /// the ID of the queried object
+        object_id: ObjectID,
+        /// optional paging cursor, the object version to start from
+        cursor: Option<SequenceNumber>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// query result ordering, default to false (ascending order), oldest version first.
+        descending_order: Option<bool>,
+    ) -> RpcResult<ObjectHistoryPage>;
+
     /// Return the sequence number of the latest checkpoint that has been executed
     #[method(name = "getLatestCheckpointSequenceNumber")]
     fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<CheckpointSequenceNumber>;
@@ -534,6 +592,23 @@ pub trait RpcTransactionBuilder {
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
     ) -> RpcResult<TransactionBytes>;
 
+    /// Create an unsigned programmable transaction, a series of commands where the results of
+    /// one command can be used as the arguments of the following commands.
+    #[method(name = "programmableTransaction")]
+    async fn programmable_transaction(
+        &self,
+        /// the transaction signer's Sui address
+        signer: SuiAddress,
+        /// the inputs of the transaction, objects or the BCS bytes of primitive values
+        inputs: Vec<ProgrammableTransactionInputParams>,
+        /// the commands to be executed sequentially
+        commands: Vec<ProgrammableTransactionCommandParams>,
+        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        gas: Option<ObjectID>,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget
+        gas_budget: u64,
+    ) -> RpcResult<TransactionBytes>;
+
     /// Add delegated stake to a validator's staking pool using multiple coins and amount.
     #[method(name = "requestAddDelegation")]
     async fn request_add_delegation(
@@ -608,18 +683,34 @@ pub trait EventStreamingApi {
         &self,
         /// the filter criteria of the event stream, see the [Sui docs](https://docs.sui.io/build/pubsub#event-filters) for detailed examples.
         filter: SuiEventFilter,
+        /// optional cursor, the ID of the last event received. Events emitted after it are replayed before streaming live events.
+        cursor: Option<EventID>,
+    );
+}
+
+#[open_rpc(namespace = "sui", tag = "Transaction Subscription")]
+#[rpc(server, client, namespace = "sui")]
+pub trait TransactionStreamingApi {
+    /// Subscribe to a stream of transactions executed by the full node
+    #[subscription(name = "subscribeTransaction", item = SuiTransactionResponse)]
+    fn subscribe_transaction(
+        &self,
+        /// the filter criteria of the transaction stream.
+        filter: TransactionFilter,
     );
 }
 
 #[open_rpc(namespace = "sui", tag = "Event Read API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait EventReadApi {
-    /// Return list of events for a specified query criteria.
+    /// Return list of events for a specified query criteria, either a single `EventQuery` or an
+    /// `EventFilter` combining several criteria, as used by `sui_subscribeEvent`, wrapped as
+    /// `{"filter": ...}`.
     #[method(name = "getEvents")]
     async fn get_events(
         &self,
         /// the event query criteria.
-        query: EventQuery,
+        query: SuiEventQuery,
         /// optional paging cursor
         cursor: Option<EventID>,
         /// maximum number of items per page, default to [QUERY_MAX_RESULT_LIMIT] if not specified.
@@ -681,6 +772,16 @@ pub trait TransactionExecutionApi {
     ) -> RpcResult<SuiExecuteTransactionResponse>;
 }
 
+pub fn check_multi_get_size(size: usize, max_size: usize) -> Result<(), anyhow::Error> {
+    if size > max_size {
+        Err(anyhow::anyhow!(
+            "Requested {size} items, exceeding the maximum of {max_size} per call"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 pub fn cap_page_limit(limit: Option<usize>) -> usize {
     let limit = limit.unwrap_or_default();
     if limit > QUERY_MAX_RESULT_LIMIT || limit == 0 {
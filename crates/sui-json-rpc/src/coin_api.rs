@@ -18,9 +18,11 @@ use sui_open_rpc::Module;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, ObjectType, SuiAddress};
 use sui_types::coin::{Coin, CoinMetadata, LockedCoin, TreasuryCap};
+use sui_types::committee::EpochId;
 use sui_types::error::SuiError;
 use sui_types::event::Event;
 use sui_types::gas_coin::GAS;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Object;
 use sui_types::parse_sui_struct_tag;
 
@@ -184,12 +186,22 @@ impl CoinReadApiServer for CoinReadApi {
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
+        checkpoint: Option<CheckpointSequenceNumber>,
     ) -> RpcResult<Balance> {
         let coin_type = Some(match coin_type {
             Some(c) => parse_sui_struct_tag(&c)?,
             None => GAS::type_(),
         });
 
+        if let Some(checkpoint) = checkpoint {
+            let coin_type = coin_type.unwrap().to_string();
+            let balance = self
+                .state
+                .get_balance_at_checkpoint(owner, &coin_type, checkpoint)
+                .map_err(Error::from)?;
+            return Ok(balance_at_checkpoint(coin_type, balance)?);
+        }
+
         // TODO: Add index to improve performance?
         let coins = self.get_owner_coin_iterator(owner, &coin_type)?;
         let mut total_balance = 0u128;
@@ -208,34 +220,55 @@ impl CoinReadApiServer for CoinReadApi {
 
         Ok(Balance {
             coin_type: coin_type.unwrap().to_string(),
-            coin_object_count,
+            coin_object_count: Some(coin_object_count),
             total_balance,
-            locked_balance,
+            locked_balance: Some(locked_balance),
         })
     }
 
-    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
+    async fn get_all_balances(
+        &self,
+        owner: SuiAddress,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> RpcResult<Vec<Balance>> {
+        if let Some(checkpoint) = checkpoint {
+            return Ok(self
+                .state
+                .get_all_balances_at_checkpoint(owner, checkpoint)
+                .map_err(Error::from)?
+                .into_iter()
+                .map(|(coin_type, balance)| balance_at_checkpoint(coin_type, balance))
+                .collect::<Result<_, _>>()?);
+        }
+
         // TODO: Add index to improve performance?
         let coins = self.get_owner_coin_iterator(owner, &None)?;
-        let mut balances: HashMap<String, Balance> = HashMap::new();
+        // Coin type to the coin object count, total balance and locked balances.
+        let mut balances: HashMap<String, (usize, u128, HashMap<EpochId, u128>)> = HashMap::new();
 
         for coin in coins {
             let coin = self.get_coin(&coin).await?;
-            let balance = balances.entry(coin.coin_type.clone()).or_insert(Balance {
-                coin_type: coin.coin_type,
-                coin_object_count: 0,
-                total_balance: 0,
-                locked_balance: Default::default(),
-            });
+            let (coin_object_count, total_balance, locked_balance) =
+                balances.entry(coin.coin_type).or_default();
             if let Some(lock) = coin.locked_until_epoch {
-                *balance.locked_balance.entry(lock).or_default() += coin.balance as u128
+                *locked_balance.entry(lock).or_default() += coin.balance as u128
             } else {
-                balance.total_balance += coin.balance as u128;
+                *total_balance += coin.balance as u128;
             }
-            balance.coin_object_count += 1;
+            *coin_object_count += 1;
         }
 
-        Ok(balances.into_values().collect())
+        Ok(balances
+            .into_iter()
+            .map(
+                |(coin_type, (coin_object_count, total_balance, locked_balance))| Balance {
+                    coin_type,
+                    coin_object_count: Some(coin_object_count),
+                    total_balance,
+                    locked_balance: Some(locked_balance),
+                },
+            )
+            .collect())
     }
 
     async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<SuiCoinMetadata> {
@@ -302,3 +335,19 @@ fn is_coin_type(type_: &StructTag, coin_type: &Option<StructTag>) -> bool {
     }
     false
 }
+
+/// Balances at a checkpoint come from the balance changes of coins, which do not track the number
+/// of coin objects nor locked coins, so these are left out.
+fn balance_at_checkpoint(coin_type: String, balance: i128) -> Result<Balance, Error> {
+    let total_balance = u128::try_from(balance).map_err(|_| {
+        Error::UnexpectedError(format!(
+            "Negative balance {balance} of coin type {coin_type} in the balance index."
+        ))
+    })?;
+    Ok(Balance {
+        coin_type,
+        coin_object_count: None,
+        total_balance,
+        locked_balance: None,
+    })
+}
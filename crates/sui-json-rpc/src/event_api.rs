@@ -17,10 +17,10 @@ use tracing::{debug, warn};
 
 use sui_core::authority::AuthorityState;
 use sui_core::event_handler::EventHandler;
-use sui_json_rpc_types::{EventPage, SuiEvent, SuiEventEnvelope, SuiEventFilter};
+use sui_json_rpc_types::{EventPage, SuiEvent, SuiEventEnvelope, SuiEventFilter, SuiEventQuery};
 use sui_open_rpc::Module;
+use sui_types::error::SuiResult;
 use sui_types::event::{EventEnvelope, EventID};
-use sui_types::query::EventQuery;
 
 use crate::api::EventReadApiServer;
 use crate::api::{cap_page_limit, EventStreamingApiServer};
@@ -40,7 +40,7 @@ impl EventStreamingApiImpl {
     }
 }
 
-fn spawn_subscription<S, T, E>(mut sink: SubscriptionSink, rx: S)
+pub(crate) fn spawn_subscription<S, T, E>(mut sink: SubscriptionSink, rx: S)
 where
     S: TryStream<Ok = T, Error = E> + Unpin + Send + 'static,
     T: Serialize,
@@ -66,6 +66,7 @@ impl EventStreamingApiServer for EventStreamingApiImpl {
         &self,
         mut sink: SubscriptionSink,
         filter: SuiEventFilter,
+        cursor: Option<EventID>,
     ) -> SubscriptionResult {
         let filter = match filter.try_into() {
             Ok(filter) => filter,
@@ -77,10 +78,24 @@ impl EventStreamingApiServer for EventStreamingApiImpl {
         };
 
         let state = self.state.clone();
-        let stream = self.event_handler.subscribe(filter);
-        let stream = stream.map(move |e: EventEnvelope| {
-            let event = SuiEvent::try_from(e.event, state.module_cache.as_ref());
-            event.map(|event| SuiEventEnvelope {
+        let stream = match cursor {
+            Some(cursor) => {
+                let position = match self.state.get_event_position(&cursor) {
+                    Ok(position) => position,
+                    Err(e) => {
+                        let e = jsonrpsee::core::Error::from(e);
+                        warn!(error = ?e, "Rejecting subscription request.");
+                        return Ok(sink.reject(e)?);
+                    }
+                };
+                self.event_handler.subscribe_from(filter, position).boxed()
+            }
+            None => self.event_handler.subscribe(filter).boxed(),
+        };
+        let stream = stream.map(move |e: SuiResult<EventEnvelope>| {
+            let e = e?;
+            let event = SuiEvent::try_from(e.event, state.module_cache.as_ref())?;
+            Ok::<_, anyhow::Error>(SuiEventEnvelope {
                 timestamp: e.timestamp,
                 tx_digest: e.tx_digest,
                 id: EventID::from((e.tx_digest, e.event_num as i64)),
@@ -122,7 +137,7 @@ impl EventReadApiImpl {
 impl EventReadApiServer for EventReadApiImpl {
     async fn get_events(
         &self,
-        query: EventQuery,
+        query: SuiEventQuery,
         cursor: Option<EventID>,
         limit: Option<usize>,
         descending_order: Option<bool>,
@@ -31,6 +31,7 @@ pub mod read_api;
 pub mod threshold_bls_api;
 pub mod transaction_builder_api;
 pub mod transaction_execution_api;
+pub mod transaction_streaming_api;
 
 #[cfg(test)]
 #[path = "unit_tests/rpc_server_tests.rs"]
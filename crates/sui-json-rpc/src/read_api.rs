@@ -7,6 +7,7 @@ use jsonrpsee::core::RpcResult;
 use move_binary_format::normalized::{Module as NormalizedModule, Type};
 use move_core_types::identifier::Identifier;
 use std::collections::BTreeMap;
+use std::str::FromStr;
 use std::sync::Arc;
 use sui_types::committee::EpochId;
 use sui_types::intent::{AppId, Intent, IntentMessage, IntentScope, IntentVersion};
@@ -17,9 +18,10 @@ use jsonrpsee::RpcModule;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
     DevInspectResults, DynamicFieldPage, GetObjectDataResponse, GetPastObjectDataResponse,
-    MoveFunctionArgType, ObjectValueKind, Page, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
-    SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionAuthSignersResponse,
-    SuiTransactionEffects, SuiTransactionResponse, TransactionsPage,
+    MoveFunctionArgType, ObjectHistoryPage, ObjectValueKind, ObjectsPage, Page,
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
+    SuiTransactionAuthSignersResponse, SuiTransactionEffects, SuiTransactionRead,
+    SuiTransactionResponse, TransactionsPage,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::SequenceNumber;
@@ -32,18 +34,19 @@ use sui_types::messages_checkpoint::{
 };
 use sui_types::move_package::normalize_modules;
 use sui_types::object::{Data, ObjectRead};
-use sui_types::query::TransactionQuery;
+use sui_types::query::{ObjectTypeFilter, TransactionQuery};
 
 use tracing::debug;
 
 use crate::api::RpcFullNodeReadApiServer;
-use crate::api::{cap_page_limit, RpcReadApiServer};
+use crate::api::{cap_page_limit, check_multi_get_size, RpcReadApiServer};
 use crate::SuiRpcModule;
 
 // An implementation of the read portion of the JSON-RPC interface intended for use in
 // Fullnodes.
 pub struct ReadApi {
     pub state: Arc<AuthorityState>,
+    multi_get_max_size: usize,
 }
 
 pub struct FullNodeApi {
@@ -65,8 +68,11 @@ impl FullNodeApi {
 }
 
 impl ReadApi {
-    pub fn new(state: Arc<AuthorityState>) -> Self {
-        Self { state }
+    pub fn new(state: Arc<AuthorityState>, multi_get_max_size: usize) -> Self {
+        Self {
+            state,
+            multi_get_max_size,
+        }
     }
 }
 
@@ -85,6 +91,27 @@ impl RpcReadApiServer for ReadApi {
             .collect())
     }
 
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        type_filter: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage> {
+        let type_filter = type_filter
+            .map(|type_filter| ObjectTypeFilter::from_str(&type_filter))
+            .transpose()?;
+        let limit = cap_page_limit(limit);
+        let mut data = self
+            .state
+            .get_owner_objects_page(address, type_filter.as_ref(), cursor, limit + 1)
+            .map_err(|e| anyhow!("{e}"))?;
+        let next_cursor = data.get(limit).map(|info| info.object_id);
+        data.truncate(limit);
+        let data = data.into_iter().map(SuiObjectInfo::from).collect();
+        Ok(ObjectsPage { data, next_cursor })
+    }
+
     async fn get_dynamic_fields(
         &self,
         parent_object_id: ObjectID,
@@ -113,6 +140,23 @@ impl RpcReadApiServer for ReadApi {
             .try_into()?)
     }
 
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<GetObjectDataResponse>> {
+        check_multi_get_size(object_ids.len(), self.multi_get_max_size)?;
+        self.state
+            .multi_get_object_read(&object_ids)
+            .await
+            .map_err(|e| {
+                debug!(?object_ids, "Failed to get objects: {:?}", e);
+                anyhow!("{e}")
+            })?
+            .into_iter()
+            .map(|object_read| Ok(object_read.try_into()?))
+            .collect()
+    }
+
     async fn get_dynamic_field_object(
         &self,
         parent_object_id: ObjectID,
@@ -162,6 +206,35 @@ impl RpcReadApiServer for ReadApi {
         })
     }
 
+    async fn multi_get_transactions(
+        &self,
+        digests: Vec<TransactionDigest>,
+    ) -> RpcResult<Vec<SuiTransactionRead>> {
+        check_multi_get_size(digests.len(), self.multi_get_max_size)?;
+        let transactions = self
+            .state
+            .multi_get_transactions(&digests)
+            .await
+            .tap_err(|err| debug!(?digests, "Failed to get transactions: {:?}", err))?;
+        let mut responses = Vec::with_capacity(transactions.len());
+        for (digest, transaction) in digests.into_iter().zip(transactions) {
+            let Some((cert, effects)) = transaction else {
+                responses.push(SuiTransactionRead::NotExists(digest));
+                continue;
+            };
+            responses.push(SuiTransactionRead::Exists(SuiTransactionResponse {
+                certificate: cert.try_into()?,
+                effects: SuiTransactionEffects::try_from(
+                    effects,
+                    self.state.module_cache.as_ref(),
+                )?,
+                timestamp_ms: self.state.get_timestamp_ms(&digest).await?,
+                parsed_data: None,
+            }));
+        }
+        Ok(responses)
+    }
+
     async fn get_transaction_auth_signers(
         &self,
         digest: TransactionDigest,
@@ -374,6 +447,28 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
             .try_into()?)
     }
 
+    async fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<ObjectHistoryPage> {
+        let limit = cap_page_limit(limit);
+        let descending = descending_order.unwrap_or_default();
+
+        // Retrieve 1 extra item for next cursor
+        let mut data = self
+            .state
+            .get_object_history(object_id, cursor, limit + 1, descending)
+            .map_err(|e| anyhow!("{e}"))?;
+
+        // extract next cursor
+        let next_cursor = data.get(limit).map(|version| version.version);
+        data.truncate(limit);
+        Ok(ObjectHistoryPage { data, next_cursor })
+    }
+
     fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<CheckpointSequenceNumber> {
         Ok(self
             .state
@@ -8,13 +8,14 @@ use jsonrpsee::core::RpcResult;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
-    GetRawObjectDataResponse, SuiObjectInfo, SuiTransactionBuilderMode, SuiTypeTag,
+    Coin, GetRawObjectDataResponse, SuiObjectInfo, SuiTransactionBuilderMode, SuiTypeTag,
     TransactionBytes,
 };
 use sui_open_rpc::Module;
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{ObjectID, ObjectType, SuiAddress},
+    gas_coin::{GasCoin, GAS},
     messages::TransactionData,
 };
 
@@ -23,7 +24,10 @@ use jsonrpsee::RpcModule;
 use sui_adapter::execution_mode::{DevInspect, Normal};
 
 use sui_json::SuiJsonValue;
-use sui_json_rpc_types::RPCTransactionRequestParams;
+use sui_json_rpc_types::{
+    ProgrammableTransactionCommandParams, ProgrammableTransactionInputParams,
+    RPCTransactionRequestParams,
+};
 
 pub struct FullNodeTransactionBuilderApi {
     builder: TransactionBuilder<Normal>,
@@ -71,6 +75,30 @@ impl DataReader for AuthorityStateDataReader {
         Ok(result.try_into()?)
     }
 
+    async fn get_gas_coins(&self, address: SuiAddress) -> Result<Vec<Coin>, anyhow::Error> {
+        let gas_type = GasCoin::type_();
+        let object_ids: Vec<_> = self
+            .0
+            .get_owner_objects(address)?
+            .into_iter()
+            .filter(|info| matches!(&info.type_, ObjectType::Struct(type_) if type_ == &gas_type))
+            .map(|info| info.object_id)
+            .collect();
+        let mut coins = vec![];
+        for object in self.0.get_objects(&object_ids).await?.into_iter().flatten() {
+            coins.push(Coin {
+                coin_type: GAS::type_().to_string(),
+                coin_object_id: object.id(),
+                version: object.version(),
+                digest: object.digest(),
+                balance: GasCoin::try_from(&object)?.value(),
+                locked_until_epoch: None,
+                previous_transaction: object.previous_transaction,
+            });
+        }
+        Ok(coins)
+    }
+
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.0.get_sui_system_state_object()?.reference_gas_price)
     }
@@ -286,6 +314,21 @@ impl RpcTransactionBuilderServer for FullNodeTransactionBuilderApi {
         Ok(TransactionBytes::from_data(data)?)
     }
 
+    async fn programmable_transaction(
+        &self,
+        signer: SuiAddress,
+        inputs: Vec<ProgrammableTransactionInputParams>,
+        commands: Vec<ProgrammableTransactionCommandParams>,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> RpcResult<TransactionBytes> {
+        Ok(TransactionBytes::from_data(
+            self.builder
+                .programmable_transaction(signer, inputs, commands, gas, gas_budget)
+                .await?,
+        )?)
+    }
+
     async fn request_add_delegation(
         &self,
         signer: SuiAddress,
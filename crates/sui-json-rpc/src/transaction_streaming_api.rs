@@ -0,0 +1,69 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::{RpcModule, SubscriptionSink};
+
+use sui_core::authority::AuthorityState;
+use sui_core::streamer::Streamer;
+use sui_json_rpc_types::{SuiTransactionEffects, SuiTransactionResponse};
+use sui_open_rpc::Module;
+use sui_types::error::SuiResult;
+use sui_types::filter::TransactionFilter;
+use sui_types::messages::ExecutedTransaction;
+
+use crate::api::TransactionStreamingApiServer;
+use crate::event_api::spawn_subscription;
+use crate::SuiRpcModule;
+
+pub struct TransactionStreamingApiImpl {
+    state: Arc<AuthorityState>,
+    transaction_streamer: Arc<Streamer<ExecutedTransaction, TransactionFilter>>,
+}
+
+impl TransactionStreamingApiImpl {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        transaction_streamer: Arc<Streamer<ExecutedTransaction, TransactionFilter>>,
+    ) -> Self {
+        Self {
+            state,
+            transaction_streamer,
+        }
+    }
+}
+
+impl TransactionStreamingApiServer for TransactionStreamingApiImpl {
+    fn subscribe_transaction(
+        &self,
+        sink: SubscriptionSink,
+        filter: TransactionFilter,
+    ) -> SubscriptionResult {
+        let state = self.state.clone();
+        let stream = self.transaction_streamer.subscribe(filter);
+        let stream = stream.map(move |tx: SuiResult<ExecutedTransaction>| {
+            let tx = tx?;
+            Ok::<_, anyhow::Error>(SuiTransactionResponse {
+                certificate: tx.certificate.try_into()?,
+                effects: SuiTransactionEffects::try_from(tx.effects, state.module_cache.as_ref())?,
+                timestamp_ms: Some(tx.timestamp_ms),
+                parsed_data: None,
+            })
+        });
+        spawn_subscription(sink, Box::pin(stream));
+        Ok(())
+    }
+}
+
+impl SuiRpcModule for TransactionStreamingApiImpl {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::TransactionStreamingApiOpenRpc::module_doc()
+    }
+}
@@ -8,6 +8,7 @@ use std::path::Path;
 
 #[cfg(not(msim))]
 use std::str::FromStr;
+use sui_config::node::default_json_rpc_multi_get_max_size;
 use sui_config::SUI_KEYSTORE_FILENAME;
 use sui_framework_build::compiled_package::BuildConfig;
 use sui_json::SuiJsonValue;
@@ -15,7 +16,7 @@ use sui_json_rpc_types::SuiObjectInfo;
 use sui_json_rpc_types::{
     Balance, CoinPage, GetObjectDataResponse, SuiCoinMetadata, SuiEvent,
     SuiExecuteTransactionResponse, SuiExecutionStatus, SuiTBlsSignObjectCommitmentType,
-    SuiTransactionResponse, TransactionBytes,
+    SuiTransactionRead, SuiTransactionResponse, TransactionBytes,
 };
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_types::balance::Supply;
@@ -331,12 +332,25 @@ async fn test_get_object_info() -> Result<(), anyhow::Error> {
     let address = cluster.accounts.first().unwrap();
     let objects = http_client.get_objects_owned_by_address(*address).await?;
 
-    for oref in objects {
+    let object_ids: Vec<ObjectID> = objects.iter().map(|oref| oref.object_id).collect();
+    let results = http_client.multi_get_objects(object_ids.clone()).await?;
+    assert_eq!(objects.len(), results.len());
+
+    for (oref, multi_result) in objects.into_iter().zip(results) {
         let result: GetObjectDataResponse = http_client.get_object(oref.object_id).await?;
+        assert_eq!(result.object()?.reference, multi_result.object()?.reference);
         assert!(
             matches!(result, GetObjectDataResponse::Exists(object) if oref.object_id == object.id() && &object.owner.get_owner_address()? == address)
         );
     }
+
+    // Requests over the batch size limit are rejected
+    let too_many = object_ids
+        .into_iter()
+        .cycle()
+        .take(default_json_rpc_multi_get_max_size() + 1)
+        .collect();
+    assert!(http_client.multi_get_objects(too_many).await.is_err());
     Ok(())
 }
 
@@ -388,10 +402,10 @@ async fn test_get_balance() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client();
     let address = cluster.accounts.first().unwrap();
 
-    let result: Balance = http_client.get_balance(*address, None).await?;
+    let result: Balance = http_client.get_balance(*address, None, None).await?;
     assert_eq!("0x2::sui::SUI", result.coin_type);
     assert_eq!(500000000000000, result.total_balance);
-    assert_eq!(5, result.coin_object_count);
+    assert_eq!(Some(5), result.coin_object_count);
 
     Ok(())
 }
@@ -615,9 +629,20 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
     let tx: Vec<TransactionDigest> = http_client.get_transactions_in_range(1, 3).await?;
     assert_eq!(2, tx.len());
 
+    // test multi_get_transactions, with an unknown digest reported per item
+    let unknown_digest = TransactionDigest::random();
+    let mut digests = tx.clone();
+    digests.push(unknown_digest);
+    let mut responses = http_client.multi_get_transactions(digests).await?;
+    assert_eq!(tx.len() + 1, responses.len());
+    assert!(
+        matches!(responses.pop(), Some(SuiTransactionRead::NotExists(digest)) if digest == unknown_digest)
+    );
+
     // test get_transaction
-    for tx_digest in tx {
+    for (tx_digest, multi_response) in tx.into_iter().zip(responses) {
         let response: SuiTransactionResponse = http_client.get_transaction(tx_digest).await?;
+        assert_eq!(response.effects, multi_response.into_transaction()?.effects);
         assert!(tx_responses.iter().any(
             |resp| matches!(resp, SuiExecuteTransactionResponse {effects, ..} if effects.effects.transaction_digest == response.effects.transaction_digest)
         ))
@@ -917,7 +942,7 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
     assert_eq!(5, objects.len());
     // verify coins and balance before test
     let coins: CoinPage = http_client.get_coins(*address, None, None, None).await?;
-    let balance: Vec<Balance> = http_client.get_all_balances(*address).await?;
+    let balance: Vec<Balance> = http_client.get_all_balances(*address, None).await?;
 
     assert_eq!(5, coins.data.len());
     for coin in &coins.data {
@@ -925,7 +950,7 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
     }
 
     assert_eq!(1, balance.len());
-    assert!(balance[0].locked_balance.is_empty());
+    assert!(balance[0].locked_balance.as_ref().unwrap().is_empty());
 
     // lock one coin
     let transaction_bytes: TransactionBytes = http_client
@@ -959,15 +984,16 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
         )
         .await?;
 
-    let balances: Vec<Balance> = http_client.get_all_balances(*address).await?;
+    let balances: Vec<Balance> = http_client.get_all_balances(*address, None).await?;
 
     assert_eq!(1, balance.len());
 
     let balance = balances.first().unwrap();
 
-    assert_eq!(5, balance.coin_object_count);
-    assert_eq!(1, balance.locked_balance.len());
-    assert!(balance.locked_balance.contains_key(&20));
+    assert_eq!(Some(5), balance.coin_object_count);
+    let locked_balance = balance.locked_balance.as_ref().unwrap();
+    assert_eq!(1, locked_balance.len());
+    assert!(locked_balance.contains_key(&20));
 
     Ok(())
 }
@@ -0,0 +1,155 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::anyhow;
+use argon2::{Algorithm, Argon2, Params, Version};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+pub const KEYSTORE_ENCRYPTION_VERSION: u8 = 1;
+const ENCRYPTION_KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+pub type EncryptionKey = Zeroizing<[u8; ENCRYPTION_KEY_LENGTH]>;
+
+/// Whether each key pair is sealed on its own, or the whole key list is sealed as one blob.
+/// Per-key encryption keeps the public keys readable, so addresses can be listed without the
+/// password.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EncryptionMode {
+    PerKey,
+    WholeFile,
+}
+
+/// Argon2id parameters used to derive the encryption key from the password.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub salt: String,
+}
+
+impl KdfParams {
+    pub fn new_with_random_salt() -> Self {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+            salt: Base64::encode(salt),
+        }
+    }
+
+    pub fn derive_key(&self, password: &str) -> Result<EncryptionKey, anyhow::Error> {
+        let params = Params::new(
+            self.m_cost,
+            self.t_cost,
+            self.p_cost,
+            Some(ENCRYPTION_KEY_LENGTH),
+        )
+        .map_err(|e| anyhow!("Invalid keystore KDF parameters: {e}"))?;
+        let salt = Base64::decode(&self.salt)
+            .map_err(|e| anyhow!("Invalid keystore KDF salt: {:?}", e))?;
+        let mut key = Zeroizing::new([0u8; ENCRYPTION_KEY_LENGTH]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), &salt, &mut key[..])
+            .map_err(|e| anyhow!("Cannot derive keystore encryption key: {e}"))?;
+        Ok(key)
+    }
+}
+
+/// An AES-256-GCM ciphertext with its nonce, both Base64 encoded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SealedData {
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl SealedData {
+    pub fn seal(key: &EncryptionKey, plaintext: &[u8], aad: &[u8]) -> Result<Self, anyhow::Error> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new_from_slice(&key[..])
+            .map_err(|e| anyhow!("Invalid keystore encryption key: {e}"))?
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|_| anyhow!("Cannot encrypt keystore"))?;
+        Ok(Self {
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    pub fn open(
+        &self,
+        key: &EncryptionKey,
+        aad: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+        let nonce = Base64::decode(&self.nonce)
+            .ok()
+            .filter(|nonce| nonce.len() == NONCE_LENGTH)
+            .ok_or_else(|| anyhow!("Invalid keystore nonce"))?;
+        let ciphertext = Base64::decode(&self.ciphertext)
+            .map_err(|e| anyhow!("Invalid keystore ciphertext: {:?}", e))?;
+        Aes256Gcm::new_from_slice(&key[..])
+            .map_err(|e| anyhow!("Invalid keystore encryption key: {e}"))?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad,
+                },
+            )
+            .map(Zeroizing::new)
+            // AES-GCM does not tell a wrong password apart from a tampered file.
+            .map_err(|_| anyhow!("Cannot decrypt keystore: wrong password or corrupted file"))
+    }
+}
+
+/// A key pair sealed under its Base64 encoded public key, which is also used as the AAD.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedKey {
+    pub public_key: String,
+    #[serde(flatten)]
+    pub sealed: SealedData,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum EncryptedContent {
+    PerKey { keys: Vec<EncryptedKey> },
+    WholeFile(SealedData),
+}
+
+impl EncryptedContent {
+    pub fn mode(&self) -> EncryptionMode {
+        match self {
+            EncryptedContent::PerKey { .. } => EncryptionMode::PerKey,
+            EncryptedContent::WholeFile(_) => EncryptionMode::WholeFile,
+        }
+    }
+}
+
+/// On-disk format of an encrypted keystore. Plaintext keystores remain a JSON array of
+/// Base64 encoded key pairs, so the two formats can be told apart when loading.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedKeystoreFile {
+    pub version: u8,
+    pub kdf: KdfParams,
+    #[serde(flatten)]
+    pub content: EncryptedContent,
+}
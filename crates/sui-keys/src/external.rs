@@ -0,0 +1,203 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A keystore that keeps no keys itself and forwards signing to an external signer process.
+//!
+//! The signer listens on a Unix domain socket. For every call the keystore opens a connection,
+//! writes one [SignerRequest] as a line of JSON and reads back one [SignerResponse] line:
+//!
+//! ```text
+//! {"method":"keys"}
+//!   -> {"result":{"keys":["<Base64 flag || pk>", ...]}}
+//! {"method":"sign_intent_message","params":{"address":"0x...","intent_message":"<Base64 bytes>"}}
+//!   -> {"result":{"signature":"<Base64 flag || sig || pk>"}}
+//! ```
+//!
+//! Failures are answered with `{"error":"<reason>"}`. The intent message is BCS serialized, so
+//! the signer can decode the intent and the transaction before deciding to sign, and the
+//! returned signature is verified against it. Raw bytes are never sent for signing: the
+//! deprecated `sign` of [AccountKeystore] is not supported.
+
+use anyhow::anyhow;
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, Signature, SuiKeyPair, SuiSignature};
+use sui_types::intent::{Intent, IntentMessage};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::warn;
+
+use crate::keystore::AccountKeystore;
+
+/// How long to wait for the signer, which may ask its operator to approve a request.
+const SIGNER_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// List the public keys the signer can sign for.
+    Keys,
+    /// Sign the Base64 encoded BCS bytes of an [IntentMessage] with the key of `address`.
+    SignIntentMessage {
+        address: SuiAddress,
+        intent_message: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerResponse {
+    Result(SignerResult),
+    Error(String),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SignerResult {
+    Keys { keys: Vec<String> },
+    Signature { signature: Signature },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExternalKeystore {
+    socket_path: PathBuf,
+}
+
+impl ExternalKeystore {
+    pub fn new(socket_path: PathBuf) -> Self {
+        Self { socket_path }
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Lists the public keys of the signer, failing if it cannot be reached or answers with an
+    /// invalid key.
+    pub fn try_keys(&self) -> Result<Vec<PublicKey>, anyhow::Error> {
+        match self.request(&SignerRequest::Keys)? {
+            SignerResult::Keys { keys } => keys
+                .iter()
+                .map(|key| {
+                    PublicKey::decode_base64(key)
+                        .map_err(|e| anyhow!("Invalid key [{key}] from external signer: {e}"))
+                })
+                .collect(),
+            SignerResult::Signature { .. } => Err(anyhow!(
+                "Invalid response from external signer: expected keys"
+            )),
+        }
+    }
+
+    /// Sends `request` to the signer, which may wait for its operator to answer. Within a
+    /// multi-threaded Tokio runtime, as in the CLI, the exchange runs in `block_in_place` so that
+    /// the other tasks of the runtime are not blocked meanwhile.
+    fn request(&self, request: &SignerRequest) -> Result<SignerResult, anyhow::Error> {
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| self.request_blocking(request))
+            }
+            _ => self.request_blocking(request),
+        }
+    }
+
+    #[cfg(unix)]
+    fn request_blocking(&self, request: &SignerRequest) -> Result<SignerResult, anyhow::Error> {
+        use std::io::{BufRead, BufReader, Write};
+        use std::os::unix::net::UnixStream;
+
+        let mut stream = UnixStream::connect(&self.socket_path).map_err(|e| {
+            anyhow!(
+                "Cannot connect to external signer at {:?}: {e}",
+                self.socket_path
+            )
+        })?;
+        stream.set_read_timeout(Some(SIGNER_TIMEOUT))?;
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        stream.write_all(line.as_bytes())?;
+
+        let mut response = String::new();
+        BufReader::new(stream).read_line(&mut response)?;
+        match serde_json::from_str(&response)
+            .map_err(|e| anyhow!("Invalid response from external signer: {e}"))?
+        {
+            SignerResponse::Result(result) => Ok(result),
+            SignerResponse::Error(error) => Err(anyhow!("External signer error: {error}")),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn request_blocking(&self, _request: &SignerRequest) -> Result<SignerResult, anyhow::Error> {
+        Err(anyhow!("External signers require Unix domain sockets"))
+    }
+
+    fn sign_intent_message(
+        &self,
+        address: &SuiAddress,
+        intent_message: &[u8],
+    ) -> Result<Signature, anyhow::Error> {
+        match self.request(&SignerRequest::SignIntentMessage {
+            address: *address,
+            intent_message: Base64::encode(intent_message),
+        })? {
+            SignerResult::Signature { signature } => Ok(signature),
+            SignerResult::Keys { .. } => Err(anyhow!(
+                "Invalid response from external signer: expected a signature"
+            )),
+        }
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    #[warn(deprecated)]
+    fn sign(&self, _address: &SuiAddress, _msg: &[u8]) -> Result<Signature, signature::Error> {
+        Err(signature::Error::from_source(
+            "External signers only sign intent messages",
+        ))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent, msg);
+        let message = bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?;
+        let signature = self
+            .sign_intent_message(address, &message)
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        // Do not hand out a signature the network would reject.
+        signature
+            .verify_secure(&intent_msg, *address)
+            .map_err(signature::Error::from_source)?;
+        Ok(signature)
+    }
+
+    fn add_key(&mut self, _keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        Err(anyhow!(
+            "Keys of an external signer cannot be added from the client"
+        ))
+    }
+
+    /// Asks the signer for its keys. A signer that cannot list them has no keys, the error is
+    /// logged: use [ExternalKeystore::try_keys] to get it instead.
+    fn keys(&self) -> Vec<PublicKey> {
+        self.try_keys().unwrap_or_else(|e| {
+            warn!(socket_path = ?self.socket_path, "Cannot list the external signer keys: {e}");
+            vec![]
+        })
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        Err(anyhow!(
+            "Private key for address [{address}] is held by an external signer"
+        ))
+    }
+}
@@ -21,13 +21,25 @@ use sui_types::crypto::{
     SignatureScheme, Signer, SuiKeyPair,
 };
 
+use crate::encryption::{
+    EncryptedContent, EncryptedKey, EncryptedKeystoreFile, EncryptionKey, EncryptionMode,
+    KdfParams, SealedData, KEYSTORE_ENCRYPTION_VERSION,
+};
+use crate::external::ExternalKeystore;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 
+/// Environment variable read for the keystore password before falling back to a prompt.
+pub const SUI_KEYSTORE_PASSWORD_ENV: &str = "SUI_KEYSTORE_PASSWORD";
+/// Environment variable read for the new password when encrypting the keystore or changing its
+/// password, before falling back to a prompt.
+pub const SUI_KEYSTORE_NEW_PASSWORD_ENV: &str = "SUI_KEYSTORE_NEW_PASSWORD";
+
 #[derive(Serialize, Deserialize)]
 #[enum_dispatch(AccountKeystore)]
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -78,6 +90,88 @@ pub trait AccountKeystore: Send + Sync {
     }
 }
 
+impl Keystore {
+    /// Whether the keystore is encrypted and has not been unlocked yet.
+    pub fn is_locked(&self) -> bool {
+        match self {
+            Keystore::File(file) => file.is_locked(),
+            Keystore::InMem(_) | Keystore::External(_) => false,
+        }
+    }
+
+    pub fn unlock(&mut self, password: &str) -> Result<(), anyhow::Error> {
+        match self {
+            Keystore::File(file) => file.unlock(password),
+            Keystore::InMem(_) | Keystore::External(_) => Ok(()),
+        }
+    }
+
+    /// Unlocks the keystore if it is locked, asking for the password via [read_password].
+    pub fn ensure_unlocked(&mut self) -> Result<(), anyhow::Error> {
+        if self.is_locked() {
+            let password = read_password("Enter keystore password: ")?;
+            self.unlock(&password)?;
+        }
+        Ok(())
+    }
+
+    /// Same as [AccountKeystore::addresses], unlocking the keystore first if it lists no address.
+    /// Whole-file encrypted keystores do not reveal any address until unlocked.
+    pub fn unlocked_addresses(&mut self) -> Result<Vec<SuiAddress>, anyhow::Error> {
+        if self.addresses().is_empty() {
+            self.ensure_unlocked()?;
+        }
+        Ok(self.addresses())
+    }
+
+    /// Same as [AccountKeystore::addresses], but fails when the keys cannot be listed, e.g. when
+    /// an external signer is unreachable, instead of listing no address.
+    pub fn try_addresses(&self) -> Result<Vec<SuiAddress>, anyhow::Error> {
+        match self {
+            Keystore::External(external) => {
+                Ok(external.try_keys()?.iter().map(|key| key.into()).collect())
+            }
+            Keystore::File(_) | Keystore::InMem(_) => Ok(self.addresses()),
+        }
+    }
+
+    /// Same as [AccountKeystore::sign_secure], unlocking the keystore first if it is locked.
+    pub fn unlock_and_sign_secure<T>(
+        &mut self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, anyhow::Error>
+    where
+        T: Serialize,
+    {
+        self.ensure_unlocked()?;
+        Ok(self.sign_secure(address, msg, intent)?)
+    }
+}
+
+/// Reads the keystore password from [SUI_KEYSTORE_PASSWORD_ENV] if set, otherwise prompts for it
+/// on the terminal without echoing.
+pub fn read_password(prompt: &str) -> Result<String, anyhow::Error> {
+    match std::env::var(SUI_KEYSTORE_PASSWORD_ENV) {
+        Ok(password) => Ok(password),
+        Err(_) => Ok(rpassword::prompt_password(prompt)?),
+    }
+}
+
+/// Reads a new keystore password from [SUI_KEYSTORE_NEW_PASSWORD_ENV] if set, otherwise prompts
+/// for it twice on the terminal without echoing.
+pub fn read_new_password() -> Result<String, anyhow::Error> {
+    if let Ok(password) = std::env::var(SUI_KEYSTORE_NEW_PASSWORD_ENV) {
+        return Ok(password);
+    }
+    let password = rpassword::prompt_password("Enter new keystore password: ")?;
+    if password != rpassword::prompt_password("Confirm new keystore password: ")? {
+        return Err(anyhow!("Passwords do not match"));
+    }
+    Ok(password)
+}
+
 impl Display for Keystore {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
@@ -91,6 +185,11 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Signer Socket : {:?}", external.socket_path())?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -99,6 +198,30 @@ impl Display for Keystore {
 pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
     path: Option<PathBuf>,
+    encryption: Option<KeystoreEncryption>,
+}
+
+/// Encryption state of a password protected [FileBasedKeystore].
+struct KeystoreEncryption {
+    kdf: KdfParams,
+    mode: EncryptionMode,
+    /// Derived from the password on unlock, `None` while the keystore is locked.
+    key: Option<EncryptionKey>,
+    /// Content as read from disk, only used while the keystore is locked.
+    sealed: EncryptedContent,
+}
+
+/// A keystore file is either the legacy JSON array of Base64 encoded key pairs, or an
+/// [EncryptedKeystoreFile].
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeystoreFile {
+    Plain(Vec<String>),
+    Encrypted(EncryptedKeystoreFile),
+}
+
+fn locked_error() -> String {
+    "Keystore is locked, unlock it with the keystore password first".to_string()
 }
 
 impl Serialize for FileBasedKeystore {
@@ -130,6 +253,9 @@ impl<'de> Deserialize<'de> for FileBasedKeystore {
 impl AccountKeystore for FileBasedKeystore {
     #[warn(deprecated)]
     fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        if self.is_locked() {
+            return Err(signature::Error::from_source(locked_error()));
+        }
         Ok(self
             .keys
             .get(address)
@@ -148,6 +274,9 @@ impl AccountKeystore for FileBasedKeystore {
     where
         T: Serialize,
     {
+        if self.is_locked() {
+            return Err(signature::Error::from_source(locked_error()));
+        }
         Ok(Signature::new_secure(
             &IntentMessage::new(intent, msg),
             self.keys.get(address).ok_or_else(|| {
@@ -157,6 +286,9 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        if self.is_locked() {
+            return Err(anyhow!(locked_error()));
+        }
         let address: SuiAddress = (&keypair.public()).into();
         self.keys.insert(address, keypair);
         self.save()?;
@@ -164,10 +296,24 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn keys(&self) -> Vec<PublicKey> {
-        self.keys.values().map(|key| key.public()).collect()
+        match &self.encryption {
+            // Per-key encryption leaves the public keys readable while locked.
+            Some(KeystoreEncryption {
+                key: None,
+                sealed: EncryptedContent::PerKey { keys },
+                ..
+            }) => keys
+                .iter()
+                .filter_map(|key| PublicKey::decode_base64(&key.public_key).ok())
+                .collect(),
+            _ => self.keys.values().map(|key| key.public()).collect(),
+        }
     }
 
     fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        if self.is_locked() {
+            return Err(anyhow!(locked_error()));
+        }
         match self.keys.get(address) {
             Some(key) => Ok(key),
             None => Err(anyhow!("Cannot find key for address: [{address}]")),
@@ -177,42 +323,168 @@ impl AccountKeystore for FileBasedKeystore {
 
 impl FileBasedKeystore {
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
-        let keys = if path.exists() {
+        let (keys, encryption) = if path.exists() {
             let reader = BufReader::new(File::open(path)?);
-            let kp_strings: Vec<String> = serde_json::from_reader(reader)?;
-            kp_strings
-                .iter()
-                .map(|kpstr| {
-                    let key = SuiKeyPair::decode_base64(kpstr);
-                    key.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| anyhow::anyhow!("Invalid Keypair file {:#?} {:?}", e, path))?
+            match serde_json::from_reader(reader)? {
+                KeystoreFile::Plain(kp_strings) => (decode_key_pairs(&kp_strings, path)?, None),
+                KeystoreFile::Encrypted(file) => {
+                    if file.version != KEYSTORE_ENCRYPTION_VERSION {
+                        return Err(anyhow!(
+                            "Unsupported keystore encryption version {} in {:?}",
+                            file.version,
+                            path
+                        ));
+                    }
+                    // Key pairs are only decrypted on unlock.
+                    let encryption = KeystoreEncryption {
+                        kdf: file.kdf,
+                        mode: file.content.mode(),
+                        key: None,
+                        sealed: file.content,
+                    };
+                    (BTreeMap::new(), Some(encryption))
+                }
+            }
         } else {
-            BTreeMap::new()
+            (BTreeMap::new(), None)
         };
 
         Ok(Self {
             keys,
             path: Some(path.to_path_buf()),
+            encryption,
+        })
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
+    pub fn encryption_mode(&self) -> Option<EncryptionMode> {
+        self.encryption.as_ref().map(|encryption| encryption.mode)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        matches!(&self.encryption, Some(encryption) if encryption.key.is_none())
+    }
+
+    /// Derives the encryption key from `password` and decrypts all key pairs. This is a no-op
+    /// for plaintext or already unlocked keystores.
+    pub fn unlock(&mut self, password: &str) -> Result<(), anyhow::Error> {
+        let Some(encryption) = self.encryption.as_mut().filter(|e| e.key.is_none()) else {
+            return Ok(());
+        };
+        let path = self.path.clone().unwrap_or_default();
+        let key = encryption.kdf.derive_key(password)?;
+        let keys = match &encryption.sealed {
+            EncryptedContent::PerKey { keys } => keys
+                .iter()
+                .map(|encrypted| {
+                    let plaintext = encrypted
+                        .sealed
+                        .open(&key, encrypted.public_key.as_bytes())?;
+                    let kp = SuiKeyPair::decode_base64(std::str::from_utf8(&plaintext)?)
+                        .map_err(|e| anyhow!("Invalid Keypair file {:#?} {:?}", e, path))?;
+                    if kp.public().encode_base64() != encrypted.public_key {
+                        return Err(anyhow!(
+                            "Decrypted key pair does not match public key {}",
+                            encrypted.public_key
+                        ));
+                    }
+                    Ok(((&kp.public()).into(), kp))
+                })
+                .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?,
+            EncryptedContent::WholeFile(sealed) => {
+                let plaintext = sealed.open(&key, &[])?;
+                let kp_strings: Vec<String> = serde_json::from_slice(&plaintext)?;
+                decode_key_pairs(&kp_strings, &path)?
+            }
+        };
+        self.keys = keys;
+        encryption.key = Some(key);
+        Ok(())
+    }
+
+    /// Encrypts the keystore with a key derived from `password` and rewrites the file. An
+    /// encrypted keystore must be unlocked first, after which this changes its password or mode.
+    pub fn encrypt(&mut self, password: &str, mode: EncryptionMode) -> Result<(), anyhow::Error> {
+        if self.is_locked() {
+            return Err(anyhow!(locked_error()));
+        }
+        let kdf = KdfParams::new_with_random_salt();
+        let key = kdf.derive_key(password)?;
+        let sealed = self.seal_keys(&key, mode)?;
+        self.encryption = Some(KeystoreEncryption {
+            kdf,
+            mode,
+            key: Some(key),
+            sealed,
+        });
+        self.save()
+    }
+
+    fn seal_keys(
+        &self,
+        key: &EncryptionKey,
+        mode: EncryptionMode,
+    ) -> Result<EncryptedContent, anyhow::Error> {
+        Ok(match mode {
+            EncryptionMode::PerKey => EncryptedContent::PerKey {
+                keys: self
+                    .keys
+                    .values()
+                    .map(|kp| {
+                        let public_key = kp.public().encode_base64();
+                        let sealed = SealedData::seal(
+                            key,
+                            kp.encode_base64().as_bytes(),
+                            public_key.as_bytes(),
+                        )?;
+                        Ok(EncryptedKey { public_key, sealed })
+                    })
+                    .collect::<Result<_, anyhow::Error>>()?,
+            },
+            EncryptionMode::WholeFile => EncryptedContent::WholeFile(SealedData::seal(
+                key,
+                serde_json::to_string(&self.encoded_key_pairs())?.as_bytes(),
+                &[],
+            )?),
         })
     }
 
+    fn encoded_key_pairs(&self) -> Vec<String> {
+        self.keys
+            .values()
+            .map(EncodeDecodeBase64::encode_base64)
+            .collect()
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn set_path(&mut self, path: &Path) {
         self.path = Some(path.to_path_buf());
     }
 
     pub fn save(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
-            let store = serde_json::to_string_pretty(
-                &self
-                    .keys
-                    .values()
-                    .map(EncodeDecodeBase64::encode_base64)
-                    .collect::<Vec<_>>(),
-            )
-            .unwrap();
-            fs::write(path, store)?
+            let store = match &self.encryption {
+                None => serde_json::to_string_pretty(&self.encoded_key_pairs()).unwrap(),
+                Some(encryption) => {
+                    let key = encryption
+                        .key
+                        .as_ref()
+                        .ok_or_else(|| anyhow!(locked_error()))?;
+                    serde_json::to_string_pretty(&EncryptedKeystoreFile {
+                        version: KEYSTORE_ENCRYPTION_VERSION,
+                        kdf: encryption.kdf.clone(),
+                        content: self.seal_keys(key, encryption.mode)?,
+                    })
+                    .unwrap()
+                }
+            };
+            write_private_file(path, store.as_bytes())?
         }
         Ok(())
     }
@@ -222,6 +494,46 @@ impl FileBasedKeystore {
     }
 }
 
+/// Replaces the file at `path` with `contents`, readable and writable by its owner only. The
+/// contents are written and synced to a temporary file next to `path`, which is then renamed
+/// over it, so that a crash never leaves a truncated file behind.
+pub fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid file path {:?}", path))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let tmp_path = dir.join(format!(".{}.tmp", file_name.to_string_lossy()));
+    let mut file = File::create(&tmp_path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    std::io::Write::write_all(&mut file, contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+fn decode_key_pairs(
+    kp_strings: &[String],
+    path: &Path,
+) -> Result<BTreeMap<SuiAddress, SuiKeyPair>, anyhow::Error> {
+    kp_strings
+        .iter()
+        .map(|kpstr| {
+            let key = SuiKeyPair::decode_base64(kpstr);
+            key.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()
+        .map_err(|e| anyhow::anyhow!("Invalid Keypair file {:#?} {:?}", e, path))
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct InMemKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
@@ -0,0 +1,211 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, ensure};
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{Signature, SuiSignature};
+use sui_types::intent::{Intent, IntentMessage};
+use sui_types::messages::TransactionData;
+use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit};
+use sui_types::signature::GenericSignature;
+
+use crate::keystore::{write_private_file, Keystore};
+
+pub const SUI_MULTISIG_FILENAME: &str = "sui.multisig";
+
+/// Named multisig accounts, stored as JSON in [SUI_MULTISIG_FILENAME] next to the keystore.
+#[derive(Default, Serialize, Deserialize)]
+pub struct MultiSigAccounts {
+    accounts: BTreeMap<String, MultiSigPublicKey>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl MultiSigAccounts {
+    pub fn new(path: &Path) -> Result<Self, anyhow::Error> {
+        let mut accounts = if path.exists() {
+            serde_json::from_slice(&fs::read(path)?)
+                .map_err(|e| anyhow!("Invalid multisig accounts file {:?}: {e}", path))?
+        } else {
+            Self::default()
+        };
+        accounts.path = Some(path.to_path_buf());
+        Ok(accounts)
+    }
+
+    /// Path of the accounts file belonging to a file based keystore, `None` for other keystores.
+    pub fn path_for_keystore(keystore: &Keystore) -> Option<PathBuf> {
+        match keystore {
+            Keystore::File(file) => file
+                .path()
+                .map(|path| path.with_file_name(SUI_MULTISIG_FILENAME)),
+            _ => None,
+        }
+    }
+
+    pub fn add(
+        &mut self,
+        name: String,
+        multisig_pk: MultiSigPublicKey,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        ensure!(
+            !self.accounts.contains_key(&name),
+            "Multisig account [{name}] already exists"
+        );
+        let address: SuiAddress = multisig_pk.clone().into();
+        ensure!(
+            self.find_by_address(&address).is_none(),
+            "Multisig account with address [{address}] already exists"
+        );
+        self.accounts.insert(name, multisig_pk);
+        self.save()?;
+        Ok(address)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<MultiSigPublicKey, anyhow::Error> {
+        let multisig_pk = self
+            .accounts
+            .remove(name)
+            .ok_or_else(|| anyhow!("Multisig account [{name}] not found"))?;
+        self.save()?;
+        Ok(multisig_pk)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&MultiSigPublicKey> {
+        self.accounts.get(name)
+    }
+
+    pub fn find_by_address(&self, address: &SuiAddress) -> Option<(&str, &MultiSigPublicKey)> {
+        self.iter()
+            .find(|(_, multisig_pk)| SuiAddress::from((*multisig_pk).clone()) == *address)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &MultiSigPublicKey)> {
+        self.accounts
+            .iter()
+            .map(|(name, multisig_pk)| (name.as_str(), multisig_pk))
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            write_private_file(path, serde_json::to_string_pretty(self)?.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// A transaction sent from a multisig account, together with the signatures collected so far.
+/// The bundle file is passed between the signers, each adding a signature, until the combined
+/// weight meets the threshold and the transaction can be executed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PartialSigBundle {
+    pub multisig_pk: MultiSigPublicKey,
+    /// Base64 encoded BCS serialized [TransactionData], signed with the default [Intent].
+    pub tx_bytes: String,
+    pub signatures: Vec<Signature>,
+}
+
+impl PartialSigBundle {
+    pub fn new(
+        multisig_pk: MultiSigPublicKey,
+        data: &TransactionData,
+    ) -> Result<Self, anyhow::Error> {
+        ensure!(
+            data.signer() == SuiAddress::from(multisig_pk.clone()),
+            "Transaction sender [{}] is not the multisig address",
+            data.signer()
+        );
+        Ok(Self {
+            multisig_pk,
+            tx_bytes: Base64::encode(bcs::to_bytes(data)?),
+            signatures: vec![],
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        serde_json::from_slice(&fs::read(path)?)
+            .map_err(|e| anyhow!("Invalid partial signature bundle {:?}: {e}", path))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        write_private_file(path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    pub fn address(&self) -> SuiAddress {
+        self.multisig_pk.clone().into()
+    }
+
+    pub fn transaction_data(&self) -> Result<TransactionData, anyhow::Error> {
+        let bytes = Base64::decode(&self.tx_bytes)
+            .map_err(|e| anyhow!("Invalid transaction bytes in bundle: {:?}", e))?;
+        Ok(bcs::from_bytes(&bytes)?)
+    }
+
+    /// Members of the multisig account that have not signed yet.
+    pub fn missing_signers(&self) -> Vec<SuiAddress> {
+        let signed = self
+            .signatures
+            .iter()
+            .filter_map(|sig| sig.to_public_key().ok())
+            .collect::<Vec<_>>();
+        self.multisig_pk
+            .pubkeys()
+            .iter()
+            .filter(|(pk, _)| !signed.contains(pk))
+            .map(|(pk, _)| pk.into())
+            .collect()
+    }
+
+    /// Adds a member's signature after checking it against the transaction. A later signature
+    /// of the same member replaces the earlier one.
+    pub fn add_signature(&mut self, signature: Signature) -> Result<(), anyhow::Error> {
+        let pk = signature.to_public_key()?;
+        ensure!(
+            self.multisig_pk.get_index(pk.clone()).is_some(),
+            "Signer [{}] is not a member of multisig account [{}]",
+            SuiAddress::from(&pk),
+            self.address()
+        );
+        let intent_msg = IntentMessage::new(Intent::default(), self.transaction_data()?);
+        signature.verify_secure(&intent_msg, (&pk).into())?;
+        self.signatures
+            .retain(|sig| sig.to_public_key().ok().as_ref() != Some(&pk));
+        self.signatures.push(signature);
+        Ok(())
+    }
+
+    /// Combined weight of the members that have signed.
+    pub fn weight(&self) -> ThresholdUnit {
+        let signed = self
+            .signatures
+            .iter()
+            .filter_map(|sig| sig.to_public_key().ok())
+            .collect::<Vec<_>>();
+        self.multisig_pk
+            .pubkeys()
+            .iter()
+            .filter(|(pk, _)| signed.contains(pk))
+            .map(|(_, weight)| *weight as ThresholdUnit)
+            .sum()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.weight() >= *self.multisig_pk.threshold()
+    }
+
+    /// Combines the collected signatures into a [MultiSig], once the threshold is met.
+    pub fn combine(&self) -> Result<GenericSignature, anyhow::Error> {
+        ensure!(
+            self.is_complete(),
+            "Multisig threshold not met: signed weight {} of {}",
+            self.weight(),
+            self.multisig_pk.threshold()
+        );
+        Ok(MultiSig::combine(self.signatures.clone(), self.multisig_pk.clone())?.into())
+    }
+}
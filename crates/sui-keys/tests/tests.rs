@@ -5,8 +5,14 @@ use std::str::FromStr;
 use fastcrypto::hash::{HashFunction, Sha3_256};
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
+use sui_keys::encryption::EncryptionMode;
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
+use sui_keys::multisig::{MultiSigAccounts, PartialSigBundle, SUI_MULTISIG_FILENAME};
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+use sui_types::crypto::{EncodeDecodeBase64, SignatureScheme, SuiSignatureInner};
+use sui_types::intent::Intent;
+use sui_types::messages::{Transaction, TransactionData};
+use sui_types::multisig::MultiSigPublicKey;
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -68,3 +74,246 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn encrypted_keystore_per_key_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    let plaintext_keys = keystore
+        .key_pairs()
+        .iter()
+        .map(|kp| kp.public())
+        .collect::<Vec<_>>();
+    keystore.encrypt("password", EncryptionMode::PerKey)?;
+
+    // The file no longer contains the private key.
+    let contents = std::fs::read_to_string(&keystore_path)?;
+    assert!(!contents.contains(&keystore.get_key(&address)?.encode_base64()));
+
+    // Public keys are readable while locked, private keys are not.
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    assert!(keystore.is_locked());
+    assert_eq!(keystore.keys(), plaintext_keys);
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore
+        .sign_secure(&address, &"message", Intent::default())
+        .is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None)
+        .is_err());
+
+    assert!(keystore.unlock("wrong password").is_err());
+    assert!(keystore.is_locked());
+    keystore.unlock("password")?;
+    assert!(!keystore.is_locked());
+    keystore.sign_secure(&address, &"message", Intent::default())?;
+
+    // Keys added after unlocking are saved encrypted as well.
+    let (address2, _, _) = keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None)?;
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    assert_eq!(keystore.keys().len(), 2);
+    keystore.unlock("password")?;
+    keystore.get_key(&address2)?;
+    Ok(())
+}
+
+#[test]
+fn encrypted_keystore_whole_file_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    keystore.encrypt("password", EncryptionMode::WholeFile)?;
+
+    let contents = std::fs::read_to_string(&keystore_path)?;
+    assert!(!contents.contains(&keystore.keys()[0].encode_base64()));
+
+    // Nothing is revealed until the keystore is unlocked.
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    assert!(keystore.is_locked());
+    assert!(keystore.addresses().is_empty());
+    keystore.unlock("password")?;
+    assert_eq!(keystore.addresses(), vec![address]);
+    keystore.sign_secure(&address, &"message", Intent::default())?;
+    Ok(())
+}
+
+/// Serves the external signer protocol from an in-memory keystore.
+#[cfg(unix)]
+fn spawn_mock_signer(listener: std::os::unix::net::UnixListener, keystore: InMemKeystore) {
+    use fastcrypto::encoding::{Base64, Encoding};
+    use std::io::{BufRead, BufReader, Write};
+    use sui_keys::external::{SignerRequest, SignerResponse, SignerResult};
+    use sui_types::crypto::Signer;
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            let response = match serde_json::from_str(&line).unwrap() {
+                SignerRequest::Keys => SignerResponse::Result(SignerResult::Keys {
+                    keys: keystore.keys().iter().map(|k| k.encode_base64()).collect(),
+                }),
+                SignerRequest::SignIntentMessage {
+                    address,
+                    intent_message,
+                } => match keystore.get_key(&address) {
+                    Ok(key) => SignerResponse::Result(SignerResult::Signature {
+                        signature: key.sign(&Base64::decode(&intent_message).unwrap()),
+                    }),
+                    Err(e) => SignerResponse::Error(e.to_string()),
+                },
+            };
+            let mut response = serde_json::to_string(&response).unwrap();
+            response.push('\n');
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_test() -> Result<(), anyhow::Error> {
+    use sui_keys::external::ExternalKeystore;
+    use sui_types::crypto::SuiSignature;
+    use sui_types::intent::IntentMessage;
+
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("signer.sock");
+    let listener = std::os::unix::net::UnixListener::bind(&socket_path)?;
+    let signer_keys = InMemKeystore::new(2);
+    let expected_addresses = signer_keys.addresses();
+    spawn_mock_signer(listener, signer_keys);
+
+    let mut keystore = Keystore::from(ExternalKeystore::new(socket_path));
+    assert_eq!(keystore.addresses(), expected_addresses);
+    assert_eq!(keystore.try_addresses()?, expected_addresses);
+
+    let address = expected_addresses[0];
+    let signature = keystore.sign_secure(&address, &"message", Intent::default())?;
+    signature.verify_secure(&IntentMessage::new(Intent::default(), "message"), address)?;
+
+    // Unknown addresses are rejected by the signer, and keys never leave it.
+    assert!(keystore
+        .sign_secure(&SuiAddress::ZERO, &"message", Intent::default())
+        .is_err());
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None)
+        .is_err());
+    // Raw bytes are never sent to the signer.
+    #[allow(deprecated)]
+    let raw_signature = keystore.sign(&address, b"message");
+    assert!(raw_signature.is_err());
+    Ok(())
+}
+
+#[test]
+fn external_keystore_unreachable_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore = Keystore::from(sui_keys::external::ExternalKeystore::new(
+        temp_dir.path().join("missing.sock"),
+    ));
+    assert!(keystore.addresses().is_empty());
+    assert!(keystore.try_addresses().is_err());
+    assert!(keystore
+        .sign_secure(&SuiAddress::ZERO, &"message", Intent::default())
+        .is_err());
+}
+
+#[test]
+fn multisig_accounts_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let path = MultiSigAccounts::path_for_keystore(&keystore).unwrap();
+    assert_eq!(path, temp_dir.path().join(SUI_MULTISIG_FILENAME));
+
+    let pks = InMemKeystore::new(3).keys();
+    let multisig_pk = MultiSigPublicKey::new(pks, vec![1, 1, 1], 2)?;
+    let mut accounts = MultiSigAccounts::new(&path)?;
+    let address = accounts.add("team".to_string(), multisig_pk.clone())?;
+    assert!(accounts
+        .add("team".to_string(), multisig_pk.clone())
+        .is_err());
+    assert!(accounts
+        .add("other".to_string(), multisig_pk.clone())
+        .is_err());
+
+    // Accounts are persisted next to the keystore, readable by their owner only.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(
+            std::fs::metadata(&path)?.permissions().mode() & 0o777,
+            0o600
+        );
+    }
+    let mut accounts = MultiSigAccounts::new(&path)?;
+    assert_eq!(accounts.get("team"), Some(&multisig_pk));
+    assert_eq!(accounts.find_by_address(&address).unwrap().0, "team");
+    accounts.remove("team")?;
+    assert!(MultiSigAccounts::new(&path)?
+        .find_by_address(&address)
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn multisig_partial_sig_bundle_test() -> Result<(), anyhow::Error> {
+    let keystore = InMemKeystore::new(3);
+    let members = keystore.addresses();
+    let multisig_pk = MultiSigPublicKey::new(keystore.keys(), vec![1, 1, 1], 2)?;
+    let multisig_address = SuiAddress::from(multisig_pk.clone());
+
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let data = TransactionData::new_pay_sui_with_dummy_gas_price(
+        multisig_address,
+        vec![gas],
+        vec![SuiAddress::random_for_testing_only()],
+        vec![10000],
+        gas,
+        1000,
+    );
+    let mut bundle = PartialSigBundle::new(multisig_pk.clone(), &data)?;
+    assert_eq!(bundle.missing_signers(), members);
+    assert!(bundle.combine().is_err());
+
+    // Bundles survive being passed around as files.
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("tx.multisig");
+    bundle.add_signature(keystore.sign_secure(&members[0], &data, Intent::default())?)?;
+    bundle.write(&path)?;
+    let mut bundle = PartialSigBundle::read(&path)?;
+    assert_eq!(bundle.weight(), 1);
+    assert!(!bundle.is_complete());
+
+    // Signing twice does not count twice, and signatures of outsiders or over other data fail.
+    bundle.add_signature(keystore.sign_secure(&members[0], &data, Intent::default())?)?;
+    assert_eq!(bundle.weight(), 1);
+    let outsider = InMemKeystore::new(4);
+    let outsider_address = outsider
+        .addresses()
+        .into_iter()
+        .find(|address| !members.contains(address))
+        .unwrap();
+    assert!(bundle
+        .add_signature(outsider.sign_secure(&outsider_address, &data, Intent::default())?)
+        .is_err());
+    assert!(bundle
+        .add_signature(keystore.sign_secure(&members[1], &"other", Intent::default())?)
+        .is_err());
+
+    bundle.add_signature(keystore.sign_secure(&members[2], &data, Intent::default())?)?;
+    assert!(bundle.is_complete());
+    assert_eq!(bundle.missing_signers(), vec![members[1]]);
+    Transaction::from_generic_sig_data(data, Intent::default(), bundle.combine()?).verify()?;
+    Ok(())
+}
@@ -0,0 +1,331 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A local archive of checkpoints, and the transactions and effects they contain.
+//!
+//! Peers are free to prune old checkpoints, so history can become unavailable over the network.
+//! The archive keeps a copy of every synced checkpoint in a directory which can be shared between
+//! nodes (e.g. a mounted bucket), and which StateSync reads from when none of its peers is able
+//! to provide a checkpoint.
+//!
+//! The archive is append-only and content-addressed. Every object is stored as a BCS-encoded file
+//! named after its digest, and is never modified once written:
+//! * `summaries/<checkpoint digest>`
+//! * `contents/<checkpoint contents digest>`
+//! * `transactions/<transaction digest>`
+//! * `effects/<transaction effects digest>`
+//!
+//! Checkpoints are located by sequence number through `index/<sequence number>` files, holding
+//! the digest of the checkpoint. A checkpoint is only indexed once its contents, transactions and
+//! effects have been written, so an indexed checkpoint is always complete. Data read from the
+//! archive is checked against its digest, but checkpoint summaries still need to be verified
+//! against the committee of their epoch.
+//!
+//! All reads and writes are blocking filesystem operations, callers running on an async runtime
+//! should go through `spawn_blocking`.
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use sui_types::{
+    base_types::ExecutionDigests,
+    digests::{CheckpointContentsDigest, CheckpointDigest},
+    message_envelope::Message,
+    messages::{CertifiedTransaction, TransactionEffects},
+    messages_checkpoint::{
+        CertifiedCheckpointSummary as Checkpoint, CheckpointContents, CheckpointSequenceNumber,
+        VerifiedCheckpoint,
+    },
+    storage::ReadStore,
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, info, warn};
+
+const SUMMARIES_DIR: &str = "summaries";
+const CONTENTS_DIR: &str = "contents";
+const TRANSACTIONS_DIR: &str = "transactions";
+const EFFECTS_DIR: &str = "effects";
+const INDEX_DIR: &str = "index";
+
+#[derive(Clone, Debug)]
+pub struct CheckpointArchive {
+    root: PathBuf,
+    /// Sequence number following the highest archived checkpoint, once the index has been
+    /// scanned.
+    next_sequence_number: Arc<Mutex<Option<CheckpointSequenceNumber>>>,
+}
+
+impl CheckpointArchive {
+    /// Open the archive rooted at `root`. Directories are created lazily, on first write.
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self {
+            root: root.into(),
+            next_sequence_number: Default::default(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, dir: &str, name: impl Display) -> PathBuf {
+        self.root.join(dir).join(name.to_string())
+    }
+
+    fn write<T: Serialize>(&self, dir: &str, name: impl Display, value: &T) -> io::Result<()> {
+        let path = self.path(dir, name);
+        // Files are never modified once written.
+        if path.exists() {
+            return Ok(());
+        }
+        fs::create_dir_all(self.root.join(dir))?;
+
+        // Write to a temporary file first and move it in place, so that readers never observe a
+        // partially written file.
+        let tmp = path.with_extension(format!("tmp-{}", rand::random::<u64>()));
+        let bytes =
+            bcs::to_bytes(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&tmp, bytes)?;
+        fs::rename(&tmp, &path)
+    }
+
+    fn read<T: DeserializeOwned>(&self, dir: &str, name: impl Display) -> io::Result<Option<T>> {
+        let bytes = match fs::read(self.path(dir, name)) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        bcs::from_bytes(&bytes)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Archive a checkpoint along with its contents, transactions and effects, which must be
+    /// available in `store`.
+    pub fn insert_checkpoint<S>(&self, store: S, checkpoint: &VerifiedCheckpoint) -> io::Result<()>
+    where
+        S: ReadStore,
+        <S as ReadStore>::Error: std::error::Error,
+    {
+        let contents = store
+            .get_checkpoint_contents(&checkpoint.content_digest())
+            .map_err(store_error)?
+            .ok_or_else(|| missing("checkpoint contents", checkpoint.content_digest()))?;
+
+        for digests in contents.iter() {
+            let transaction = store
+                .get_transaction(&digests.transaction)
+                .map_err(store_error)?
+                .ok_or_else(|| missing("transaction", digests.transaction))?;
+            let effects = store
+                .get_transaction_effects(&digests.effects)
+                .map_err(store_error)?
+                .ok_or_else(|| missing("transaction effects", digests.effects))?;
+            self.write(TRANSACTIONS_DIR, digests.transaction, transaction.inner())?;
+            self.write(EFFECTS_DIR, digests.effects, &effects)?;
+        }
+        self.write(CONTENTS_DIR, checkpoint.content_digest(), &contents)?;
+        self.write(SUMMARIES_DIR, checkpoint.digest(), checkpoint.inner())?;
+        self.write(
+            INDEX_DIR,
+            checkpoint.sequence_number(),
+            &checkpoint.digest(),
+        )?;
+
+        if let Some(next) = self.next_sequence_number.lock().unwrap().as_mut() {
+            *next = std::cmp::max(*next, checkpoint.sequence_number() + 1);
+        }
+        Ok(())
+    }
+
+    pub fn get_checkpoint_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> io::Result<Option<Checkpoint>> {
+        let Some(digest) = self.read::<CheckpointDigest>(INDEX_DIR, sequence_number)? else {
+            return Ok(None);
+        };
+        let checkpoint = self
+            .read::<Checkpoint>(SUMMARIES_DIR, digest)?
+            .ok_or_else(|| missing("checkpoint summary", digest))?;
+        if checkpoint.digest() != digest || checkpoint.sequence_number() != sequence_number {
+            return Err(corrupted("checkpoint summary", digest));
+        }
+        Ok(Some(checkpoint))
+    }
+
+    pub fn get_checkpoint_contents(
+        &self,
+        digest: &CheckpointContentsDigest,
+    ) -> io::Result<Option<CheckpointContents>> {
+        let contents = self.read::<CheckpointContents>(CONTENTS_DIR, digest)?;
+        match contents {
+            Some(contents) if contents.digest() != *digest => {
+                Err(corrupted("checkpoint contents", digest))
+            }
+            contents => Ok(contents),
+        }
+    }
+
+    pub fn get_transaction_and_effects(
+        &self,
+        digests: &ExecutionDigests,
+    ) -> io::Result<Option<(CertifiedTransaction, TransactionEffects)>> {
+        let (Some(transaction), Some(effects)) = (
+            self.read::<CertifiedTransaction>(TRANSACTIONS_DIR, digests.transaction)?,
+            self.read::<TransactionEffects>(EFFECTS_DIR, digests.effects)?,
+        ) else {
+            return Ok(None);
+        };
+        if transaction.digest() != &digests.transaction
+            || effects.digest() != digests.effects
+            || effects.transaction_digest != digests.transaction
+        {
+            return Err(corrupted("transaction", digests.transaction));
+        }
+        Ok(Some((transaction, effects)))
+    }
+
+    /// Returns true if the checkpoint with the given sequence number, along with its contents,
+    /// transactions and effects, has been archived.
+    pub fn contains_checkpoint(&self, sequence_number: CheckpointSequenceNumber) -> bool {
+        self.path(INDEX_DIR, sequence_number).exists()
+    }
+
+    /// Returns the sequence number of the highest archived checkpoint. The index is only scanned
+    /// on first use, checkpoints archived since by other nodes sharing the archive are then found
+    /// by looking up the following sequence numbers.
+    pub fn highest_sequence_number(&self) -> io::Result<Option<CheckpointSequenceNumber>> {
+        let mut cached = self.next_sequence_number.lock().unwrap();
+        let mut next = match *cached {
+            Some(next) => next,
+            None => self.scan_index()?.map_or(0, |highest| highest + 1),
+        };
+        while self.contains_checkpoint(next) {
+            next += 1;
+        }
+        *cached = Some(next);
+        Ok(next.checked_sub(1))
+    }
+
+    fn scan_index(&self) -> io::Result<Option<CheckpointSequenceNumber>> {
+        match fs::read_dir(self.root.join(INDEX_DIR)) {
+            Ok(entries) => Ok(entries
+                .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+                .max()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn store_error(e: impl std::error::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e.to_string())
+}
+
+fn missing(kind: &str, digest: impl Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{kind} {digest} not found"),
+    )
+}
+
+fn corrupted(kind: &str, digest: impl Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("archived {kind} {digest} does not match its digest"),
+    )
+}
+
+/// Background task writing every synced checkpoint to the archive.
+pub(super) struct ArchiveWriter<S> {
+    archive: CheckpointArchive,
+    store: S,
+}
+
+impl<S> ArchiveWriter<S>
+where
+    S: ReadStore,
+    <S as ReadStore>::Error: std::error::Error,
+{
+    pub(super) fn new(archive: CheckpointArchive, store: S) -> Self {
+        Self { archive, store }
+    }
+
+    /// Archive checkpoints as they are synced, until the StateSync subsystem shuts down. This
+    /// performs blocking IO and should be run on a blocking thread.
+    pub(super) fn run(self, mut receiver: broadcast::Receiver<VerifiedCheckpoint>) {
+        info!(archive = %self.archive.root().display(), "Checkpoint archiver started");
+
+        let mut next = match self.catch_up(0) {
+            Ok(next) => next,
+            Err(e) => {
+                warn!("unable to archive checkpoints: {e}");
+                0
+            }
+        };
+        loop {
+            match receiver.blocking_recv() {
+                Ok(checkpoint) if checkpoint.sequence_number() < next => {}
+                // Checkpoints are synced in order, so this is the next one unless we missed some
+                // notifications.
+                Ok(checkpoint) if checkpoint.sequence_number() == next => {
+                    match self.archive.insert_checkpoint(&self.store, &checkpoint) {
+                        Ok(()) => next += 1,
+                        Err(e) => warn!(
+                            "unable to archive checkpoint {}: {e}",
+                            checkpoint.sequence_number()
+                        ),
+                    }
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => match self.catch_up(next) {
+                    Ok(n) => next = n,
+                    Err(e) => warn!("unable to archive checkpoints: {e}"),
+                },
+                Err(RecvError::Closed) => break,
+            }
+        }
+
+        info!("Checkpoint archiver ended");
+    }
+
+    /// Archive all synced checkpoints from `next` onwards that have not been archived yet,
+    /// returning the sequence number of the next checkpoint to archive. Checkpoints whose data is
+    /// not available locally are skipped.
+    fn catch_up(&self, next: CheckpointSequenceNumber) -> io::Result<CheckpointSequenceNumber> {
+        let next = std::cmp::max(
+            next,
+            self.archive.highest_sequence_number()?.map_or(0, |s| s + 1),
+        );
+        let highest_synced = self
+            .store
+            .get_highest_synced_checkpoint()
+            .map_err(store_error)?
+            .sequence_number();
+
+        for sequence_number in next..=highest_synced {
+            let Some(checkpoint) = self
+                .store
+                .get_checkpoint_by_sequence_number(sequence_number)
+                .map_err(store_error)? else {
+                // The store may not have history from before a state snapshot it was restored from.
+                debug!("checkpoint {sequence_number} not found, skipping");
+                continue;
+            };
+            match self.archive.insert_checkpoint(&self.store, &checkpoint) {
+                Ok(()) => {}
+                // A store restored from a state snapshot has the summaries of the epochs before
+                // it, but not their contents.
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    info!("unable to archive checkpoint {sequence_number}, skipping: {e}");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(std::cmp::max(next, highest_synced + 1))
+    }
+}
@@ -16,8 +16,10 @@ use tokio::{
 };
 
 use super::{
-    metrics::Metrics, server::Server, Handle, PeerHeights, StateSync, StateSyncEventLoop,
-    StateSyncMessage, StateSyncServer,
+    archive::{ArchiveWriter, CheckpointArchive},
+    metrics::Metrics,
+    server::Server,
+    Handle, PeerHeights, StateSync, StateSyncEventLoop, StateSyncMessage, StateSyncServer,
 };
 use sui_types::storage::WriteStore;
 
@@ -114,6 +116,7 @@ where
         let store = store.unwrap();
         let config = config.unwrap_or_default();
         let metrics = metrics.unwrap_or_else(Metrics::disabled);
+        let archive = config.archive_path.clone().map(CheckpointArchive::new);
 
         let (sender, mailbox) = mpsc::channel(config.mailbox_capacity());
         let (checkpoint_event_sender, _receiver) =
@@ -127,6 +130,7 @@ where
             peers: HashMap::new(),
             unprocessed_checkpoints: HashMap::new(),
             sequence_number_to_digest: HashMap::new(),
+            archive_height: None,
         }
         .pipe(RwLock::new)
         .pipe(Arc::new);
@@ -146,6 +150,7 @@ where
                 peer_heights,
                 checkpoint_event_sender,
                 metrics,
+                archive,
             },
             server,
         )
@@ -160,6 +165,7 @@ pub struct UnstartedStateSync<S> {
     pub(super) peer_heights: Arc<RwLock<PeerHeights>>,
     pub(super) checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     pub(super) metrics: Metrics,
+    pub(super) archive: Option<CheckpointArchive>,
 }
 
 impl<S> UnstartedStateSync<S>
@@ -176,6 +182,7 @@ where
             peer_heights,
             checkpoint_event_sender,
             metrics,
+            archive,
         } = self;
 
         (
@@ -191,12 +198,23 @@ where
                 checkpoint_event_sender,
                 network,
                 metrics,
+                archive,
             },
             handle,
         )
     }
 
     pub fn start(self, network: anemo::Network) -> Handle {
+        if let Some(archive) = self
+            .archive
+            .clone()
+            .filter(|_| self.config.enable_archive_writer())
+        {
+            let writer = ArchiveWriter::new(archive, self.store.clone());
+            let receiver = self.checkpoint_event_sender.subscribe();
+            tokio::task::spawn_blocking(move || writer.run(receiver));
+        }
+
         let (event_loop, handle) = self.build(network);
         tokio::spawn(event_loop.start());
 
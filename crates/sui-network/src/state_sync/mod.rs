@@ -77,6 +77,7 @@ use tracing::{debug, info, trace, warn};
 mod generated {
     include!(concat!(env!("OUT_DIR"), "/sui.StateSync.rs"));
 }
+pub mod archive;
 mod builder;
 mod metrics;
 mod server;
@@ -84,6 +85,7 @@ pub mod test_utils;
 #[cfg(test)]
 mod tests;
 
+pub use archive::CheckpointArchive;
 pub use builder::{Builder, UnstartedStateSync};
 pub use generated::{
     state_sync_client::StateSyncClient,
@@ -130,6 +132,8 @@ struct PeerHeights {
     peers: HashMap<PeerId, PeerStateSyncInfo>,
     unprocessed_checkpoints: HashMap<CheckpointDigest, Checkpoint>,
     sequence_number_to_digest: HashMap<CheckpointSequenceNumber, CheckpointDigest>,
+    /// Highest checkpoint we know of in the local archive.
+    archive_height: Option<CheckpointSequenceNumber>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -153,9 +157,22 @@ impl PeerHeights {
         self.peers
             .values()
             .filter_map(|info| info.on_same_chain_as_us.then_some(info.height))
+            .chain(self.archive_height)
             .max()
     }
 
+    // Returns a bool that indicates if the archive holds a checkpoint higher than we knew of.
+    pub fn update_archive_height(&mut self, checkpoint: Checkpoint) -> bool {
+        if Some(checkpoint.sequence_number()) <= self.archive_height {
+            return false;
+        }
+
+        self.archive_height = Some(checkpoint.sequence_number());
+        self.insert_checkpoint(checkpoint);
+
+        true
+    }
+
     pub fn peers_on_same_chain(&self) -> impl Iterator<Item = (&PeerId, &PeerStateSyncInfo)> {
         self.peers
             .iter()
@@ -268,6 +285,7 @@ struct StateSyncEventLoop<S> {
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     network: anemo::Network,
     metrics: Metrics,
+    archive: Option<CheckpointArchive>,
 }
 
 impl<S> StateSyncEventLoop<S>
@@ -502,6 +520,15 @@ where
             self.config.timeout(),
         );
         self.tasks.spawn(task);
+
+        if let Some(archive) = self.archive.clone() {
+            let task = query_archive_for_its_latest_checkpoint(
+                archive,
+                self.peer_heights.clone(),
+                self.weak_sender.clone(),
+            );
+            self.tasks.spawn(task);
+        }
     }
 
     fn maybe_start_checkpoint_summary_sync_task(&mut self) {
@@ -533,6 +560,7 @@ where
                 self.store.clone(),
                 self.peer_heights.clone(),
                 self.metrics.clone(),
+                self.archive.clone(),
                 self.config.checkpoint_header_download_concurrency(),
                 self.config.timeout(),
                 // The if condition should ensure that this is Some
@@ -566,7 +594,8 @@ where
 
         if highest_verified_checkpoint.sequence_number()
             > highest_synced_checkpoint.sequence_number()
-            // skip if we aren't connected to any peers that can help
+            // skip if we aren't connected to any peers that can help, and the archive can't
+            // either
             && self
                 .peer_heights
                 .read()
@@ -578,6 +607,7 @@ where
                 self.network.clone(),
                 self.store.clone(),
                 self.peer_heights.clone(),
+                self.archive.clone(),
                 self.weak_sender.clone(),
                 self.checkpoint_event_sender.clone(),
                 self.metrics.clone(),
@@ -769,10 +799,59 @@ async fn query_peers_for_their_latest_checkpoint(
     }
 }
 
+async fn query_archive_for_its_latest_checkpoint(
+    archive: CheckpointArchive,
+    peer_heights: Arc<RwLock<PeerHeights>>,
+    sender: mpsc::WeakSender<StateSyncMessage>,
+) {
+    let Some(checkpoint) = read_from_archive(Some(&archive), |archive| {
+        let Some(sequence_number) = archive.highest_sequence_number()? else {
+            return Ok(None);
+        };
+        archive.get_checkpoint_by_sequence_number(sequence_number)
+    })
+    .await
+    .tap_err(|e| warn!("unable to read the latest checkpoint from archive: {e}"))
+    .ok()
+    .flatten() else {
+        return;
+    };
+
+    if !peer_heights
+        .write()
+        .unwrap()
+        .update_archive_height(checkpoint)
+    {
+        return;
+    }
+
+    if let Some(sender) = sender.upgrade() {
+        let _ = sender.send(StateSyncMessage::StartSyncJob).await;
+    }
+}
+
+/// Read from the archive, if there is one, on a blocking thread.
+async fn read_from_archive<T, F>(
+    archive: Option<&CheckpointArchive>,
+    f: F,
+) -> std::io::Result<Option<T>>
+where
+    T: Send + 'static,
+    F: FnOnce(&CheckpointArchive) -> std::io::Result<Option<T>> + Send + 'static,
+{
+    let Some(archive) = archive.cloned() else {
+        return Ok(None);
+    };
+    tokio::task::spawn_blocking(move || f(&archive))
+        .await
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
+}
+
 async fn sync_to_checkpoint<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    archive: Option<CheckpointArchive>,
     metrics: Metrics,
     checkpoint_header_download_concurrency: usize,
     timeout: Duration,
@@ -820,6 +899,7 @@ where
                 .collect::<Vec<_>>();
             rand::seq::SliceRandom::shuffle(peers.as_mut_slice(), &mut rng);
             let peer_heights = peer_heights.clone();
+            let archive = archive.clone();
             async move {
                 if let Some(checkpoint) = peer_heights
                     .read()
@@ -856,6 +936,18 @@ where
                     }
                 }
 
+                // None of our peers were able to help, fall back to the archive
+                if let Some(checkpoint) = read_from_archive(archive.as_ref(), move |archive| {
+                    archive.get_checkpoint_by_sequence_number(next)
+                })
+                .await
+                .tap_err(|e| warn!("unable to read checkpoint {next} from archive: {e}"))
+                .ok()
+                .flatten()
+                {
+                    return (Some(checkpoint), next, None);
+                }
+
                 (None, next, None)
             }
         })
@@ -972,6 +1064,7 @@ async fn sync_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    archive: Option<CheckpointArchive>,
     sender: mpsc::WeakSender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     metrics: Metrics,
@@ -1003,6 +1096,7 @@ async fn sync_checkpoint_contents<S>(
                 network.clone(),
                 &store,
                 peer_heights.clone(),
+                archive.as_ref(),
                 transaction_download_concurrency,
                 timeout,
                 checkpoint,
@@ -1047,6 +1141,7 @@ async fn sync_one_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    archive: Option<&CheckpointArchive>,
     transaction_download_concurrency: usize,
     timeout: Duration,
     checkpoint: VerifiedCheckpoint,
@@ -1069,7 +1164,7 @@ where
         .collect::<Vec<_>>();
     rand::seq::SliceRandom::shuffle(peers.as_mut_slice(), &mut rng);
 
-    let Some(contents) = get_checkpoint_contents(&mut peers, &store, checkpoint.content_digest(), timeout).await else {
+    let Some(contents) = get_checkpoint_contents(&mut peers, &store, archive, checkpoint.content_digest(), timeout).await else {
         return Err(anyhow!("unable to sync checkpoint contents for checkpoint {}", checkpoint.sequence_number()));
     };
 
@@ -1079,7 +1174,9 @@ where
     let mut stream = contents
         .into_inner()
         .into_iter()
-        .map(|digests| get_transaction_and_effects(peers.clone(), store.clone(), digests, timeout))
+        .map(|digests| {
+            get_transaction_and_effects(peers.clone(), store.clone(), archive, digests, timeout)
+        })
         .pipe(futures::stream::iter)
         .buffer_unordered(transaction_download_concurrency);
 
@@ -1093,6 +1190,7 @@ where
 async fn get_checkpoint_contents<S>(
     peers: &mut [StateSyncClient<anemo::Peer>],
     store: S,
+    archive: Option<&CheckpointArchive>,
     digest: CheckpointContentsDigest,
     timeout: Duration,
 ) -> Option<CheckpointContents>
@@ -1128,12 +1226,24 @@ where
         }
     }
 
-    None
+    // None of our peers were able to help, fall back to the archive
+    let contents = read_from_archive(archive, move |archive| {
+        archive.get_checkpoint_contents(&digest)
+    })
+    .await
+    .tap_err(|e| warn!("unable to read checkpoint contents {digest} from archive: {e}"))
+    .ok()
+    .flatten()?;
+    store
+        .insert_checkpoint_contents(contents.clone())
+        .expect("store operation should not fail");
+    Some(contents)
 }
 
 async fn get_transaction_and_effects<S>(
     peers: Vec<StateSyncClient<anemo::Peer>>,
     store: S,
+    archive: Option<&CheckpointArchive>,
     digests: ExecutionDigests,
     timeout: Duration,
 ) -> Result<()>
@@ -1154,6 +1264,7 @@ where
 
     // Iterate through our selected peers trying each one in turn until we're able to
     // successfully get the target checkpoint
+    let mut found = None;
     for mut peer in peers {
         let request = Request::new(digests).with_timeout(timeout);
         if let Some((transaction, effects)) = peer
@@ -1168,24 +1279,46 @@ where
                 && effects.digest() == digests.effects
                 && effects.transaction_digest == digests.transaction
             {
-                // TODO this should just be a bare Transaction type and not a TransactionCertificate
-                // since Certificates are intended to be ephemeral and thrown away at the end of an
-                // epoch
-                store
-                    .insert_transaction(sui_types::messages::VerifiedCertificate::new_unchecked(
-                        transaction,
-                    ))
-                    .expect("store operation should not fail");
-                store
-                    .insert_transaction_effects(effects)
-                    .expect("store operation should not fail");
-                // TODO: If the transaction has already been executed, we should check that the executed
-                // effects match. If they don't, it's a bug and we should panic.
-                return Ok(());
+                found = Some((transaction, effects));
+                break;
             }
         }
     }
 
+    // None of our peers were able to help, fall back to the archive. Data read from the archive
+    // has already been checked against the requested digests.
+    if found.is_none() {
+        found = read_from_archive(archive, move |archive| {
+            archive.get_transaction_and_effects(&digests)
+        })
+        .await
+        .tap_err(|e| {
+            warn!(
+                "unable to read transaction {:?} from archive: {e}",
+                digests.transaction
+            )
+        })
+        .ok()
+        .flatten();
+    }
+
+    if let Some((transaction, effects)) = found {
+        // TODO this should just be a bare Transaction type and not a TransactionCertificate
+        // since Certificates are intended to be ephemeral and thrown away at the end of an
+        // epoch
+        store
+            .insert_transaction(sui_types::messages::VerifiedCertificate::new_unchecked(
+                transaction,
+            ))
+            .expect("store operation should not fail");
+        store
+            .insert_transaction_effects(effects)
+            .expect("store operation should not fail");
+        // TODO: If the transaction has already been executed, we should check that the executed
+        // effects match. If they don't, it's a bug and we should panic.
+        return Ok(());
+    }
+
     Err(anyhow!(
         "unable to sync transaction {:?} from any of our peers",
         digests.transaction
@@ -11,8 +11,8 @@ use sui_types::{
         SuiAuthoritySignature,
     },
     messages_checkpoint::{
-        CertifiedCheckpointSummary, CheckpointContents, CheckpointDigest, CheckpointSequenceNumber,
-        CheckpointSummary, EndOfEpochData, VerifiedCheckpoint,
+        CertifiedCheckpointSummary, CheckpointCommitment, CheckpointContents, CheckpointDigest,
+        CheckpointSequenceNumber, CheckpointSummary, EndOfEpochData, VerifiedCheckpoint,
     },
 };
 
@@ -156,6 +156,23 @@ impl CommitteeFixture {
         CheckpointSequenceNumber,
         CheckpointDigest,
         VerifiedCheckpoint,
+    ) {
+        self.make_end_of_epoch_checkpoint_with_commitments(
+            previous_checkpoint,
+            end_of_epoch_data,
+            vec![],
+        )
+    }
+
+    pub fn make_end_of_epoch_checkpoint_with_commitments(
+        &self,
+        previous_checkpoint: VerifiedCheckpoint,
+        end_of_epoch_data: Option<EndOfEpochData>,
+        epoch_commitments: Vec<CheckpointCommitment>,
+    ) -> (
+        CheckpointSequenceNumber,
+        CheckpointDigest,
+        VerifiedCheckpoint,
     ) {
         let summary = CheckpointSummary {
             epoch: self.epoch,
@@ -167,7 +184,8 @@ impl CommitteeFixture {
             end_of_epoch_data,
             timestamp_ms: 0,
             version_specific_data: Vec::new(),
-        };
+        }
+        .with_epoch_commitments(epoch_commitments);
 
         let checkpoint = self.create_certified_checkpoint(summary);
 
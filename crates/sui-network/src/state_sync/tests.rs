@@ -3,19 +3,21 @@
 
 use crate::{
     state_sync::{
+        archive::ArchiveWriter,
         test_utils::{empty_contents, CommitteeFixture},
-        Builder, GetCheckpointSummaryRequest, PeerStateSyncInfo, StateSync, StateSyncMessage,
-        UnstartedStateSync,
+        Builder, CheckpointArchive, GetCheckpointSummaryRequest, PeerStateSyncInfo, StateSync,
+        StateSyncMessage, UnstartedStateSync,
     },
     utils::build_network,
 };
 use anemo::{PeerId, Request};
 use std::{collections::HashMap, time::Duration};
+use sui_config::p2p::StateSyncConfig;
 use sui_types::{
     messages_checkpoint::CheckpointDigest,
     storage::{ReadStore, SharedInMemoryStore, WriteStore},
 };
-use tokio::time::timeout;
+use tokio::{sync::broadcast, time::timeout};
 
 #[tokio::test]
 async fn server_push_checkpoint() {
@@ -398,3 +400,266 @@ async fn sync_with_checkpoints_being_inserted() {
         &sequence_number_to_digest
     );
 }
+
+#[test]
+fn archive_round_trip() {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    let (ordered_checkpoints, _sequence_number_to_digest, _checkpoints) =
+        committee.make_checkpoints(10, None);
+
+    let store = SharedInMemoryStore::default();
+    store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        vec![],
+        vec![],
+        committee.committee().to_owned(),
+    );
+    for checkpoint in ordered_checkpoints.clone() {
+        store.inner_mut().insert_checkpoint(checkpoint);
+    }
+    store
+        .inner_mut()
+        .update_highest_synced_checkpoint(ordered_checkpoints.last().unwrap());
+
+    let dir = tempfile::tempdir().unwrap();
+    let archive = CheckpointArchive::new(dir.path());
+    assert_eq!(archive.highest_sequence_number().unwrap(), None);
+
+    // The writer archives everything that was synced before it started, and stops once state
+    // sync shuts down.
+    let (sender, receiver) = broadcast::channel(1);
+    drop(sender);
+    ArchiveWriter::new(archive.clone(), store).run(receiver);
+
+    assert_eq!(
+        archive.highest_sequence_number().unwrap(),
+        Some(ordered_checkpoints.last().unwrap().sequence_number())
+    );
+    for checkpoint in &ordered_checkpoints {
+        let archived = archive
+            .get_checkpoint_by_sequence_number(checkpoint.sequence_number())
+            .unwrap()
+            .unwrap();
+        assert_eq!(archived.summary, checkpoint.summary);
+    }
+    assert_eq!(
+        archive
+            .get_checkpoint_contents(&empty_contents().digest())
+            .unwrap(),
+        Some(empty_contents())
+    );
+    assert!(archive
+        .get_checkpoint_by_sequence_number(ordered_checkpoints.len() as u64)
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn sync_from_archive() {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    let (ordered_checkpoints, sequence_number_to_digest, _checkpoints) =
+        committee.make_checkpoints(20, None);
+
+    // Archive all the checkpoints
+    let dir = tempfile::tempdir().unwrap();
+    let archive = CheckpointArchive::new(dir.path());
+    {
+        let store = SharedInMemoryStore::default();
+        store.inner_mut().insert_genesis_state(
+            ordered_checkpoints.first().cloned().unwrap(),
+            empty_contents(),
+            vec![],
+            vec![],
+            committee.committee().to_owned(),
+        );
+        for checkpoint in &ordered_checkpoints {
+            store.inner_mut().insert_checkpoint(checkpoint.clone());
+            archive.insert_checkpoint(&store, checkpoint).unwrap();
+        }
+    }
+
+    // Build and connect two nodes, Node 1 using the archive
+    let config = StateSyncConfig {
+        archive_path: Some(dir.path().to_owned()),
+        ..Default::default()
+    };
+    let (builder, server) = Builder::new()
+        .config(config)
+        .store(SharedInMemoryStore::default())
+        .build();
+    let network_1 = build_network(|router| router.add_rpc_service(server));
+    let (mut event_loop_1, _handle_1) = builder.build(network_1.clone());
+    let (builder, server) = Builder::new().store(SharedInMemoryStore::default()).build();
+    let network_2 = build_network(|router| router.add_rpc_service(server));
+    let (event_loop_2, _handle_2) = builder.build(network_2.clone());
+    network_1.connect(network_2.local_addr()).await.unwrap();
+
+    for event_loop in [&event_loop_1, &event_loop_2] {
+        event_loop.store.inner_mut().insert_genesis_state(
+            ordered_checkpoints.first().cloned().unwrap(),
+            empty_contents(),
+            vec![],
+            vec![],
+            committee.committee().to_owned(),
+        );
+    }
+
+    // Node 2 claims to be at the latest checkpoint, but has pruned all the history
+    event_loop_1.peer_heights.write().unwrap().peers.insert(
+        network_2.peer_id(),
+        PeerStateSyncInfo {
+            genesis_checkpoint_digest: ordered_checkpoints[0].digest(),
+            on_same_chain_as_us: true,
+            height: ordered_checkpoints.last().unwrap().sequence_number(),
+        },
+    );
+    event_loop_1
+        .peer_heights
+        .write()
+        .unwrap()
+        .insert_checkpoint(ordered_checkpoints.last().cloned().unwrap().into_inner());
+
+    // Sync the checkpoint summaries, and then their contents
+    event_loop_1.maybe_start_checkpoint_summary_sync_task();
+    event_loop_1.tasks.join_next().await.unwrap().unwrap();
+    assert_eq!(
+        ordered_checkpoints.last().map(|x| x.digest()),
+        Some(
+            event_loop_1
+                .store
+                .get_highest_verified_checkpoint()
+                .unwrap()
+                .digest()
+        )
+    );
+    assert_eq!(
+        event_loop_1
+            .store
+            .inner()
+            .checkpoint_sequence_number_to_digest(),
+        &sequence_number_to_digest
+    );
+
+    event_loop_1.maybe_start_checkpoint_contents_sync_task();
+    event_loop_1.tasks.join_next().await.unwrap().unwrap();
+    assert_eq!(
+        ordered_checkpoints.last().map(|x| x.digest()),
+        Some(
+            event_loop_1
+                .store
+                .get_highest_synced_checkpoint()
+                .unwrap()
+                .digest()
+        )
+    );
+}
+
+#[tokio::test]
+async fn sync_from_archive_without_peers() {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    let (ordered_checkpoints, _sequence_number_to_digest, _checkpoints) =
+        committee.make_checkpoints(20, None);
+
+    // Archive all the checkpoints
+    let dir = tempfile::tempdir().unwrap();
+    let archive = CheckpointArchive::new(dir.path());
+    {
+        let store = SharedInMemoryStore::default();
+        store.inner_mut().insert_genesis_state(
+            ordered_checkpoints.first().cloned().unwrap(),
+            empty_contents(),
+            vec![],
+            vec![],
+            committee.committee().to_owned(),
+        );
+        for checkpoint in &ordered_checkpoints {
+            store.inner_mut().insert_checkpoint(checkpoint.clone());
+            archive.insert_checkpoint(&store, checkpoint).unwrap();
+        }
+    }
+
+    // Build a node using the archive, which isn't connected to any peer
+    let config = StateSyncConfig {
+        archive_path: Some(dir.path().to_owned()),
+        ..Default::default()
+    };
+    let (builder, server) = Builder::new()
+        .config(config)
+        .store(SharedInMemoryStore::default())
+        .build();
+    let network = build_network(|router| router.add_rpc_service(server));
+    let (mut event_loop, _handle) = builder.build(network);
+    event_loop.store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        vec![],
+        vec![],
+        committee.committee().to_owned(),
+    );
+
+    // The archive is queried for its latest checkpoint on every tick, alongside peers
+    event_loop.handle_tick(std::time::Instant::now());
+    event_loop.tasks.join_next().await.unwrap().unwrap();
+    event_loop.tasks.join_next().await.unwrap().unwrap();
+    assert_eq!(
+        event_loop
+            .peer_heights
+            .read()
+            .unwrap()
+            .highest_known_checkpoint_sequence_number(),
+        ordered_checkpoints.last().map(|x| x.sequence_number())
+    );
+
+    event_loop.maybe_start_checkpoint_summary_sync_task();
+    event_loop.tasks.join_next().await.unwrap().unwrap();
+    event_loop.maybe_start_checkpoint_contents_sync_task();
+    event_loop.tasks.join_next().await.unwrap().unwrap();
+    assert_eq!(
+        ordered_checkpoints.last().map(|x| x.digest()),
+        Some(
+            event_loop
+                .store
+                .get_highest_synced_checkpoint()
+                .unwrap()
+                .digest()
+        )
+    );
+}
+
+#[test]
+fn archive_highest_sequence_number_is_cached() {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    let (ordered_checkpoints, _sequence_number_to_digest, _checkpoints) =
+        committee.make_checkpoints(10, None);
+
+    let store = SharedInMemoryStore::default();
+    store.inner_mut().insert_genesis_state(
+        ordered_checkpoints.first().cloned().unwrap(),
+        empty_contents(),
+        vec![],
+        vec![],
+        committee.committee().to_owned(),
+    );
+    for checkpoint in &ordered_checkpoints {
+        store.inner_mut().insert_checkpoint(checkpoint.clone());
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let archive = CheckpointArchive::new(dir.path());
+    let (first, rest) = ordered_checkpoints.split_at(5);
+    for checkpoint in first {
+        archive.insert_checkpoint(&store, checkpoint).unwrap();
+    }
+    assert_eq!(archive.highest_sequence_number().unwrap(), Some(4));
+
+    // Checkpoints archived by another node sharing the archive are picked up as well
+    let other = CheckpointArchive::new(dir.path());
+    for checkpoint in rest {
+        other.insert_checkpoint(&store, checkpoint).unwrap();
+    }
+    assert_eq!(
+        archive.highest_sequence_number().unwrap(),
+        ordered_checkpoints.last().map(|x| x.sequence_number())
+    );
+}
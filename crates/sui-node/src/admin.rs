@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::SuiNode;
 use axum::{
     extract::Extension,
     http::StatusCode,
@@ -8,19 +9,103 @@ use axum::{
     Router,
 };
 use mysten_metrics::spawn_monitored_task;
+use std::fmt::Write;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use sui_config::node::TransactionDenyConfig;
+use sui_types::base_types::ObjectID;
 use telemetry_subscribers::FilterHandle;
+use tokio::sync::OnceCell;
 use tracing::info;
 
+// Example commands:
+//
+// Set the log filter:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/logging' -d 'info,sui_core=debug'
+//
+// Dump the current epoch and committee, or the checkpoint watermarks:
+//
+//   $ curl 'http://127.0.0.1:1337/epoch'
+//   $ curl 'http://127.0.0.1:1337/watermarks'
+//
+// Take a RocksDB checkpoint of the authority store in a new directory:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/db/checkpoint' -d '/opt/sui/db-checkpoint'
+//
+// Replace the transaction deny-list:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/deny-list' --data-binary @deny-list.yaml
+//
+// Release the lock held on the latest version of an owned object, only available on full nodes
+// unless explicitly enabled:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/clear-lock' -d '0x5'
 const LOGGING_ROUTE: &str = "/logging";
+const EPOCH_ROUTE: &str = "/epoch";
+const WATERMARKS_ROUTE: &str = "/watermarks";
+const PENDING_CERTIFICATES_ROUTE: &str = "/pending-certificates";
+const DB_CHECKPOINT_ROUTE: &str = "/db/checkpoint";
+const DB_COMPACT_ROUTE: &str = "/db/compact";
+const PRUNE_ROUTE: &str = "/prune";
+const CLEAR_LOCK_ROUTE: &str = "/clear-lock";
+const DENY_LIST_ROUTE: &str = "/deny-list";
 
-pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
+/// Handle to the node operated through the admin server. The server is started before the node,
+/// so that the log filter can be changed while the node starts, and routes operating the node
+/// are unavailable until it is set.
+#[derive(Clone, Default)]
+pub struct AdminNodeHandle {
+    node: Arc<OnceCell<Arc<SuiNode>>>,
+}
+
+impl AdminNodeHandle {
+    pub fn set(&self, node: Arc<SuiNode>) {
+        if self.node.set(node).is_err() {
+            panic!("Admin server node is already set");
+        }
+    }
+
+    fn get(&self) -> Result<Arc<SuiNode>, (StatusCode, String)> {
+        self.node.get().cloned().ok_or_else(|| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "node is still starting".into(),
+            )
+        })
+    }
+}
+
+/// Whether object locks can be cleared through the admin server. Clearing the lock of an object
+/// used by a transaction that ends up certified can equivocate, which validators must avoid.
+#[derive(Clone, Copy)]
+struct ClearLockEnabled(bool);
+
+pub fn start_admin_server(
+    port: u16,
+    filter_handle: FilterHandle,
+    clear_lock_enabled: bool,
+) -> AdminNodeHandle {
     let filter = filter_handle.get().unwrap();
+    let node = AdminNodeHandle::default();
 
     let app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(LOGGING_ROUTE, post(set_filter))
-        .layer(Extension(filter_handle));
+        .route(EPOCH_ROUTE, get(get_epoch))
+        .route(WATERMARKS_ROUTE, get(get_watermarks))
+        .route(PENDING_CERTIFICATES_ROUTE, get(get_pending_certificates))
+        .route(DB_CHECKPOINT_ROUTE, post(checkpoint_db))
+        .route(DB_COMPACT_ROUTE, post(compact_db))
+        .route(PRUNE_ROUTE, post(prune_objects))
+        .route(CLEAR_LOCK_ROUTE, post(clear_object_lock))
+        .route(DENY_LIST_ROUTE, get(get_deny_list))
+        .route(DENY_LIST_ROUTE, post(set_deny_list))
+        .layer(Extension(filter_handle))
+        .layer(Extension(ClearLockEnabled(clear_lock_enabled)))
+        .layer(Extension(node.clone()));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
     info!(
@@ -35,8 +120,12 @@ pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
             .await
             .unwrap();
     });
+
+    node
 }
 
+type AdminResult = Result<(StatusCode, String), (StatusCode, String)>;
+
 async fn get_filter(Extension(filter_handle): Extension<FilterHandle>) -> (StatusCode, String) {
     match filter_handle.get() {
         Ok(filter) => (StatusCode::OK, filter),
@@ -56,3 +145,226 @@ async fn set_filter(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
     }
 }
+
+async fn get_epoch(Extension(node): Extension<AdminNodeHandle>) -> AdminResult {
+    let epoch_store = node.get()?.state().load_epoch_store_one_call_per_task();
+    let committee = epoch_store.committee();
+
+    let mut output = String::new();
+    writeln!(output, "epoch: {}", epoch_store.epoch()).unwrap();
+    writeln!(
+        output,
+        "protocol version: {}",
+        committee.protocol_version.as_u64()
+    )
+    .unwrap();
+    writeln!(output, "committee (total votes {}):", committee.total_votes).unwrap();
+    for (name, votes) in &committee.voting_rights {
+        writeln!(output, "  {name}: {votes}").unwrap();
+    }
+    Ok((StatusCode::OK, output))
+}
+
+async fn get_watermarks(Extension(node): Extension<AdminNodeHandle>) -> AdminResult {
+    let node = node.get()?;
+    let store = &node.checkpoint_store;
+    let watermarks = store
+        .get_highest_verified_checkpoint()
+        .and_then(|verified| {
+            Ok((
+                verified.map(|c| c.sequence_number()),
+                store.get_highest_synced_checkpoint_seq_number()?,
+                store.get_highest_executed_checkpoint_seq_number()?,
+            ))
+        });
+    match watermarks {
+        Ok((verified, synced, executed)) => Ok((
+            StatusCode::OK,
+            format!(
+                "highest verified checkpoint: {verified:?}\n\
+                 highest synced checkpoint: {synced:?}\n\
+                 highest executed checkpoint: {executed:?}\n"
+            ),
+        )),
+        Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+    }
+}
+
+async fn get_pending_certificates(Extension(node): Extension<AdminNodeHandle>) -> AdminResult {
+    let state = node.get()?.state();
+
+    let mut output = String::new();
+    let pending = state.pending_certificates();
+    writeln!(output, "pending certificates: {}", pending.len()).unwrap();
+    for (digest, missing) in pending {
+        writeln!(output, "  {digest}: missing {missing:?}").unwrap();
+    }
+    let executing = state.executing_certificates();
+    writeln!(output, "executing certificates: {}", executing.len()).unwrap();
+    for digest in executing {
+        writeln!(output, "  {digest}").unwrap();
+    }
+    Ok((StatusCode::OK, output))
+}
+
+async fn checkpoint_db(Extension(node): Extension<AdminNodeHandle>, path: String) -> AdminResult {
+    let database = node.get()?.state().database.clone();
+    let path = PathBuf::from(path.trim());
+    if path.as_os_str().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "missing checkpoint path".into()));
+    }
+    // Checkpoints flush memtables and hard link SST files, which blocks.
+    let result = {
+        let path = path.clone();
+        tokio::task::spawn_blocking(move || database.checkpoint_db(&path)).await
+    };
+    match result {
+        Ok(Ok(())) => {
+            info!(path =% path.display(), "Created authority store checkpoint");
+            Ok((StatusCode::OK, "".into()))
+        }
+        Ok(Err(err)) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+        Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+    }
+}
+
+async fn compact_db(Extension(node): Extension<AdminNodeHandle>) -> AdminResult {
+    let database = node.get()?.state().database.clone();
+    // Manual compactions run on the calling thread until they complete.
+    match tokio::task::spawn_blocking(move || database.compact_objects()).await {
+        Ok(Ok(())) => {
+            info!("Compacted objects table");
+            Ok((StatusCode::OK, "".into()))
+        }
+        Ok(Err(err)) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+        Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+    }
+}
+
+async fn prune_objects(Extension(node): Extension<AdminNodeHandle>) -> AdminResult {
+    match node
+        .get()?
+        .state()
+        .database
+        .prune_objects_immediately()
+        .await
+    {
+        Ok(num_pruned) => Ok((
+            StatusCode::OK,
+            format!("pruned {num_pruned} object versions\n"),
+        )),
+        Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+    }
+}
+
+async fn clear_object_lock(
+    Extension(node): Extension<AdminNodeHandle>,
+    Extension(ClearLockEnabled(enabled)): Extension<ClearLockEnabled>,
+    object_id: String,
+) -> AdminResult {
+    if !enabled {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "clearing object locks is only enabled on full nodes, \
+             or with --enable-admin-clear-lock"
+                .into(),
+        ));
+    }
+    let node = node.get()?;
+    let object_id = ObjectID::from_str(object_id.trim())
+        .map_err(|err| (StatusCode::BAD_REQUEST, err.to_string()))?;
+    match node.state().database.clear_object_lock(object_id).await {
+        Ok((obj_ref, locked_by)) => Ok((
+            StatusCode::OK,
+            format!("cleared lock on {obj_ref:?}, previously held by {locked_by:?}\n"),
+        )),
+        Err(err) => Err((StatusCode::BAD_REQUEST, err.to_string())),
+    }
+}
+
+async fn get_deny_list(Extension(node): Extension<AdminNodeHandle>) -> AdminResult {
+    let deny_config = node.get()?.state().transaction_deny_config().load_full();
+    match serde_yaml::to_string(deny_config.as_ref()) {
+        Ok(yaml) => Ok((StatusCode::OK, yaml)),
+        Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+    }
+}
+
+async fn set_deny_list(
+    Extension(node): Extension<AdminNodeHandle>,
+    deny_list: String,
+) -> AdminResult {
+    let node = node.get()?;
+    match serde_yaml::from_str::<TransactionDenyConfig>(&deny_list) {
+        Ok(deny_config) => {
+            node.state().update_transaction_deny_config(deny_config);
+            Ok((StatusCode::OK, "".into()))
+        }
+        Err(err) => Err((StatusCode::BAD_REQUEST, err.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_admin_server_before_node_starts() {
+        let (_guard, filter_handle) = telemetry_subscribers::TelemetryConfig::new()
+            .with_env()
+            .init();
+        let port = sui_config::utils::get_available_port("127.0.0.1");
+        let _node = start_admin_server(port, filter_handle, false);
+        let url = |route: &str| format!("http://127.0.0.1:{port}{route}");
+        let client = reqwest::Client::new();
+
+        // Wait for the server to be listening.
+        let mut response = client.get(url(LOGGING_ROUTE)).send().await;
+        for _ in 0..50 {
+            if response.is_ok() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            response = client.get(url(LOGGING_ROUTE)).send().await;
+        }
+        assert_eq!(response.unwrap().status(), StatusCode::OK);
+
+        // The log filter can be changed while the node is starting.
+        let response = client
+            .post(url(LOGGING_ROUTE))
+            .body("info,sui_node=debug")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let filter = client
+            .get(url(LOGGING_ROUTE))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(filter.contains("sui_node=debug"), "{filter}");
+
+        for route in [EPOCH_ROUTE, WATERMARKS_ROUTE, DENY_LIST_ROUTE] {
+            let response = client.get(url(route)).send().await.unwrap();
+            assert_eq!(
+                response.status(),
+                StatusCode::SERVICE_UNAVAILABLE,
+                "{route}"
+            );
+        }
+        let response = client.post(url(DB_COMPACT_ROUTE)).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Clearing locks is refused outright on validators.
+        let response = client
+            .post(url(CLEAR_LOCK_ROUTE))
+            .body("0x5")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -19,11 +19,14 @@ use prometheus::Registry;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use sui_config::node::EventStoreConfig;
 use sui_config::{ConsensusConfig, NodeConfig};
 use sui_core::authority_aggregator::{AuthorityAggregator, NetworkTransactionCertifier};
 use sui_core::authority_server::ValidatorService;
 use sui_core::checkpoints::checkpoint_executor;
 use sui_core::epoch::committee_store::CommitteeStore;
+use sui_core::state_accumulator::StateAccumulator;
+use sui_core::state_snapshot::restore_state_snapshot;
 use sui_core::storage::RocksDbStore;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_core::{
@@ -37,19 +40,23 @@ use sui_json_rpc::read_api::FullNodeApi;
 use sui_json_rpc::read_api::ReadApi;
 use sui_json_rpc::transaction_builder_api::FullNodeTransactionBuilderApi;
 use sui_json_rpc::transaction_execution_api::FullNodeTransactionExecutionApi;
+use sui_json_rpc::transaction_streaming_api::TransactionStreamingApiImpl;
 use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle};
 use sui_network::api::ValidatorServer;
 use sui_network::discovery;
 use sui_network::{state_sync, DEFAULT_CONNECT_TIMEOUT_SEC, DEFAULT_HTTP2_KEEPALIVE_SEC};
 
-use sui_protocol_config::ProtocolConfig;
+use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
 
+#[cfg(feature = "postgres")]
+use sui_storage::event_store::PgEventStore;
 use sui_storage::{
     event_store::{EventStoreType, SqlEventStore},
     IndexStore,
 };
 use sui_types::committee::Committee;
 use sui_types::crypto::KeypairTraits;
+use sui_types::messages::{AuthorityCapabilities, ConsensusTransaction};
 use sui_types::quorum_driver_types::QuorumDriverEffectsQueueResult;
 use tokio::sync::{broadcast, mpsc};
 use tokio::sync::{watch, Mutex};
@@ -109,6 +116,7 @@ pub struct SuiNode {
     _discovery: discovery::Handle,
     state_sync: state_sync::Handle,
     checkpoint_store: Arc<CheckpointStore>,
+    accumulator: Arc<StateAccumulator>,
 
     end_of_epoch_channel: tokio::sync::broadcast::Sender<Committee>,
 
@@ -143,6 +151,17 @@ impl SuiNode {
             &genesis_committee,
             None,
         ));
+        let checkpoint_store = CheckpointStore::new(&config.db_path().join("checkpoints"));
+        let restored_snapshot = match &config.state_snapshot_path {
+            Some(snapshot_path) => restore_state_snapshot(
+                snapshot_path,
+                &config.db_path().join("store"),
+                genesis,
+                &committee_store,
+                &checkpoint_store,
+            )?,
+            None => None,
+        };
         let (checkpoint_sender, checkpoint_receiver) = mpsc::channel(10);
         let store = Arc::new(
             AuthorityStore::open(
@@ -152,6 +171,7 @@ impl SuiNode {
                 &committee_store,
                 &config.authority_store_pruning_config,
                 checkpoint_receiver,
+                &prometheus_registry,
             )
             .await?,
         );
@@ -159,7 +179,9 @@ impl SuiNode {
         let committee = committee_store
             .get_committee(&cur_epoch)?
             .expect("Committee of the current epoch must exist");
-        let epoch_start_configuration = if cur_epoch == genesis.epoch() {
+        let epoch_start_configuration = if let Some(snapshot) = &restored_snapshot {
+            Some(snapshot.epoch_start_configuration.clone())
+        } else if cur_epoch == genesis.epoch() {
             let checkpoint = genesis.checkpoint();
             let summary = &checkpoint.summary;
             Some(EpochStartConfiguration {
@@ -178,12 +200,12 @@ impl SuiNode {
             epoch_start_configuration,
         );
 
-        let checkpoint_store = CheckpointStore::new(&config.db_path().join("checkpoints"));
         checkpoint_store.insert_genesis_checkpoint(
             genesis.checkpoint(),
             genesis.checkpoint_contents().clone(),
             &epoch_store,
         );
+        let accumulator = Arc::new(StateAccumulator::new(store.clone()));
         let state_sync_store = RocksDbStore::new(
             store.clone(),
             committee_store.clone(),
@@ -197,17 +219,13 @@ impl SuiNode {
         };
 
         let event_store = if config.enable_event_processing {
-            let path = config.db_path().join("events.db");
-            let db = SqlEventStore::new_from_file(&path).await?;
-            db.initialize().await?;
-
             if index_store.is_none() {
                 return Err(anyhow!(
                     "event storage requires that IndexStore be enabled as well"
                 ));
             }
 
-            Some(Arc::new(EventStoreType::SqlEventStore(db)))
+            Some(Arc::new(Self::open_event_store(config).await?))
         } else {
             None
         };
@@ -226,7 +244,7 @@ impl SuiNode {
             checkpoint_store.clone(),
             &prometheus_registry,
             &config.authority_store_pruning_config,
-            genesis.objects(),
+            config.transaction_deny_config.clone(),
         )
         .await;
 
@@ -280,6 +298,7 @@ impl SuiNode {
                 epoch_store.clone(),
                 checkpoint_store.clone(),
                 state_sync_handle.clone(),
+                accumulator.clone(),
                 &registry_service,
             )
             .await?;
@@ -302,6 +321,7 @@ impl SuiNode {
             _discovery: discovery_handle,
             state_sync: state_sync_handle,
             checkpoint_store,
+            accumulator,
             end_of_epoch_channel,
 
             #[cfg(msim)]
@@ -355,6 +375,30 @@ impl SuiNode {
             .is_transaction_executed_in_checkpoint(digest)
     }
 
+    async fn open_event_store(config: &NodeConfig) -> Result<EventStoreType> {
+        match &config.event_store_config {
+            EventStoreConfig::Sqlite => {
+                let path = config.db_path().join("events.db");
+                let db = SqlEventStore::new_from_file(&path).await?;
+                db.initialize().await?;
+                Ok(EventStoreType::SqlEventStore(db))
+            }
+            #[cfg(feature = "postgres")]
+            EventStoreConfig::Postgres {
+                url,
+                max_connections,
+            } => {
+                let db = PgEventStore::new(url, *max_connections).await?;
+                db.initialize().await?;
+                Ok(EventStoreType::PgEventStore(db))
+            }
+            #[cfg(not(feature = "postgres"))]
+            EventStoreConfig::Postgres { .. } => Err(anyhow!(
+                "the Postgres event store requires sui-node to be built with the `postgres` feature"
+            )),
+        }
+    }
+
     fn create_p2p_network(
         config: &NodeConfig,
         state_sync_store: RocksDbStore,
@@ -452,6 +496,7 @@ impl SuiNode {
         epoch_store: Arc<AuthorityPerEpochStore>,
         checkpoint_store: Arc<CheckpointStore>,
         state_sync_handle: state_sync::Handle,
+        accumulator: Arc<StateAccumulator>,
         registry_service: &RegistryService,
     ) -> Result<ValidatorComponents> {
         let consensus_config = config
@@ -493,6 +538,7 @@ impl SuiNode {
             state_sync_handle,
             narwhal_manager,
             narwhal_epoch_data_remover,
+            accumulator,
             validator_server_handle,
             checkpoint_metrics,
             sui_tx_validator_metrics,
@@ -509,6 +555,7 @@ impl SuiNode {
         state_sync_handle: state_sync::Handle,
         narwhal_manager: NarwhalManager,
         narwhal_epoch_data_remover: EpochDataRemover,
+        accumulator: Arc<StateAccumulator>,
         validator_server_handle: JoinHandle<Result<()>>,
         checkpoint_metrics: Arc<CheckpointMetrics>,
         sui_tx_validator_metrics: Arc<SuiTxValidatorMetrics>,
@@ -520,6 +567,7 @@ impl SuiNode {
             epoch_store.clone(),
             state.clone(),
             state_sync_handle,
+            accumulator,
             checkpoint_metrics.clone(),
         );
 
@@ -553,12 +601,23 @@ impl SuiNode {
                     SharedWorkerCache::from(worker_cache),
                     consensus_handler,
                     SuiTxValidator::new(
-                        epoch_store,
+                        epoch_store.clone(),
                         state.transaction_manager().clone(),
                         sui_tx_validator_metrics.clone(),
                     ),
                 )
                 .await;
+
+            // Advertise the protocol versions supported by this binary, so that the committee
+            // can decide the protocol version of the next epoch.
+            let capabilities =
+                AuthorityCapabilities::new(state.name, SupportedProtocolVersions::SYSTEM_DEFAULT);
+            info!(?capabilities, "Sending capabilities");
+            consensus_adapter.submit(
+                ConsensusTransaction::new_capability_notification(capabilities),
+                None,
+                &epoch_store,
+            )?;
         } else {
             warn!(
                 "Current Sui epoch doesn't match the system state epoch. Not starting Narwhal yet"
@@ -583,6 +642,7 @@ impl SuiNode {
         epoch_store: Arc<AuthorityPerEpochStore>,
         state: Arc<AuthorityState>,
         state_sync_handle: state_sync::Handle,
+        accumulator: Arc<StateAccumulator>,
         checkpoint_metrics: Arc<CheckpointMetrics>,
     ) -> (Arc<CheckpointService>, watch::Sender<()>) {
         let checkpoint_output = Box::new(SubmitCheckpointToConsensus {
@@ -608,6 +668,7 @@ impl SuiNode {
             checkpoint_output,
             Box::new(certified_checkpoint_output),
             Box::new(NetworkTransactionCertifier::default()),
+            accumulator,
             checkpoint_metrics,
             max_tx_per_checkpoint,
         )
@@ -738,9 +799,11 @@ impl SuiNode {
             self.checkpoint_store.clone(),
             self.state.database.clone(),
             self.state.transaction_manager().clone(),
+            self.accumulator.clone(),
             self.config.checkpoint_executor_config.clone(),
             &self.registry_service.default_registry(),
             checkpoint_sender,
+            self.state.index_store().cloned(),
         );
 
         loop {
@@ -813,6 +876,7 @@ impl SuiNode {
                             self.state_sync.clone(),
                             narwhal_manager,
                             narwhal_epoch_data_remover,
+                            self.accumulator.clone(),
                             validator_server_handle,
                             checkpoint_metrics,
                             sui_tx_validator_metrics,
@@ -842,6 +906,7 @@ impl SuiNode {
                             new_epoch_store.clone(),
                             self.checkpoint_store.clone(),
                             self.state_sync.clone(),
+                            self.accumulator.clone(),
                             &self.registry_service,
                         )
                         .await?,
@@ -890,7 +955,10 @@ pub async fn build_server(
 
     let mut server = JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), prometheus_registry)?;
 
-    server.register_module(ReadApi::new(state.clone()))?;
+    server.register_module(ReadApi::new(
+        state.clone(),
+        config.json_rpc_multi_get_max_size,
+    ))?;
     server.register_module(CoinReadApi::new(state.clone()))?;
     server.register_module(FullNodeApi::new(state.clone()))?;
     server.register_module(BcsApiImpl::new(state.clone()))?;
@@ -913,6 +981,13 @@ pub async fn build_server(
         server.register_module(EventStreamingApiImpl::new(state.clone(), event_handler))?;
     }
 
+    if let Some(transaction_streamer) = state.transaction_streamer.clone() {
+        server.register_module(TransactionStreamingApiImpl::new(
+            state.clone(),
+            transaction_streamer,
+        ))?;
+    }
+
     let rpc_server_handle = server.start(config.json_rpc_address).await?;
 
     Ok(Some(rpc_server_handle))
@@ -41,6 +41,12 @@ struct Args {
 
     #[clap(long, help = "Specify address to listen on")]
     listen_address: Option<Multiaddr>,
+
+    #[clap(
+        long,
+        help = "Allow clearing object locks through the admin interface of a validator"
+    )]
+    enable_admin_clear_lock: bool,
 }
 
 #[tokio::main]
@@ -82,9 +88,15 @@ async fn main() -> Result<()> {
         }
     });
 
-    sui_node::admin::start_admin_server(config.admin_interface_port, filter_handle);
+    let admin_node = sui_node::admin::start_admin_server(
+        config.admin_interface_port,
+        filter_handle,
+        !is_validator || args.enable_admin_clear_lock,
+    );
+
+    let node = sui_node::SuiNode::start(&config, registry_service).await?;
+    admin_node.set(node);
 
-    let _node = sui_node::SuiNode::start(&config, registry_service).await?;
     // TODO: Do we want to provide a way for the node to gracefully shutdown?
     loop {
         tokio::time::sleep(Duration::from_secs(1000)).await;
@@ -11,6 +11,7 @@ use sui_core::SUI_CORE_VERSION;
 
 use sui_json_rpc::api::EventReadApiOpenRpc;
 use sui_json_rpc::api::EventStreamingApiOpenRpc;
+use sui_json_rpc::api::TransactionStreamingApiOpenRpc;
 use sui_json_rpc::bcs_api::BcsApiImpl;
 use sui_json_rpc::coin_api::CoinReadApi;
 use sui_json_rpc::governance_api::GovernanceReadApi;
@@ -55,6 +56,7 @@ async fn main() {
     open_rpc.add_module(BcsApiImpl::rpc_doc_module());
     open_rpc.add_module(EventStreamingApiOpenRpc::module_doc());
     open_rpc.add_module(EventReadApiOpenRpc::module_doc());
+    open_rpc.add_module(TransactionStreamingApiOpenRpc::module_doc());
     open_rpc.add_module(FullNodeTransactionExecutionApi::rpc_doc_module());
     open_rpc.add_module(FullNodeTransactionBuilderApi::rpc_doc_module());
     open_rpc.add_module(GovernanceReadApi::rpc_doc_module());
@@ -8,9 +8,11 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 /// The minimum and maximum protocol versions supported by this build.
 pub const MIN_PROTOCOL_VERSION: u64 = 1;
-pub const MAX_PROTOCOL_VERSION: u64 = 1;
+pub const MAX_PROTOCOL_VERSION: u64 = 2;
 
-#[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[derive(
+    Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
 pub struct ProtocolVersion(u64);
 
 impl ProtocolVersion {
@@ -30,6 +32,59 @@ impl ProtocolVersion {
     pub fn as_u64(&self) -> u64 {
         self.0
     }
+
+    /// The version following this one. The result is not checked against the versions supported
+    /// by this binary, as it may be a version that only other validators support so far.
+    pub fn next(&self) -> Self {
+        Self(self.0 + 1)
+    }
+}
+
+/// The range of protocol versions that a validator's binary supports. Validators advertise it to
+/// the rest of the committee, which moves to a new protocol version at the end of an epoch once
+/// enough stake supports it.
+#[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SupportedProtocolVersions {
+    pub min: ProtocolVersion,
+    pub max: ProtocolVersion,
+}
+
+impl SupportedProtocolVersions {
+    /// The versions supported by this binary.
+    pub const SYSTEM_DEFAULT: Self = Self {
+        min: ProtocolVersion::MIN,
+        max: ProtocolVersion::MAX,
+    };
+
+    /// Use only for testing: allows advertising versions this binary may not actually support.
+    pub fn new_for_testing(min: u64, max: u64) -> Self {
+        Self {
+            min: ProtocolVersion(min),
+            max: ProtocolVersion(max),
+        }
+    }
+
+    pub fn is_version_supported(&self, v: ProtocolVersion) -> bool {
+        v >= self.min && v <= self.max
+    }
+}
+
+/// Records on/off feature flags that may start out disabled and be enabled by a later protocol
+/// version. Unlike the constants below, a flag is never absent: it defaults to `false`, so
+/// versions that predate it keep the feature disabled.
+#[derive(Default, Clone, Serialize, Debug)]
+struct FeatureFlags {
+    // Add feature flags here, e.g.:
+    // new_protocol_feature: bool,
+    /// Programmable transactions are accepted for signing.
+    programmable_transactions: bool,
+    /// Move packages can be upgraded, and upgraded packages are encoded with their version and
+    /// linkage.
+    package_upgrades: bool,
+    /// Transactions can pay for gas with several coins, which are smashed into the first one.
+    multiple_gas_coins: bool,
+    /// The last checkpoint of an epoch commits to the root state hash of the epoch.
+    epoch_commitments: bool,
 }
 
 /// Constants that change the behavior of the protocol.
@@ -51,6 +106,8 @@ impl ProtocolVersion {
 /// result in forking if not prevented here).
 #[derive(Clone)]
 pub struct ProtocolConfig {
+    feature_flags: FeatureFlags,
+
     // ==== Move VM, Move bytecode verifier, and execution limits ===
     /// Maximum Move bytecode version the VM understands. All older versions are accepted.
     move_binary_format_version: Option<u32>,
@@ -190,6 +247,25 @@ pub struct ProtocolConfig {
 
 const CONSTANT_ERR_MSG: &str = "protocol constant not present in current protocol version";
 
+// feature flags
+impl ProtocolConfig {
+    pub fn programmable_transactions(&self) -> bool {
+        self.feature_flags.programmable_transactions
+    }
+
+    pub fn package_upgrades(&self) -> bool {
+        self.feature_flags.package_upgrades
+    }
+
+    pub fn multiple_gas_coins(&self) -> bool {
+        self.feature_flags.multiple_gas_coins
+    }
+
+    pub fn epoch_commitments(&self) -> bool {
+        self.feature_flags.epoch_commitments
+    }
+}
+
 // getters
 impl ProtocolConfig {
     pub fn move_binary_format_version(&self) -> u32 {
@@ -365,6 +441,7 @@ impl ProtocolConfig {
         // To change the values here you must create a new protocol version with the new values!
         match version.0 {
             1 => Self {
+                feature_flags: FeatureFlags::default(),
                 move_binary_format_version: Some(6),
                 max_move_object_size: Some(250 * 1024),
                 max_move_package_size: Some(100 * 1024),
@@ -405,6 +482,16 @@ impl ProtocolConfig {
                 // new_constant: None,
             },
 
+            2 => Self {
+                feature_flags: FeatureFlags {
+                    programmable_transactions: true,
+                    package_upgrades: true,
+                    multiple_gas_coins: true,
+                    epoch_commitments: true,
+                },
+                ..Self::get_for_version_impl(ProtocolVersion(1))
+            },
+
             // Use this template when making changes:
             //
             // NEW_VERSION => Self {
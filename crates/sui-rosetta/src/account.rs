@@ -95,9 +95,13 @@ async fn get_sub_account_balances(
                 })
         }
         SubAccountType::LockedSui => {
-            let sui_balance = client.coin_read_api().get_balance(address, None).await?;
+            let sui_balance = client
+                .coin_read_api()
+                .get_balance(address, None, None)
+                .await?;
             sui_balance
                 .locked_balance
+                .unwrap_or_default()
                 .into_iter()
                 .map(|(lock, amount)| (Some(lock), amount))
                 .collect()
@@ -389,6 +389,8 @@ pub enum OperationType {
     EpochChange,
     Genesis,
     ConsensusCommitPrologue,
+    ProgrammableTransaction,
+    Upgrade,
 }
 
 impl From<&SuiTransactionKind> for OperationType {
@@ -406,6 +408,10 @@ impl From<&SuiTransactionKind> for OperationType {
             SuiTransactionKind::ConsensusCommitPrologue(_) => {
                 OperationType::ConsensusCommitPrologue
             }
+            SuiTransactionKind::ProgrammableTransaction(_) => {
+                OperationType::ProgrammableTransaction
+            }
+            SuiTransactionKind::Upgrade(_) => OperationType::Upgrade,
         }
     }
 }
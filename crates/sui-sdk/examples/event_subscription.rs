@@ -13,7 +13,7 @@ async fn main() -> Result<(), anyhow::Error> {
         .await?;
     let mut subscribe_all = sui
         .event_api()
-        .subscribe_event(SuiEventFilter::All(vec![]))
+        .subscribe_event(SuiEventFilter::All(vec![]), None)
         .await?;
     loop {
         println!("{:?}", subscribe_all.next().await);
@@ -14,9 +14,10 @@ use std::time::{Duration, Instant};
 use sui_json_rpc::api::GovernanceReadApiClient;
 use sui_json_rpc_types::{
     Balance, Coin, CoinPage, DynamicFieldPage, EventPage, GetObjectDataResponse,
-    GetPastObjectDataResponse, GetRawObjectDataResponse, SuiCoinMetadata, SuiEventEnvelope,
-    SuiEventFilter, SuiExecuteTransactionResponse, SuiMoveNormalizedModule, SuiObjectInfo,
-    SuiTransactionEffects, SuiTransactionResponse, TransactionsPage,
+    GetPastObjectDataResponse, GetRawObjectDataResponse, ObjectHistoryPage, ObjectsPage,
+    SuiCoinMetadata, SuiEventEnvelope, SuiEventFilter, SuiEventQuery,
+    SuiExecuteTransactionResponse, SuiMoveNormalizedModule, SuiObjectInfo, SuiTransactionEffects,
+    SuiTransactionRead, SuiTransactionResponse, TransactionsPage,
 };
 use sui_types::balance::Supply;
 use sui_types::base_types::{
@@ -25,6 +26,7 @@ use sui_types::base_types::{
 use sui_types::committee::EpochId;
 use sui_types::error::TRANSACTION_NOT_FOUND_MSG_PREFIX;
 use sui_types::event::EventID;
+use sui_types::filter::TransactionFilter;
 use sui_types::messages::{
     CommitteeInfoResponse, ExecuteTransactionRequestType, TransactionData, VerifiedTransaction,
 };
@@ -32,19 +34,22 @@ use sui_types::messages_checkpoint::{
     CheckpointContents, CheckpointContentsDigest, CheckpointDigest, CheckpointSequenceNumber,
     CheckpointSummary,
 };
-use sui_types::query::{EventQuery, TransactionQuery};
+use sui_types::query::TransactionQuery;
 use sui_types::sui_system_state::{SuiSystemState, ValidatorMetadata};
 
 use futures::StreamExt;
 use sui_json_rpc::api::{
     CoinReadApiClient, EventReadApiClient, EventStreamingApiClient, RpcBcsApiClient,
     RpcFullNodeReadApiClient, RpcReadApiClient, TransactionExecutionApiClient,
+    TransactionStreamingApiClient,
 };
 use sui_types::governance::DelegatedStake;
 
+pub use sui_transaction_builder::CoinSelectionStrategy;
+
 #[derive(Debug)]
 pub struct ReadApi {
-    api: Arc<RpcClient>,
+    pub(crate) api: Arc<RpcClient>,
 }
 
 impl ReadApi {
@@ -59,6 +64,20 @@ impl ReadApi {
         Ok(self.api.http.get_objects_owned_by_address(address).await?)
     }
 
+    pub async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        type_filter: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> SuiRpcResult<ObjectsPage> {
+        Ok(self
+            .api
+            .http
+            .get_owned_objects(address, type_filter, cursor, limit)
+            .await?)
+    }
+
     pub async fn get_dynamic_fields(
         &self,
         object_id: ObjectID,
@@ -79,6 +98,13 @@ impl ReadApi {
         Ok(self.api.http.get_object(object_id).await?)
     }
 
+    pub async fn multi_get_parsed_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> SuiRpcResult<Vec<GetObjectDataResponse>> {
+        Ok(self.api.http.multi_get_objects(object_ids).await?)
+    }
+
     pub async fn try_get_parsed_past_object(
         &self,
         object_id: ObjectID,
@@ -91,6 +117,20 @@ impl ReadApi {
             .await?)
     }
 
+    pub async fn get_object_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> SuiRpcResult<ObjectHistoryPage> {
+        Ok(self
+            .api
+            .http
+            .get_object_history(object_id, cursor, limit, Some(descending_order))
+            .await?)
+    }
+
     pub async fn get_object(&self, object_id: ObjectID) -> SuiRpcResult<GetRawObjectDataResponse> {
         Ok(self.api.http.get_raw_object(object_id).await?)
     }
@@ -114,6 +154,29 @@ impl ReadApi {
         Ok(self.api.http.get_transaction(digest).await?)
     }
 
+    pub async fn multi_get_transactions(
+        &self,
+        digests: Vec<TransactionDigest>,
+    ) -> SuiRpcResult<Vec<SuiTransactionRead>> {
+        Ok(self.api.http.multi_get_transactions(digests).await?)
+    }
+
+    pub async fn subscribe_transaction(
+        &self,
+        filter: TransactionFilter,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionResponse>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<SuiTransactionResponse> =
+                    c.subscribe_transaction(filter).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
     pub async fn get_committee_info(
         &self,
         epoch: Option<EpochId>,
@@ -360,16 +423,45 @@ impl CoinReadApi {
         Ok(coins)
     }
 
+    /// Selects coins owned by `address` with a total balance of at least `amount`, picked
+    /// according to `strategy`.
+    pub async fn select_coins_with_strategy(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<String>,
+        amount: u128,
+        strategy: CoinSelectionStrategy,
+        exclude: Vec<ObjectID>,
+    ) -> SuiRpcResult<Vec<Coin>> {
+        let coins = self
+            .get_coins_stream(address, coin_type)
+            .filter(|coin: &Coin| future::ready(!exclude.contains(&coin.coin_object_id)))
+            .collect::<Vec<_>>()
+            .await;
+        strategy
+            .select(coins, amount)
+            .ok_or(Error::InsufficientFund { address, amount })
+    }
+
     pub async fn get_balance(
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
+        checkpoint: Option<CheckpointSequenceNumber>,
     ) -> SuiRpcResult<Balance> {
-        Ok(self.api.http.get_balance(owner, coin_type).await?)
+        Ok(self
+            .api
+            .http
+            .get_balance(owner, coin_type, checkpoint)
+            .await?)
     }
 
-    pub async fn get_all_balances(&self, owner: SuiAddress) -> SuiRpcResult<Vec<Balance>> {
-        Ok(self.api.http.get_all_balances(owner).await?)
+    pub async fn get_all_balances(
+        &self,
+        owner: SuiAddress,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> SuiRpcResult<Vec<Balance>> {
+        Ok(self.api.http.get_all_balances(owner, checkpoint).await?)
     }
 
     pub async fn get_coin_metadata(&self, coin_type: String) -> SuiRpcResult<SuiCoinMetadata> {
@@ -394,11 +486,12 @@ impl EventApi {
     pub async fn subscribe_event(
         &self,
         filter: SuiEventFilter,
+        cursor: Option<EventID>,
     ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEventEnvelope>>> {
         match &self.api.ws {
             Some(c) => {
                 let subscription: Subscription<SuiEventEnvelope> =
-                    c.subscribe_event(filter).await?;
+                    c.subscribe_event(filter, cursor).await?;
                 Ok(subscription.map(|item| Ok(item?)))
             }
             _ => Err(Error::Subscription(
@@ -407,9 +500,10 @@ impl EventApi {
         }
     }
 
+    /// Query events with either a single `EventQuery` or a `SuiEventFilter` tree.
     pub async fn get_events(
         &self,
-        query: EventQuery,
+        query: impl Into<SuiEventQuery>,
         cursor: Option<EventID>,
         limit: Option<usize>,
         descending_order: bool,
@@ -417,18 +511,18 @@ impl EventApi {
         Ok(self
             .api
             .http
-            .get_events(query, cursor, limit, Some(descending_order))
+            .get_events(query.into(), cursor, limit, Some(descending_order))
             .await?)
     }
 
     pub fn get_events_stream(
         &self,
-        query: EventQuery,
+        query: impl Into<SuiEventQuery>,
         cursor: Option<EventID>,
         descending_order: bool,
     ) -> impl Stream<Item = SuiEventEnvelope> + '_ {
         stream::unfold(
-            (vec![], cursor, true, query),
+            (vec![], cursor, true, query.into()),
             move |(mut data, cursor, first, query)| async move {
                 if let Some(item) = data.pop() {
                     Some((item, (data, cursor, false, query)))
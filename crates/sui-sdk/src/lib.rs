@@ -20,10 +20,11 @@ pub use sui_json as json;
 
 use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriver, ReadApi};
 pub use sui_json_rpc_types as rpc_types;
-use sui_json_rpc_types::{GetRawObjectDataResponse, SuiObjectInfo};
+use sui_json_rpc_types::{Coin, GetRawObjectDataResponse, SuiObjectInfo};
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::gas_coin::GAS;
 
 pub mod apis;
 pub mod error;
@@ -280,6 +281,23 @@ impl DataReader for ReadApi {
         Ok(self.get_object(object_id).await?)
     }
 
+    async fn get_gas_coins(&self, address: SuiAddress) -> Result<Vec<Coin>, anyhow::Error> {
+        let coin_read_api = CoinReadApi::new(self.api.clone());
+        let coin_type = Some(GAS::type_().to_string());
+        let mut coins = vec![];
+        let mut cursor = None;
+        loop {
+            let page = coin_read_api
+                .get_coins(address, coin_type.clone(), cursor, None)
+                .await?;
+            coins.extend(page.data);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                return Ok(coins);
+            }
+        }
+    }
+
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.get_reference_gas_price().await?)
     }
@@ -5,10 +5,13 @@ use std::str::FromStr;
 use tempfile::TempDir;
 
 use fastcrypto::hash::{HashFunction, Sha3_256};
+use sui_json_rpc_types::Coin;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::apis::CoinSelectionStrategy;
+use sui_sdk::SUI_COIN_TYPE;
 use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
 use sui_types::{
-    base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
+    base_types::{random_object_ref, SuiAddress, TransactionDigest, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
 };
 #[test]
@@ -68,3 +71,55 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn coin_selection_strategies_test() {
+    let coin = |balance: u64, locked_until_epoch: Option<u64>| {
+        let (coin_object_id, version, digest) = random_object_ref();
+        Coin {
+            coin_type: SUI_COIN_TYPE.to_string(),
+            coin_object_id,
+            version,
+            digest,
+            balance,
+            locked_until_epoch,
+            previous_transaction: TransactionDigest::random(),
+        }
+    };
+    let coins = vec![
+        coin(10, None),
+        coin(50, None),
+        coin(30, None),
+        coin(100, Some(5)),
+        coin(40, Some(2)),
+    ];
+    let balances = |strategy: CoinSelectionStrategy, amount: u128| {
+        strategy
+            .select(coins.clone(), amount)
+            .map(|selected| selected.iter().map(|c| c.balance).collect::<Vec<_>>())
+    };
+
+    assert_eq!(
+        Some(vec![100]),
+        balances(CoinSelectionStrategy::LargestFirst, 20)
+    );
+    assert_eq!(
+        Some(vec![100, 50, 40]),
+        balances(CoinSelectionStrategy::LargestFirst, 160)
+    );
+    assert_eq!(None, balances(CoinSelectionStrategy::LargestFirst, 250));
+    assert_eq!(
+        Some(vec![30]),
+        balances(CoinSelectionStrategy::FewestInputs, 20)
+    );
+    assert_eq!(
+        Some(vec![100, 50, 10]),
+        balances(CoinSelectionStrategy::FewestInputs, 160)
+    );
+    // Locked coins are never selected when avoiding them.
+    assert_eq!(
+        Some(vec![50, 30]),
+        balances(CoinSelectionStrategy::AvoidLocked, 60)
+    );
+    assert_eq!(None, balances(CoinSelectionStrategy::AvoidLocked, 100));
+}
@@ -0,0 +1,31 @@
+-- Events table, with the same columns as the SQLite event store.
+-- `fields` holds the Move event's JSON value, or the extra fields of other events.
+CREATE TABLE IF NOT EXISTS events (
+    timestamp BIGINT NOT NULL,
+    seq_num BIGINT NOT NULL,
+    event_num BIGINT NOT NULL,
+    tx_digest BYTEA NOT NULL,
+    event_type SMALLINT NOT NULL,
+    package_id BYTEA,
+    module_name TEXT,
+    function TEXT,
+    object_type TEXT,
+    object_id BYTEA,
+    fields JSONB NOT NULL,
+    move_event_name TEXT,
+    contents BYTEA,
+    sender BYTEA,
+    recipient TEXT,
+    PRIMARY KEY (seq_num, event_num)
+);
+
+CREATE INDEX IF NOT EXISTS timestamp_idx ON events (timestamp);
+CREATE INDEX IF NOT EXISTS tx_digest_idx ON events (tx_digest);
+CREATE INDEX IF NOT EXISTS event_type_idx ON events (event_type);
+CREATE INDEX IF NOT EXISTS package_id_idx ON events (package_id);
+CREATE INDEX IF NOT EXISTS module_name_idx ON events (module_name);
+CREATE INDEX IF NOT EXISTS sender_idx ON events (sender);
+CREATE INDEX IF NOT EXISTS recipient_idx ON events (recipient);
+CREATE INDEX IF NOT EXISTS object_id_idx ON events (object_id);
+CREATE INDEX IF NOT EXISTS object_type_idx ON events (object_type);
+CREATE INDEX IF NOT EXISTS move_event_name_idx ON events (move_event_name);
@@ -13,6 +13,7 @@
 
 use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::time::Duration;
 use std::usize;
 
 use anyhow::anyhow;
@@ -27,6 +28,8 @@ use move_core_types::value::MoveValue;
 use serde_json::Value;
 use tokio_stream::StreamExt;
 
+#[cfg(feature = "postgres")]
+pub use postgres::PgEventStore;
 pub use sql::SqlEventStore;
 use sui_json_rpc_types::{SuiEvent, SuiEventEnvelope};
 use sui_types::base_types::{
@@ -36,8 +39,11 @@ use sui_types::error::SuiError;
 use sui_types::error::SuiError::{StorageCorruptedFieldError, StorageMissingFieldError};
 use sui_types::event::{BalanceChangeType, Event, EventID};
 use sui_types::event::{EventEnvelope, EventType};
+use sui_types::filter::EventFilter;
 use sui_types::object::Owner;
 
+#[cfg(feature = "postgres")]
+pub mod postgres;
 pub mod sql;
 pub mod test_utils;
 
@@ -94,6 +100,18 @@ pub struct StoredEvent {
 }
 
 impl StoredEvent {
+    pub fn id(&self) -> &EventID {
+        &self.id
+    }
+
+    pub fn seq_num(&self) -> i64 {
+        self.seq_num
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
     pub fn into_move_event(self) -> Result<SuiEvent, anyhow::Error> {
         let package_id = self.package_id()?;
         let transaction_module = self.transaction_module()?;
@@ -516,6 +534,17 @@ pub trait EventStore {
         descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
+    /// Returns at most `limit` events matching an `EventFilter` tree, the same filter language
+    /// used by live event subscriptions, sorted in time order defined by the descending parameter.
+    async fn events_by_filter(
+        &self,
+        filter: &EventFilter,
+        tx_seq: i64,
+        event_seq: i64,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
     /// Generic event iterator that returns events emitted between
     /// `[start_time, end_time)`, sorted in time order defined by the descending parameter.
     async fn event_iterator(
@@ -534,6 +563,20 @@ pub trait EventStore {
 #[enum_dispatch(EventStore)]
 pub enum EventStoreType {
     SqlEventStore,
+    #[cfg(feature = "postgres")]
+    PgEventStore,
+}
+
+impl EventStoreType {
+    /// Runs the periodic maintenance needed by the backend, if any, every `interval`.
+    pub async fn maintenance_task(&self, interval: Duration) {
+        match self {
+            // SQLite needs its WAL truncated regularly
+            EventStoreType::SqlEventStore(db) => db.wal_cleanup_thread(Some(interval)).await,
+            #[cfg(feature = "postgres")]
+            EventStoreType::PgEventStore(_) => {}
+        }
+    }
 }
 
 /// A wrapper around streaming results which makes them easier to deal with
@@ -279,7 +279,7 @@ impl SqlEventStore {
     }
 
     // convert an event's extra fields into a stringified JSON Value.
-    fn event_to_json(event: &EventEnvelope) -> String {
+    pub(crate) fn event_to_json(event: &EventEnvelope) -> String {
         // For move events, we only store the move_struct_json_value
         if let Some(json_value) = &event.move_struct_json_value {
             json_value.to_string()
@@ -670,6 +670,42 @@ impl EventStore for SqlEventStore {
             .map_err(convert_sqlx_err)?;
         Ok(rows)
     }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_filter(
+        &self,
+        filter: &EventFilter,
+        tx_seq: i64,
+        event_seq: i64,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let _guard = self.query_lock.read().await;
+        let mut binds = vec![];
+        let clause = filter_clause(filter, &mut binds)?;
+        let query = get_event_query_with_clause(&clause, descending);
+        // Filter trees produce arbitrary query strings, don't fill the statement cache with them.
+        let mut query = sqlx::query(&query)
+            .persistent(false)
+            .bind(tx_seq)
+            .bind(tx_seq)
+            .bind(event_seq);
+        for bind in binds {
+            query = match bind {
+                FilterBind::Int(value) => query.bind(value),
+                FilterBind::Real(value) => query.bind(value),
+                FilterBind::Text(value) => query.bind(value),
+                FilterBind::Blob(value) => query.bind(value),
+            };
+        }
+        let rows = query
+            .bind(limit as i64)
+            .map(StoredEvent::from)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(rows)
+    }
 }
 
 fn convert_sqlx_err(err: sqlx::Error) -> SuiError {
@@ -677,6 +713,15 @@ fn convert_sqlx_err(err: sqlx::Error) -> SuiError {
 }
 
 fn get_event_query(causes: Vec<(&str, Comparator)>, descending: bool) -> String {
+    let causes = causes
+        .iter()
+        .map(|(cause, cmp)| format!("{cause} {cmp} ?"))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    get_event_query_with_clause(&causes, descending)
+}
+
+fn get_event_query_with_clause(clause: &str, descending: bool) -> String {
     let (seq_num_cmp, event_num_cmp, order) = if descending {
         (Comparator::LessThan, Comparator::LessThanOrEq, "DESC")
     } else {
@@ -685,21 +730,169 @@ fn get_event_query(causes: Vec<(&str, Comparator)>, descending: bool) -> String
     let mut query = format!(
         "SELECT * FROM events WHERE (seq_num {seq_num_cmp} ? OR (seq_num = ? AND event_num {event_num_cmp} ?))"
     );
-    if !causes.is_empty() {
+    if !clause.is_empty() {
         query.push_str(" AND ");
+        query.push_str(clause);
     }
-    let causes = causes
-        .iter()
-        .map(|(cause, cmp)| format!("{cause} {cmp} ?"))
-        .collect::<Vec<_>>()
-        .join(" AND ");
-    query.push_str(&causes);
     query.push_str(&format!(
         " ORDER BY seq_num {order}, event_num {order} LIMIT ?"
     ));
     query
 }
 
+/// A value bound to a `?` placeholder of a filter clause.
+enum FilterBind {
+    Int(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// Translates an `EventFilter` tree into a boolean SQL expression, pushing the values of its
+/// `?` placeholders into `binds` in order.  Every criterion except Move event fields maps onto
+/// an indexed column; Move event fields are read from the `fields` JSON column.
+fn filter_clause(filter: &EventFilter, binds: &mut Vec<FilterBind>) -> Result<String, SuiError> {
+    Ok(match filter {
+        EventFilter::Package(package_id) => {
+            binds.push(FilterBind::Blob(package_id.to_vec()));
+            "package_id = ?".to_string()
+        }
+        EventFilter::Module(module) => {
+            binds.push(FilterBind::Text(module.to_string()));
+            "module_name = ?".to_string()
+        }
+        EventFilter::MoveEventType(struct_tag) => {
+            binds.push(FilterBind::Text(struct_tag.to_string()));
+            "move_event_name = ?".to_string()
+        }
+        EventFilter::EventType(event_type) => {
+            binds.push(FilterBind::Int(*event_type as i64));
+            "event_type = ?".to_string()
+        }
+        EventFilter::SenderAddress(sender) => {
+            binds.push(FilterBind::Blob(sender.to_vec()));
+            "sender = ?".to_string()
+        }
+        EventFilter::Recipient(recipient) => {
+            let recipient_str =
+                serde_json::to_string(recipient).map_err(|e| SuiError::OwnerFailedToSerialize {
+                    error: e.to_string(),
+                })?;
+            binds.push(FilterBind::Text(recipient_str));
+            "recipient = ?".to_string()
+        }
+        EventFilter::ObjectId(object_id) => {
+            binds.push(FilterBind::Blob(object_id.to_vec()));
+            "object_id = ?".to_string()
+        }
+        EventFilter::Transaction(digest) => {
+            binds.push(FilterBind::Blob(digest.into_inner().to_vec()));
+            "tx_digest = ?".to_string()
+        }
+        EventFilter::TimeRange {
+            start_time,
+            end_time,
+        } => {
+            binds.push(FilterBind::Int(*start_time as i64));
+            binds.push(FilterBind::Int(*end_time as i64));
+            "(timestamp >= ? AND timestamp < ?)".to_string()
+        }
+        EventFilter::MoveEventField { path, value } => {
+            binds.push(FilterBind::Text(json_pointer_to_path(path)?));
+            match value {
+                Value::Null => "json_type(fields, ?) = 'null'".to_string(),
+                Value::Bool(b) => {
+                    binds.push(FilterBind::Int(*b as i64));
+                    "json_extract(fields, ?) = ?".to_string()
+                }
+                Value::Number(n) => {
+                    binds.push(match n.as_i64() {
+                        Some(n) => FilterBind::Int(n),
+                        None => FilterBind::Real(n.as_f64().unwrap_or(f64::NAN)),
+                    });
+                    "json_extract(fields, ?) = ?".to_string()
+                }
+                Value::String(s) => {
+                    binds.push(FilterBind::Text(s.clone()));
+                    "json_extract(fields, ?) = ?".to_string()
+                }
+                // Objects and arrays are extracted as minified JSON text.
+                Value::Array(_) | Value::Object(_) => {
+                    binds.push(FilterBind::Text(value.to_string()));
+                    "json_extract(fields, ?) = json(?)".to_string()
+                }
+            }
+        }
+        EventFilter::MoveEventFieldGreaterThan { path, value } => {
+            numeric_field_clause(path, Comparator::MoreThan, *value, binds)?
+        }
+        EventFilter::MoveEventFieldLessThan { path, value } => {
+            numeric_field_clause(path, Comparator::LessThan, *value, binds)?
+        }
+        EventFilter::MatchAll(filters) => join_filter_clauses(filters, " AND ", "1", binds)?,
+        EventFilter::MatchAny(filters) => join_filter_clauses(filters, " OR ", "0", binds)?,
+    })
+}
+
+fn join_filter_clauses(
+    filters: &[EventFilter],
+    separator: &str,
+    empty: &str,
+    binds: &mut Vec<FilterBind>,
+) -> Result<String, SuiError> {
+    if filters.is_empty() {
+        return Ok(empty.to_string());
+    }
+    let clauses = filters
+        .iter()
+        .map(|f| filter_clause(f, binds))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(format!("({})", clauses.join(separator)))
+}
+
+/// Compares a Move integer field, which is stored either as a JSON number or, for integers wider
+/// than u32, as a string of digits.
+fn numeric_field_clause(
+    path: &str,
+    cmp: Comparator,
+    value: u64,
+    binds: &mut Vec<FilterBind>,
+) -> Result<String, SuiError> {
+    let value = i64::try_from(value).map_err(|_| SuiError::InvalidEventFilter {
+        error: format!("Field comparison value {value} is out of range"),
+    })?;
+    let path = json_pointer_to_path(path)?;
+    binds.push(FilterBind::Text(path.clone()));
+    binds.push(FilterBind::Text(path));
+    binds.push(FilterBind::Int(value));
+    Ok(format!(
+        "(json_extract(fields, ?) NOT GLOB '*[^0-9]*' AND CAST(json_extract(fields, ?) AS INTEGER) {cmp} ?)"
+    ))
+}
+
+/// Converts a JSON pointer (e.g. `/a/b/0`), as used by the in-memory event filters, into a
+/// SQLite JSON path (e.g. `$."a"."b"[0]`).
+fn json_pointer_to_path(pointer: &str) -> Result<String, SuiError> {
+    if pointer.is_empty() {
+        return Ok("$".to_string());
+    }
+    let Some(tokens) = pointer.strip_prefix('/') else {
+        return Err(SuiError::InvalidEventFilter {
+            error: format!("Invalid JSON pointer [{pointer}], it must start with '/'"),
+        });
+    };
+    let mut path = "$".to_string();
+    for token in tokens.split('/') {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        if !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit()) {
+            path.push_str(&format!("[{token}]"));
+        } else {
+            path.push_str(&format!(".\"{token}\""));
+        }
+    }
+    Ok(path)
+}
+
 enum Comparator {
     Equal,
     LessThanOrEq,
@@ -727,6 +920,7 @@ mod tests {
     use move_core_types::{account_address::AccountAddress, identifier::Identifier};
 
     use sui_types::event::EventEnvelope;
+    use sui_types::filter::Filter;
 
     use super::test_utils;
     use super::*;
@@ -1405,6 +1599,107 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_eventstore_query_by_filter() -> Result<(), SuiError> {
+        telemetry_subscribers::init_for_testing();
+
+        // Initialize store
+        let db = SqlEventStore::new_memory_only_not_prod().await?;
+        db.initialize().await?;
+
+        // Insert some records
+        info!("Inserting records!");
+        let package_id = ObjectID::from_hex_literal("0x42").unwrap();
+        let digest = TransactionDigest::random();
+        let mut to_insert = vec![];
+        for (i, amount) in ["100", "200", "300"].iter().enumerate() {
+            let mut event = test_utils::new_test_move_event(
+                1_000_000 + i as u64 * 1_000,
+                if i == 0 {
+                    digest
+                } else {
+                    TransactionDigest::random()
+                },
+                i as u64 + 1,
+                0, // event_num
+                package_id,
+                "query_by_filter",
+                "test_foo",
+            );
+            // u64 fields are rendered as strings of digits
+            if let Some(Value::Object(fields)) = &mut event.move_struct_json_value {
+                fields.insert("amount".to_string(), json!(amount));
+                fields.insert("kind".to_string(), json!("coin"));
+            }
+            to_insert.push(event);
+        }
+        to_insert.push(test_utils::new_test_publish_event(
+            1_003_000,
+            TransactionDigest::random(),
+            4,
+            0, // event_num
+            None,
+        ));
+
+        assert_eq!(db.add_events(&to_insert).await?, 4);
+        info!("Done inserting");
+
+        // Each filter must select the same events from the store as it matches in memory.
+        let check = |filter: &EventFilter, events: &[StoredEvent], expected: &[usize]| {
+            assert_eq!(events.len(), expected.len(), "{filter:?}");
+            for (event, i) in events.iter().zip(expected) {
+                test_queried_event_vs_test_envelope(event, &to_insert[*i]);
+            }
+            let matched = (0..to_insert.len())
+                .filter(|i| filter.matches(&to_insert[*i]))
+                .collect::<Vec<_>>();
+            assert_eq!(matched, expected, "{filter:?}");
+        };
+
+        let filter = EventFilter::MatchAll(vec![
+            EventFilter::Package(package_id),
+            EventFilter::MoveEventFieldGreaterThan {
+                path: "/amount".to_string(),
+                value: 150,
+            },
+        ]);
+        let events = db.events_by_filter(&filter, 0, 0, 10, false).await?;
+        check(&filter, &events, &[1, 2]);
+
+        let filter = EventFilter::MatchAny(vec![
+            EventFilter::Transaction(digest),
+            EventFilter::TimeRange {
+                start_time: 1_002_000,
+                end_time: 1_004_000,
+            },
+        ]);
+        let events = db.events_by_filter(&filter, 0, 0, 10, false).await?;
+        check(&filter, &events, &[0, 2, 3]);
+
+        let filter = EventFilter::MatchAll(vec![
+            EventFilter::MoveEventField {
+                path: "/kind".to_string(),
+                value: json!("coin"),
+            },
+            EventFilter::MoveEventFieldLessThan {
+                path: "/amount".to_string(),
+                value: 300,
+            },
+            EventFilter::EventType(EventType::MoveEvent),
+        ]);
+        let events = db.events_by_filter(&filter, 0, 0, 10, false).await?;
+        check(&filter, &events, &[0, 1]);
+
+        // Cursor and ordering apply on top of the filter
+        let events = db
+            .events_by_filter(&filter, i64::MAX, i64::MAX, 1, true)
+            .await?;
+        assert_eq!(events.len(), 1);
+        test_queried_event_vs_test_envelope(&events[0], &to_insert[1]);
+
+        Ok(())
+    }
+
     // Test we can retrieve u64 object version (aka sequence number) values
     // stored as string in sqlite
     #[tokio::test]
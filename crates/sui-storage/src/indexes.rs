@@ -6,11 +6,13 @@
 
 use anyhow::anyhow;
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use serde::{de::DeserializeOwned, Serialize};
 use std::cmp::min;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
-use tracing::debug;
+use tracing::{debug, info};
 use typed_store::rocks::DBOptions;
 use typed_store::rocks::{DBMap, MetricConf};
 use typed_store::traits::Map;
@@ -18,22 +20,48 @@ use typed_store::traits::{TableSummary, TypedStoreDebug};
 use typed_store_derive::DBMapUtils;
 
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest, TxSequenceNumber};
-use sui_types::base_types::{ObjectInfo, ObjectRef};
+use sui_types::base_types::{ObjectInfo, ObjectRef, ObjectType};
 use sui_types::dynamic_field::DynamicFieldInfo;
 use sui_types::error::{SuiError, SuiResult};
+use sui_types::event::Event;
 use sui_types::fp_ensure;
+use sui_types::messages::TransactionEffects;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
-use sui_types::query::TransactionQuery;
+use sui_types::parse_sui_struct_tag;
+use sui_types::query::{ObjectTypeFilter, TransactionQuery};
 
 use crate::default_db_options;
 
 type OwnerIndexKey = (SuiAddress, ObjectID);
+/// Owner, then the package, module, struct name and type parameters of the object's type, then object ID.
+type OwnerTypeIndexKey = (SuiAddress, ObjectID, String, String, Vec<TypeTag>, ObjectID);
 type DynamicFieldKey = (ObjectID, ObjectID);
+/// Owner, coin type, then the checkpoint changing the balance.
+type BalanceIndexKey = (SuiAddress, String, CheckpointSequenceNumber);
+type TransactionIter<'a> = Box<dyn Iterator<Item = (TxSequenceNumber, TransactionDigest)> + 'a>;
 
 pub const MAX_TX_RANGE_SIZE: u64 = 4096;
 
 pub const MAX_GET_OWNED_OBJECT_SIZE: usize = 256;
 
+/// Maximum number of transactions scanned by a combined query, whether they match or not.
+pub const MAX_TX_QUERY_SCAN_SIZE: usize = 100_000;
+
+const BACKFILL_BATCH_SIZE: usize = 10_000;
+
+const BALANCE_INDEX_START_KEY: u64 = 0;
+
+/// Name of the backfill building `owner_type_index` from `owner_index`.
+const OWNER_TYPE_INDEX_BACKFILL: &str = "owner_type_index";
+/// Name of the backfill building `transactions_by_timestamp` from `timestamps`.
+const TRANSACTIONS_BY_TIMESTAMP_BACKFILL: &str = "transactions_by_timestamp";
+/// Name of the backfill seeding `balance_index` from the coins owned at a checkpoint.
+const BALANCE_INDEX_BACKFILL: &str = "balance_index";
+/// Name of the backfill building the checkpoint and Move event indexes of transactions.
+const TRANSACTION_CHECKPOINT_AND_MOVE_EVENT_BACKFILL: &str =
+    "transaction_checkpoint_and_move_event";
+
 pub struct ObjectIndexChanges {
     pub deleted_owners: Vec<OwnerIndexKey>,
     pub deleted_dynamic_fields: Vec<DynamicFieldKey>,
@@ -71,6 +99,32 @@ pub struct IndexStoreTables {
     #[default_options_override_fn = "timestamps_table_default_config"]
     timestamps: DBMap<TransactionDigest, u64>,
 
+    /// Index from timestamp to transactions indexed at that time, see `timestamps`.
+    #[default_options_override_fn = "transactions_by_timestamp_table_default_config"]
+    transactions_by_timestamp: DBMap<(u64, TxSequenceNumber), TransactionDigest>,
+
+    /// Index from checkpoint sequence number to transactions included in that checkpoint.
+    #[default_options_override_fn = "transactions_by_checkpoint_table_default_config"]
+    transactions_by_checkpoint:
+        DBMap<(CheckpointSequenceNumber, TxSequenceNumber), TransactionDigest>,
+
+    /// Index from transaction digest to the checkpoint that includes it.
+    #[default_options_override_fn = "transaction_checkpoint_table_default_config"]
+    transaction_checkpoint: DBMap<TransactionDigest, CheckpointSequenceNumber>,
+
+    /// Index from Move event struct type to transactions that emitted an event of that type.
+    #[default_options_override_fn = "transactions_by_move_event_table_default_config"]
+    transactions_by_move_event: DBMap<(String, TxSequenceNumber), TransactionDigest>,
+
+    /// Balance of the coins of each type owned by an address, after each checkpoint changing it.
+    /// A balance at any checkpoint is the one of the closest checkpoint at or before it.
+    #[default_options_override_fn = "balance_index_table_default_config"]
+    balance_index: DBMap<BalanceIndexKey, i128>,
+
+    /// The checkpoint `balance_index` was seeded at, under `BALANCE_INDEX_START_KEY`. Balances
+    /// before it are unknown.
+    balance_index_start: DBMap<u64, CheckpointSequenceNumber>,
+
     /// Ordering of all indexed transactions.
     #[default_options_override_fn = "transactions_order_table_default_config"]
     transaction_order: DBMap<TxSequenceNumber, TransactionDigest>,
@@ -86,12 +140,22 @@ pub struct IndexStoreTables {
     #[default_options_override_fn = "owner_index_table_default_config"]
     owner_index: DBMap<OwnerIndexKey, ObjectInfo>,
 
+    /// This is a secondary index of `owner_index`, keyed by the owner, the components of the Move
+    /// type of the objects and their object ID. It allows listing the objects owned by a specific
+    /// user by package, module, struct or exact type, without scanning all their objects.
+    #[default_options_override_fn = "owner_type_index_table_default_config"]
+    owner_type_index: DBMap<OwnerTypeIndexKey, ObjectInfo>,
+
     /// This is an index of object references to currently existing dynamic field object, indexed by the
     /// composite key of the object ID of their parent and the object ID of the dynamic field object.
     /// This composite index allows an efficient iterator to list all objects currently owned
     /// by a specific object, and their object reference.
     #[default_options_override_fn = "dynamic_field_index_table_default_config"]
     dynamic_field_index: DBMap<DynamicFieldKey, DynamicFieldInfo>,
+
+    /// Names of the backfills of indexes added after the database was created which ran to
+    /// completion. An interrupted backfill is not recorded, and runs again on the next start.
+    completed_backfills: DBMap<String, ()>,
 }
 
 pub struct IndexStore {
@@ -124,9 +188,27 @@ fn transactions_by_move_function_table_default_config() -> DBOptions {
 fn timestamps_table_default_config() -> DBOptions {
     default_db_options(None, Some(1_000_000)).1
 }
+fn transactions_by_timestamp_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn transactions_by_checkpoint_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn transaction_checkpoint_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).1
+}
+fn transactions_by_move_event_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn balance_index_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
 fn owner_index_table_default_config() -> DBOptions {
     default_db_options(None, Some(1_000_000)).0
 }
+fn owner_type_index_table_default_config() -> DBOptions {
+    default_db_options(None, Some(1_000_000)).0
+}
 fn dynamic_field_index_table_default_config() -> DBOptions {
     default_db_options(None, Some(1_000_000)).0
 }
@@ -144,18 +226,150 @@ impl IndexStore {
             .unwrap_or(0)
             .into();
 
+        Self::run_backfill(
+            &tables,
+            OWNER_TYPE_INDEX_BACKFILL,
+            Self::backfill_owner_type_index,
+        )
+        .expect("Failed to build owner type index");
+        Self::run_backfill(
+            &tables,
+            TRANSACTIONS_BY_TIMESTAMP_BACKFILL,
+            Self::backfill_transactions_by_timestamp,
+        )
+        .expect("Failed to build transactions by timestamp index");
+
         Self {
             tables,
             next_sequence_number,
         }
     }
 
+    /// Run the backfill `name` unless it already completed. Backfills run synchronously when the
+    /// store is opened, before any new transaction is indexed, which may delay the start of a
+    /// node with a large database the first time. They must be idempotent, since an interrupted
+    /// backfill runs again from the start.
+    fn run_backfill(
+        tables: &IndexStoreTables,
+        name: &str,
+        backfill: impl FnOnce(&IndexStoreTables) -> SuiResult,
+    ) -> SuiResult {
+        if tables.completed_backfills.contains_key(&name.to_string())? {
+            return Ok(());
+        }
+        backfill(tables)?;
+        tables.completed_backfills.insert(&name.to_string(), &())?;
+        Ok(())
+    }
+
+    /// Build the owner type index from the owner index, for databases created before it existed.
+    fn backfill_owner_type_index(tables: &IndexStoreTables) -> SuiResult {
+        info!("Building owner type index from owner index");
+        let mut entries = tables.owner_index.iter().peekable();
+        while entries.peek().is_some() {
+            let batch = tables.owner_type_index.batch().insert_batch(
+                &tables.owner_type_index,
+                entries
+                    .by_ref()
+                    .take(BACKFILL_BATCH_SIZE)
+                    .filter_map(|((owner, _), info)| {
+                        owner_type_index_key(owner, &info).map(|key| (key, info))
+                    }),
+            )?;
+            batch.write()?;
+        }
+        Ok(())
+    }
+
+    /// Build the transactions by timestamp index from the timestamps of indexed transactions, for
+    /// databases created before it existed.
+    fn backfill_transactions_by_timestamp(tables: &IndexStoreTables) -> SuiResult {
+        info!("Building transactions by timestamp index");
+        let mut transactions = tables.transaction_order.iter().peekable();
+        while transactions.peek().is_some() {
+            let chunk: Vec<_> = transactions.by_ref().take(BACKFILL_BATCH_SIZE).collect();
+            let timestamps = tables
+                .timestamps
+                .multi_get(chunk.iter().map(|(_, digest)| digest))?;
+            let batch = tables.transactions_by_timestamp.batch().insert_batch(
+                &tables.transactions_by_timestamp,
+                chunk
+                    .iter()
+                    .zip(timestamps)
+                    .filter_map(|((seq, digest), timestamp)| {
+                        timestamp.map(|timestamp| ((timestamp, *seq), *digest))
+                    }),
+            )?;
+            batch.write()?;
+        }
+        Ok(())
+    }
+
+    /// Build the checkpoint and Move event indexes of the transactions indexed before they
+    /// existed, unless this already completed. `lookup` returns the checkpoint including each
+    /// transaction, if any, and the types of the Move events it emitted.
+    pub fn backfill_checkpoints_and_move_events(
+        &self,
+        lookup: impl Fn(
+            &[TransactionDigest],
+        ) -> SuiResult<Vec<(Option<CheckpointSequenceNumber>, Vec<StructTag>)>>,
+    ) -> SuiResult {
+        Self::run_backfill(
+            &self.tables,
+            TRANSACTION_CHECKPOINT_AND_MOVE_EVENT_BACKFILL,
+            |tables| {
+                info!("Building transaction checkpoint and Move event indexes");
+                let mut transactions = tables.transaction_order.iter().peekable();
+                while transactions.peek().is_some() {
+                    let (sequences, digests): (Vec<_>, Vec<_>) =
+                        transactions.by_ref().take(BACKFILL_BATCH_SIZE).unzip();
+                    let found = lookup(&digests)?;
+                    fp_ensure!(
+                        found.len() == digests.len(),
+                        SuiError::GenericStorageError(
+                            "Backfill lookup returned a wrong number of transactions".to_string()
+                        )
+                    );
+                    let entries: Vec<_> = sequences.into_iter().zip(digests).zip(found).collect();
+                    let checkpoints =
+                        entries
+                            .iter()
+                            .filter_map(|((seq, digest), (checkpoint, _))| {
+                                checkpoint.map(|checkpoint| (checkpoint, *seq, *digest))
+                            });
+                    let batch = tables.transactions_by_checkpoint.batch();
+                    let batch = batch.insert_batch(
+                        &tables.transactions_by_checkpoint,
+                        checkpoints
+                            .clone()
+                            .map(|(checkpoint, seq, digest)| ((checkpoint, seq), digest)),
+                    )?;
+                    let batch = batch.insert_batch(
+                        &tables.transaction_checkpoint,
+                        checkpoints.map(|(checkpoint, _, digest)| (digest, checkpoint)),
+                    )?;
+                    let batch = batch.insert_batch(
+                        &tables.transactions_by_move_event,
+                        entries.iter().flat_map(|((seq, digest), (_, types))| {
+                            types
+                                .iter()
+                                .map(|type_| ((type_.to_string(), *seq), *digest))
+                        }),
+                    )?;
+                    batch.write()?;
+                }
+                Ok(())
+            },
+        )
+    }
+
     pub fn index_tx(
         &self,
         sender: SuiAddress,
         active_inputs: impl Iterator<Item = ObjectID>,
         mutated_objects: impl Iterator<Item = (ObjectRef, Owner)> + Clone,
         move_functions: impl Iterator<Item = (ObjectID, Identifier, Identifier)> + Clone,
+        move_events: impl Iterator<Item = StructTag>,
         object_index_changes: ObjectIndexChanges,
         digest: &TransactionDigest,
         timestamp_ms: u64,
@@ -216,7 +430,29 @@ impl IndexStore {
             std::iter::once((*digest, timestamp_ms)),
         )?;
 
+        let batch = batch.insert_batch(
+            &self.tables.transactions_by_timestamp,
+            std::iter::once(((timestamp_ms, sequence), *digest)),
+        )?;
+
+        let batch = batch.insert_batch(
+            &self.tables.transactions_by_move_event,
+            move_events.map(|type_| ((type_.to_string(), sequence), *digest)),
+        )?;
+
         // Owner index
+        let deleted_owner_types =
+            self.owner_type_index_keys(&object_index_changes.deleted_owners)?;
+        let batch = batch.delete_batch(&self.tables.owner_type_index, deleted_owner_types)?;
+        let batch = batch.insert_batch(
+            &self.tables.owner_type_index,
+            object_index_changes
+                .new_owners
+                .iter()
+                .filter_map(|((owner, _), info)| {
+                    owner_type_index_key(*owner, info).map(|key| (key, info.clone()))
+                }),
+        )?;
         let batch = batch.delete_batch(
             &self.tables.owner_index,
             object_index_changes.deleted_owners.into_iter(),
@@ -239,6 +475,149 @@ impl IndexStore {
         Ok(sequence)
     }
 
+    /// Records the checkpoint including the given transactions. Transactions which have not been
+    /// indexed yet are skipped, and must be indexed with the checkpoint once `index_tx` is done.
+    pub fn index_checkpoint(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        digests: &[TransactionDigest],
+    ) -> SuiResult {
+        let indexed: Vec<_> = digests
+            .iter()
+            .zip(self.tables.transactions_seq.multi_get(digests)?)
+            .filter_map(|(digest, sequence)| sequence.map(|sequence| (*digest, sequence)))
+            .collect();
+
+        let batch = self.tables.transactions_by_checkpoint.batch();
+        let batch = batch.insert_batch(
+            &self.tables.transactions_by_checkpoint,
+            indexed
+                .iter()
+                .map(|(digest, sequence)| ((checkpoint, *sequence), *digest)),
+        )?;
+        let batch = batch.insert_batch(
+            &self.tables.transaction_checkpoint,
+            indexed.iter().map(|(digest, _)| (*digest, checkpoint)),
+        )?;
+        batch.write()?;
+        Ok(())
+    }
+
+    /// Seed the balance index with the balances of the coins owned once `checkpoint` was executed,
+    /// unless this already completed. `balances` returns the total balance of each owner and coin
+    /// type, and is only called on the first run.
+    pub fn seed_balances(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        balances: impl FnOnce() -> SuiResult<BTreeMap<(SuiAddress, String), i128>>,
+    ) -> SuiResult {
+        Self::run_backfill(&self.tables, BALANCE_INDEX_BACKFILL, |tables| {
+            info!(checkpoint, "Seeding balance index");
+            let batch = tables.balance_index.batch();
+            let batch = batch.insert_batch(
+                &tables.balance_index,
+                balances()?
+                    .into_iter()
+                    .map(|((owner, coin_type), balance)| ((owner, coin_type, checkpoint), balance)),
+            )?;
+            let batch = batch.insert_batch(
+                &tables.balance_index_start,
+                std::iter::once((BALANCE_INDEX_START_KEY, checkpoint)),
+            )?;
+            batch.write()?;
+            Ok(())
+        })
+    }
+
+    /// Returns the first checkpoint the balance index knows the balances at, if it was seeded.
+    pub fn balance_index_start(&self) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self
+            .tables
+            .balance_index_start
+            .get(&BALANCE_INDEX_START_KEY)?)
+    }
+
+    /// Records the balances of the owners of coins changed by the transactions of a checkpoint.
+    /// Checkpoints must be indexed in order, with the effects of all their transactions, once the
+    /// index was seeded. Checkpoints up to the seed are already accounted for, and skipped.
+    pub fn index_checkpoint_balances(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        effects: &[TransactionEffects],
+    ) -> SuiResult {
+        let Some(start) = self.balance_index_start()? else {
+            return Err(SuiError::GenericStorageError(
+                "Balance index must be seeded before indexing checkpoints".to_string(),
+            ));
+        };
+        if checkpoint <= start {
+            return Ok(());
+        }
+        let mut changes: BTreeMap<(SuiAddress, String), i128> = BTreeMap::new();
+        for event in effects.iter().flat_map(|effects| &effects.events) {
+            if let Event::CoinBalanceChange {
+                owner: Owner::AddressOwner(owner),
+                coin_type,
+                amount,
+                ..
+            } = event
+            {
+                *changes.entry((*owner, coin_type.clone())).or_default() += amount;
+            }
+        }
+
+        // Start from the balances before this checkpoint, so that indexing it again is harmless.
+        let mut balances = vec![];
+        for ((owner, coin_type), amount) in changes {
+            let balance = self.get_balance_at_checkpoint(owner, &coin_type, checkpoint - 1)?;
+            balances.push(((owner, coin_type, checkpoint), balance + amount));
+        }
+        let batch = self.tables.balance_index.batch();
+        let batch = batch.insert_batch(&self.tables.balance_index, balances.into_iter())?;
+        batch.write()?;
+        Ok(())
+    }
+
+    /// Returns the balance of the coins of the given type owned by the address at a checkpoint.
+    pub fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: &str,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<i128> {
+        Ok(self
+            .tables
+            .balance_index
+            .iter()
+            .skip_prior_to(&(owner, coin_type.to_string(), checkpoint))?
+            .next()
+            .filter(|((address, type_, _), _)| *address == owner && type_ == coin_type)
+            .map(|(_, balance)| balance)
+            .unwrap_or_default())
+    }
+
+    /// Returns the non-zero balances of all the coin types owned by the address at a checkpoint.
+    pub fn get_all_balances_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<BTreeMap<String, i128>> {
+        let mut balances = BTreeMap::new();
+        for ((_, coin_type, _), balance) in self
+            .tables
+            .balance_index
+            .iter()
+            .skip_to(&(owner, String::new(), CheckpointSequenceNumber::MIN))?
+            .take_while(|((address, _, _), _)| *address == owner)
+            .filter(|((_, _, balance_checkpoint), _)| *balance_checkpoint <= checkpoint)
+        {
+            // Entries are ordered by checkpoint for each coin type, the last one is the closest.
+            balances.insert(coin_type, balance);
+        }
+        balances.retain(|_, balance| *balance != 0);
+        Ok(balances)
+    }
+
     pub fn next_sequence_number(&self) -> TxSequenceNumber {
         self.next_sequence_number.load(Ordering::SeqCst) + 1
     }
@@ -252,13 +631,19 @@ impl IndexStore {
     ) -> Result<Vec<TransactionDigest>, anyhow::Error> {
         // Lookup TransactionDigest sequence number,
         // also default cursor to 0 or the current sequence number depends on ordering.
-        let cursor = if let Some(cursor) = cursor {
-            self.get_transaction_seq(&cursor)?
-                .ok_or_else(|| anyhow!("Transaction [{cursor:?}] not found."))?
-        } else if reverse {
-            TxSequenceNumber::MAX
+        let digest_cursor = if let Some(cursor) = cursor {
+            Some((
+                cursor,
+                self.get_transaction_seq(&cursor)?
+                    .ok_or_else(|| anyhow!("Transaction [{cursor:?}] not found."))?,
+            ))
         } else {
-            TxSequenceNumber::MIN
+            None
+        };
+        let cursor = match digest_cursor {
+            Some((_, cursor)) => cursor,
+            None if reverse => TxSequenceNumber::MAX,
+            None => TxSequenceNumber::MIN,
         };
 
         Ok(match query {
@@ -281,6 +666,15 @@ impl IndexStore {
             TransactionQuery::ToAddress(address) => {
                 self.get_transactions_to_addr(address, cursor, limit, reverse)?
             }
+            TransactionQuery::TimeRange { .. }
+            | TransactionQuery::Checkpoint { .. }
+            | TransactionQuery::MoveEvent(_)
+            | TransactionQuery::MatchAll(_) => self.get_transactions_by_queries(
+                flatten_query(query),
+                digest_cursor,
+                limit,
+                reverse,
+            )?,
             TransactionQuery::All => {
                 let iter = self.tables.transaction_order.iter();
 
@@ -524,6 +918,314 @@ impl IndexStore {
         )
     }
 
+    /// Looks up the candidate transactions with the first query, and keeps the ones matching all
+    /// the other queries. Fails if more than `MAX_TX_QUERY_SCAN_SIZE` candidates are scanned
+    /// before the page is filled, the first query must then be narrowed.
+    fn get_transactions_by_queries(
+        &self,
+        queries: Vec<TransactionQuery>,
+        cursor: Option<(TransactionDigest, TxSequenceNumber)>,
+        limit: Option<usize>,
+        reverse: bool,
+    ) -> Result<Vec<TransactionDigest>, anyhow::Error> {
+        let (query, filters) = match queries.split_first() {
+            Some((query, filters)) => (query, filters),
+            None => (&TransactionQuery::All, &[][..]),
+        };
+        let mut digests = vec![];
+        for (scanned, (seq, digest)) in self.transactions_iter(query, cursor, reverse)?.enumerate()
+        {
+            if limit.map_or(false, |limit| digests.len() >= limit) {
+                break;
+            }
+            if scanned >= MAX_TX_QUERY_SCAN_SIZE {
+                return Err(anyhow!(
+                    "Query scanned more than {MAX_TX_QUERY_SCAN_SIZE} transactions, narrow its first criterion."
+                ));
+            }
+            if self.matches_all(filters, seq, &digest)? {
+                digests.push(digest);
+            }
+        }
+        Ok(digests)
+    }
+
+    /// Iterates over the transactions matching a single query from the cursor, inclusive.
+    fn transactions_iter(
+        &self,
+        query: &TransactionQuery,
+        cursor: Option<(TransactionDigest, TxSequenceNumber)>,
+        reverse: bool,
+    ) -> Result<TransactionIter<'_>, anyhow::Error> {
+        let cursor_seq = cursor.map(|(_, seq)| seq);
+        Ok(match query {
+            TransactionQuery::All | TransactionQuery::MatchAll(_) => {
+                let iter = self.tables.transaction_order.iter();
+                if reverse {
+                    Box::new(
+                        iter.skip_prior_to(&cursor_seq.unwrap_or(TxSequenceNumber::MAX))?
+                            .reverse(),
+                    )
+                } else {
+                    Box::new(iter.skip_to(&cursor_seq.unwrap_or(TxSequenceNumber::MIN))?)
+                }
+            }
+            TransactionQuery::MoveFunction {
+                package,
+                module,
+                function,
+            } => {
+                let (package, module, function) = (*package, module.clone(), function.clone());
+                let key = (
+                    package,
+                    module.clone().unwrap_or_default(),
+                    function.clone().unwrap_or_default(),
+                    cursor_seq.unwrap_or(if reverse {
+                        TxSequenceNumber::MAX
+                    } else {
+                        TxSequenceNumber::MIN
+                    }),
+                );
+                let iter = self.tables.transactions_by_move_function.iter();
+                let iter: Box<dyn Iterator<Item = _> + '_> = if reverse {
+                    Box::new(iter.skip_prior_to(&key)?.reverse())
+                } else {
+                    Box::new(iter.skip_to(&key)?)
+                };
+                Box::new(
+                    iter.take_while(move |((id, m, f, _), _)| {
+                        *id == package
+                            && module.as_ref().map(|x| x == m).unwrap_or(true)
+                            && function.as_ref().map(|x| x == f).unwrap_or(true)
+                    })
+                    .map(|((_, _, _, seq), digest)| (seq, digest)),
+                )
+            }
+            TransactionQuery::InputObject(object_id) => Self::key_range_iter(
+                &self.tables.transactions_by_input_object_id,
+                (*object_id, *object_id),
+                cursor_seq.map(|seq| (*object_id, seq)),
+                reverse,
+            )?,
+            TransactionQuery::MutatedObject(object_id) => Self::key_range_iter(
+                &self.tables.transactions_by_mutated_object_id,
+                (*object_id, *object_id),
+                cursor_seq.map(|seq| (*object_id, seq)),
+                reverse,
+            )?,
+            TransactionQuery::FromAddress(address) => Self::key_range_iter(
+                &self.tables.transactions_from_addr,
+                (*address, *address),
+                cursor_seq.map(|seq| (*address, seq)),
+                reverse,
+            )?,
+            TransactionQuery::ToAddress(address) => Self::key_range_iter(
+                &self.tables.transactions_to_addr,
+                (*address, *address),
+                cursor_seq.map(|seq| (*address, seq)),
+                reverse,
+            )?,
+            TransactionQuery::MoveEvent(type_) => {
+                let type_ = parse_sui_struct_tag(type_)?.to_string();
+                Self::key_range_iter(
+                    &self.tables.transactions_by_move_event,
+                    (type_.clone(), type_.clone()),
+                    cursor_seq.map(|seq| (type_, seq)),
+                    reverse,
+                )?
+            }
+            TransactionQuery::TimeRange {
+                start_time,
+                end_time,
+            } => {
+                if start_time >= end_time {
+                    return Ok(Box::new(std::iter::empty()));
+                }
+                let cursor = match cursor {
+                    Some((digest, seq)) => Some((
+                        self.get_timestamp_ms(&digest)?
+                            .ok_or_else(|| anyhow!("Transaction [{digest:?}] not found."))?,
+                        seq,
+                    )),
+                    None => None,
+                };
+                Self::key_range_iter(
+                    &self.tables.transactions_by_timestamp,
+                    (*start_time, end_time - 1),
+                    cursor,
+                    reverse,
+                )?
+            }
+            TransactionQuery::Checkpoint { start, end } => {
+                if start >= end {
+                    return Ok(Box::new(std::iter::empty()));
+                }
+                let cursor = match cursor {
+                    Some((digest, seq)) => Some((
+                        self.tables
+                            .transaction_checkpoint
+                            .get(&digest)?
+                            .ok_or_else(|| {
+                                anyhow!("Transaction [{digest:?}] is not included in a checkpoint.")
+                            })?,
+                        seq,
+                    )),
+                    None => None,
+                };
+                Self::key_range_iter(
+                    &self.tables.transactions_by_checkpoint,
+                    (*start, end - 1),
+                    cursor,
+                    reverse,
+                )?
+            }
+        })
+    }
+
+    /// Iterates over an index for the keys in the inclusive range `keys`, starting from the
+    /// cursor if any.
+    fn key_range_iter<'a, KeyT>(
+        index: &'a DBMap<(KeyT, TxSequenceNumber), TransactionDigest>,
+        (first, last): (KeyT, KeyT),
+        cursor: Option<(KeyT, TxSequenceNumber)>,
+        reverse: bool,
+    ) -> SuiResult<TransactionIter<'a>>
+    where
+        KeyT: Clone + Serialize + DeserializeOwned + PartialOrd + 'a,
+    {
+        Ok(if reverse {
+            let start = match cursor {
+                Some(cursor) if cursor.0 <= last => cursor,
+                _ => (last, TxSequenceNumber::MAX),
+            };
+            Box::new(
+                index
+                    .iter()
+                    .skip_prior_to(&start)?
+                    .reverse()
+                    .take_while(move |((key, _), _)| *key >= first)
+                    .map(|((_, seq), digest)| (seq, digest)),
+            )
+        } else {
+            let start = match cursor {
+                Some(cursor) if cursor.0 >= first => cursor,
+                _ => (first, TxSequenceNumber::MIN),
+            };
+            Box::new(
+                index
+                    .iter()
+                    .skip_to(&start)?
+                    .take_while(move |((key, _), _)| *key <= last)
+                    .map(|((_, seq), digest)| (seq, digest)),
+            )
+        })
+    }
+
+    fn matches_all(
+        &self,
+        queries: &[TransactionQuery],
+        seq: TxSequenceNumber,
+        digest: &TransactionDigest,
+    ) -> Result<bool, anyhow::Error> {
+        for query in queries {
+            if !self.matches_query(query, seq, digest)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Checks whether an indexed transaction matches the query, with point lookups in the indexes.
+    fn matches_query(
+        &self,
+        query: &TransactionQuery,
+        seq: TxSequenceNumber,
+        digest: &TransactionDigest,
+    ) -> Result<bool, anyhow::Error> {
+        Ok(match query {
+            TransactionQuery::All => true,
+            TransactionQuery::MoveFunction {
+                package,
+                module,
+                function,
+            } => self.called_move_function(*package, module, function, seq)?,
+            TransactionQuery::InputObject(object_id) => self
+                .tables
+                .transactions_by_input_object_id
+                .contains_key(&(*object_id, seq))?,
+            TransactionQuery::MutatedObject(object_id) => self
+                .tables
+                .transactions_by_mutated_object_id
+                .contains_key(&(*object_id, seq))?,
+            TransactionQuery::FromAddress(address) => self
+                .tables
+                .transactions_from_addr
+                .contains_key(&(*address, seq))?,
+            TransactionQuery::ToAddress(address) => self
+                .tables
+                .transactions_to_addr
+                .contains_key(&(*address, seq))?,
+            TransactionQuery::MoveEvent(type_) => self
+                .tables
+                .transactions_by_move_event
+                .contains_key(&(parse_sui_struct_tag(type_)?.to_string(), seq))?,
+            TransactionQuery::TimeRange {
+                start_time,
+                end_time,
+            } => matches!(
+                self.get_timestamp_ms(digest)?,
+                Some(timestamp) if *start_time <= timestamp && timestamp < *end_time
+            ),
+            TransactionQuery::Checkpoint { start, end } => matches!(
+                self.tables.transaction_checkpoint.get(digest)?,
+                Some(checkpoint) if *start <= checkpoint && checkpoint < *end
+            ),
+            TransactionQuery::MatchAll(queries) => self.matches_all(queries, seq, digest)?,
+        })
+    }
+
+    /// Checks whether the transaction called a matching Move function, by probing the index of
+    /// each function of the package (or module) in turn rather than scanning all its calls.
+    fn called_move_function(
+        &self,
+        package: ObjectID,
+        module: &Option<String>,
+        function: &Option<String>,
+        seq: TxSequenceNumber,
+    ) -> SuiResult<bool> {
+        let mut key = (
+            package,
+            module.clone().unwrap_or_default(),
+            function.clone().unwrap_or_default(),
+            seq,
+        );
+        loop {
+            let Some(((id, m, f, s), _)) = self
+                .tables
+                .transactions_by_move_function
+                .iter()
+                .skip_to(&key)?
+                .next() else {
+                return Ok(false);
+            };
+            if id != package || module.as_ref().map_or(false, |module| *module != m) {
+                return Ok(false);
+            }
+            if function.as_ref().map_or(true, |function| *function == f) {
+                if s == seq {
+                    return Ok(true);
+                }
+                if (&m, &f) != (&key.1, &key.2) {
+                    // First call of another function, probe it for the transaction.
+                    key = (id, m, f, seq);
+                    continue;
+                }
+            }
+            // Skip to the next function.
+            key = (id, m, f, TxSequenceNumber::MAX);
+        }
+    }
+
     pub fn get_transaction_seq(
         &self,
         digest: &TransactionDigest,
@@ -594,8 +1296,93 @@ impl IndexStore {
             .map(|(_, object_info)| object_info))
     }
 
+    /// Returns at most `limit` objects owned by `owner`, starting from the `cursor` object if
+    /// given, and only of the types matching `type_filter` if given. Without a type filter objects
+    /// are ordered by object ID, otherwise by type then object ID.
+    pub fn get_owner_objects_page(
+        &self,
+        owner: SuiAddress,
+        type_filter: Option<&ObjectTypeFilter>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        debug!(
+            ?owner,
+            ?type_filter,
+            ?cursor,
+            ?limit,
+            "get_owner_objects_page"
+        );
+        let Some(type_filter) = type_filter else {
+            return Ok(self
+                .tables
+                .owner_index
+                .iter()
+                .skip_to(&(owner, cursor.unwrap_or(ObjectID::ZERO)))?
+                .take_while(|((object_owner, _), _)| object_owner == &owner)
+                .take(limit)
+                .map(|(_, object_info)| object_info)
+                .collect());
+        };
+
+        let start = match cursor {
+            Some(cursor) => {
+                let key = self
+                    .tables
+                    .owner_index
+                    .get(&(owner, cursor))?
+                    .and_then(|info| owner_type_index_key(owner, &info))
+                    .ok_or_else(|| SuiError::FullNodeInvalidOwnedObjectsQuery {
+                        error: format!("Cursor object [{cursor}] is not owned by [{owner}]"),
+                    })?;
+                fp_ensure!(
+                    owner_type_index_key_matches(&key, owner, type_filter),
+                    SuiError::FullNodeInvalidOwnedObjectsQuery {
+                        error: format!(
+                            "Cursor object [{cursor}] does not match type filter {type_filter:?}"
+                        ),
+                    }
+                );
+                key
+            }
+            None => owner_type_index_lower_bound(owner, type_filter),
+        };
+        Ok(self
+            .tables
+            .owner_type_index
+            .iter()
+            .skip_to(&start)?
+            .take_while(|(key, _)| owner_type_index_key_matches(key, owner, type_filter))
+            .take(limit)
+            .map(|(_, object_info)| object_info)
+            .collect())
+    }
+
+    /// Keys of the owner type index for the given owner index keys.
+    fn owner_type_index_keys(&self, keys: &[OwnerIndexKey]) -> SuiResult<Vec<OwnerTypeIndexKey>> {
+        Ok(self
+            .tables
+            .owner_index
+            .multi_get(keys)?
+            .into_iter()
+            .zip(keys)
+            .filter_map(|(info, (owner, _))| {
+                info.and_then(|info| owner_type_index_key(*owner, &info))
+            })
+            .collect())
+    }
+
     pub fn insert_genesis_objects(&self, object_index_changes: ObjectIndexChanges) -> SuiResult {
         let batch = self.tables.owner_index.batch();
+        let batch = batch.insert_batch(
+            &self.tables.owner_type_index,
+            object_index_changes
+                .new_owners
+                .iter()
+                .filter_map(|((owner, _), info)| {
+                    owner_type_index_key(*owner, info).map(|key| (key, info.clone()))
+                }),
+        )?;
         let batch = batch.insert_batch(
             &self.tables.owner_index,
             object_index_changes.new_owners.into_iter(),
@@ -612,3 +1399,570 @@ impl IndexStore {
         self.tables.owner_index.is_empty()
     }
 }
+
+/// Flattens nested `MatchAll` queries, so that the first query can be used to look up candidates.
+fn flatten_query(query: TransactionQuery) -> Vec<TransactionQuery> {
+    match query {
+        TransactionQuery::MatchAll(queries) => {
+            queries.into_iter().flat_map(flatten_query).collect()
+        }
+        query => vec![query],
+    }
+}
+
+/// Owner type index key of an object, packages are not indexed.
+fn owner_type_index_key(owner: SuiAddress, info: &ObjectInfo) -> Option<OwnerTypeIndexKey> {
+    match &info.type_ {
+        ObjectType::Struct(tag) => Some((
+            owner,
+            tag.address.into(),
+            tag.module.to_string(),
+            tag.name.to_string(),
+            tag.type_params.clone(),
+            info.object_id,
+        )),
+        ObjectType::Package => None,
+    }
+}
+
+/// The smallest owner type index key matching the filter, empty strings and vectors sort first.
+fn owner_type_index_lower_bound(owner: SuiAddress, filter: &ObjectTypeFilter) -> OwnerTypeIndexKey {
+    match filter {
+        ObjectTypeFilter::Package(package) => (
+            owner,
+            *package,
+            String::new(),
+            String::new(),
+            vec![],
+            ObjectID::ZERO,
+        ),
+        ObjectTypeFilter::Module { package, module } => (
+            owner,
+            *package,
+            module.clone(),
+            String::new(),
+            vec![],
+            ObjectID::ZERO,
+        ),
+        ObjectTypeFilter::StructName {
+            package,
+            module,
+            name,
+        } => (
+            owner,
+            *package,
+            module.clone(),
+            name.clone(),
+            vec![],
+            ObjectID::ZERO,
+        ),
+        ObjectTypeFilter::StructType(tag) => (
+            owner,
+            tag.address.into(),
+            tag.module.to_string(),
+            tag.name.to_string(),
+            tag.type_params.clone(),
+            ObjectID::ZERO,
+        ),
+    }
+}
+
+fn owner_type_index_key_matches(
+    key: &OwnerTypeIndexKey,
+    owner: SuiAddress,
+    filter: &ObjectTypeFilter,
+) -> bool {
+    let (key_owner, key_package, key_module, key_name, key_type_params, _) = key;
+    *key_owner == owner
+        && match filter {
+            ObjectTypeFilter::Package(package) => key_package == package,
+            ObjectTypeFilter::Module { package, module } => {
+                key_package == package && key_module == module
+            }
+            ObjectTypeFilter::StructName {
+                package,
+                module,
+                name,
+            } => key_package == package && key_module == module && key_name == name,
+            ObjectTypeFilter::StructType(tag) => {
+                *key_package == ObjectID::from(tag.address)
+                    && key_module == tag.module.as_str()
+                    && key_name == tag.name.as_str()
+                    && key_type_params == &tag.type_params
+            }
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use sui_types::base_types::{ObjectDigest, SequenceNumber};
+    use sui_types::event::BalanceChangeType;
+    use sui_types::parse_sui_struct_tag;
+
+    fn object_info(owner: SuiAddress, type_: &str) -> ObjectInfo {
+        ObjectInfo {
+            object_id: ObjectID::random(),
+            version: SequenceNumber::new(),
+            digest: ObjectDigest::random(),
+            type_: ObjectType::Struct(parse_sui_struct_tag(type_).unwrap()),
+            owner: Owner::AddressOwner(owner),
+            previous_transaction: TransactionDigest::genesis(),
+        }
+    }
+
+    fn ids(infos: &[ObjectInfo]) -> Vec<ObjectID> {
+        infos.iter().map(|info| info.object_id).collect()
+    }
+
+    #[test]
+    fn test_owner_objects_page_by_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_store = IndexStore::new(dir.path().to_path_buf());
+
+        let owner = SuiAddress::random_for_testing_only();
+        let other_owner = SuiAddress::random_for_testing_only();
+        let mut sui_coins: Vec<_> = (0..3)
+            .map(|_| object_info(owner, "0x2::coin::Coin<0x2::sui::SUI>"))
+            .collect();
+        sui_coins.sort_by_key(|info| info.object_id);
+        let other_coin = object_info(owner, "0x2::coin::Coin<0x3::foo::FOO>");
+        let nft = object_info(owner, "0x2::devnet_nft::DevNetNFT");
+        let not_owned = object_info(other_owner, "0x2::coin::Coin<0x2::sui::SUI>");
+
+        let new_owners = sui_coins
+            .iter()
+            .chain([&other_coin, &nft, &not_owned])
+            .map(|info| {
+                let Owner::AddressOwner(owner) = info.owner else { unreachable!() };
+                ((owner, info.object_id), info.clone())
+            })
+            .collect();
+        index_store
+            .insert_genesis_objects(ObjectIndexChanges {
+                deleted_owners: vec![],
+                deleted_dynamic_fields: vec![],
+                new_owners,
+                new_dynamic_fields: vec![],
+            })
+            .unwrap();
+
+        let page = |filter: &str, cursor, limit| {
+            let filter = ObjectTypeFilter::from_str(filter).unwrap();
+            index_store
+                .get_owner_objects_page(owner, Some(&filter), cursor, limit)
+                .unwrap()
+        };
+
+        // Without a filter, all owned objects are returned.
+        let all = index_store
+            .get_owner_objects_page(owner, None, None, 10)
+            .unwrap();
+        assert_eq!(all.len(), 5);
+
+        // Exact type, paginated.
+        let first = page("0x2::coin::Coin<0x2::sui::SUI>", None, 2);
+        assert_eq!(ids(&first), ids(&sui_coins[..2]));
+        let second = page(
+            "0x2::coin::Coin<0x2::sui::SUI>",
+            Some(sui_coins[2].object_id),
+            2,
+        );
+        assert_eq!(ids(&second), ids(&sui_coins[2..]));
+
+        // Struct of any type parameters, module and package.
+        let coins = page("0x2::coin::Coin", None, 10);
+        assert_eq!(coins.len(), 4);
+        assert!(ids(&coins).contains(&other_coin.object_id));
+        assert_eq!(page("0x2::coin", None, 10).len(), 4);
+        assert_eq!(ids(&page("0x2::devnet_nft", None, 10)), vec![nft.object_id]);
+        assert_eq!(page("0x2", None, 10).len(), 5);
+        assert!(page("0x3", None, 10).is_empty());
+
+        // A cursor must be an owned object matching the filter.
+        assert!(index_store
+            .get_owner_objects_page(
+                owner,
+                Some(&ObjectTypeFilter::from_str("0x2::coin").unwrap()),
+                Some(nft.object_id),
+                10
+            )
+            .is_err());
+        assert!(index_store
+            .get_owner_objects_page(owner, None, Some(not_owned.object_id), 10)
+            .is_ok());
+
+        // Objects leaving the owner are removed from the type index.
+        index_store
+            .index_tx(
+                owner,
+                std::iter::empty(),
+                std::iter::empty(),
+                std::iter::empty(),
+                std::iter::empty(),
+                ObjectIndexChanges {
+                    deleted_owners: vec![(owner, nft.object_id)],
+                    deleted_dynamic_fields: vec![],
+                    new_owners: vec![],
+                    new_dynamic_fields: vec![],
+                },
+                &TransactionDigest::random(),
+                0,
+            )
+            .unwrap();
+        assert!(page("0x2::devnet_nft", None, 10).is_empty());
+        assert_eq!(page("0x2", None, 10).len(), 4);
+    }
+
+    #[test]
+    fn test_owner_type_index_backfill_resumes() {
+        let dir = tempfile::tempdir().unwrap();
+        let owner = SuiAddress::random_for_testing_only();
+        let infos: Vec<_> = (0..3)
+            .map(|_| object_info(owner, "0x2::coin::Coin<0x2::sui::SUI>"))
+            .collect();
+        {
+            let index_store = IndexStore::new(dir.path().to_path_buf());
+            index_store
+                .insert_genesis_objects(ObjectIndexChanges {
+                    deleted_owners: vec![],
+                    deleted_dynamic_fields: vec![],
+                    new_owners: infos
+                        .iter()
+                        .map(|info| ((owner, info.object_id), info.clone()))
+                        .collect(),
+                    new_dynamic_fields: vec![],
+                })
+                .unwrap();
+            // Simulate a database from before the owner type index, on which the backfill was
+            // interrupted after indexing a single object.
+            let tables = &index_store.tables;
+            tables.owner_type_index.clear().unwrap();
+            let (key, info) = tables.owner_index.iter().next().unwrap();
+            let type_key = owner_type_index_key(key.0, &info).unwrap();
+            tables.owner_type_index.insert(&type_key, &info).unwrap();
+            tables
+                .completed_backfills
+                .remove(&OWNER_TYPE_INDEX_BACKFILL.to_string())
+                .unwrap();
+        }
+
+        let index_store = IndexStore::new(dir.path().to_path_buf());
+        let filter = ObjectTypeFilter::from_str("0x2::coin").unwrap();
+        let mut expected = ids(&infos);
+        expected.sort();
+        assert_eq!(
+            ids(&index_store
+                .get_owner_objects_page(owner, Some(&filter), None, 10)
+                .unwrap()),
+            expected
+        );
+        assert!(index_store
+            .tables
+            .completed_backfills
+            .contains_key(&OWNER_TYPE_INDEX_BACKFILL.to_string())
+            .unwrap());
+    }
+
+    #[test]
+    fn test_transactions_by_combined_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_store = IndexStore::new(dir.path().to_path_buf());
+
+        let sender = SuiAddress::random_for_testing_only();
+        let other_sender = SuiAddress::random_for_testing_only();
+        let index_tx = |sender, call: Option<(&str, &str)>, event: Option<&str>, timestamp_ms| {
+            let digest = TransactionDigest::random();
+            index_store
+                .index_tx(
+                    sender,
+                    std::iter::empty(),
+                    std::iter::empty(),
+                    call.into_iter().map(|(module, function)| {
+                        (
+                            ObjectID::from_hex_literal("0x2").unwrap(),
+                            Identifier::new(module).unwrap(),
+                            Identifier::new(function).unwrap(),
+                        )
+                    }),
+                    event
+                        .into_iter()
+                        .map(|type_| parse_sui_struct_tag(type_).unwrap()),
+                    ObjectIndexChanges {
+                        deleted_owners: vec![],
+                        deleted_dynamic_fields: vec![],
+                        new_owners: vec![],
+                        new_dynamic_fields: vec![],
+                    },
+                    &digest,
+                    timestamp_ms,
+                )
+                .unwrap();
+            digest
+        };
+        let tx0 = index_tx(sender, Some(("coin", "split")), Some("0x2::foo::Ev"), 100);
+        let tx1 = index_tx(other_sender, Some(("coin", "split")), None, 200);
+        let tx2 = index_tx(sender, Some(("pay", "pay")), Some("0x2::foo::Ev"), 300);
+        let tx3 = index_tx(sender, None, None, 400);
+        index_store.index_checkpoint(0, &[tx0, tx1]).unwrap();
+        // Transactions not indexed yet are skipped.
+        index_store
+            .index_checkpoint(1, &[tx2, TransactionDigest::random()])
+            .unwrap();
+
+        let query = |query, cursor, limit, reverse| {
+            index_store
+                .get_transactions(query, cursor, limit, reverse)
+                .unwrap()
+        };
+        let time_range = |start_time, end_time| TransactionQuery::TimeRange {
+            start_time,
+            end_time,
+        };
+        let package = ObjectID::from_hex_literal("0x2").unwrap();
+
+        assert_eq!(query(time_range(150, 400), None, None, false), [tx1, tx2]);
+        assert_eq!(query(time_range(150, 400), None, None, true), [tx2, tx1]);
+        assert_eq!(
+            query(
+                TransactionQuery::Checkpoint { start: 0, end: 1 },
+                None,
+                None,
+                false
+            ),
+            [tx0, tx1]
+        );
+        assert_eq!(
+            query(
+                TransactionQuery::Checkpoint { start: 0, end: 5 },
+                Some(tx1),
+                None,
+                false
+            ),
+            [tx1, tx2]
+        );
+        assert_eq!(
+            query(
+                TransactionQuery::MoveEvent("0x2::foo::Ev".into()),
+                None,
+                None,
+                false
+            ),
+            [tx0, tx2]
+        );
+
+        let from_sender = TransactionQuery::FromAddress(sender);
+        let match_all = |query| TransactionQuery::MatchAll(vec![from_sender.clone(), query]);
+        assert_eq!(
+            query(
+                match_all(TransactionQuery::MoveFunction {
+                    package,
+                    module: Some("coin".into()),
+                    function: None,
+                }),
+                None,
+                None,
+                false
+            ),
+            [tx0]
+        );
+        assert_eq!(
+            query(
+                match_all(TransactionQuery::MoveFunction {
+                    package,
+                    module: None,
+                    function: Some("pay".into()),
+                }),
+                None,
+                None,
+                false
+            ),
+            [tx2]
+        );
+        assert_eq!(
+            query(match_all(time_range(0, 1000)), None, Some(2), false),
+            [tx0, tx2]
+        );
+        assert_eq!(
+            query(match_all(time_range(0, 1000)), Some(tx2), Some(2), false),
+            [tx2, tx3]
+        );
+        assert_eq!(
+            query(
+                TransactionQuery::MatchAll(vec![
+                    TransactionQuery::MoveEvent("0x2::foo::Ev".into()),
+                    match_all(TransactionQuery::Checkpoint { start: 1, end: 2 }),
+                ]),
+                None,
+                None,
+                true
+            ),
+            [tx2]
+        );
+    }
+
+    #[test]
+    fn test_transaction_indexes_backfill() {
+        let dir = tempfile::tempdir().unwrap();
+        let event_type = parse_sui_struct_tag("0x2::foo::Ev").unwrap();
+        let digests: Vec<_> = (0..3).map(|_| TransactionDigest::random()).collect();
+        {
+            let index_store = IndexStore::new(dir.path().to_path_buf());
+            for (i, digest) in digests.iter().enumerate() {
+                index_store
+                    .index_tx(
+                        SuiAddress::random_for_testing_only(),
+                        std::iter::empty(),
+                        std::iter::empty(),
+                        std::iter::empty(),
+                        std::iter::once(event_type.clone()),
+                        ObjectIndexChanges {
+                            deleted_owners: vec![],
+                            deleted_dynamic_fields: vec![],
+                            new_owners: vec![],
+                            new_dynamic_fields: vec![],
+                        },
+                        digest,
+                        100 * i as u64,
+                    )
+                    .unwrap();
+            }
+            // Simulate a database from before these indexes.
+            let tables = &index_store.tables;
+            tables.transactions_by_timestamp.clear().unwrap();
+            tables.transactions_by_move_event.clear().unwrap();
+            tables.completed_backfills.clear().unwrap();
+        }
+
+        let index_store = IndexStore::new(dir.path().to_path_buf());
+        let query = |query| {
+            index_store
+                .get_transactions(query, None, None, false)
+                .unwrap()
+        };
+        assert_eq!(
+            query(TransactionQuery::TimeRange {
+                start_time: 100,
+                end_time: 300
+            }),
+            digests[1..]
+        );
+        assert!(query(TransactionQuery::MoveEvent("0x2::foo::Ev".into())).is_empty());
+
+        // The transactions of the first two checkpoints were executed, the last one was not.
+        index_store
+            .backfill_checkpoints_and_move_events(|batch| {
+                Ok(batch
+                    .iter()
+                    .map(|digest| {
+                        let position = digests.iter().position(|d| d == digest).unwrap();
+                        let checkpoint = (position < 2).then_some(position as u64);
+                        (checkpoint, vec![event_type.clone()])
+                    })
+                    .collect())
+            })
+            .unwrap();
+        assert_eq!(
+            query(TransactionQuery::Checkpoint { start: 0, end: 5 }),
+            digests[..2]
+        );
+        assert_eq!(
+            query(TransactionQuery::MoveEvent("0x2::foo::Ev".into())),
+            digests
+        );
+
+        // A completed backfill doesn't run again.
+        index_store
+            .backfill_checkpoints_and_move_events(|_| panic!("Backfill ran twice"))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_balances_at_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_store = IndexStore::new(dir.path().to_path_buf());
+
+        let owner = SuiAddress::random_for_testing_only();
+        let other_owner = SuiAddress::random_for_testing_only();
+        let sui = "0x2::sui::SUI";
+        let foo = "0x3::foo::FOO";
+        let effects = |changes: &[(SuiAddress, &str, i128)]| TransactionEffects {
+            events: changes
+                .iter()
+                .map(|(owner, coin_type, amount)| Event::CoinBalanceChange {
+                    package_id: ObjectID::random(),
+                    transaction_module: Identifier::new("coin").unwrap(),
+                    sender: *owner,
+                    change_type: BalanceChangeType::Receive,
+                    owner: Owner::AddressOwner(*owner),
+                    coin_type: coin_type.to_string(),
+                    coin_object_id: ObjectID::random(),
+                    version: SequenceNumber::new(),
+                    amount: *amount,
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        // Balances can't be indexed before the index is seeded.
+        assert!(index_store
+            .index_checkpoint_balances(0, &[effects(&[(owner, sui, 100)])])
+            .is_err());
+        assert_eq!(index_store.balance_index_start().unwrap(), None);
+        index_store
+            .seed_balances(0, || {
+                Ok(BTreeMap::from([
+                    ((owner, sui.to_string()), 100),
+                    ((owner, foo.to_string()), 10),
+                    ((other_owner, sui.to_string()), 5),
+                ]))
+            })
+            .unwrap();
+        // Seeding again is a no-op.
+        index_store
+            .seed_balances(1, || panic!("Balance index seeded twice"))
+            .unwrap();
+        assert_eq!(index_store.balance_index_start().unwrap(), Some(0));
+        // The seeded checkpoint already accounts for its own changes.
+        index_store
+            .index_checkpoint_balances(0, &[effects(&[(owner, sui, 100)])])
+            .unwrap();
+        let checkpoint_2 = [effects(&[(owner, sui, -30), (owner, sui, 5)])];
+        index_store
+            .index_checkpoint_balances(2, &checkpoint_2)
+            .unwrap();
+        // Indexing a checkpoint again does not apply its changes twice.
+        index_store
+            .index_checkpoint_balances(2, &checkpoint_2)
+            .unwrap();
+        index_store
+            .index_checkpoint_balances(3, &[effects(&[(owner, foo, -10)])])
+            .unwrap();
+
+        let balance = |owner, coin_type, checkpoint| {
+            index_store
+                .get_balance_at_checkpoint(owner, coin_type, checkpoint)
+                .unwrap()
+        };
+        assert_eq!(balance(owner, sui, 0), 100);
+        assert_eq!(balance(owner, sui, 1), 100);
+        assert_eq!(balance(owner, sui, 2), 75);
+        assert_eq!(balance(owner, sui, 10), 75);
+        assert_eq!(balance(other_owner, sui, 3), 5);
+        assert_eq!(balance(other_owner, foo, 3), 0);
+
+        let all_balances = |checkpoint| {
+            index_store
+                .get_all_balances_at_checkpoint(owner, checkpoint)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            all_balances(2),
+            [(foo.to_string(), 10), (sui.to_string(), 75)]
+        );
+        assert_eq!(all_balances(3), [(sui.to_string(), 75)]);
+    }
+}
@@ -10,6 +10,7 @@ use anyhow::Result;
 use std::path::PathBuf;
 use sui_config::genesis::Genesis;
 use sui_core::authority_client::AuthorityAPI;
+use sui_core::state_snapshot::export_state_snapshot;
 
 use sui_types::{base_types::*, object::Owner};
 
@@ -125,6 +126,23 @@ pub enum ToolCommand {
         sequence_number: Option<CheckpointSequenceNumber>,
     },
 
+    /// Export the live object set at the end of an epoch, along with the checkpoints needed to
+    /// verify it, from the DB of a stopped node. The snapshot can be used to bootstrap a full node
+    /// by setting `state-snapshot-path` in its config.
+    #[clap(name = "export-snapshot")]
+    ExportSnapshot {
+        /// Path of the DB of the node
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(
+            long,
+            help = "Epoch to export the snapshot at - if not specified, the last completed epoch"
+        )]
+        epoch: Option<EpochId>,
+        #[clap(long, help = "Path of the snapshot file to write")]
+        output: PathBuf,
+    },
+
     #[clap(name = "anemo")]
     Anemo {
         #[clap(next_help_heading = "foo", flatten)]
@@ -269,6 +287,23 @@ impl ToolCommand {
                     println!("Content: {:?}\n", contents);
                 }
             }
+            ToolCommand::ExportSnapshot {
+                db_path,
+                epoch,
+                output,
+            } => {
+                let header = export_state_snapshot(&db_path, epoch, &output)?;
+                let checkpoint = header
+                    .checkpoint()
+                    .expect("Exported snapshot has a checkpoint");
+                println!(
+                    "Exported {} objects at the end of epoch {} (checkpoint {}) to {}",
+                    header.object_count,
+                    checkpoint.epoch(),
+                    checkpoint.sequence_number(),
+                    output.display()
+                );
+            }
             ToolCommand::Anemo { args } => {
                 let config = crate::make_anemo_config();
                 anemo_cli::run(config, args).await
@@ -10,6 +10,7 @@ use async_trait::async_trait;
 use futures::future::join_all;
 
 use anyhow::{anyhow, ensure};
+use fastcrypto::encoding::Encoding;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::TypeTag;
 
@@ -18,15 +19,18 @@ use sui_adapter::execution_mode::ExecutionMode;
 use sui_json::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
 use sui_json_rpc_types::GetRawObjectDataResponse;
 use sui_json_rpc_types::SuiObjectInfo;
-use sui_json_rpc_types::{RPCTransactionRequestParams, SuiData, SuiTypeTag};
+use sui_json_rpc_types::{
+    Coin as SuiCoin, ProgrammableTransactionCommandParams, ProgrammableTransactionInputParams,
+    RPCTransactionRequestParams, SuiData, SuiTypeTag,
+};
 use sui_types::base_types::{ObjectID, ObjectRef, ObjectType, SuiAddress};
 use sui_types::coin::{Coin, LockedCoin};
 use sui_types::error::SuiError;
-use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
-    CallArg, InputObjectKind, MoveCall, ObjectArg, SingleTransactionKind, TransactionData,
-    TransactionKind, TransferObject,
+    CallArg, InputObjectKind, MoveCall, ObjectArg, ProgrammableTransaction, SingleTransactionKind,
+    TransactionData, TransactionKind, TransferObject, MAX_EXTRA_GAS_PAYMENT_OBJECTS,
 };
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 
 use sui_types::governance::{
     ADD_DELEGATION_LOCKED_COIN_FUN_NAME, ADD_DELEGATION_MUL_COIN_FUN_NAME,
@@ -52,20 +56,76 @@ pub trait DataReader {
         object_id: ObjectID,
     ) -> Result<GetRawObjectDataResponse, anyhow::Error>;
 
+    /// Returns all the SUI coins owned by the address, in as few reads as possible.
+    async fn get_gas_coins(&self, address: SuiAddress) -> Result<Vec<SuiCoin>, anyhow::Error>;
+
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
 }
 
+/// How coins covering an amount are selected, e.g. the gas coins of a transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoinSelectionStrategy {
+    /// The largest coins first.
+    LargestFirst,
+    /// As few coins as possible, the last one being the smallest coin covering the rest of the
+    /// amount, so that large coins are not split for small amounts.
+    #[default]
+    FewestInputs,
+    /// The largest coins first, never selecting locked coins.
+    AvoidLocked,
+}
+
+impl CoinSelectionStrategy {
+    /// Selects coins among `coins` with a total balance of at least `amount`, or None if there
+    /// are not enough of them.
+    pub fn select(&self, mut coins: Vec<SuiCoin>, amount: u128) -> Option<Vec<SuiCoin>> {
+        if *self == Self::AvoidLocked {
+            coins.retain(|coin| coin.locked_until_epoch.is_none());
+        }
+        coins.sort_by(|a, b| b.balance.cmp(&a.balance));
+
+        let mut total = 0u128;
+        let mut count = 0;
+        while total < amount {
+            total += coins.get(count)?.balance as u128;
+            count += 1;
+        }
+        if *self == Self::FewestInputs && count > 0 {
+            // Coins are sorted by decreasing balance, so the coins covering the rest of the
+            // amount come first among the coins left.
+            let rest = amount - (total - coins[count - 1].balance as u128);
+            let smallest = coins[count - 1..]
+                .iter()
+                .rposition(|coin| coin.balance as u128 >= rest)
+                .unwrap_or_default();
+            coins.swap(count - 1, count - 1 + smallest);
+        }
+        coins.truncate(count);
+        Some(coins)
+    }
+}
+
 #[derive(Clone)]
 pub struct TransactionBuilder<Mode: ExecutionMode>(
     Arc<dyn DataReader + Sync + Send>,
+    CoinSelectionStrategy,
     PhantomData<Mode>,
 );
 
 impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
     pub fn new(data_reader: Arc<dyn DataReader + Sync + Send>) -> Self {
-        Self(data_reader, PhantomData)
+        Self(data_reader, CoinSelectionStrategy::default(), PhantomData)
     }
 
+    /// Selects the gas coins of the transactions built with `strategy`.
+    pub fn with_gas_selection_strategy(mut self, strategy: CoinSelectionStrategy) -> Self {
+        self.1 = strategy;
+        self
+    }
+
+    /// Selects the gas payment of a transaction: the `input_gas` coin if provided, else the gas
+    /// coins covering the budget picked by the gas selection strategy. The coins after the first
+    /// one are smashed into it at execution.
     async fn select_gas(
         &self,
         signer: SuiAddress,
@@ -73,33 +133,29 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         budget: u64,
         input_objects: Vec<ObjectID>,
         gas_price: u64,
-    ) -> Result<ObjectRef, anyhow::Error> {
+    ) -> Result<(ObjectRef, Vec<ObjectRef>), anyhow::Error> {
         if let Some(gas) = input_gas {
-            self.get_object_ref(gas).await
-        } else {
-            let objs = self.0.get_objects_owned_by_address(signer).await?;
-            let gas_objs = objs
-                .iter()
-                .filter(|obj| obj.type_ == GasCoin::type_().to_string());
-            let required_gas_amount = (budget as u128) * (gas_price as u128);
-
-            for obj in gas_objs {
-                let response = self.0.get_object(obj.object_id).await?;
-                let obj = response.object()?;
-                let gas: GasCoin = bcs::from_bytes(
-                    &obj.data
-                        .try_as_move()
-                        .ok_or_else(|| anyhow!("Cannot parse move object to gas object"))?
-                        .bcs_bytes,
-                )?;
-                if !input_objects.contains(&obj.id())
-                    && (gas.value() as u128) >= required_gas_amount
-                {
-                    return Ok(obj.reference.to_object_ref());
-                }
-            }
-            Err(anyhow!("Cannot find gas coin for signer address [{signer}] with amount sufficient for the required gas amount [{required_gas_amount}]."))
+            return Ok((self.get_object_ref(gas).await?, vec![]));
         }
+        let required_gas_amount = (budget as u128) * (gas_price as u128);
+        let candidates = self
+            .0
+            .get_gas_coins(signer)
+            .await?
+            .into_iter()
+            .filter(|coin| !input_objects.contains(&coin.coin_object_id))
+            .collect();
+
+        let selected = self
+            .1
+            .select(candidates, required_gas_amount)
+            .filter(|selected| selected.len() <= MAX_EXTRA_GAS_PAYMENT_OBJECTS + 1)
+            .ok_or_else(|| anyhow!("Cannot find gas coins for signer address [{signer}] with amount sufficient for the required gas amount [{required_gas_amount}]."))?;
+        let mut gas_refs = selected.iter().map(SuiCoin::object_ref);
+        let gas = gas_refs
+            .next()
+            .ok_or_else(|| anyhow!("Gas budget and gas price must not be zero"))?;
+        Ok((gas, gas_refs.collect()))
     }
 
     pub async fn transfer_object(
@@ -112,7 +168,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
     ) -> anyhow::Result<TransactionData> {
         let single_transfer = self.single_transfer_object(object_id, recipient).await?;
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, vec![object_id], gas_price)
             .await?;
 
@@ -122,7 +178,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas,
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
     }
 
     async fn single_transfer_object(
@@ -175,13 +232,14 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             .into_iter()
             .collect::<anyhow::Result<Vec<ObjectRef>>>()?;
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, input_coins, gas_price)
             .await?;
 
         let data = TransactionData::new_pay(
             signer, coin_refs, recipients, amounts, gas, gas_budget, gas_price,
-        );
+        )
+        .with_extra_gas_payment(extra_gas);
         Ok(data)
     }
 
@@ -272,7 +330,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             })
             .collect();
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, input_objects, gas_price)
             .await?;
 
@@ -282,7 +340,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas,
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
     }
 
     pub async fn single_move_call(
@@ -353,7 +412,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             .try_as_package()
             .cloned()
             .ok_or_else(|| anyhow!("Object [{}] is not a move package.", package_id))?;
-        let package: MovePackage = MovePackage::new(package.id, &package.module_map)?;
+        let package: MovePackage = MovePackage::new_with_upgrade_info(
+            package.id,
+            package.version,
+            &package.module_map,
+            package.type_origin_table,
+            package.linkage_table,
+        )?;
 
         let json_args = resolve_move_function_args(
             &package,
@@ -403,16 +468,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(sender, gas, gas_budget, vec![], gas_price)
             .await?;
-        Ok(TransactionData::new_module(
-            sender,
-            gas,
-            compiled_modules,
-            gas_budget,
-            gas_price,
-        ))
+        Ok(
+            TransactionData::new_module(sender, gas, compiled_modules, gas_budget, gas_price)
+                .with_extra_gas_payment(extra_gas),
+        )
     }
 
     // TODO: consolidate this with Pay transactions
@@ -429,7 +491,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, vec![coin_object_id], gas_price)
             .await?;
 
@@ -446,7 +508,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             ],
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
     }
 
     // TODO: consolidate this with Pay transactions
@@ -463,7 +526,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, vec![coin_object_id], gas_price)
             .await?;
 
@@ -480,7 +543,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             ],
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
     }
 
     // TODO: consolidate this with Pay transactions
@@ -498,7 +562,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(
                 signer,
                 gas,
@@ -521,7 +585,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             ],
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
     }
 
     pub async fn batch_transaction(
@@ -574,7 +639,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             })
             .collect();
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, inputs, gas_price)
             .await?;
 
@@ -584,7 +649,71 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas,
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
+    }
+
+    /// Creates a transaction from a programmable transaction, e.g. one built with
+    /// `ProgrammableTransactionBuilder`
+    pub async fn programmable(
+        &self,
+        signer: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let single_tx = SingleTransactionKind::ProgrammableTransaction(pt);
+        let input_objects = single_tx
+            .input_objects()?
+            .iter()
+            .flat_map(|obj| match obj {
+                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let (gas, extra_gas) = self
+            .select_gas(signer, gas, gas_budget, input_objects, gas_price)
+            .await?;
+
+        Ok(TransactionData::new(
+            TransactionKind::Single(single_tx),
+            signer,
+            gas,
+            gas_budget,
+            gas_price,
+        )
+        .with_extra_gas_payment(extra_gas))
+    }
+
+    /// Creates a programmable transaction from its JSON-RPC representation, resolving input
+    /// objects to their latest versions
+    pub async fn programmable_transaction(
+        &self,
+        signer: SuiAddress,
+        inputs: Vec<ProgrammableTransactionInputParams>,
+        commands: Vec<ProgrammableTransactionCommandParams>,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let mut objects = BTreeMap::new();
+        for input in inputs {
+            let call_arg = match input {
+                ProgrammableTransactionInputParams::Object(id) => {
+                    CallArg::Object(self.get_object_arg(id, &mut objects).await?)
+                }
+                ProgrammableTransactionInputParams::Pure(bytes) => {
+                    CallArg::Pure(bytes.to_vec().map_err(|e| anyhow!(e))?)
+                }
+            };
+            builder.input(call_arg);
+        }
+        for command in commands {
+            builder.command(command.try_into()?);
+        }
+        self.programmable(signer, builder.finish(), gas, gas_budget)
+            .await
     }
 
     pub async fn request_add_delegation(
@@ -597,7 +726,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, coins.clone(), gas_price)
             .await?;
 
@@ -651,7 +780,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             ],
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
     }
 
     pub async fn request_withdraw_delegation(
@@ -665,7 +795,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         let delegation = self.get_object_ref(delegation).await?;
         let staked_sui = self.get_object_ref(staked_sui).await?;
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, vec![], gas_price)
             .await?;
         Ok(TransactionData::new_move_call(
@@ -686,7 +816,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             ],
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
     }
 
     pub async fn request_switch_delegation(
@@ -701,7 +832,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         let delegation = self.get_object_ref(delegation).await?;
         let staked_sui = self.get_object_ref(staked_sui).await?;
         let gas_price = self.0.get_reference_gas_price().await?;
-        let gas = self
+        let (gas, extra_gas) = self
             .select_gas(signer, gas, gas_budget, vec![], gas_price)
             .await?;
         Ok(TransactionData::new_move_call(
@@ -723,7 +854,8 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             ],
             gas_budget,
             gas_price,
-        ))
+        )
+        .with_extra_gas_payment(extra_gas))
     }
 
     // TODO: we should add retrial to reduce the transaction building error rate
@@ -37,7 +37,10 @@ use std::{
     sync::Arc,
 };
 use sui_adapter::execution_engine;
-use sui_adapter::{adapter::new_move_vm, execution_mode};
+use sui_adapter::{
+    adapter::{new_move_vm, LinkedVMCache},
+    execution_mode,
+};
 use sui_framework::DEFAULT_FRAMEWORK_PATH;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::clock::Clock;
@@ -73,6 +76,7 @@ const RNG_SEED: [u8; 32] = [
 
 pub struct SuiTestAdapter<'a> {
     vm: Arc<MoveVM>,
+    linked_vms: LinkedVMCache,
     pub(crate) storage: Arc<InMemoryStorage>,
     native_functions: NativeFunctionTable,
     pub(crate) compiled_state: CompiledState<'a>,
@@ -242,6 +246,7 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter<'a> {
 
         let mut test_adapter = Self {
             vm: Arc::new(new_move_vm(native_functions.clone(), &PROTOCOL_CONSTANTS).unwrap()),
+            linked_vms: LinkedVMCache::default(),
             storage: Arc::new(InMemoryStorage::new(objects)),
             native_functions,
             compiled_state: CompiledState::new(
@@ -584,6 +589,7 @@ impl<'a> SuiTestAdapter<'a> {
         let transaction_data = transaction.into_inner().into_data().intent_message.value;
         let signer = transaction_data.signer();
         let gas = transaction_data.gas();
+        let extra_gas = transaction_data.extra_gas_payment().to_vec();
         let (
             inner,
             TransactionEffects {
@@ -606,9 +612,11 @@ impl<'a> SuiTestAdapter<'a> {
             transaction_data.kind,
             signer,
             gas,
+            extra_gas,
             transaction_digest,
             transaction_dependencies,
             &self.vm,
+            &self.linked_vms,
             &self.native_functions,
             gas_status,
             // TODO: Support different epochs in transactional tests.
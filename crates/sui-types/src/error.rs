@@ -6,7 +6,7 @@ use crate::{
     base_types::*,
     committee::{Committee, EpochId, StakeUnit},
     messages::{ExecutionFailureStatus, MoveLocation},
-    messages_checkpoint::CheckpointSequenceNumber,
+    messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber},
     object::Owner,
 };
 use fastcrypto::error::FastCryptoError;
@@ -102,6 +102,8 @@ pub enum SuiError {
         queue_len: usize,
         threshold: usize,
     },
+    #[error("Transaction denied: {error}")]
+    TransactionDenied { error: String },
 
     // Signature verification
     #[error("Signature is not valid: {}", error)]
@@ -244,6 +246,8 @@ pub enum SuiError {
     SubscriptionItemsDroppedError(u64),
     #[error("Subscription service closed.")]
     SubscriptionServiceClosed,
+    #[error("Subscriber fell behind by more than {0} items and was disconnected.")]
+    SubscriptionLagged(usize),
     #[error("Checkpointing error: {}", error)]
     CheckpointingError { error: String },
     #[error("Checkpoint {checkpoint:?} does not exist in checkpoint store")]
@@ -326,6 +330,8 @@ pub enum SuiError {
         gas_budget: u128,
         gas_price: u64,
     },
+    #[error("Invalid gas payment: {}", error)]
+    InvalidGasPayment { error: String },
 
     // Internal state errors
     #[error("Attempt to update state of TxContext from a different instance than original.")]
@@ -428,6 +434,8 @@ pub enum SuiError {
     StorageMissingFieldError(String),
     #[error("Corrupted fields/data in storage error: {0}")]
     StorageCorruptedFieldError(String),
+    #[error("Restoring the state snapshot of checkpoint {digest} was interrupted, restore the same snapshot again or delete the store")]
+    StateSnapshotRestoreInterrupted { digest: CheckpointDigest },
     #[error("Intended epoch ({intended_epoch:?}) doesn't match with the epoch of the per-epoch store tables ({store_epoch:?})")]
     StoreAccessEpochMismatch {
         intended_epoch: EpochId,
@@ -458,6 +466,8 @@ pub enum SuiError {
     ObjectDeserializationError { error: String },
     #[error("Event store component is not active on this node")]
     NoEventStore,
+    #[error("Invalid event filter: {error:?}")]
+    InvalidEventFilter { error: String },
 
     // Client side error
     #[error("Client state has a different pending transaction.")]
@@ -471,6 +481,8 @@ pub enum SuiError {
     },
     #[error("Invalid transaction range query to the fullnode: {:?}", error)]
     FullNodeInvalidTxRangeQuery { error: String },
+    #[error("Invalid owned objects query to the fullnode: {:?}", error)]
+    FullNodeInvalidOwnedObjectsQuery { error: String },
 
     // Errors related to the authority-consensus interface.
     #[error("Authority state can be modified by a single consensus client at the time")]
@@ -3,11 +3,14 @@
 
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::StructTag;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::base_types::SuiAddress;
+use crate::base_types::{SuiAddress, TransactionDigest};
 use crate::event::EventType;
 use crate::event::{Event, EventEnvelope};
+use crate::messages::ExecutedTransaction;
 use crate::object::Owner;
 use crate::ObjectID;
 
@@ -21,10 +24,29 @@ pub enum EventFilter {
     Module(Identifier),
     MoveEventType(StructTag),
     EventType(EventType),
-    MoveEventField { path: String, value: Value },
+    MoveEventField {
+        path: String,
+        value: Value,
+    },
+    /// Numeric move event field at the JSON pointer `path` is greater than `value`.
+    MoveEventFieldGreaterThan {
+        path: String,
+        value: u64,
+    },
+    /// Numeric move event field at the JSON pointer `path` is less than `value`.
+    MoveEventFieldLessThan {
+        path: String,
+        value: u64,
+    },
     SenderAddress(SuiAddress),
     Recipient(Owner),
     ObjectId(ObjectID),
+    Transaction(TransactionDigest),
+    /// Events emitted within [start_time, end_time), in milliseconds since epoch.
+    TimeRange {
+        start_time: u64,
+        end_time: u64,
+    },
     MatchAll(Vec<EventFilter>),
     MatchAny(Vec<EventFilter>),
 }
@@ -42,6 +64,12 @@ impl EventFilter {
                 }
                 _ => false,
             },
+            EventFilter::MoveEventFieldGreaterThan { path, value } => {
+                matches!(Self::numeric_field(item, path), Some(v) if v > *value as u128)
+            }
+            EventFilter::MoveEventFieldLessThan { path, value } => {
+                matches!(Self::numeric_field(item, path), Some(v) if v < *value as u128)
+            }
             EventFilter::SenderAddress(sender) => {
                 matches!(&item.event.sender(), Some(addr) if addr == sender)
             }
@@ -60,9 +88,23 @@ impl EventFilter {
             EventFilter::Recipient(recipient) => {
                 matches!(item.event.recipient(), Some(event_recipient) if event_recipient == recipient)
             }
+            EventFilter::Transaction(digest) => &item.tx_digest == digest,
+            EventFilter::TimeRange {
+                start_time,
+                end_time,
+            } => *start_time <= item.timestamp && item.timestamp < *end_time,
         })
     }
 
+    /// Move integers wider than u32 are rendered as strings in the event JSON, so accept both.
+    fn numeric_field(item: &EventEnvelope, path: &str) -> Option<u128> {
+        match item.move_struct_json_value.as_ref()?.pointer(path)? {
+            Value::Number(n) => n.as_u64().map(u128::from),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
     pub fn and(self, other_filter: EventFilter) -> Self {
         Self::MatchAll(vec![self, other_filter])
     }
@@ -74,6 +116,66 @@ impl Filter<EventEnvelope> for EventFilter {
     }
 }
 
+/// Filter for a stream of executed transactions, with the same criteria as `TransactionQuery`.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+pub enum TransactionFilter {
+    /// Filter by move function.
+    MoveFunction {
+        package: ObjectID,
+        module: Option<String>,
+        function: Option<String>,
+    },
+    /// Filter by input object.
+    InputObject(ObjectID),
+    /// Filter by mutated object.
+    MutatedObject(ObjectID),
+    /// Filter by sender address.
+    FromAddress(SuiAddress),
+    /// Filter by recipient address.
+    ToAddress(SuiAddress),
+}
+
+impl TransactionFilter {
+    fn try_matches(&self, item: &ExecutedTransaction) -> Result<bool, anyhow::Error> {
+        let data = &item.certificate.data().intent_message.value;
+        Ok(match self {
+            TransactionFilter::MoveFunction {
+                package,
+                module,
+                function,
+            } => data.move_functions().into_iter().any(
+                |(call_package, call_module, call_function)| {
+                    call_package == *package
+                        && module
+                            .as_ref()
+                            .map_or(true, |m| m.as_str() == call_module.as_str())
+                        && function
+                            .as_ref()
+                            .map_or(true, |f| f.as_str() == call_function.as_str())
+                },
+            ),
+            TransactionFilter::InputObject(object_id) => data
+                .input_objects()?
+                .iter()
+                .any(|o| o.object_id() == *object_id),
+            TransactionFilter::MutatedObject(object_id) => item
+                .effects
+                .all_mutated()
+                .any(|(obj_ref, _, _)| obj_ref.0 == *object_id),
+            TransactionFilter::FromAddress(sender) => item.certificate.sender_address() == *sender,
+            TransactionFilter::ToAddress(recipient) => item.effects.all_mutated().any(
+                |(_, owner, _)| matches!(owner, Owner::AddressOwner(addr) if addr == recipient),
+            ),
+        })
+    }
+}
+
+impl Filter<ExecutedTransaction> for TransactionFilter {
+    fn matches(&self, item: &ExecutedTransaction) -> bool {
+        self.try_matches(item).unwrap_or_default()
+    }
+}
+
 pub trait Filter<T> {
     fn matches(&self, item: &T) -> bool;
 }
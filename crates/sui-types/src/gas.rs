@@ -440,12 +440,15 @@ pub fn check_gas_balance(
     gas_price: u64,
     extra_amount: u64,
     extra_objs: Vec<Object>,
+    smashed_gas_objs: Vec<Object>,
     cost_table: &SuiCostTable,
 ) -> SuiResult {
-    if !(matches!(gas_object.owner, Owner::AddressOwner(_))) {
-        return Err(SuiError::GasObjectNotOwnedObject {
-            owner: gas_object.owner,
-        });
+    for gas_object in std::iter::once(gas_object).chain(&smashed_gas_objs) {
+        if !(matches!(gas_object.owner, Owner::AddressOwner(_))) {
+            return Err(SuiError::GasObjectNotOwnedObject {
+                owner: gas_object.owner,
+            });
+        }
     }
 
     let max_gas_budget = cost_table.max_gas_budget;
@@ -465,17 +468,19 @@ pub fn check_gas_balance(
         });
     }
 
-    // TODO: remove this check if gas payment with multiple coins is supported.
-    // This check is necessary now because, when transactions failed due to execution error,
-    // balance of gas budget will be reverted to pre-transaction state.
+    // When transactions failed due to execution error, balance of gas budget will be reverted
+    // to pre-transaction state, except for the gas coins smashed into the gas object.
     // Meanwhile we need to make sure that the pre-transaction balance is sufficient
     // to pay for gas cost before execution error occurs.
-    let gas_balance = get_gas_balance(gas_object)?;
+    let mut gas_balance = get_gas_balance(gas_object)? as u128;
+    for smashed_gas_obj in &smashed_gas_objs {
+        gas_balance += get_gas_balance(smashed_gas_obj)? as u128;
+    }
     let gas_budget_amount = (gas_budget as u128) * (gas_price as u128);
 
-    ok_or_gas_balance_error!((gas_balance as u128), gas_budget_amount, gas_price)?;
+    ok_or_gas_balance_error!(gas_balance, gas_budget_amount, gas_price)?;
 
-    let mut total_balance = gas_balance as u128;
+    let mut total_balance = gas_balance;
     for extra_obj in extra_objs {
         total_balance += get_gas_balance(&extra_obj)? as u128;
     }
@@ -31,7 +31,7 @@ use move_core_types::{
     account_address::AccountAddress, identifier::Identifier, language_storage::TypeTag,
     value::MoveStructLayout,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_with::serde_as;
 use serde_with::Bytes;
 use std::collections::hash_map::DefaultHasher;
@@ -41,8 +41,10 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashSet},
     hash::{Hash, Hasher},
     iter,
+    time::{SystemTime, UNIX_EPOCH},
 };
 use strum::IntoStaticStr;
+use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
 use tap::Pipe;
 use tracing::debug;
 
@@ -115,6 +117,20 @@ pub struct MoveModulePublish {
     pub modules: Vec<Vec<u8>>,
 }
 
+/// Publish the next version of `package`, authorized by `upgrade_cap`. The modules must be
+/// compatible with the modules of `package`, and are published at a new ID.
+#[serde_as]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct MovePackageUpgrade {
+    pub package: ObjectID,
+    #[serde_as(as = "Vec<Bytes>")]
+    pub modules: Vec<Vec<u8>>,
+    /// The IDs of the versions of its dependencies the upgrade links against. Dependencies that
+    /// are not listed keep the version `package` links against.
+    pub dependencies: Vec<ObjectID>,
+    pub upgrade_cap: ObjectRef,
+}
+
 // TODO: we can deprecate TransferSui when its callsites on RPC & SDK are
 // fully replaced by PaySui and PayAllSui.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
@@ -245,6 +261,8 @@ pub enum SingleTransactionKind {
     ConsensusCommitPrologue(ConsensusCommitPrologue),
     /// A transaction that allows the interleaving of native commands and Move calls
     ProgrammableTransaction(ProgrammableTransaction),
+    /// Upgrade a Move package
+    Upgrade(MovePackageUpgrade),
     // .. more transaction types go here
 }
 
@@ -501,17 +519,53 @@ impl ProgrammableTransaction {
             .collect())
     }
 
-    fn validity_check(&self) -> SuiResult {
-        if !cfg!(test) {
-            return Err(SuiError::Unknown(
-                "Programmable transactions are not yet available".to_owned(),
-            ));
-        }
+    fn validity_check(&self, config: &ProtocolConfig) -> SuiResult {
+        fp_ensure!(
+            config.programmable_transactions(),
+            SuiError::UnsupportedFeatureError {
+                error: "Programmable transactions are not enabled in this protocol version"
+                    .to_owned(),
+            }
+        );
+        fp_ensure!(
+            !self.commands.is_empty(),
+            SuiError::UnsupportedFeatureError {
+                error: "Programmable transactions must have at least one command".to_owned(),
+            }
+        );
+        // vectors of objects are built with Move calls in programmable transactions
+        fp_ensure!(
+            !self
+                .inputs
+                .iter()
+                .any(|arg| matches!(arg, CallArg::ObjVec(_))),
+            SuiError::UnsupportedFeatureError {
+                error: "Vectors of objects are not supported as programmable transaction inputs"
+                    .to_owned(),
+            }
+        );
         for c in &self.commands {
             c.validity_check()?
         }
         Ok(())
     }
+
+    pub fn shared_input_objects(&self) -> impl Iterator<Item = SharedInputObject> + '_ {
+        self.inputs.iter().filter_map(|arg| match arg {
+            CallArg::Pure(_)
+            | CallArg::Object(ObjectArg::ImmOrOwnedObject(_))
+            | CallArg::ObjVec(_) => None,
+            CallArg::Object(ObjectArg::SharedObject {
+                id,
+                initial_shared_version,
+                mutable,
+            }) => Some(SharedInputObject {
+                id: *id,
+                initial_shared_version: *initial_shared_version,
+                mutable: *mutable,
+            }),
+        })
+    }
 }
 
 impl Display for Argument {
@@ -605,7 +659,10 @@ impl SingleTransactionKind {
     pub fn shared_input_objects(&self) -> impl Iterator<Item = SharedInputObject> + '_ {
         match &self {
             Self::Call(_) | Self::ChangeEpoch(_) | Self::ConsensusCommitPrologue(_) => {
-                Either::Left(self.all_move_call_shared_input_objects())
+                Either::Left(Either::Left(self.all_move_call_shared_input_objects()))
+            }
+            Self::ProgrammableTransaction(p) => {
+                Either::Left(Either::Right(p.shared_input_objects()))
             }
             _ => Either::Right(iter::empty()),
         }
@@ -675,6 +732,23 @@ impl SingleTransactionKind {
         }
     }
 
+    /// The package, module and function of every Move function called by the transaction, either
+    /// by a `Call` or by the `MoveCall` commands of a programmable transaction.
+    pub fn move_functions(&self) -> Vec<(ObjectID, &Identifier, &Identifier)> {
+        match &self {
+            Self::Call(call) => vec![(call.package, &call.module, &call.function)],
+            Self::ProgrammableTransaction(p) => p
+                .commands
+                .iter()
+                .filter_map(|command| match command {
+                    Command::MoveCall(call) => Some((call.package, &call.module, &call.function)),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
     /// Return the metadata of each of the input objects for the transaction.
     /// For a Move object, we attach the object reference;
     /// for a Move package, we provide the object id only since they never change on chain.
@@ -721,6 +795,29 @@ impl SingleTransactionKind {
                 }]
             }
             Self::ProgrammableTransaction(p) => return p.input_objects(),
+            Self::Upgrade(MovePackageUpgrade {
+                package,
+                modules,
+                dependencies,
+                upgrade_cap,
+            }) => {
+                let mut inputs = vec![
+                    InputObjectKind::MovePackage(*package),
+                    InputObjectKind::ImmOrOwnedMoveObject(*upgrade_cap),
+                ];
+                inputs.extend(
+                    dependencies
+                        .iter()
+                        .map(|id| InputObjectKind::MovePackage(*id)),
+                );
+                let listed: BTreeSet<_> = inputs.iter().map(|o| o.object_id()).collect();
+                inputs.extend(
+                    Command::publish_command_input_objects(modules)
+                        .into_iter()
+                        .filter(|o| !listed.contains(&o.object_id())),
+                );
+                inputs
+            }
         };
         // Ensure that there are no duplicate inputs. This cannot be removed because:
         // In [`AuthorityState::check_locks`], we check that there are no duplicate mutable
@@ -736,7 +833,7 @@ impl SingleTransactionKind {
         Ok(input_objects)
     }
 
-    pub fn validity_check(&self, gas_payment: &ObjectRef) -> SuiResult {
+    pub fn validity_check(&self, config: &ProtocolConfig, gas_payment: &ObjectRef) -> SuiResult {
         match self {
             SingleTransactionKind::Call(call) => {
                 let is_blocked = BLOCKED_MOVE_FUNCTIONS.contains(&(
@@ -746,6 +843,15 @@ impl SingleTransactionKind {
                 ));
                 fp_ensure!(!is_blocked, SuiError::BlockedMoveFunction);
             }
+            SingleTransactionKind::Upgrade(_) => {
+                fp_ensure!(
+                    config.package_upgrades(),
+                    SuiError::UnsupportedFeatureError {
+                        error: "Package upgrades are not enabled in this protocol version"
+                            .to_owned(),
+                    }
+                );
+            }
             SingleTransactionKind::Pay(_)
             | SingleTransactionKind::Publish(_)
             | SingleTransactionKind::TransferObject(_)
@@ -769,7 +875,7 @@ impl SingleTransactionKind {
                     SuiError::UnexpectedGasPaymentObject
                 );
             }
-            SingleTransactionKind::ProgrammableTransaction(p) => p.validity_check()?,
+            SingleTransactionKind::ProgrammableTransaction(p) => p.validity_check(config)?,
         };
         Ok(())
     }
@@ -844,6 +950,14 @@ impl Display for SingleTransactionKind {
             Self::Publish(_p) => {
                 writeln!(writer, "Transaction Kind : Publish")?;
             }
+            Self::Upgrade(u) => {
+                writeln!(writer, "Transaction Kind : Upgrade")?;
+                writeln!(writer, "Package ID : {}", u.package.to_hex_literal())?;
+                let (object_id, seq, digest) = u.upgrade_cap;
+                writeln!(writer, "Upgrade Capability ID : {}", &object_id)?;
+                writeln!(writer, "Sequence Number : {:?}", seq)?;
+                writeln!(writer, "Object Digest : {}", digest)?;
+            }
             Self::Call(c) => {
                 writeln!(writer, "Transaction Kind : Call")?;
                 writeln!(writer, "Package ID : {}", c.package.to_hex_literal())?;
@@ -982,15 +1096,133 @@ impl Display for TransactionKind {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+/// Maximum number of extra gas coins a transaction can smash into its gas payment.
+pub const MAX_EXTRA_GAS_PAYMENT_OBJECTS: usize = 32;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
+#[serde(try_from = "TransactionDataRepr")]
 pub struct TransactionData {
     pub kind: TransactionKind,
     sender: SuiAddress,
     gas_payment: ObjectRef,
+    /// Coins merged into the `gas_payment` coin before execution, to pay for gas with many coins.
+    extra_gas_payment: Vec<ObjectRef>,
     pub gas_price: u64,
     pub gas_budget: u64,
 }
 
+/// The encoding of `TransactionData`. Transactions paying for gas with a single coin keep the
+/// encoding that predates gas smashing, a struct starting with their `TransactionKind`: the
+/// `Single` and `Batch` variants have the index of the `TransactionKind` variant they mirror, so
+/// that they are encoded the same way. Transactions paying for gas with several coins are
+/// encoded in the `V2` variant, which lists all of their gas coins.
+#[derive(Deserialize)]
+#[serde(rename = "TransactionData")]
+#[allow(clippy::large_enum_variant)]
+enum TransactionDataRepr {
+    Single(SingleTransactionKind, SuiAddress, ObjectRef, u64, u64),
+    Batch(Vec<SingleTransactionKind>, SuiAddress, ObjectRef, u64, u64),
+    V2(TransactionDataV2),
+}
+
+#[derive(Serialize)]
+#[serde(rename = "TransactionData")]
+enum TransactionDataReprRef<'a> {
+    Single(&'a SingleTransactionKind, SuiAddress, ObjectRef, u64, u64),
+    Batch(&'a [SingleTransactionKind], SuiAddress, ObjectRef, u64, u64),
+    V2(TransactionDataV2Ref<'a>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "TransactionDataV2")]
+struct TransactionDataV2 {
+    kind: TransactionKind,
+    sender: SuiAddress,
+    /// The gas coins, smashed into the first one before execution.
+    gas_payment: Vec<ObjectRef>,
+    gas_price: u64,
+    gas_budget: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "TransactionDataV2")]
+struct TransactionDataV2Ref<'a> {
+    kind: &'a TransactionKind,
+    sender: SuiAddress,
+    gas_payment: Vec<ObjectRef>,
+    gas_price: u64,
+    gas_budget: u64,
+}
+
+impl TryFrom<TransactionDataRepr> for TransactionData {
+    type Error = String;
+
+    fn try_from(data: TransactionDataRepr) -> Result<Self, Self::Error> {
+        let data = match data {
+            TransactionDataRepr::Single(kind, sender, gas_payment, gas_price, gas_budget) => {
+                Self::new(
+                    TransactionKind::Single(kind),
+                    sender,
+                    gas_payment,
+                    gas_budget,
+                    gas_price,
+                )
+            }
+            TransactionDataRepr::Batch(kinds, sender, gas_payment, gas_price, gas_budget) => {
+                Self::new(
+                    TransactionKind::Batch(kinds),
+                    sender,
+                    gas_payment,
+                    gas_budget,
+                    gas_price,
+                )
+            }
+            TransactionDataRepr::V2(mut data) => {
+                // A single gas coin must use the original encoding, for every transaction to
+                // have a single encoding and digest.
+                if data.gas_payment.len() < 2 {
+                    return Err("TransactionDataV2 must pay for gas with several coins".to_owned());
+                }
+                let extra_gas_payment = data.gas_payment.split_off(1);
+                Self::new(
+                    data.kind,
+                    data.sender,
+                    data.gas_payment[0],
+                    data.gas_budget,
+                    data.gas_price,
+                )
+                .with_extra_gas_payment(extra_gas_payment)
+            }
+        };
+        Ok(data)
+    }
+}
+
+impl Serialize for TransactionData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (sender, gas_payment) = (self.sender, self.gas_payment);
+        let (gas_price, gas_budget) = (self.gas_price, self.gas_budget);
+        match &self.kind {
+            TransactionKind::Single(kind) if self.extra_gas_payment.is_empty() => {
+                TransactionDataReprRef::Single(kind, sender, gas_payment, gas_price, gas_budget)
+            }
+            TransactionKind::Batch(kinds) if self.extra_gas_payment.is_empty() => {
+                TransactionDataReprRef::Batch(kinds, sender, gas_payment, gas_price, gas_budget)
+            }
+            kind => TransactionDataReprRef::V2(TransactionDataV2Ref {
+                kind,
+                sender,
+                gas_payment: std::iter::once(gas_payment)
+                    .chain(self.extra_gas_payment.iter().copied())
+                    .collect(),
+                gas_price,
+                gas_budget,
+            }),
+        }
+        .serialize(serializer)
+    }
+}
+
 impl TransactionData {
     pub fn new_with_dummy_gas_price(
         kind: TransactionKind,
@@ -1003,6 +1235,7 @@ impl TransactionData {
             sender,
             gas_price: DUMMY_GAS_PRICE,
             gas_payment,
+            extra_gas_payment: vec![],
             gas_budget,
         }
     }
@@ -1019,6 +1252,7 @@ impl TransactionData {
             sender,
             gas_price,
             gas_payment,
+            extra_gas_payment: vec![],
             gas_budget,
         }
     }
@@ -1240,10 +1474,40 @@ impl TransactionData {
         Self::new(kind, sender, gas_payment, gas_budget, gas_price)
     }
 
+    pub fn new_upgrade(
+        sender: SuiAddress,
+        gas_payment: ObjectRef,
+        package: ObjectID,
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<ObjectID>,
+        upgrade_cap: ObjectRef,
+        gas_budget: u64,
+        gas_price: u64,
+    ) -> Self {
+        let kind = TransactionKind::Single(SingleTransactionKind::Upgrade(MovePackageUpgrade {
+            package,
+            modules,
+            dependencies,
+            upgrade_cap,
+        }));
+        Self::new(kind, sender, gas_payment, gas_budget, gas_price)
+    }
+
+    /// Pays for gas with the `extra_gas_payment` coins as well, which are smashed into the gas
+    /// payment coin at execution.
+    pub fn with_extra_gas_payment(mut self, extra_gas_payment: Vec<ObjectRef>) -> Self {
+        self.extra_gas_payment = extra_gas_payment;
+        self
+    }
+
     pub fn gas(&self) -> ObjectRef {
         self.gas_payment
     }
 
+    pub fn extra_gas_payment(&self) -> &[ObjectRef] {
+        &self.extra_gas_payment
+    }
+
     pub fn signer(&self) -> SuiAddress {
         self.sender
     }
@@ -1267,6 +1531,14 @@ impl TransactionData {
             .collect()
     }
 
+    /// See [SingleTransactionKind::move_functions].
+    pub fn move_functions(&self) -> Vec<(ObjectID, &Identifier, &Identifier)> {
+        self.kind
+            .single_transactions()
+            .flat_map(|s| s.move_functions())
+            .collect()
+    }
+
     pub fn input_objects(&self) -> SuiResult<Vec<InputObjectKind>> {
         let mut inputs = self
             .kind
@@ -1277,15 +1549,65 @@ impl TransactionData {
             inputs.push(InputObjectKind::ImmOrOwnedMoveObject(
                 *self.gas_payment_object_ref(),
             ));
+            inputs.extend(
+                self.extra_gas_payment
+                    .iter()
+                    .map(|obj_ref| InputObjectKind::ImmOrOwnedMoveObject(*obj_ref)),
+            );
         }
         Ok(inputs)
     }
 
-    pub fn validity_check(&self) -> SuiResult {
-        Self::validity_check_impl(&self.kind, &self.gas_payment)
+    pub fn validity_check(&self, config: &ProtocolConfig) -> SuiResult {
+        if !self.extra_gas_payment.is_empty() {
+            fp_ensure!(
+                config.multiple_gas_coins(),
+                SuiError::UnsupportedFeatureError {
+                    error: "Gas smashing is not enabled in this protocol version".to_owned(),
+                }
+            );
+            fp_ensure!(
+                !self.kind.is_system_tx() && !self.kind.is_pay_sui_tx(),
+                SuiError::InvalidGasPayment {
+                    error: "System and SUI payment transactions cannot smash gas coins".to_string(),
+                }
+            );
+            fp_ensure!(
+                self.extra_gas_payment.len() <= MAX_EXTRA_GAS_PAYMENT_OBJECTS,
+                SuiError::InvalidGasPayment {
+                    error: format!(
+                        "Too many extra gas coins: {}, max: {MAX_EXTRA_GAS_PAYMENT_OBJECTS}",
+                        self.extra_gas_payment.len()
+                    ),
+                }
+            );
+            let mut used = HashSet::from([self.gas_payment.0]);
+            fp_ensure!(
+                self.extra_gas_payment
+                    .iter()
+                    .all(|obj_ref| used.insert(obj_ref.0)),
+                SuiError::InvalidGasPayment {
+                    error: "Gas coins cannot appear more than once".to_string(),
+                }
+            );
+            fp_ensure!(
+                self.kind
+                    .input_objects()?
+                    .iter()
+                    .all(|input| !used.contains(&input.object_id())),
+                SuiError::InvalidGasPayment {
+                    error: "Gas coins cannot be inputs of the transaction".to_string(),
+                }
+            );
+        }
+        Self::validity_check_impl(config, &self.kind, &self.gas_payment)
     }
 
-    pub fn validity_check_impl(kind: &TransactionKind, gas_payment: &ObjectRef) -> SuiResult {
+    pub fn validity_check_impl(
+        config: &ProtocolConfig,
+        kind: &TransactionKind,
+        gas_payment: &ObjectRef,
+    ) -> SuiResult {
         match kind {
             TransactionKind::Batch(b) => {
                 fp_ensure!(
@@ -1306,7 +1628,8 @@ impl TransactionData {
                     | SingleTransactionKind::Genesis(_)
                     | SingleTransactionKind::Publish(_)
                     | SingleTransactionKind::ConsensusCommitPrologue(_)
-                    | SingleTransactionKind::ProgrammableTransaction(_) => false,
+                    | SingleTransactionKind::ProgrammableTransaction(_)
+                    | SingleTransactionKind::Upgrade(_) => false,
                 });
                 fp_ensure!(
                     valid,
@@ -1317,10 +1640,10 @@ impl TransactionData {
                     }
                 );
                 for s in b {
-                    s.validity_check(gas_payment)?
+                    s.validity_check(config, gas_payment)?
                 }
             }
-            TransactionKind::Single(s) => s.validity_check(gas_payment)?,
+            TransactionKind::Single(s) => s.validity_check(config, gas_payment)?,
         }
         Ok(())
     }
@@ -1521,6 +1844,15 @@ pub type TxCertAndSignedEffects = (CertifiedTransaction, SignedTransactionEffect
 pub type VerifiedCertificate = VerifiedEnvelope<SenderSignedData, AuthorityStrongQuorumSignInfo>;
 pub type TrustedCertificate = TrustedEnvelope<SenderSignedData, AuthorityStrongQuorumSignInfo>;
 
+/// A certificate that has been executed, along with its effects and the time of its execution.
+#[derive(Clone, Debug)]
+pub struct ExecutedTransaction {
+    pub certificate: CertifiedTransaction,
+    pub effects: TransactionEffects,
+    /// UTC timestamp in milliseconds since epoch (1/1/1970)
+    pub timestamp_ms: u64,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum ObjectInfoRequestKind {
     /// Request the latest object state.
@@ -1795,6 +2127,32 @@ pub enum ExecutionFailureStatus {
     MoveAbort(MoveLocation, u64), // TODO func def + offset?
     VMVerificationOrDeserializationError,
     VMInvariantViolation,
+
+    //
+    // Programmable transaction errors
+    //
+    /// The number of arguments to a command does not match the number of parameters
+    ArityMismatch,
+    CommandArgumentError {
+        arg_idx: u16,
+        kind: CommandArgumentError,
+    },
+    /// A public (non-entry) function called from a programmable transaction returns a reference
+    InvalidPublicFunctionReturnType {
+        idx: u16,
+    },
+    /// A value without `drop` was not consumed by the end of the programmable transaction
+    UnusedValueWithoutDrop {
+        result_idx: u16,
+        secondary_idx: u16,
+    },
+
+    //
+    // Package upgrade errors
+    //
+    PackageUpgradeError {
+        upgrade_error: PackageUpgradeError,
+    },
 }
 
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Hash)]
@@ -1835,6 +2193,53 @@ pub enum EntryTypeArgumentErrorKind {
     ConstraintNotSatisfied,
 }
 
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
+pub enum CommandArgumentError {
+    /// The type of the value does not match the expected type
+    TypeMismatch,
+    /// The argument cannot be deserialized into a value of the specified type
+    InvalidBCSBytes,
+    /// The argument cannot be instantiated from raw bytes
+    InvalidUsageOfPureArg,
+    /// Out of bounds access to an input or result
+    IndexOutOfBounds { idx: u16 },
+    /// Out of bounds access to a nested result
+    SecondaryIndexOutOfBounds { result_idx: u16, secondary_idx: u16 },
+    /// A single value was expected, but the result has zero or several values
+    InvalidResultArity { result_idx: u16 },
+    /// The gas coin can only be used by-value with `TransferObjects`
+    InvalidGasCoinUsage,
+    /// The value was previously moved, or is being borrowed in an incompatible way
+    InvalidValueUsage,
+    /// Immutable and shared objects cannot be passed by-value
+    InvalidObjectByValue,
+    /// Immutable objects cannot be passed by mutable reference
+    InvalidObjectByMutRef,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
+pub enum PackageUpgradeError {
+    /// The package to upgrade could not be loaded
+    UnableToFetchPackage { package_id: ObjectID },
+    /// The object to upgrade is not a package
+    NotAPackage { object_id: ObjectID },
+    /// The new modules are not compatible with the modules of the package being upgraded
+    IncompatibleUpgrade,
+    /// The capability object is not an `UpgradeCap`
+    NotAnUpgradeCap { object_id: ObjectID },
+    /// The capability does not authorize upgrading the package
+    PackageIDDoesNotMatch {
+        package_id: ObjectID,
+        cap_package_id: ObjectID,
+    },
+    /// A dependency is older than the version of the same package that another dependency links
+    /// against
+    DependencyDowngrade {
+        package_id: ObjectID,
+        linked_package_id: ObjectID,
+    },
+}
+
 #[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
 pub struct CircularObjectOwnership {
     pub object: ObjectID,
@@ -1874,6 +2279,14 @@ impl ExecutionFailureStatus {
     pub fn invalid_shared_by_value(object: ObjectID) -> Self {
         InvalidSharedByValue { object }.into()
     }
+
+    pub fn command_argument_error(kind: CommandArgumentError, arg_idx: u16) -> Self {
+        Self::CommandArgumentError { arg_idx, kind }
+    }
+
+    pub fn package_upgrade_error(upgrade_error: PackageUpgradeError) -> Self {
+        Self::PackageUpgradeError { upgrade_error }
+    }
 }
 
 impl Display for ExecutionFailureStatus {
@@ -2023,6 +2436,118 @@ impl Display for ExecutionFailureStatus {
             ExecutionFailureStatus::VMInvariantViolation => {
                 write!(f, "MOVE VM INVARIANT VIOLATION.")
             }
+            ExecutionFailureStatus::ArityMismatch => write!(
+                f,
+                "Arity mismatch for Move function. \
+                The number of arguments does not match the number of parameters"
+            ),
+            ExecutionFailureStatus::CommandArgumentError { arg_idx, kind } => {
+                write!(f, "Invalid command argument at {arg_idx}. {kind}")
+            }
+            ExecutionFailureStatus::InvalidPublicFunctionReturnType { idx } => write!(
+                f,
+                "Invalid public Move function signature. \
+                Unsupported return type for return value {idx}"
+            ),
+            ExecutionFailureStatus::UnusedValueWithoutDrop {
+                result_idx,
+                secondary_idx,
+            } => write!(
+                f,
+                "Unused result without the drop ability. \
+                Command result {result_idx}, return value {secondary_idx}"
+            ),
+            ExecutionFailureStatus::PackageUpgradeError { upgrade_error } => {
+                write!(f, "Invalid package upgrade. {upgrade_error}")
+            }
+        }
+    }
+}
+
+impl Display for PackageUpgradeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackageUpgradeError::UnableToFetchPackage { package_id } => {
+                write!(f, "Unable to fetch package at {package_id}")
+            }
+            PackageUpgradeError::NotAPackage { object_id } => {
+                write!(f, "Object {object_id} is not a package")
+            }
+            PackageUpgradeError::IncompatibleUpgrade => write!(
+                f,
+                "New modules are incompatible with the modules of the upgraded package"
+            ),
+            PackageUpgradeError::NotAnUpgradeCap { object_id } => {
+                write!(f, "Object {object_id} is not an upgrade capability")
+            }
+            PackageUpgradeError::PackageIDDoesNotMatch {
+                package_id,
+                cap_package_id,
+            } => write!(
+                f,
+                "Upgrade capability is for package {cap_package_id}, \
+                not for package {package_id}"
+            ),
+            PackageUpgradeError::DependencyDowngrade {
+                package_id,
+                linked_package_id,
+            } => write!(
+                f,
+                "Dependency {package_id} is older than version {linked_package_id} \
+                of the same package, which another dependency links against"
+            ),
+        }
+    }
+}
+
+impl Display for CommandArgumentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandArgumentError::TypeMismatch => {
+                write!(f, "The type of the value does not match the expected type")
+            }
+            CommandArgumentError::InvalidBCSBytes => write!(
+                f,
+                "The argument cannot be deserialized into a value of the specified type"
+            ),
+            CommandArgumentError::InvalidUsageOfPureArg => {
+                write!(f, "The argument cannot be instantiated from raw bytes")
+            }
+            CommandArgumentError::IndexOutOfBounds { idx } => {
+                write!(f, "Out of bounds access to input or result vector {idx}")
+            }
+            CommandArgumentError::SecondaryIndexOutOfBounds {
+                result_idx,
+                secondary_idx,
+            } => write!(
+                f,
+                "Out of bounds secondary access to result vector {result_idx} \
+                at secondary index {secondary_idx}"
+            ),
+            CommandArgumentError::InvalidResultArity { result_idx } => write!(
+                f,
+                "Invalid usage of result {result_idx}, \
+                expected a single result but found either no return values or multiple."
+            ),
+            CommandArgumentError::InvalidGasCoinUsage => write!(
+                f,
+                "Invalid usage of the gas coin. \
+                It can only be used by-value with TransferObjects"
+            ),
+            CommandArgumentError::InvalidValueUsage => write!(
+                f,
+                "Invalid usage of value. \
+                Mutably borrowed values require unique usage. \
+                Immutably borrowed values cannot be taken or borrowed mutably. \
+                Taken values cannot be used again."
+            ),
+            CommandArgumentError::InvalidObjectByValue => {
+                write!(f, "Immutable and shared objects cannot be passed by-value.")
+            }
+            CommandArgumentError::InvalidObjectByMutRef => write!(
+                f,
+                "Immutable objects cannot be passed by mutable reference, &mut."
+            ),
         }
     }
 }
@@ -2619,6 +3144,7 @@ pub enum ConsensusTransactionKey {
     Certificate(TransactionDigest),
     CheckpointSignature(AuthorityName, CheckpointSequenceNumber),
     EndOfPublish(AuthorityName),
+    CapabilityNotification(AuthorityName, u64 /* generation */),
 }
 
 impl Debug for ConsensusTransactionKey {
@@ -2629,6 +3155,61 @@ impl Debug for ConsensusTransactionKey {
                 write!(f, "CheckpointSignature({:?}, {:?})", name.concise(), seq)
             }
             Self::EndOfPublish(name) => write!(f, "EndOfPublish({:?})", name.concise()),
+            Self::CapabilityNotification(name, generation) => write!(
+                f,
+                "CapabilityNotification({:?}, {:?})",
+                name.concise(),
+                generation
+            ),
+        }
+    }
+}
+
+/// Used to advertise the capabilities of each authority via consensus. This allows validators to
+/// negotiate the creation of the ChangeEpoch transaction.
+#[derive(Serialize, Deserialize, Clone, Hash)]
+pub struct AuthorityCapabilities {
+    /// Originating authority - must match narwhal transaction source.
+    pub authority: AuthorityName,
+    /// Generation number set by sending authority. Used to determine which of multiple
+    /// AuthorityCapabilities messages from the same authority is the most recent.
+    ///
+    /// (Currently, we just set this to the current time in milliseconds since the epoch, but this
+    /// should not be interpreted as a timestamp.)
+    pub generation: u64,
+
+    /// ProtocolVersions that the authority supports.
+    pub supported_protocol_versions: SupportedProtocolVersions,
+}
+
+impl Debug for AuthorityCapabilities {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthorityCapabilities")
+            .field("authority", &self.authority.concise())
+            .field("generation", &self.generation)
+            .field(
+                "supported_protocol_versions",
+                &self.supported_protocol_versions,
+            )
+            .finish()
+    }
+}
+
+impl AuthorityCapabilities {
+    pub fn new(
+        authority: AuthorityName,
+        supported_protocol_versions: SupportedProtocolVersions,
+    ) -> Self {
+        let generation = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Sui did not exist prior to 1970")
+            .as_millis()
+            .try_into()
+            .expect("This build of sui is not supported in the year 500,000,000");
+        Self {
+            authority,
+            generation,
+            supported_protocol_versions,
         }
     }
 }
@@ -2638,6 +3219,7 @@ pub enum ConsensusTransactionKind {
     UserTransaction(Box<CertifiedTransaction>),
     CheckpointSignature(Box<CheckpointSignatureMessage>),
     EndOfPublish(AuthorityName),
+    CapabilityNotification(AuthorityCapabilities),
 }
 
 impl ConsensusTransaction {
@@ -2676,6 +3258,16 @@ impl ConsensusTransaction {
         }
     }
 
+    pub fn new_capability_notification(capabilities: AuthorityCapabilities) -> Self {
+        let mut hasher = DefaultHasher::new();
+        capabilities.hash(&mut hasher);
+        let tracking_id = hasher.finish().to_le_bytes();
+        Self {
+            tracking_id,
+            kind: ConsensusTransactionKind::CapabilityNotification(capabilities),
+        }
+    }
+
     pub fn get_tracking_id(&self) -> u64 {
         (&self.tracking_id[..])
             .read_u64::<BigEndian>()
@@ -2688,7 +3280,8 @@ impl ConsensusTransaction {
                 certificate.verify_signature(committee)
             }
             ConsensusTransactionKind::CheckpointSignature(data) => data.verify(committee),
-            ConsensusTransactionKind::EndOfPublish(_) => Ok(()),
+            ConsensusTransactionKind::EndOfPublish(_)
+            | ConsensusTransactionKind::CapabilityNotification(_) => Ok(()),
         }
     }
 
@@ -2706,6 +3299,9 @@ impl ConsensusTransaction {
             ConsensusTransactionKind::EndOfPublish(authority) => {
                 ConsensusTransactionKey::EndOfPublish(*authority)
             }
+            ConsensusTransactionKind::CapabilityNotification(cap) => {
+                ConsensusTransactionKey::CapabilityNotification(cap.authority, cap.generation)
+            }
         }
     }
 
@@ -5,9 +5,11 @@ use std::fmt::{Debug, Display, Formatter};
 use std::slice::Iter;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::accumulator::Accumulator;
 use crate::base_types::ExecutionDigests;
 use crate::committee::{EpochId, ProtocolVersion, StakeUnit};
 use crate::crypto::{AuthoritySignInfo, AuthoritySignInfoTrait, AuthorityStrongQuorumSignInfo};
+use crate::digests::Sha3Digest;
 use crate::error::SuiResult;
 use crate::gas::GasCostSummary;
 use crate::signature::GenericSignature;
@@ -17,6 +19,7 @@ use crate::{
     crypto::{sha3_hash, AuthoritySignature, VerificationObligation},
     error::SuiError,
 };
+use fastcrypto::hash::MultisetHash;
 use fastcrypto::traits::Signer;
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
@@ -47,6 +50,34 @@ pub struct CheckpointResponse {
 
 // The constituent parts of checkpoints, signed and certified
 
+/// The digest of the multiset hash of the live object set, as accumulated by
+/// `StateAccumulator`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ECMHLiveObjectSetDigest {
+    pub digest: Sha3Digest,
+}
+
+impl From<&Accumulator> for ECMHLiveObjectSetDigest {
+    fn from(accumulator: &Accumulator) -> Self {
+        Self {
+            digest: Sha3Digest::new(accumulator.digest().into()),
+        }
+    }
+}
+
+/// A commitment to some part of the state that is included in a checkpoint, allowing nodes to
+/// detect whether they have diverged.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum CheckpointCommitment {
+    ECMHLiveObjectSetDigest(ECMHLiveObjectSetDigest),
+}
+
+impl From<ECMHLiveObjectSetDigest> for CheckpointCommitment {
+    fn from(digest: ECMHLiveObjectSetDigest) -> Self {
+        Self::ECMHLiveObjectSetDigest(digest)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct EndOfEpochData {
     /// next_epoch_committee is `Some` if and only if the current checkpoint is
@@ -98,7 +129,7 @@ impl CheckpointSummary {
         transactions: &CheckpointContents,
         previous_digest: Option<CheckpointDigest>,
         epoch_rolling_gas_cost_summary: GasCostSummary,
-        next_epoch_committee: Option<Committee>,
+        end_of_epoch_data: Option<EndOfEpochData>,
         timestamp_ms: CheckpointTimestamp,
     ) -> CheckpointSummary {
         let content_digest = transactions.digest();
@@ -110,15 +141,38 @@ impl CheckpointSummary {
             content_digest,
             previous_digest,
             epoch_rolling_gas_cost_summary,
-            end_of_epoch_data: next_epoch_committee.map(|c| EndOfEpochData {
-                next_epoch_committee: c.voting_rights,
-                next_epoch_protocol_version: c.protocol_version,
-            }),
+            end_of_epoch_data,
             timestamp_ms,
             version_specific_data: Vec::new(),
         }
     }
 
+    /// Commits the last checkpoint of an epoch to the state of the network at its end. The
+    /// commitments are carried in `version_specific_data`, so that summaries without any keep
+    /// their encoding.
+    pub fn with_epoch_commitments(mut self, epoch_commitments: Vec<CheckpointCommitment>) -> Self {
+        if !epoch_commitments.is_empty() {
+            self.version_specific_data =
+                bcs::to_bytes(&epoch_commitments).expect("Serialization cannot fail");
+        }
+        self
+    }
+
+    /// Commitments to the state of the network at the end of the epoch, such as the root of the
+    /// live object set accumulator. Empty unless this is the last checkpoint of an epoch built
+    /// with the `epoch_commitments` protocol feature enabled.
+    pub fn epoch_commitments(&self) -> SuiResult<Vec<CheckpointCommitment>> {
+        if self.version_specific_data.is_empty() {
+            return Ok(vec![]);
+        }
+        bcs::from_bytes(&self.version_specific_data).map_err(|e| SuiError::GenericAuthorityError {
+            error: format!(
+                "Invalid epoch commitments in checkpoint {}: {e}",
+                self.sequence_number
+            ),
+        })
+    }
+
     pub fn sequence_number(&self) -> &CheckpointSequenceNumber {
         &self.sequence_number
     }
@@ -206,7 +260,7 @@ impl SignedCheckpointSummary {
         transactions: &CheckpointContents,
         previous_digest: Option<CheckpointDigest>,
         epoch_rolling_gas_cost_summary: GasCostSummary,
-        next_epoch_committee: Option<Committee>,
+        end_of_epoch_data: Option<EndOfEpochData>,
         timestamp_ms: CheckpointTimestamp,
     ) -> SignedCheckpointSummary {
         let checkpoint = CheckpointSummary::new(
@@ -216,7 +270,7 @@ impl SignedCheckpointSummary {
             transactions,
             previous_digest,
             epoch_rolling_gas_cost_summary,
-            next_epoch_committee,
+            end_of_epoch_data,
             timestamp_ms,
         );
         SignedCheckpointSummary::new_from_summary(checkpoint, authority, signer)
@@ -587,4 +641,28 @@ mod tests {
 
         assert!(CertifiedCheckpointSummary::aggregate(signed_checkpoints, &committee).is_err());
     }
+
+    #[test]
+    fn test_epoch_commitments_encoding() {
+        let set = CheckpointContents::new_with_causally_ordered_transactions(
+            [ExecutionDigests::random()].into_iter(),
+        );
+        let summary =
+            CheckpointSummary::new(1, 1, 0, &set, None, GasCostSummary::default(), None, 0);
+
+        // Without commitments, the summary keeps the encoding that predates them.
+        let without_commitments = summary.clone().with_epoch_commitments(vec![]);
+        assert_eq!(
+            bcs::to_bytes(&without_commitments).unwrap(),
+            bcs::to_bytes(&summary).unwrap()
+        );
+        assert!(without_commitments.epoch_commitments().unwrap().is_empty());
+
+        let commitments: Vec<CheckpointCommitment> =
+            vec![ECMHLiveObjectSetDigest::from(&Accumulator::default()).into()];
+        let with_commitments = summary.with_epoch_commitments(commitments.clone());
+        let decoded: CheckpointSummary =
+            bcs::from_bytes(&bcs::to_bytes(&with_commitments).unwrap()).unwrap();
+        assert_eq!(decoded.epoch_commitments().unwrap(), commitments);
+    }
 }
@@ -2,16 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    base_types::ObjectID,
+    base_types::{ObjectID, SequenceNumber},
     error::{ExecutionError, ExecutionErrorKind, SuiError, SuiResult},
+    id::{ID, UID},
+    object::PACKAGE_VERSION,
+    SUI_FRAMEWORK_ADDRESS,
 };
 use move_binary_format::access::ModuleAccess;
 use move_binary_format::binary_views::BinaryIndexedView;
 use move_binary_format::file_format::CompiledModule;
 use move_binary_format::normalized;
-use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use move_core_types::{
+    account_address::AccountAddress,
+    ident_str,
+    identifier::{IdentStr, Identifier},
+    language_storage::StructTag,
+};
 use move_disassembler::disassembler::Disassembler;
 use move_ir_types::location::Spanned;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::serde_as;
@@ -41,14 +50,100 @@ pub struct FnInfoKey {
 /// A map from function info keys to function info
 pub type FnInfoMap = BTreeMap<FnInfoKey, FnInfo>;
 
+pub const PACKAGE_MODULE_NAME: &IdentStr = ident_str!("package");
+pub const UPGRADE_CAP_STRUCT_NAME: &IdentStr = ident_str!("UpgradeCap");
+
+/// Identifies the package that first defined a struct. Upgraded packages keep the types of the
+/// versions they upgrade, so a struct can originate from an earlier version of its package.
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize, Hash, JsonSchema)]
+pub struct TypeOrigin {
+    pub module_name: String,
+    pub struct_name: String,
+    pub package: ObjectID,
+}
+
+/// The version of a dependency that an upgraded package links against
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Deserialize, Serialize, Hash, JsonSchema)]
+pub struct UpgradeInfo {
+    /// The ID the linked version of the dependency is stored at
+    pub upgraded_id: ObjectID,
+    /// The version of the linked version of the dependency
+    pub upgraded_version: SequenceNumber,
+}
+
 // serde_bytes::ByteBuf is an analog of Vec<u8> with built-in fast serialization.
 #[serde_as]
 #[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize, Hash)]
+#[serde(rename = "UpgradedMovePackage")]
 pub struct MovePackage {
     id: ObjectID,
+    /// The version of the package, starting at `PACKAGE_VERSION` and incremented by each upgrade.
+    /// Each version of a package is a separate immutable object, stored at its own ID (and at
+    /// object version `PACKAGE_VERSION`), but their modules keep the address of the original
+    /// package.
+    version: SequenceNumber,
     // TODO use session cache
     #[serde_as(as = "BTreeMap<_, Bytes>")]
     module_map: BTreeMap<String, Vec<u8>>,
+    /// The package that defined each struct in the package. Empty for packages that have not
+    /// been upgraded, which define all of their structs.
+    type_origin_table: Vec<TypeOrigin>,
+    /// The version each package the modules link against is loaded from, by original package ID,
+    /// including this package itself. Empty for packages that have not been upgraded, which link
+    /// against the original version of every package.
+    linkage_table: BTreeMap<ObjectID, UpgradeInfo>,
+}
+
+/// The encoding of packages that have not been upgraded, which predates package upgrades. Such
+/// packages keep it so that their objects, and the digests of genesis and of every transaction
+/// that published a package, do not change. See `Data`'s `Serialize` implementation.
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename = "MovePackage")]
+pub(crate) struct OriginalMovePackage {
+    id: ObjectID,
+    #[serde_as(as = "BTreeMap<_, Bytes>")]
+    module_map: BTreeMap<String, Vec<u8>>,
+}
+
+#[serde_as]
+#[derive(Serialize)]
+#[serde(rename = "MovePackage")]
+pub(crate) struct OriginalMovePackageRef<'a> {
+    id: ObjectID,
+    #[serde_as(as = "&BTreeMap<_, Bytes>")]
+    module_map: &'a BTreeMap<String, Vec<u8>>,
+}
+
+impl From<OriginalMovePackage> for MovePackage {
+    fn from(package: OriginalMovePackage) -> Self {
+        Self {
+            id: package.id,
+            version: PACKAGE_VERSION,
+            module_map: package.module_map,
+            type_origin_table: vec![],
+            linkage_table: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a MovePackage> for OriginalMovePackageRef<'a> {
+    fn from(package: &'a MovePackage) -> Self {
+        Self {
+            id: package.id,
+            module_map: &package.module_map,
+        }
+    }
+}
+
+/// Rust version of the Move sui::package::UpgradeCap type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct UpgradeCap {
+    pub id: UID,
+    /// The latest version of the package that can be upgraded with this capability
+    pub package: ID,
+    /// The number of upgrades performed with this capability
+    pub version: u64,
 }
 
 impl MovePackage {
@@ -56,9 +151,34 @@ impl MovePackage {
         id: ObjectID,
         module_map: &BTreeMap<String, Vec<u8>>,
     ) -> Result<Self, ExecutionError> {
+        Self::new_with_upgrade_info(id, PACKAGE_VERSION, module_map, vec![], BTreeMap::new())
+    }
+
+    /// Create a package at `version`. The type origin and linkage tables are only kept for
+    /// upgraded packages: packages at `PACKAGE_VERSION` define all their types and link against
+    /// the original version of their dependencies.
+    pub fn new_with_upgrade_info(
+        id: ObjectID,
+        version: SequenceNumber,
+        module_map: &BTreeMap<String, Vec<u8>>,
+        type_origin_table: Vec<TypeOrigin>,
+        linkage_table: BTreeMap<ObjectID, UpgradeInfo>,
+    ) -> Result<Self, ExecutionError> {
+        let is_upgraded = version != PACKAGE_VERSION;
         let pkg = Self {
             id,
+            version,
             module_map: module_map.clone(),
+            type_origin_table: if is_upgraded {
+                type_origin_table
+            } else {
+                vec![]
+            },
+            linkage_table: if is_upgraded {
+                linkage_table
+            } else {
+                BTreeMap::new()
+            },
         };
         let object_size = pkg.size() as u64;
         // This assumes jkjk
@@ -97,6 +217,48 @@ impl MovePackage {
         )
     }
 
+    /// Create the next version of this package, stored at `new_id` and containing `modules`,
+    /// which link against the package versions in `linkage_table`. Structs defined by a previous
+    /// version keep their origin, while structs introduced by `modules` originate from `new_id`.
+    pub fn new_upgraded<T: IntoIterator<Item = CompiledModule>>(
+        &self,
+        new_id: ObjectID,
+        modules: T,
+        mut linkage_table: BTreeMap<ObjectID, UpgradeInfo>,
+    ) -> Result<Self, ExecutionError> {
+        let mut type_origin_table = self.type_origin_table();
+        let module_map = modules
+            .into_iter()
+            .map(|module| {
+                let origins = new_type_origins(&module, new_id, |m, s| {
+                    type_origin_table
+                        .iter()
+                        .any(|o| o.module_name == m && o.struct_name == s)
+                });
+                type_origin_table.extend(origins);
+                let mut bytes = Vec::new();
+                module.serialize(&mut bytes).unwrap();
+                (module.self_id().name().to_string(), bytes)
+            })
+            .collect();
+
+        let version = SequenceNumber::from_u64(self.version.value() + 1);
+        linkage_table.insert(
+            self.original_package_id(),
+            UpgradeInfo {
+                upgraded_id: new_id,
+                upgraded_version: version,
+            },
+        );
+        Self::new_with_upgrade_info(
+            new_id,
+            version,
+            &module_map,
+            type_origin_table,
+            linkage_table,
+        )
+    }
+
     /// Return the size of the package in bytes. Only count the bytes of the modules themselves--the
     /// fact that we store them in a map is an implementation detail
     pub fn size(&self) -> usize {
@@ -107,6 +269,75 @@ impl MovePackage {
         self.id
     }
 
+    pub fn version(&self) -> SequenceNumber {
+        self.version
+    }
+
+    /// Whether this package is a later version of another package
+    pub fn is_upgraded(&self) -> bool {
+        self.version != PACKAGE_VERSION
+    }
+
+    /// The ID of the first version of this package, which is the address its modules are
+    /// published at.
+    pub fn original_package_id(&self) -> ObjectID {
+        if !self.is_upgraded() {
+            return self.id;
+        }
+        let bytes = self
+            .module_map
+            .values()
+            .next()
+            .expect("Tried to get the original ID of an empty package");
+        let module = CompiledModule::deserialize(bytes)
+            .expect("Unwrap safe because Sui serializes/verifies modules before publishing them");
+        ObjectID::from(*module.self_id().address())
+    }
+
+    /// The package that defined each struct in the package
+    pub fn type_origin_table(&self) -> Vec<TypeOrigin> {
+        if self.is_upgraded() {
+            return self.type_origin_table.clone();
+        }
+        self.module_map
+            .values()
+            .flat_map(|bytes| {
+                let module = CompiledModule::deserialize(bytes).expect(
+                    "Unwrap safe because Sui serializes/verifies modules before publishing them",
+                );
+                new_type_origins(&module, self.id, |_, _| false)
+            })
+            .collect()
+    }
+
+    /// The ID of the package version that defined struct `struct_name` in module `module_name`,
+    /// or `None` if this version of the package does not define it
+    pub fn type_origin(&self, module_name: &str, struct_name: &str) -> Option<ObjectID> {
+        if self.is_upgraded() {
+            return self
+                .type_origin_table
+                .iter()
+                .find(|o| o.module_name == module_name && o.struct_name == struct_name)
+                .map(|o| o.package);
+        }
+        let module = self
+            .deserialize_module(&Identifier::new(module_name).ok()?)
+            .ok()?;
+        let defined = module.struct_defs().iter().any(|def| {
+            module
+                .identifier_at(module.struct_handle_at(def.struct_handle).name)
+                .as_str()
+                == struct_name
+        });
+        defined.then_some(self.id)
+    }
+
+    /// The version of each package the modules link against, by original package ID. Packages
+    /// that are missing from the table are linked against at their original version.
+    pub fn linkage_table(&self) -> &BTreeMap<ObjectID, UpgradeInfo> {
+        &self.linkage_table
+    }
+
     pub fn serialized_module_map(&self) -> &BTreeMap<String, Vec<u8>> {
         &self.module_map
     }
@@ -132,6 +363,48 @@ impl MovePackage {
     }
 }
 
+impl UpgradeCap {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: PACKAGE_MODULE_NAME.to_owned(),
+            name: UPGRADE_CAP_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    /// Is this other StructTag representing an UpgradeCap?
+    pub fn is_upgrade_cap(other: &StructTag) -> bool {
+        other.address == SUI_FRAMEWORK_ADDRESS
+            && other.module.as_ident_str() == PACKAGE_MODULE_NAME
+            && other.name.as_ident_str() == UPGRADE_CAP_STRUCT_NAME
+    }
+}
+
+/// The origins of the structs defined in `module` that are not `already_defined` by a previous
+/// version of the package
+fn new_type_origins(
+    module: &CompiledModule,
+    package: ObjectID,
+    already_defined: impl Fn(&str, &str) -> bool,
+) -> Vec<TypeOrigin> {
+    let module_name = module.self_id().name().to_string();
+    module
+        .struct_defs()
+        .iter()
+        .map(|def| {
+            let handle = module.struct_handle_at(def.struct_handle);
+            module.identifier_at(handle.name).to_string()
+        })
+        .filter(|struct_name| !already_defined(&module_name, struct_name))
+        .map(|struct_name| TypeOrigin {
+            module_name: module_name.clone(),
+            struct_name,
+            package,
+        })
+        .collect()
+}
+
 pub fn disassemble_modules<'a, I>(modules: I) -> SuiResult<BTreeMap<String, Value>>
 where
     I: Iterator<Item = &'a Vec<u8>>,
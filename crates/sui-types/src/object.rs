@@ -12,14 +12,14 @@ use move_core_types::language_storage::StructTag;
 use move_core_types::language_storage::TypeTag;
 use move_core_types::value::{MoveStruct, MoveStructLayout, MoveTypeLayout};
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_with::serde_as;
 use serde_with::Bytes;
 
 use crate::crypto::{deterministic_random_account_key, sha3_hash};
 use crate::error::{ExecutionError, ExecutionErrorKind};
 use crate::error::{SuiError, SuiResult};
-use crate::move_package::MovePackage;
+use crate::move_package::{MovePackage, OriginalMovePackage, OriginalMovePackageRef};
 use crate::{
     base_types::{
         ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
@@ -287,7 +287,8 @@ impl MoveObject {
     }
 }
 
-#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize, Hash)]
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Hash)]
+#[serde(from = "DataRepr")]
 #[allow(clippy::large_enum_variant)]
 pub enum Data {
     /// An object whose governing logic lives in a published Move module
@@ -297,6 +298,47 @@ pub enum Data {
     // ... Sui "native" types go here
 }
 
+/// The encoding of `Data`. Packages that have not been upgraded keep the encoding that predates
+/// package upgrades, while upgraded packages are encoded with their version and linkage in a
+/// separate variant.
+#[derive(Deserialize)]
+#[serde(rename = "Data")]
+#[allow(clippy::large_enum_variant)]
+enum DataRepr {
+    Move(MoveObject),
+    Package(OriginalMovePackage),
+    UpgradedPackage(MovePackage),
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Data")]
+enum DataReprRef<'a> {
+    Move(&'a MoveObject),
+    Package(OriginalMovePackageRef<'a>),
+    UpgradedPackage(&'a MovePackage),
+}
+
+impl From<DataRepr> for Data {
+    fn from(data: DataRepr) -> Self {
+        match data {
+            DataRepr::Move(o) => Data::Move(o),
+            DataRepr::Package(p) => Data::Package(p.into()),
+            DataRepr::UpgradedPackage(p) => Data::Package(p),
+        }
+    }
+}
+
+impl Serialize for Data {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Data::Move(o) => DataReprRef::Move(o),
+            Data::Package(p) if p.is_upgraded() => DataReprRef::UpgradedPackage(p),
+            Data::Package(p) => DataReprRef::Package(p.into()),
+        }
+        .serialize(serializer)
+    }
+}
+
 impl Data {
     pub fn try_as_move(&self) -> Option<&MoveObject> {
         use Data::*;
@@ -0,0 +1,118 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Utility for building programmable transactions. Inputs and command results are referred to by
+//! the `Argument` handles returned when they are added.
+
+use anyhow::Context;
+use move_core_types::{identifier::Identifier, language_storage::TypeTag};
+use serde::Serialize;
+
+use crate::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    messages::{
+        Argument, CallArg, Command, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction,
+    },
+};
+
+#[cfg(test)]
+#[path = "unit_tests/programmable_transaction_builder_tests.rs"]
+mod programmable_transaction_builder_tests;
+
+#[derive(Default)]
+pub struct ProgrammableTransactionBuilder {
+    inputs: Vec<CallArg>,
+    commands: Vec<Command>,
+}
+
+impl ProgrammableTransactionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn finish(self) -> ProgrammableTransaction {
+        let Self { inputs, commands } = self;
+        ProgrammableTransaction { inputs, commands }
+    }
+
+    /// Adds an input to the transaction. Inputs are not deduplicated.
+    pub fn input(&mut self, call_arg: CallArg) -> Argument {
+        let idx = self.inputs.len();
+        self.inputs.push(call_arg);
+        Argument::Input(idx as u16)
+    }
+
+    /// Adds the BCS bytes of `value` as a pure input
+    pub fn pure<T: Serialize>(&mut self, value: T) -> anyhow::Result<Argument> {
+        let bytes = bcs::to_bytes(&value).context("Unable to serialize pure argument")?;
+        Ok(self.input(CallArg::Pure(bytes)))
+    }
+
+    pub fn obj(&mut self, obj_arg: ObjectArg) -> Argument {
+        self.input(CallArg::Object(obj_arg))
+    }
+
+    /// Adds a command to the transaction, returning the handle to its result
+    pub fn command(&mut self, command: Command) -> Argument {
+        let idx = self.commands.len();
+        self.commands.push(command);
+        Argument::Result(idx as u16)
+    }
+
+    pub fn move_call(
+        &mut self,
+        package: ObjectID,
+        module: Identifier,
+        function: Identifier,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<Argument>,
+    ) -> Argument {
+        self.command(Command::MoveCall(Box::new(ProgrammableMoveCall {
+            package,
+            module,
+            function,
+            type_arguments,
+            arguments,
+        })))
+    }
+
+    pub fn transfer_objects(
+        &mut self,
+        recipient: SuiAddress,
+        objects: Vec<Argument>,
+    ) -> anyhow::Result<()> {
+        let recipient = self.pure(recipient)?;
+        self.command(Command::TransferObjects(objects, recipient));
+        Ok(())
+    }
+
+    /// Splits `amount` off of `coin`, returning the handle to the new coin
+    pub fn split_coin(&mut self, coin: Argument, amount: u64) -> anyhow::Result<Argument> {
+        let amount = self.pure(amount)?;
+        Ok(self.command(Command::SplitCoin(coin, amount)))
+    }
+
+    pub fn merge_coins(&mut self, target: Argument, coins: Vec<Argument>) {
+        self.command(Command::MergeCoins(target, coins));
+    }
+
+    pub fn publish(&mut self, modules: Vec<Vec<u8>>) {
+        self.command(Command::Publish(modules));
+    }
+
+    /// Sends `amount` of the gas coin to `recipient`
+    pub fn pay_sui(&mut self, recipient: SuiAddress, amount: u64) -> anyhow::Result<()> {
+        let coin = self.split_coin(Argument::GasCoin, amount)?;
+        self.transfer_objects(recipient, vec![coin])
+    }
+
+    /// Transfers an owned object to `recipient`
+    pub fn transfer_object(
+        &mut self,
+        recipient: SuiAddress,
+        object_ref: ObjectRef,
+    ) -> anyhow::Result<()> {
+        let object = self.obj(ObjectArg::ImmOrOwnedObject(object_ref));
+        self.transfer_objects(recipient, vec![object])
+    }
+}
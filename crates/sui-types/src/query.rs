@@ -1,14 +1,20 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::base_types::{SuiAddress, TransactionDigest};
 use crate::event::EventType;
+use crate::messages_checkpoint::CheckpointSequenceNumber;
 use crate::object::Owner;
-use crate::ObjectID;
+use crate::{parse_sui_struct_tag, ObjectID};
 
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum TransactionQuery {
@@ -28,6 +34,29 @@ pub enum TransactionQuery {
     FromAddress(SuiAddress),
     /// Query by recipient address.
     ToAddress(SuiAddress),
+    /// Query by transactions indexed in [start_time, end_time) interval
+    #[serde(rename_all = "camelCase")]
+    TimeRange {
+        /// left endpoint of time interval, milliseconds since epoch, inclusive
+        start_time: u64,
+        /// right endpoint of time interval, milliseconds since epoch, exclusive
+        end_time: u64,
+    },
+    /// Query by transactions included in checkpoints [start, end)
+    Checkpoint {
+        /// first checkpoint sequence number, inclusive
+        start: CheckpointSequenceNumber,
+        /// last checkpoint sequence number, exclusive
+        end: CheckpointSequenceNumber,
+    },
+    /// Query by the type of a Move event emitted by the transaction
+    MoveEvent(
+        /// the event struct name type, e.g. `0x2::devnet_nft::MintNFTEvent`
+        String,
+    ),
+    /// Query by transactions matching all the given queries. The first query is used to look up
+    /// the candidate transactions, so it should be the most selective one.
+    MatchAll(Vec<TransactionQuery>),
 }
 
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
@@ -67,3 +96,50 @@ pub enum EventQuery {
         end_time: u64,
     },
 }
+
+/// Filter on the Move type of owned objects, from the broadest to the narrowest criteria.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ObjectTypeFilter {
+    /// Objects of a type defined in the given package, e.g. `0x2`.
+    Package(ObjectID),
+    /// Objects of a type defined in the given module, e.g. `0x2::coin`.
+    Module { package: ObjectID, module: String },
+    /// Objects of the given struct, with any type parameters, e.g. `0x2::coin::Coin`.
+    StructName {
+        package: ObjectID,
+        module: String,
+        name: String,
+    },
+    /// Objects of exactly the given type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`.
+    StructType(StructTag),
+}
+
+impl FromStr for ObjectTypeFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('<') {
+            return Ok(Self::StructType(parse_sui_struct_tag(s)?));
+        }
+        let parse_package = |package: &str| {
+            ObjectID::from_hex_literal(package)
+                .map_err(|e| anyhow!("Invalid package ID [{package}] in type filter: {e}"))
+        };
+        Ok(match s.split("::").collect::<Vec<_>>()[..] {
+            [package] => Self::Package(parse_package(package)?),
+            [package, module] => Self::Module {
+                package: parse_package(package)?,
+                module: Identifier::new(module)?.into_string(),
+            },
+            [_, _, _] => {
+                let tag = parse_sui_struct_tag(s)?;
+                Self::StructName {
+                    package: tag.address.into(),
+                    module: tag.module.into_string(),
+                    name: tag.name.into_string(),
+                }
+            }
+            _ => bail!("Invalid type filter [{s}], expected a package, module or struct type."),
+        })
+    }
+}
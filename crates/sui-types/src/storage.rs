@@ -74,12 +74,24 @@ impl SingleTxContext {
     pub fn pay_all_sui(sender: SuiAddress) -> Self {
         Self::sui_transaction(ident_str!("pay_all_sui"), sender)
     }
+    pub fn transfer_objects(sender: SuiAddress) -> Self {
+        Self::sui_transaction(ident_str!("transfer_objects"), sender)
+    }
+    pub fn split_coin(sender: SuiAddress) -> Self {
+        Self::sui_transaction(ident_str!("split_coin"), sender)
+    }
+    pub fn merge_coins(sender: SuiAddress) -> Self {
+        Self::sui_transaction(ident_str!("merge_coins"), sender)
+    }
     pub fn unused_input(sender: SuiAddress) -> Self {
         Self::sui_transaction(ident_str!("unused_input_object"), sender)
     }
     pub fn publish(sender: SuiAddress) -> Self {
         Self::sui_transaction(ident_str!("publish"), sender)
     }
+    pub fn upgrade(sender: SuiAddress) -> Self {
+        Self::sui_transaction(ident_str!("upgrade"), sender)
+    }
     pub fn gas(sender: SuiAddress) -> Self {
         Self::sui_transaction(ident_str!("gas"), sender)
     }
@@ -111,6 +123,10 @@ pub trait Storage {
     fn read_object(&self, id: &ObjectID) -> Option<&Object>;
 
     fn apply_object_changes(&mut self, changes: BTreeMap<ObjectID, ObjectChange>);
+
+    /// Load the modules published at each runtime ID in `linkage` from the upgraded package it
+    /// maps to, rather than from the original package stored at that ID
+    fn set_linkage(&mut self, linkage: BTreeMap<ObjectID, ObjectID>);
 }
 
 pub trait BackingPackageStore {
@@ -21,10 +21,11 @@ use crate::{
     base_types::{
         ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
     },
-    error::{ExecutionError, SuiError, SuiResult},
+    error::{ExecutionError, ExecutionErrorKind, SuiError, SuiResult},
     event::Event,
     fp_bail, gas,
     gas::{GasCostSummary, SuiGasStatus},
+    gas_coin::GasCoin,
     messages::{ExecutionStatus, InputObjects, TransactionEffects},
     object::Owner,
     object::{Data, Object},
@@ -102,6 +103,11 @@ pub struct TemporaryStore<S> {
     events: Vec<Event>,
     gas_charged: Option<(SuiAddress, ObjectID, GasCostSummary)>,
     storage_rebate_rate: u64,
+    /// Maps the runtime ID of a package to the ID of the upgraded package its modules are loaded
+    /// from while calling into that upgrade.
+    linkage: BTreeMap<ObjectID, ObjectID>,
+    /// The gas coins smashed into the gas object, which must be smashed again after a reset.
+    smashed_gas: Option<(SuiAddress, ObjectID, Vec<ObjectID>)>,
 }
 
 impl<S> TemporaryStore<S> {
@@ -127,6 +133,8 @@ impl<S> TemporaryStore<S> {
             events: Vec::new(),
             gas_charged: None,
             storage_rebate_rate: protocol_config.storage_rebate_rate(),
+            linkage: BTreeMap::new(),
+            smashed_gas: None,
         }
     }
 
@@ -702,6 +710,62 @@ impl<S> TemporaryStore<S> {
         self.gas_charged = Some((sender, gas_object_id, cost_summary));
     }
 
+    /// Merges the balances of the `extra_gas_coins` into the gas object and deletes them, so
+    /// that gas can be paid with many coins. Unlike other changes, the merge is kept across
+    /// `reset`, as gas is charged from the merged coin even when execution fails.
+    pub fn smash_gas(
+        &mut self,
+        sender: SuiAddress,
+        gas_object_id: ObjectID,
+        extra_gas_coins: Vec<ObjectID>,
+    ) -> Result<(), ExecutionError> {
+        if extra_gas_coins.is_empty() {
+            return Ok(());
+        }
+        let missing_input = |id: &ObjectID| {
+            ExecutionError::new_with_source(
+                ExecutionErrorKind::InvariantViolation,
+                format!("Gas coin {id} is not an input object"),
+            )
+        };
+        let mut gas_object = self
+            .input_objects
+            .get(&gas_object_id)
+            .ok_or_else(|| missing_input(&gas_object_id))?
+            .clone();
+        let overflow = || {
+            ExecutionError::new_with_source(
+                ExecutionErrorKind::InvalidGasObject,
+                "Total balance of the gas coins overflows",
+            )
+        };
+        let mut extra_balance = 0u64;
+        let mut smashed = Vec::with_capacity(extra_gas_coins.len());
+        for id in &extra_gas_coins {
+            let coin = self
+                .input_objects
+                .get(id)
+                .ok_or_else(|| missing_input(id))?;
+            extra_balance = extra_balance
+                .checked_add(GasCoin::try_from(coin)?.value())
+                .ok_or_else(overflow)?;
+            smashed.push((*id, coin.version()));
+        }
+        GasCoin::try_from(&gas_object)?
+            .value()
+            .checked_add(extra_balance)
+            .ok_or_else(overflow)?;
+        gas::refund_gas(&mut gas_object, extra_balance);
+
+        let ctx = SingleTxContext::gas(sender);
+        self.write_object(&ctx, gas_object, WriteKind::Mutate);
+        for (id, version) in smashed {
+            self.delete_object(&ctx, &id, version, DeleteKind::Normal);
+        }
+        self.smashed_gas = Some((sender, gas_object_id, extra_gas_coins));
+        Ok(())
+    }
+
     pub fn delete_object(
         &mut self,
         ctx: &SingleTxContext,
@@ -726,11 +790,15 @@ impl<S> TemporaryStore<S> {
         self.deleted.insert(*id, (ctx.clone(), version, kind));
     }
 
-    /// Resets any mutations and deletions recorded in the store.
+    /// Resets any mutations and deletions recorded in the store, except for the gas smashing.
     pub fn reset(&mut self) {
         self.written.clear();
         self.deleted.clear();
         self.events.clear();
+        if let Some((sender, gas_object_id, extra_gas_coins)) = self.smashed_gas.take() {
+            self.smash_gas(sender, gas_object_id, extra_gas_coins)
+                .expect("Gas smashing succeeded before the reset");
+        }
     }
 
     pub fn log_event(&mut self, event: Event) {
@@ -789,12 +857,17 @@ impl<S: ChildObjectResolver> Storage for TemporaryStore<S> {
     fn apply_object_changes(&mut self, changes: BTreeMap<ObjectID, ObjectChange>) {
         TemporaryStore::apply_object_changes(self, changes)
     }
+
+    fn set_linkage(&mut self, linkage: BTreeMap<ObjectID, ObjectID>) {
+        self.linkage = linkage
+    }
 }
 
 impl<S: BackingPackageStore> ModuleResolver for TemporaryStore<S> {
     type Error = SuiError;
     fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
-        let package_id = &ObjectID::from(*module_id.address());
+        let runtime_id = ObjectID::from(*module_id.address());
+        let package_id = self.linkage.get(&runtime_id).unwrap_or(&runtime_id);
         let package_obj;
         let package = match self.read_object(package_id) {
             Some(object) => object,
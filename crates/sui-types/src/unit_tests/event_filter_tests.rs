@@ -69,6 +69,73 @@ fn test_move_event_filter() {
     assert!(EventFilter::MatchAny(filters.clone()).matches(&envelope));
 }
 
+#[test]
+fn test_move_event_field_comparison_and_time_filter() {
+    let tx_digest = TransactionDigest::random();
+    let move_event = Event::MoveEvent {
+        package_id: ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+        transaction_module: Identifier::from(ident_str!("test_module")),
+        sender: SuiAddress::random_for_testing_only(),
+        type_: GasCoin::type_(),
+        contents: GasCoin::new(ObjectID::random(), 10000).to_bcs_bytes(),
+    };
+    // u64 fields are rendered as strings, smaller integers as numbers.
+    let envelope = EventEnvelope {
+        timestamp: 1000,
+        tx_digest,
+        seq_num: 0,
+        event_num: 0,
+        event: move_event,
+        move_struct_json_value: Some(json!({"balance": "10000", "decimals": 9})),
+    };
+
+    let filters = vec![
+        EventFilter::MoveEventFieldGreaterThan {
+            path: "/balance".to_string(),
+            value: 9999,
+        },
+        EventFilter::MoveEventFieldLessThan {
+            path: "/balance".to_string(),
+            value: 10001,
+        },
+        EventFilter::MoveEventFieldGreaterThan {
+            path: "/decimals".to_string(),
+            value: 8,
+        },
+        EventFilter::Transaction(tx_digest),
+        EventFilter::TimeRange {
+            start_time: 1000,
+            end_time: 1001,
+        },
+    ];
+    for filter in &filters {
+        assert!(filter.matches(&envelope))
+    }
+
+    let false_filters = vec![
+        EventFilter::MoveEventFieldGreaterThan {
+            path: "/balance".to_string(),
+            value: 10000,
+        },
+        EventFilter::MoveEventFieldLessThan {
+            path: "/balance".to_string(),
+            value: 10000,
+        },
+        EventFilter::MoveEventFieldLessThan {
+            path: "/missing".to_string(),
+            value: 10000,
+        },
+        EventFilter::Transaction(TransactionDigest::random()),
+        EventFilter::TimeRange {
+            start_time: 0,
+            end_time: 1000,
+        },
+    ];
+    for filter in &false_filters {
+        assert!(!filter.matches(&envelope))
+    }
+}
+
 #[test]
 fn test_transfer_filter() {
     let object_id = ObjectID::random();
@@ -851,6 +851,84 @@ fn test_move_input_objects() {
     assert!(input_objects.is_empty());
 }
 
+#[test]
+fn test_gas_payment_encoding() {
+    let sender = SuiAddress::random_for_testing_only();
+    let gas_object_ref = random_object_ref();
+    let kind = SingleTransactionKind::TransferObject(TransferObject {
+        recipient: SuiAddress::random_for_testing_only(),
+        object_ref: random_object_ref(),
+    });
+    let data = TransactionData::new(
+        TransactionKind::Single(kind.clone()),
+        sender,
+        gas_object_ref,
+        MAX_GAS,
+        1,
+    );
+
+    // A single gas coin keeps the encoding that predates gas smashing.
+    let bytes = bcs::to_bytes(&data).unwrap();
+    let original = (
+        TransactionKind::Single(kind.clone()),
+        sender,
+        gas_object_ref,
+        1u64,
+        MAX_GAS,
+    );
+    assert_eq!(bytes, bcs::to_bytes(&original).unwrap());
+    assert_eq!(bcs::from_bytes::<TransactionData>(&bytes).unwrap(), data);
+
+    let smashed = data.with_extra_gas_payment(vec![random_object_ref()]);
+    let bytes = bcs::to_bytes(&smashed).unwrap();
+    assert_eq!(bytes[0], 2);
+    assert_eq!(bcs::from_bytes::<TransactionData>(&bytes).unwrap(), smashed);
+
+    // A single gas coin cannot be encoded in the second version.
+    let mut bytes = vec![2];
+    let v2 = (
+        TransactionKind::Single(kind),
+        sender,
+        vec![gas_object_ref],
+        1u64,
+        MAX_GAS,
+    );
+    bytes.extend(bcs::to_bytes(&v2).unwrap());
+    assert!(bcs::from_bytes::<TransactionData>(&bytes).is_err());
+}
+
+#[test]
+fn test_extra_gas_payment_validity_check() {
+    let object_ref = random_object_ref();
+    let data = dummy_move_call(
+        ObjectID::random(),
+        "foo",
+        "bar",
+        vec![],
+        random_object_ref(),
+        vec![CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref))],
+    );
+    let config = ProtocolConfig::get_for_version(ProtocolVersion::MAX);
+    let smashed = data
+        .clone()
+        .with_extra_gas_payment(vec![random_object_ref()]);
+    smashed.validity_check(&config).unwrap();
+
+    let disabled = ProtocolConfig::get_for_version(ProtocolVersion::new(1));
+    assert!(!disabled.multiple_gas_coins());
+    assert!(matches!(
+        smashed.validity_check(&disabled),
+        Err(SuiError::UnsupportedFeatureError { .. })
+    ));
+
+    // An input object of the transaction cannot pay for its gas as well.
+    assert!(matches!(
+        data.with_extra_gas_payment(vec![object_ref])
+            .validity_check(&config),
+        Err(SuiError::InvalidGasPayment { .. })
+    ));
+}
+
 fn dummy_move_call(
     package: ObjectID,
     module: &str,
@@ -870,3 +948,32 @@ fn dummy_move_call(
         MAX_GAS,
     )
 }
+
+#[test]
+fn test_move_functions() {
+    use crate::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+    let package = ObjectID::random();
+    let module = Identifier::new("module").unwrap();
+    let function = Identifier::new("function").unwrap();
+    let call = SingleTransactionKind::Call(MoveCall {
+        package,
+        module: module.clone(),
+        function: function.clone(),
+        type_arguments: vec![],
+        arguments: vec![],
+    });
+    assert_eq!(call.move_functions(), vec![(package, &module, &function)]);
+
+    // Move calls are also found among the commands of programmable transactions.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder
+        .pay_sui(SuiAddress::random_for_testing_only(), 1)
+        .unwrap();
+    builder.move_call(package, module.clone(), function.clone(), vec![], vec![]);
+    let programmable = SingleTransactionKind::ProgrammableTransaction(builder.finish());
+    assert_eq!(
+        programmable.move_functions(),
+        vec![(package, &module, &function)]
+    );
+}
@@ -0,0 +1,77 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::identifier::Identifier;
+
+use super::*;
+use crate::base_types::{dbg_addr, random_object_ref};
+use crate::gas_coin::GAS;
+use crate::messages::InputObjectKind;
+use crate::SUI_FRAMEWORK_OBJECT_ID;
+
+#[test]
+fn test_pay_sui() {
+    let recipient = dbg_addr(1);
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.pay_sui(recipient, 100).unwrap();
+    let pt = builder.finish();
+
+    assert_eq!(
+        pt.inputs,
+        vec![
+            CallArg::Pure(bcs::to_bytes(&100u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&recipient).unwrap()),
+        ]
+    );
+    assert_eq!(
+        pt.commands,
+        vec![
+            Command::SplitCoin(Argument::GasCoin, Argument::Input(0)),
+            Command::TransferObjects(vec![Argument::Result(0)], Argument::Input(1)),
+        ]
+    );
+}
+
+#[test]
+fn test_handles_refer_to_inputs_and_results() {
+    let recipient = dbg_addr(1);
+    let coin_ref = random_object_ref();
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    let coin = builder.obj(ObjectArg::ImmOrOwnedObject(coin_ref));
+    assert_eq!(coin, Argument::Input(0));
+    let split = builder.split_coin(coin, 10).unwrap();
+    assert_eq!(split, Argument::Result(0));
+    let value = builder.move_call(
+        SUI_FRAMEWORK_OBJECT_ID,
+        Identifier::new("coin").unwrap(),
+        Identifier::new("value").unwrap(),
+        vec![GAS::type_tag()],
+        vec![split],
+    );
+    assert_eq!(value, Argument::Result(1));
+    builder.merge_coins(coin, vec![split]);
+    builder.transfer_objects(recipient, vec![coin]).unwrap();
+    let pt = builder.finish();
+
+    assert_eq!(
+        pt.inputs,
+        vec![
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_ref)),
+            CallArg::Pure(bcs::to_bytes(&10u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&recipient).unwrap()),
+        ]
+    );
+    assert_eq!(pt.commands.len(), 4);
+    assert_eq!(
+        pt.commands[3],
+        Command::TransferObjects(vec![Argument::Input(0)], Argument::Input(2))
+    );
+    assert_eq!(
+        pt.input_objects().unwrap(),
+        vec![
+            InputObjectKind::ImmOrOwnedMoveObject(coin_ref),
+            InputObjectKind::MovePackage(SUI_FRAMEWORK_OBJECT_ID),
+        ]
+    );
+}
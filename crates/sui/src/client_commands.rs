@@ -37,15 +37,18 @@ use sui_json_rpc_types::{
 use sui_json_rpc_types::{GetRawObjectDataResponse, SuiData};
 use sui_json_rpc_types::{SuiCertifiedTransaction, SuiExecutionStatus, SuiTransactionEffects};
 use sui_keys::keystore::AccountKeystore;
+use sui_keys::multisig::{MultiSigAccounts, PartialSigBundle};
 use sui_sdk::SuiClient;
 use sui_sdk::TransactionExecutionResult;
 use sui_types::dynamic_field::DynamicFieldType;
 use sui_types::intent::Intent;
 use sui_types::signature::GenericSignature;
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    crypto::sha3_hash,
     gas_coin::GasCoin,
-    messages::{Transaction, VerifiedTransaction},
+    messages::{Transaction, TransactionData, VerifiedTransaction},
+    multisig::{MultiSigPublicKey, ThresholdUnit},
     object::Owner,
     parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
 };
@@ -447,6 +450,14 @@ pub enum SuiClientCommands {
         #[clap(long)]
         signature: String,
     },
+
+    /// Execute a transaction from a multisig account once the signatures collected in its
+    /// partial signature bundle meet the threshold, see `sui keytool multi-sig-sign`.
+    ExecuteMultiSig {
+        /// Path of the partial signature bundle file.
+        #[clap(long)]
+        bundle: PathBuf,
+    },
 }
 
 impl SuiClientCommands {
@@ -454,6 +465,11 @@ impl SuiClientCommands {
         self,
         context: &mut WalletContext,
     ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        if context.serialize_unsigned() && !self.builds_transaction() {
+            return Err(anyhow!(
+                "--serialize-unsigned is only supported by commands building a transaction"
+            ));
+        }
         let ret = Ok(match self {
             SuiClientCommands::Publish {
                 package_path,
@@ -510,16 +526,10 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .publish(sender, compiled_modules, gas, gas_budget)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&sender, &data, Intent::default())?;
-                let response = context
-                    .execute_transaction(
-                        Transaction::from_data(data, Intent::default(), signature).verify()?,
-                    )
-                    .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
 
                 SuiClientCommandResult::Publish(response)
             }
@@ -549,10 +559,15 @@ impl SuiClientCommands {
                 gas_budget,
                 args,
             } => {
-                let (cert, effects) = call_move(
+                let data = move_call_data(
                     package, &module, &function, type_args, gas, gas_budget, args, context,
                 )
                 .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
+                let (cert, effects) = check_move_call(response)?;
                 SuiClientCommandResult::Call(cert, effects)
             }
 
@@ -570,16 +585,10 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .transfer_object(from, object_id, gas, gas_budget, to)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&from, &data, Intent::default())?;
-                let response = context
-                    .execute_transaction(
-                        Transaction::from_data(data, Intent::default(), signature).verify()?,
-                    )
-                    .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
                 let cert = response.certificate;
                 let effects = response.effects;
 
@@ -603,16 +612,10 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .transfer_sui(from, object_id, gas_budget, to, amount)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&from, &data, Intent::default())?;
-                let response = context
-                    .execute_transaction(
-                        Transaction::from_data(data, Intent::default(), signature).verify()?,
-                    )
-                    .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
                 let cert = response.certificate;
                 let effects = response.effects;
 
@@ -651,16 +654,10 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .pay(from, input_coins, recipients, amounts, gas, gas_budget)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&from, &data, Intent::default())?;
-                let response = context
-                    .execute_transaction(
-                        Transaction::from_data(data, Intent::default(), signature).verify()?,
-                    )
-                    .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
                 let cert = response.certificate;
                 let effects = response.effects;
                 if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
@@ -700,16 +697,10 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .pay_sui(signer, input_coins, recipients, amounts, gas_budget)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&signer, &data, Intent::default())?;
-                let response = context
-                    .execute_transaction(
-                        Transaction::from_data(data, Intent::default(), signature).verify()?,
-                    )
-                    .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
 
                 let cert = response.certificate;
                 let effects = response.effects;
@@ -738,16 +729,10 @@ impl SuiClientCommands {
                     .pay_all_sui(signer, input_coins, recipient, gas_budget)
                     .await?;
 
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&signer, &data, Intent::default())?;
-                let response = context
-                    .execute_transaction(
-                        Transaction::from_data(data, Intent::default(), signature).verify()?,
-                    )
-                    .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
 
                 let cert = response.certificate;
                 let effects = response.effects;
@@ -761,7 +746,7 @@ impl SuiClientCommands {
             }
 
             SuiClientCommands::Addresses => SuiClientCommandResult::Addresses(
-                context.config.keystore.addresses(),
+                context.config.keystore.try_addresses()?,
                 context.active_address().ok(),
             ),
 
@@ -779,6 +764,7 @@ impl SuiClientCommands {
                 key_scheme,
                 derivation_path,
             } => {
+                context.unlock_keystore()?;
                 let (address, phrase, scheme) = context
                     .config
                     .keystore
@@ -825,16 +811,10 @@ impl SuiClientCommands {
                         return Err(anyhow!("Exactly one of `count` and `amounts` must be present for split-coin command."));
                     }
                 };
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&signer, &data, Intent::default())?;
-                let response = context
-                    .execute_transaction(
-                        Transaction::from_data(data, Intent::default(), signature).verify()?,
-                    )
-                    .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
                 SuiClientCommandResult::SplitCoin(response)
             }
             SuiClientCommands::MergeCoin {
@@ -849,16 +829,10 @@ impl SuiClientCommands {
                     .transaction_builder()
                     .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
                     .await?;
-                let signature =
-                    context
-                        .config
-                        .keystore
-                        .sign_secure(&signer, &data, Intent::default())?;
-                let response = context
-                    .execute_transaction(
-                        Transaction::from_data(data, Intent::default(), signature).verify()?,
-                    )
-                    .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
 
                 SuiClientCommandResult::MergeCoin(response)
             }
@@ -868,7 +842,9 @@ impl SuiClientCommands {
                         Self::switch_env(&mut context.config, env)?;
                     }
                     (Some(addr), None) => {
-                        if !context.config.keystore.addresses().contains(&addr) {
+                        if !context.config.keystore.addresses().contains(&addr)
+                            && context.multisig_account(&addr)?.is_none()
+                        {
                             return Err(anyhow!("Address {} not managed by wallet", addr));
                         }
                         context.config.active_address = Some(addr);
@@ -897,7 +873,7 @@ impl SuiClientCommands {
                 for a in args_json.as_array().unwrap() {
                     args.push(SuiJsonValue::new(a.clone()).unwrap());
                 }
-                let (_, effects) = call_move(
+                let data = move_call_data(
                     ObjectID::from(SUI_FRAMEWORK_ADDRESS),
                     "devnet_nft",
                     "mint",
@@ -908,6 +884,11 @@ impl SuiClientCommands {
                     context,
                 )
                 .await?;
+                let response = match sign_and_execute(context, data).await? {
+                    Ok(response) => response,
+                    Err(result) => return Ok(result),
+                };
+                let (_, effects) = check_move_call(response)?;
                 let nft_id = effects
                     .created
                     .first()
@@ -960,6 +941,17 @@ impl SuiClientCommands {
                 let response = context.execute_transaction(verified).await?;
                 SuiClientCommandResult::ExecuteSignedTx(response)
             }
+            SuiClientCommands::ExecuteMultiSig { bundle } => {
+                let bundle = PartialSigBundle::read(&bundle)?;
+                let verified = Transaction::from_generic_sig_data(
+                    bundle.transaction_data()?,
+                    Intent::default(),
+                    bundle.combine()?,
+                )
+                .verify()?;
+                let response = context.execute_transaction(verified).await?;
+                SuiClientCommandResult::ExecuteSignedTx(response)
+            }
             SuiClientCommands::NewEnv { alias, rpc, ws } => {
                 if context.config.envs.iter().any(|env| env.alias == alias) {
                     return Err(anyhow!(
@@ -1023,6 +1015,24 @@ impl SuiClientCommands {
         ret
     }
 
+    /// Whether the command builds a transaction, which `--serialize-unsigned` prints instead of
+    /// signing and executing it.
+    fn builds_transaction(&self) -> bool {
+        matches!(
+            self,
+            SuiClientCommands::Publish { .. }
+                | SuiClientCommands::Call { .. }
+                | SuiClientCommands::Transfer { .. }
+                | SuiClientCommands::TransferSui { .. }
+                | SuiClientCommands::Pay { .. }
+                | SuiClientCommands::PaySui { .. }
+                | SuiClientCommands::PayAllSui { .. }
+                | SuiClientCommands::SplitCoin { .. }
+                | SuiClientCommands::MergeCoin { .. }
+                | SuiClientCommands::CreateExampleNFT { .. }
+        )
+    }
+
     pub fn switch_env(config: &mut SuiClientConfig, env: &str) -> Result<(), anyhow::Error> {
         let env = Some(env.into());
         ensure!(config.get_env(&env).is_some(), "Environment config not found for [{env:?}], add new environment config using the `sui client new-env` command.");
@@ -1035,6 +1045,7 @@ pub struct WalletContext {
     pub config: PersistedConfig<SuiClientConfig>,
     request_timeout: Option<std::time::Duration>,
     client: Arc<RwLock<Option<SuiClient>>>,
+    serialize_unsigned: bool,
 }
 
 impl WalletContext {
@@ -1054,6 +1065,7 @@ impl WalletContext {
             config,
             request_timeout,
             client: Default::default(),
+            serialize_unsigned: false,
         };
         Ok(context)
     }
@@ -1079,8 +1091,37 @@ impl WalletContext {
         })
     }
 
+    /// Whether commands print their unsigned transaction instead of signing and executing it.
+    pub fn serialize_unsigned(&self) -> bool {
+        self.serialize_unsigned
+    }
+
+    pub fn set_serialize_unsigned(&mut self, serialize_unsigned: bool) {
+        self.serialize_unsigned = serialize_unsigned;
+    }
+
+    /// The multisig account with `address` stored next to the keystore, if any.
+    pub fn multisig_account(
+        &self,
+        address: &SuiAddress,
+    ) -> Result<Option<MultiSigPublicKey>, anyhow::Error> {
+        let Some(path) = MultiSigAccounts::path_for_keystore(&self.config.keystore) else {
+            return Ok(None);
+        };
+        Ok(MultiSigAccounts::new(&path)?
+            .find_by_address(address)
+            .map(|(_, multisig_pk)| multisig_pk.clone()))
+    }
+
+    /// Unlocks an encrypted keystore the first time a command needs private keys, so commands
+    /// that only read from the network never ask for the password.
+    pub fn unlock_keystore(&mut self) -> Result<(), anyhow::Error> {
+        self.config.keystore.ensure_unlocked()
+    }
+
     pub fn active_address(&mut self) -> Result<SuiAddress, anyhow::Error> {
-        if self.config.keystore.addresses().is_empty() {
+        let addresses = self.config.keystore.unlocked_addresses()?;
+        if addresses.is_empty() {
             return Err(anyhow!(
                 "No managed addresses. Create new address with `new-address` command."
             ));
@@ -1091,7 +1132,7 @@ impl WalletContext {
         self.config.active_address = Some(
             self.config
                 .active_address
-                .unwrap_or(*self.config.keystore.addresses().get(0).unwrap()),
+                .unwrap_or(*addresses.get(0).unwrap()),
         );
 
         Ok(self.config.active_address.unwrap())
@@ -1389,6 +1430,17 @@ impl Display for SuiClientCommandResult {
                 writeln!(writer, "Intent message to sign: {}", data_to_sign)?;
                 writeln!(writer, "Raw transaction to execute: {}", data_to_execute)?;
             }
+            SuiClientCommandResult::MultiSigBundle(response) => {
+                writeln!(writer, "{}", response)?;
+            }
+            SuiClientCommandResult::SerializeUnsigned(tx_bytes) => {
+                writeln!(writer, "Raw transaction to sign: {}", tx_bytes)?;
+                writeln!(
+                    writer,
+                    "Sign it with `sui keytool sign --data <bytes>`, then submit it with \
+                     `sui client execute-signed-tx --tx-bytes <bytes> --signature <signature>`."
+                )?;
+            }
             SuiClientCommandResult::ActiveEnv(env) => {
                 write!(writer, "{}", env.as_deref().unwrap_or("None"))?;
             }
@@ -1412,7 +1464,19 @@ impl Display for SuiClientCommandResult {
     }
 }
 
-pub async fn call_move(
+fn check_move_call(
+    response: SuiTransactionResponse,
+) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+    let cert = response.certificate;
+    let effects = response.effects;
+
+    if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
+        return Err(anyhow!("Error calling module: {:#?}", effects.status));
+    }
+    Ok((cert, effects))
+}
+
+async fn move_call_data(
     package: ObjectID,
     module: &str,
     function: &str,
@@ -1421,7 +1485,7 @@ pub async fn call_move(
     gas_budget: u64,
     args: Vec<SuiJsonValue>,
     context: &mut WalletContext,
-) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+) -> Result<TransactionData, anyhow::Error> {
     // Convert all numeric input to String, this will allow number input from the CLI without failing SuiJSON's checks.
     let args = args
         .into_iter()
@@ -1432,7 +1496,7 @@ pub async fn call_move(
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
     let client = context.get_client().await?;
-    let data = client
+    client
         .transaction_builder()
         .move_call(
             sender,
@@ -1447,21 +1511,94 @@ pub async fn call_move(
             gas,
             gas_budget,
         )
-        .await?;
-    let signature = context
-        .config
-        .keystore
-        .sign_secure(&sender, &data, Intent::default())?;
-    let transaction = Transaction::from_data(data, Intent::default(), signature).verify()?;
-
-    let response = context.execute_transaction(transaction).await?;
-    let cert = response.certificate;
-    let effects = response.effects;
+        .await
+}
 
-    if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
-        return Err(anyhow!("Error calling module: {:#?}", effects.status));
+/// A transaction built by a command, either signed and ready to execute, or handed back to the
+/// user to be signed elsewhere.
+#[allow(clippy::large_enum_variant)]
+enum PreparedTransaction {
+    Signed(VerifiedTransaction),
+    Deferred(SuiClientCommandResult),
+}
+
+/// Signs `data` with the local key of its sender. In `--serialize-unsigned` mode the unsigned
+/// transaction is returned instead. A transaction sent from a stored multisig account is signed
+/// by the members in the local keystore and only executed right away if their weight meets the
+/// threshold, otherwise it is written to a partial signature bundle for the other members.
+fn prepare_transaction(
+    context: &mut WalletContext,
+    data: TransactionData,
+) -> Result<PreparedTransaction, anyhow::Error> {
+    if context.serialize_unsigned() {
+        return Ok(PreparedTransaction::Deferred(
+            SuiClientCommandResult::SerializeUnsigned(serialize_unsigned(&data)?),
+        ));
     }
-    Ok((cert, effects))
+    let sender = data.signer();
+    if let Some(multisig_pk) = context.multisig_account(&sender)? {
+        let mut bundle = PartialSigBundle::new(multisig_pk, &data)?;
+        let local = context.config.keystore.unlocked_addresses()?;
+        for signer in bundle.missing_signers() {
+            if local.contains(&signer) {
+                let signature = context.config.keystore.unlock_and_sign_secure(
+                    &signer,
+                    &data,
+                    Intent::default(),
+                )?;
+                bundle.add_signature(signature)?;
+            }
+        }
+        if bundle.is_complete() {
+            let transaction =
+                Transaction::from_generic_sig_data(data, Intent::default(), bundle.combine()?);
+            return Ok(PreparedTransaction::Signed(transaction.verify()?));
+        }
+        // Multisig accounts are stored next to the keystore, and so are their bundles.
+        let path = MultiSigAccounts::path_for_keystore(&context.config.keystore)
+            .ok_or_else(|| anyhow!("Multisig accounts require a file based keystore"))?
+            .with_file_name(format!(
+                "{}.multisig",
+                TransactionDigest::new(sha3_hash(&data))
+            ));
+        bundle.write(&path)?;
+        return Ok(PreparedTransaction::Deferred(
+            SuiClientCommandResult::MultiSigBundle(MultiSigBundleResponse {
+                path,
+                address: sender,
+                weight: bundle.weight(),
+                threshold: *bundle.multisig_pk.threshold(),
+            }),
+        ));
+    }
+    let signature =
+        context
+            .config
+            .keystore
+            .unlock_and_sign_secure(&sender, &data, Intent::default())?;
+    Ok(PreparedTransaction::Signed(
+        Transaction::from_data(data, Intent::default(), signature).verify()?,
+    ))
+}
+
+/// Signs `data` as `prepare_transaction` does and executes it. The result handed back to the user
+/// is returned instead when the transaction is not executed right away.
+async fn sign_and_execute(
+    context: &mut WalletContext,
+    data: TransactionData,
+) -> Result<Result<SuiTransactionResponse, SuiClientCommandResult>, anyhow::Error> {
+    match prepare_transaction(context, data)? {
+        PreparedTransaction::Signed(transaction) => {
+            Ok(Ok(context.execute_transaction(transaction).await?))
+        }
+        PreparedTransaction::Deferred(result) => Ok(Err(result)),
+    }
+}
+
+/// Encodes the unsigned transaction printed in `--serialize-unsigned` mode, in the format
+/// expected by `sui keytool sign` and `sui client execute-signed-tx`.
+fn serialize_unsigned(data: &TransactionData) -> Result<String, anyhow::Error> {
+    Ok(Base64::encode(bcs::to_bytes(data)?))
 }
 
 fn convert_number_to_string(value: Value) -> Value {
@@ -1567,10 +1704,44 @@ pub enum SuiClientCommandResult {
     Envs(Vec<SuiEnv>, Option<String>),
     CreateExampleNFT(GetObjectDataResponse),
     SerializeTransferSui(String, String),
+    SerializeUnsigned(String),
+    MultiSigBundle(MultiSigBundleResponse),
     ExecuteSignedTx(SuiTransactionResponse),
     NewEnv(SuiEnv),
 }
 
+#[derive(Serialize, Clone, Debug)]
+pub struct MultiSigBundleResponse {
+    /// Partial signature bundle file to pass on to the other signers.
+    pub path: PathBuf,
+    /// Multisig address sending the transaction.
+    pub address: SuiAddress,
+    pub weight: ThresholdUnit,
+    pub threshold: ThresholdUnit,
+}
+
+impl Display for MultiSigBundleResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Transaction from multisig address [{}] written to {}",
+            self.address,
+            self.path.display()
+        )?;
+        writeln!(
+            f,
+            "Signed weight: {} of threshold {}",
+            self.weight, self.threshold
+        )?;
+        write!(
+            f,
+            "Add signatures with `sui keytool multi-sig-sign --bundle {0}`, then submit with \
+             `sui client execute-multi-sig --bundle {0}`.",
+            self.path.display()
+        )
+    }
+}
+
 #[derive(Serialize, Clone, Debug)]
 pub struct SwitchResponse {
     /// Active address
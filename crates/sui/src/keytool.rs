@@ -19,7 +19,9 @@ use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit
 use sui_types::signature::GenericSignature;
 use tracing::info;
 
-use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::encryption::EncryptionMode;
+use sui_keys::keystore::{read_new_password, read_password, AccountKeystore, Keystore};
+use sui_keys::multisig::{MultiSigAccounts, PartialSigBundle};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{get_authority_key_pair, EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
 #[cfg(test)]
@@ -57,10 +59,12 @@ pub enum KeyToolCommand {
     /// of the BCS serialized transaction bytes itself (the result of
     /// [transaction builder API](https://docs.sui.io/sui-jsonrpc) and its intent. If
     /// intent is absent, default will be used. See [struct IntentMessage] and [struct Intent]
-    /// for more details.
+    /// for more details. This also signs the output of `sui client --serialize-unsigned`, by
+    /// default with the key of the transaction sender.
     Sign {
+        /// Address whose key signs, defaults to the sender of the transaction.
         #[clap(long, parse(try_from_str = decode_bytes_hex))]
-        address: SuiAddress,
+        address: Option<SuiAddress>,
         #[clap(long)]
         data: String,
         #[clap(long)]
@@ -104,6 +108,43 @@ pub enum KeyToolCommand {
         #[clap(long)]
         threshold: ThresholdUnit,
     },
+
+    /// Store a named multisig account next to sui.keystore, given the public keys `flag || pk`
+    /// in Base64, their weights and the threshold. Commands of `sui client` sent from the
+    /// account's address then write a partial signature bundle instead of signing locally.
+    MultiSigAdd {
+        #[clap(long)]
+        name: String,
+        #[clap(long)]
+        threshold: ThresholdUnit,
+        #[clap(long, multiple_occurrences = false, multiple_values = true)]
+        pks: Vec<PublicKey>,
+        #[clap(long, multiple_occurrences = false, multiple_values = true)]
+        weights: Vec<WeightUnit>,
+    },
+
+    /// List the multisig accounts stored next to sui.keystore.
+    MultiSigList,
+
+    /// Sign the transaction in a partial signature bundle and add the signature to the bundle
+    /// file. Signs with every key in sui.keystore that is a member of the multisig account and
+    /// has not signed yet, or only with the key of `--address`.
+    MultiSigSign {
+        #[clap(long)]
+        bundle: PathBuf,
+        #[clap(long, parse(try_from_str = decode_bytes_hex))]
+        address: Option<SuiAddress>,
+    },
+
+    /// Encrypt sui.keystore with a password, converting a plaintext keystore in place. Each
+    /// key is encrypted separately by default so addresses stay listable without the password,
+    /// use --whole-file to hide them as well. The new password is read from
+    /// SUI_KEYSTORE_NEW_PASSWORD if set. Run on an encrypted keystore to change its password or
+    /// mode, its current password being read from SUI_KEYSTORE_PASSWORD if set.
+    Encrypt {
+        #[clap(long)]
+        whole_file: bool,
+    },
 }
 
 impl KeyToolCommand {
@@ -160,10 +201,11 @@ impl KeyToolCommand {
                     "Sui Address", "Public Key (Base64)", "Scheme"
                 );
                 println!("{}", ["-"; 100].join(""));
-                for pub_key in keystore.keys() {
+                let addresses = keystore.unlocked_addresses()?;
+                for (address, pub_key) in addresses.into_iter().zip(keystore.keys()) {
                     println!(
                         " {0: ^42} | {1: ^45} | {2: ^6}",
-                        Into::<SuiAddress>::into(&pub_key),
+                        address,
                         pub_key.encode_base64(),
                         pub_key.scheme().to_string()
                     );
@@ -174,22 +216,26 @@ impl KeyToolCommand {
                 data,
                 intent,
             } => {
-                println!("Signer address: {}", address);
-                println!("Raw tx_bytes to execute: {}", data);
-                let intent = intent.unwrap_or_default();
-                println!("Intent: {:?}", intent);
                 let msg: TransactionData =
                     bcs::from_bytes(&Base64::decode(&data).map_err(|e| {
                         anyhow!("Cannot deserialize data as TransactionData {:?}", e)
                     })?)?;
+                let address = address.unwrap_or_else(|| msg.signer());
+                println!("Signer address: {}", address);
+                println!("Raw tx_bytes to execute: {}", data);
+                let intent = intent.unwrap_or_default();
+                println!("Intent: {:?}", intent);
                 let intent_msg = IntentMessage::new(intent, msg);
                 println!(
                     "Intent message to sign: {:?}",
                     Base64::encode(bcs::to_bytes(&intent_msg)?)
                 );
 
-                let sui_signature =
-                    keystore.sign_secure(&address, &intent_msg.value, intent_msg.intent)?;
+                let sui_signature = keystore.unlock_and_sign_secure(
+                    &address,
+                    &intent_msg.value,
+                    intent_msg.intent,
+                )?;
                 println!(
                     "Serialized signature (`flag || sig || pk` in Base64): {:?}",
                     sui_signature.encode_base64()
@@ -200,6 +246,7 @@ impl KeyToolCommand {
                 key_scheme,
                 derivation_path,
             } => {
+                keystore.ensure_unlocked()?;
                 let address =
                     keystore.import_from_mnemonic(&mnemonic_phrase, key_scheme, derivation_path)?;
                 info!("Key imported for address [{address}]");
@@ -265,12 +312,102 @@ impl KeyToolCommand {
                 println!("MultiSig parsed: {:?}", generic_sig);
                 println!("MultiSig serialized: {:?}", generic_sig.encode_base64());
             }
+            KeyToolCommand::MultiSigAdd {
+                name,
+                threshold,
+                pks,
+                weights,
+            } => {
+                let multisig_pk = MultiSigPublicKey::new(pks, weights, threshold)?;
+                let address = multisig_accounts(keystore)?.add(name.clone(), multisig_pk)?;
+                println!("Added multisig account [{name}] with address: {address}");
+            }
+            KeyToolCommand::MultiSigList => {
+                println!(
+                    " {0: ^20} | {1: ^42} | {2: ^9} | {3: ^7}",
+                    "Name", "MultiSig Address", "Threshold", "Signers"
+                );
+                println!("{}", ["-"; 90].join(""));
+                for (name, multisig_pk) in multisig_accounts(keystore)?.iter() {
+                    println!(
+                        " {0: ^20} | {1: ^42} | {2: ^9} | {3: ^7}",
+                        name,
+                        SuiAddress::from(multisig_pk.clone()),
+                        multisig_pk.threshold(),
+                        multisig_pk.pubkeys().len()
+                    );
+                }
+            }
+            KeyToolCommand::MultiSigSign {
+                bundle: bundle_path,
+                address,
+            } => {
+                let mut bundle = PartialSigBundle::read(&bundle_path)?;
+                let data = bundle.transaction_data()?;
+                let signers = match address {
+                    Some(address) => vec![address],
+                    None => {
+                        let local = keystore.unlocked_addresses()?;
+                        bundle
+                            .missing_signers()
+                            .into_iter()
+                            .filter(|signer| local.contains(signer))
+                            .collect()
+                    }
+                };
+                if signers.is_empty() {
+                    return Err(anyhow!(
+                        "No key in the keystore can add a signature for multisig address [{}]",
+                        bundle.address()
+                    ));
+                }
+                for signer in signers {
+                    let signature =
+                        keystore.unlock_and_sign_secure(&signer, &data, Intent::default())?;
+                    bundle.add_signature(signature)?;
+                    println!("Signed by: {signer}");
+                }
+                bundle.write(&bundle_path)?;
+                println!(
+                    "Signed weight: {} of threshold {}",
+                    bundle.weight(),
+                    bundle.multisig_pk.threshold()
+                );
+                if bundle.is_complete() {
+                    println!(
+                        "Threshold met, execute it with `sui client execute-multi-sig --bundle {}`",
+                        bundle_path.display()
+                    );
+                }
+            }
+            KeyToolCommand::Encrypt { whole_file } => {
+                let Keystore::File(keystore) = keystore else {
+                    return Err(anyhow!("Only file based keystores can be encrypted"));
+                };
+                if keystore.is_locked() {
+                    let password = read_password("Enter current keystore password: ")?;
+                    keystore.unlock(&password)?;
+                }
+                let mode = if whole_file {
+                    EncryptionMode::WholeFile
+                } else {
+                    EncryptionMode::PerKey
+                };
+                keystore.encrypt(&read_new_password()?, mode)?;
+                println!("Keystore encrypted with mode {:?}", mode);
+            }
         }
 
         Ok(())
     }
 }
 
+fn multisig_accounts(keystore: &Keystore) -> Result<MultiSigAccounts, anyhow::Error> {
+    let path = MultiSigAccounts::path_for_keystore(keystore)
+        .ok_or_else(|| anyhow!("Multisig accounts are only stored for file based keystores"))?;
+    MultiSigAccounts::new(&path)
+}
+
 fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
     let path_str = format!("{}.key", address).to_lowercase();
     let path = Path::new(&path_str);
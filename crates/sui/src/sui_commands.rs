@@ -91,6 +91,10 @@ pub enum SuiCommand {
         /// Return command outputs in json format.
         #[clap(long, global = true)]
         json: bool,
+        /// Print the unsigned transaction bytes instead of signing and executing, so the
+        /// transaction can be signed elsewhere with `sui keytool sign`.
+        #[clap(long, global = true)]
+        serialize_unsigned: bool,
     },
 
     /// Tool to build and test Move applications.
@@ -206,10 +210,16 @@ impl SuiCommand {
                 let context = WalletContext::new(&config, None).await?;
                 start_console(context, &mut stdout(), &mut stderr()).await
             }
-            SuiCommand::Client { config, cmd, json } => {
+            SuiCommand::Client {
+                config,
+                cmd,
+                json,
+                serialize_unsigned,
+            } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path).await?;
                 let mut context = WalletContext::new(&config_path, None).await?;
+                context.set_serialize_unsigned(serialize_unsigned);
                 if let Some(cmd) = cmd {
                     cmd.execute(&mut context).await?.print(!json);
                 } else {
@@ -8,6 +8,7 @@ use move_package::BuildConfig;
 use serde_json::json;
 use tokio::time::sleep;
 
+use fastcrypto::encoding::{Base64, Encoding};
 use sui::client_commands::SwitchResponse;
 use sui::{
     client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
@@ -28,8 +29,11 @@ use sui_keys::keystore::AccountKeystore;
 use sui_macros::sim_test;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{
-    Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
+    Ed25519SuiSignature, EncodeDecodeBase64, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair,
+    SuiSignatureInner,
 };
+use sui_types::intent::Intent;
+use sui_types::messages::TransactionData;
 use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
 use sui_types::{sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS};
 use test_utils::messages::make_transactions_with_wallet_context;
@@ -1175,6 +1179,60 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_serialize_unsigned_and_execute_signed_tx() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let gas = object_refs.first().unwrap().object_id;
+    let object_id = object_refs.get(1).unwrap().object_id;
+
+    context.set_serialize_unsigned(true);
+    let resp = SuiClientCommands::Transfer {
+        to: address1,
+        object_id,
+        gas: Some(gas),
+        gas_budget: 50000,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::SerializeUnsigned(tx_bytes) = resp else {
+        panic!("Expected unsigned transaction bytes")
+    };
+
+    // Nothing was executed, the object still belongs to the sender.
+    assert_eq!(context.get_object_owner(&object_id).await?, address);
+
+    // Commands building no transaction reject the flag rather than ignoring it.
+    assert!(SuiClientCommands::Gas { address: None }
+        .execute(context)
+        .await
+        .is_err());
+
+    // Sign the bytes elsewhere, as `sui keytool sign` does, and execute them.
+    let data: TransactionData = bcs::from_bytes(&Base64::decode(&tx_bytes).unwrap())?;
+    assert_eq!(data.signer(), address);
+    let signature = context
+        .config
+        .keystore
+        .sign_secure(&address, &data, Intent::default())?;
+    context.set_serialize_unsigned(false);
+    SuiClientCommands::ExecuteSignedTx {
+        tx_bytes,
+        signature: signature.encode_base64(),
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(context.get_object_owner(&object_id).await?, address1);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delegation_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await?;
@@ -11,7 +11,11 @@ use fastcrypto::encoding::Encoding;
 use fastcrypto::encoding::Hex;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
+use sui_keys::keystore::{
+    AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore, SUI_KEYSTORE_NEW_PASSWORD_ENV,
+    SUI_KEYSTORE_PASSWORD_ENV,
+};
+use sui_keys::multisig::{MultiSigAccounts, PartialSigBundle};
 use sui_types::base_types::ObjectDigest;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::SequenceNumber;
@@ -333,7 +337,7 @@ fn test_sign_command() -> Result<(), anyhow::Error> {
 
     // Sign an intent message for the transaction data and a passed-in intent with scope as PersonalMessage.
     KeyToolCommand::Sign {
-        address: *sender,
+        address: Some(*sender),
         data: Base64::encode(bcs::to_bytes(&tx_data)?),
         intent: Some(Intent::default().with_scope(IntentScope::PersonalMessage)),
     }
@@ -341,10 +345,123 @@ fn test_sign_command() -> Result<(), anyhow::Error> {
 
     // Sign an intent message for the transaction data without intent passed in, so default is used.
     KeyToolCommand::Sign {
-        address: *sender,
+        address: Some(*sender),
         data: Base64::encode(bcs::to_bytes(&tx_data)?),
         intent: None,
     }
     .execute(&mut keystore)?;
+
+    // Without an address, the transaction is signed by its sender, as for the output of
+    // `sui client --serialize-unsigned`.
+    KeyToolCommand::Sign {
+        address: None,
+        data: Base64::encode(bcs::to_bytes(&tx_data)?),
+        intent: None,
+    }
+    .execute(&mut keystore)?;
+
+    // Signing for an address not in the keystore fails.
+    assert!(KeyToolCommand::Sign {
+        address: Some(SuiAddress::random_for_testing_only()),
+        data: Base64::encode(bcs::to_bytes(&tx_data)?),
+        intent: None,
+    }
+    .execute(&mut keystore)
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_encrypt_keystore_command() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&path)?);
+    keystore.import_from_mnemonic(TEST_MNEMONIC, SignatureScheme::ED25519, None)?;
+    let addresses = keystore.addresses();
+
+    std::env::set_var(SUI_KEYSTORE_NEW_PASSWORD_ENV, "password");
+    KeyToolCommand::Encrypt { whole_file: false }.execute(&mut keystore)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(
+            std::fs::metadata(&path)?.permissions().mode() & 0o777,
+            0o600
+        );
+    }
+
+    // The migrated keystore lists its addresses but needs the password to sign.
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&path)?);
+    assert!(keystore.is_locked());
+    assert_eq!(keystore.addresses(), addresses);
+    assert!(keystore.unlock("wrong password").is_err());
+    keystore.unlock("password")?;
+    keystore.get_key(&addresses[0])?;
+
+    // The current and new passwords are read from separate variables.
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&path)?);
+    std::env::set_var(SUI_KEYSTORE_PASSWORD_ENV, "password");
+    std::env::set_var(SUI_KEYSTORE_NEW_PASSWORD_ENV, "new password");
+    KeyToolCommand::Encrypt { whole_file: true }.execute(&mut keystore)?;
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&path)?);
+    assert!(keystore.unlock("password").is_err());
+    keystore.unlock("new password")?;
+    assert_eq!(keystore.addresses(), addresses);
+    Ok(())
+}
+
+#[test]
+fn test_multisig_account_and_sign_command() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let mut keystore = Keystore::from(FileBasedKeystore::new(
+        &temp_dir.path().join("sui.keystore"),
+    )?);
+    keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None)?;
+
+    KeyToolCommand::MultiSigAdd {
+        name: "team".to_string(),
+        threshold: 2,
+        pks: keystore.keys(),
+        weights: vec![1, 1],
+    }
+    .execute(&mut keystore)?;
+    KeyToolCommand::MultiSigList.execute(&mut keystore)?;
+    let path = MultiSigAccounts::path_for_keystore(&keystore).unwrap();
+    let multisig_pk = MultiSigAccounts::new(&path)?.get("team").unwrap().clone();
+
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let tx_data = TransactionData::new_pay_sui_with_dummy_gas_price(
+        multisig_pk.clone().into(),
+        vec![gas],
+        vec![SuiAddress::random_for_testing_only()],
+        vec![10000],
+        gas,
+        1000,
+    );
+    let bundle_path = temp_dir.path().join("tx.multisig");
+    PartialSigBundle::new(multisig_pk, &tx_data)?.write(&bundle_path)?;
+
+    // A key outside the keystore cannot sign.
+    assert!(KeyToolCommand::MultiSigSign {
+        bundle: bundle_path.clone(),
+        address: Some(SuiAddress::random_for_testing_only()),
+    }
+    .execute(&mut keystore)
+    .is_err());
+
+    // Without an address every local member signs, which meets the threshold.
+    KeyToolCommand::MultiSigSign {
+        bundle: bundle_path.clone(),
+        address: None,
+    }
+    .execute(&mut keystore)?;
+    let bundle = PartialSigBundle::read(&bundle_path)?;
+    assert!(bundle.is_complete());
+    bundle.combine()?;
     Ok(())
 }
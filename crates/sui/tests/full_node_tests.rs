@@ -24,6 +24,7 @@ use sui_types::base_types::{ObjectRef, SequenceNumber};
 use sui_types::crypto::{get_key_pair, SuiKeyPair};
 use sui_types::event::BalanceChangeType;
 use sui_types::event::Event;
+use sui_types::filter::TransactionFilter;
 use sui_types::message_envelope::Message;
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
@@ -278,7 +279,8 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
             EventQuery::TimeRange {
                 start_time: ts.unwrap() - HOUR_MS,
                 end_time: ts.unwrap() + HOUR_MS,
-            },
+            }
+            .into(),
             None,
             100,
             false,
@@ -303,7 +305,7 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     // query by sender
     let events_by_sender = node
         .state()
-        .get_events(EventQuery::Sender(sender), None, 10, false)
+        .get_events(EventQuery::Sender(sender).into(), None, 10, false)
         .await?;
     assert_eq!(events_by_sender[0].1.tx_digest, digest);
     let events_by_sender = events_by_sender
@@ -323,7 +325,7 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     // query by tx digest
     let events_by_tx = node
         .state()
-        .get_events(EventQuery::Transaction(digest), None, 10, false)
+        .get_events(EventQuery::Transaction(digest).into(), None, 10, false)
         .await?;
     assert_eq!(events_by_tx[0].1.tx_digest, digest);
     let events_by_tx = events_by_tx
@@ -344,7 +346,7 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     let events_by_recipient = node
         .state()
         .get_events(
-            EventQuery::Recipient(Owner::AddressOwner(receiver)),
+            EventQuery::Recipient(Owner::AddressOwner(receiver)).into(),
             None,
             100,
             false,
@@ -356,7 +358,12 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     // query by object
     let mut events_by_object = node
         .state()
-        .get_events(EventQuery::Object(transferred_object), None, 100, false)
+        .get_events(
+            EventQuery::Object(transferred_object).into(),
+            None,
+            100,
+            false,
+        )
         .await?;
     let events_by_object = events_by_object.split_off(events_by_object.len() - 2);
     assert_eq!(events_by_object[0].1.tx_digest, digest);
@@ -378,7 +385,8 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
             EventQuery::MoveModule {
                 package: SUI_FRAMEWORK_OBJECT_ID,
                 module: "transfer_object".to_string(),
-            },
+            }
+            .into(),
             None,
             10,
             false,
@@ -603,7 +611,12 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
     // Query by move event struct name
     let events_by_sender = node
         .state()
-        .get_events(EventQuery::MoveEvent(struct_tag_str), None, 10, false)
+        .get_events(
+            EventQuery::MoveEvent(struct_tag_str).into(),
+            None,
+            10,
+            false,
+        )
         .await?;
     assert_eq!(events_by_sender.len(), 1);
     assert_eq!(events_by_sender[0].1.event, expected_event);
@@ -621,6 +634,134 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
     Ok(())
 }
 
+#[sim_test]
+async fn test_full_node_sub_move_event_from_cursor_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new()
+        .enable_fullnode_events()
+        .build()
+        .await?;
+
+    let fullnode = start_fullnode_from_config(
+        test_cluster
+            .fullnode_config_builder()
+            .with_event_store()
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let node = fullnode.sui_node;
+    let ws_client = fullnode.ws_client;
+    let context = &mut test_cluster.wallet;
+    let struct_tag_str = sui_framework_address_concat_string("::devnet_nft::MintNFTEvent");
+
+    // Mint two NFTs before subscribing.
+    let mut digests = vec![];
+    for _ in 0..2 {
+        let (_, _, digest) = create_devnet_nft(context).await?;
+        wait_for_tx(digest, node.state().clone()).await;
+        digests.push(digest);
+    }
+    let events = node
+        .state()
+        .get_events(
+            EventQuery::MoveEvent(struct_tag_str.clone()).into(),
+            None,
+            10,
+            false,
+        )
+        .await?;
+    assert_eq!(events.len(), 2);
+
+    // Resume from the first event, the second one is replayed from the event store.
+    let mut sub: Subscription<SuiEventEnvelope> = ws_client
+        .subscribe(
+            "sui_subscribeEvent",
+            rpc_params![
+                SuiEventFilter::MoveEventType(struct_tag_str.clone()),
+                events[0].0.clone()
+            ],
+            "sui_unsubscribeEvent",
+        )
+        .await
+        .unwrap();
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => {
+            assert_eq!(envelope.tx_digest, digests[1]);
+            assert_eq!(envelope.id, events[1].0);
+        }
+        other => panic!("Failed to get replayed SuiEvent, but {:?}", other),
+    }
+
+    // Then events are streamed live.
+    let (_, _, digest) = create_devnet_nft(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => assert_eq!(envelope.tx_digest, digest),
+        other => panic!("Failed to get live SuiEvent, but {:?}", other),
+    }
+
+    // No more
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because no new events are coming in. Got {:?}",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_full_node_sub_transaction_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+
+    let fullnode =
+        start_fullnode_from_config(test_cluster.fullnode_config_builder().build().unwrap())
+            .await
+            .unwrap();
+
+    let node = fullnode.sui_node;
+    let ws_client = fullnode.ws_client;
+    let receiver = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+
+    let mut sub: Subscription<SuiTransactionResponse> = ws_client
+        .subscribe(
+            "sui_subscribeTransaction",
+            rpc_params![TransactionFilter::ToAddress(receiver)],
+            "sui_unsubscribeTransaction",
+        )
+        .await
+        .unwrap();
+
+    let (_, _, _, digest, _, _) = transfer_coin(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(response))) => {
+            assert_eq!(response.certificate.transaction_digest, digest);
+            assert!(response.timestamp_ms.is_some());
+        }
+        other => panic!("Failed to get SuiTransactionResponse, but {:?}", other),
+    }
+
+    // Minting an NFT does not send anything to the receiver.
+    let (_, _, digest) = create_devnet_nft(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because no matching transactions are coming in. Got {:?}",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
 // Test fullnode has event read jsonrpc endpoints working
 #[sim_test]
 async fn test_full_node_event_read_api_ok() {
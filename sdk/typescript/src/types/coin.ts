@@ -36,12 +36,14 @@ export type PaginatedCoins = Infer<typeof PaginatedCoins>;
 
 export const CoinBalance = object({
   coinType: string(),
-  coinObjectCount: number(),
+  coinObjectCount: optional(number()),
   totalBalance: number(),
-  lockedBalance: object({
-    epochId: optional(number()),
-    number: optional(number()),
-  }),
+  lockedBalance: optional(
+    object({
+      epochId: optional(number()),
+      number: optional(number()),
+    }),
+  ),
 });
 
 export type CoinBalance = Infer<typeof CoinBalance>;